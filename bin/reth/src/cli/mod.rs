@@ -6,9 +6,10 @@ use crate::{
         LogArgs,
     },
     commands::{
-        config_cmd, db, debug_cmd, dump_genesis, import, init_cmd, init_state,
+        config_cmd, db, debug_cmd, dump_genesis, dump_state, export, export_era, import,
+        import_era, init_cmd, init_state,
         node::{self, NoArgs},
-        p2p, recover, stage, test_vectors,
+        p2p, recover, stage, static_file, test_vectors,
     },
     version::{LONG_VERSION, SHORT_VERSION},
 };
@@ -148,6 +149,9 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::InitState(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::ImportEra(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::ExportEra(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
             Commands::ImportOp(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
@@ -155,8 +159,10 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
                 runner.run_blocking_until_ctrl_c(command.execute())
             }
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::DumpState(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::StaticFile(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
@@ -190,6 +196,15 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand),
+    /// Exports canonical blocks to RLP encoded files.
+    #[command(name = "export")]
+    Export(export::ExportCommand),
+    /// This syncs pre-merge blocks from Era1 archives.
+    #[command(name = "import-era")]
+    ImportEra(import_era::ImportEraCommand),
+    /// Exports blocks to Era1 archives.
+    #[command(name = "export-era")]
+    ExportEra(export_era::ExportEraCommand),
     /// This syncs RLP encoded OP blocks below Bedrock from a file, without executing.
     #[cfg(feature = "optimism")]
     #[command(name = "import-op")]
@@ -200,12 +215,18 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     ImportReceiptsOp(crate::commands::import_receipts_op::ImportReceiptsOpCommand),
     /// Dumps genesis block JSON configuration to stdout.
     DumpGenesis(dump_genesis::DumpGenesisCommand),
+    /// Writes the state at a block to a state dump file.
+    #[command(name = "dump-state")]
+    DumpState(dump_state::DumpStateCommand),
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command),
+    /// Static file maintenance utilities
+    #[command(name = "static-file")]
+    StaticFile(static_file::Command),
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(p2p::Command),
@@ -1,11 +1,15 @@
 use crate::utils::DbTool;
 use clap::Parser;
 use reth_db::{
-    static_file::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, ReceiptMask, TransactionMask},
-    tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
+    static_file::{
+        AccountChangeSetMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, ReceiptMask,
+        StorageChangeSetMask, TransactionMask, TransactionSenderMask,
+    },
+    tables, RawKey, RawTable, Receipts, TableViewer, TransactionSenders, Transactions,
 };
 use reth_db_api::{
     database::Database,
+    models::{StoredBlockAccountChanges, StoredBlockStorageChanges},
     table::{Decompress, DupSort, Table},
 };
 use reth_primitives::{BlockHash, Header, StaticFileSegment};
@@ -71,6 +75,18 @@ impl Command {
                         table_key::<tables::Receipts>(&key)?,
                         <ReceiptMask<<Receipts as Table>::Value>>::MASK,
                     ),
+                    StaticFileSegment::AccountChangeSets => (
+                        table_key::<tables::AccountChangeSets>(&key)?,
+                        <AccountChangeSetMask<StoredBlockAccountChanges>>::MASK,
+                    ),
+                    StaticFileSegment::StorageChangeSets => (
+                        table_key::<tables::AccountChangeSets>(&key)?,
+                        <StorageChangeSetMask<StoredBlockStorageChanges>>::MASK,
+                    ),
+                    StaticFileSegment::TransactionSenders => (
+                        table_key::<tables::TransactionSenders>(&key)?,
+                        <TransactionSenderMask<<TransactionSenders as Table>::Value>>::MASK,
+                    ),
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -112,6 +128,25 @@ impl Command {
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
+                                StaticFileSegment::AccountChangeSets => {
+                                    let changeset = StoredBlockAccountChanges::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
+                                StaticFileSegment::StorageChangeSets => {
+                                    let changeset = StoredBlockStorageChanges::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
+                                StaticFileSegment::TransactionSenders => {
+                                    let sender =
+                                        <<TransactionSenders as Table>::Value>::decompress(
+                                            content[0].as_slice(),
+                                        )?;
+                                    println!("{}", serde_json::to_string_pretty(&sender)?);
+                                }
                             }
                         }
                     }
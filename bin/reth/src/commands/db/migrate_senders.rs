@@ -0,0 +1,196 @@
+use clap::Parser;
+use reth_db::tables;
+use reth_db_api::database::Database;
+use reth_primitives::{static_file::HighestStaticFiles, BlockNumber, StaticFileSegment};
+use reth_provider::{
+    ProviderFactory, PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneMode, PruneModes, PruneSegment};
+use reth_stages::StageId;
+use reth_static_file::StaticFileProducer;
+use tracing::info;
+
+/// The arguments for the `reth db migrate-senders` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The highest block whose transaction senders are moved to static files.
+    ///
+    /// Defaults to the checkpoint of the sender recovery stage.
+    #[arg(long)]
+    to_block: Option<BlockNumber>,
+}
+
+impl Command {
+    /// Execute `db migrate-senders` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let static_file_provider = provider_factory.static_file_provider();
+
+        let checkpoint = {
+            let provider = provider_factory.provider()?;
+            if provider.get_prune_checkpoint(PruneSegment::SenderRecovery)?.is_some() &&
+                static_file_provider
+                    .get_highest_static_file_block(StaticFileSegment::TransactionSenders)
+                    .is_none()
+            {
+                eyre::bail!("Transaction senders are pruned, there is nothing to migrate.")
+            }
+
+            provider.get_stage_checkpoint(StageId::SenderRecovery)?.unwrap_or_default().block_number
+        };
+
+        let to_block = self.to_block.unwrap_or(checkpoint);
+        if to_block > checkpoint {
+            eyre::bail!(
+                "Cannot migrate senders up to block {to_block}, senders are only recovered up to block {checkpoint}."
+            )
+        }
+
+        // Copy the senders to static files
+        {
+            let static_file_producer =
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::none());
+            let static_file_producer = static_file_producer.lock();
+            let targets = static_file_producer.get_static_file_targets(HighestStaticFiles {
+                transaction_senders: Some(to_block),
+                ..Default::default()
+            })?;
+            static_file_producer.run(targets)?;
+        }
+
+        let (Some(highest_block), Some(highest_tx)) = (
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+        ) else {
+            info!(target: "reth::cli", "No transaction senders to migrate");
+            return Ok(())
+        };
+
+        // Delete the senders which have been copied to static files from the database
+        let provider_rw = provider_factory.provider_rw()?;
+        let (deleted, _) = provider_rw.prune_table_with_range::<tables::TransactionSenders>(
+            ..=highest_tx,
+            &mut PruneLimiter::default(),
+            |_| false,
+            |_| {},
+        )?;
+        provider_rw.save_prune_checkpoint(
+            PruneSegment::SenderRecovery,
+            PruneCheckpoint {
+                block_number: Some(highest_block),
+                tx_number: Some(highest_tx),
+                prune_mode: PruneMode::before_inclusive(highest_block),
+            },
+        )?;
+        provider_rw.commit()?;
+
+        info!(target: "reth::cli", highest_block, deleted, "Moved transaction senders to static files");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::{
+        models::StoredBlockBodyIndices,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::Address;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StageCheckpointWriter, TransactionsProvider,
+    };
+    use reth_stages::StageCheckpoint;
+
+    #[test]
+    fn migrate_senders() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+
+        // blocks 0..=3 with two transactions each
+        let senders = (0..8).map(Address::with_last_byte).collect::<Vec<_>>();
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 0..=3 {
+            provider_rw
+                .tx_ref()
+                .put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num: block * 2, tx_count: 2 },
+                )
+                .unwrap();
+        }
+        for (tx_number, sender) in senders.iter().enumerate() {
+            provider_rw
+                .tx_ref()
+                .put::<tables::TransactionSenders>(tx_number as u64, *sender)
+                .unwrap();
+        }
+        provider_rw
+            .save_stage_checkpoint(StageId::SenderRecovery, StageCheckpoint::new(3))
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        // senders are only recovered up to block 3
+        assert!(Command { to_block: Some(4) }.execute(factory.clone()).is_err());
+
+        Command { to_block: Some(1) }.execute(factory.clone()).unwrap();
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
+            Some(1)
+        );
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+            Some(3)
+        );
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::TransactionSenders>(), Ok(4));
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::SenderRecovery).unwrap(),
+            Some(PruneCheckpoint {
+                block_number: Some(1),
+                tx_number: Some(3),
+                prune_mode: PruneMode::Before(2)
+            })
+        );
+        assert_eq!(provider.senders_by_tx_range(0..8).unwrap(), senders);
+        drop(provider);
+
+        // defaults to the checkpoint of the sender recovery stage
+        Command { to_block: None }.execute(factory.clone()).unwrap();
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+            Some(7)
+        );
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::TransactionSenders>(), Ok(0));
+        assert_eq!(provider.senders_by_tx_range(0..8).unwrap(), senders);
+        for (tx_number, sender) in senders.iter().enumerate() {
+            assert_eq!(provider.transaction_sender(tx_number as u64).unwrap(), Some(*sender));
+        }
+    }
+
+    #[test]
+    fn migrate_pruned_senders() {
+        let factory = create_test_provider_factory();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::SenderRecovery,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: Some(3),
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        assert!(Command { to_block: None }.execute(factory).is_err());
+    }
+}
@@ -177,10 +177,10 @@ impl Command {
         let accounts = provider_rw.basic_accounts(account_lists)?;
         provider_rw.insert_account_for_hashing(accounts)?;
 
-        let (state_root, incremental_trie_updates) = StateRoot::incremental_root_with_updates(
-            provider_rw.tx_ref(),
-            block.number..=block.number,
-        )?;
+        let prefix_sets = provider_rw.changeset_prefix_sets(block.number..=block.number)?;
+        let (state_root, incremental_trie_updates) = StateRoot::from_tx(provider_rw.tx_ref())
+            .with_prefix_sets(prefix_sets)
+            .root_with_updates()?;
         if state_root != block.state_root {
             eyre::bail!(
                 "Computed incremental state root mismatch. Expected: {:?}. Got: {:?}",
@@ -0,0 +1,58 @@
+//! Command that writes the state at a block to a state dump file.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_db_common::dump::write_state_dump;
+use reth_primitives::BlockNumber;
+use reth_provider::StageCheckpointReader;
+use reth_stages::StageId;
+use std::{fs::File, io::BufWriter, path::PathBuf};
+use tracing::info;
+
+/// Writes the state at a block to a JSONL state dump file, which can be read by `init-state`.
+#[derive(Debug, Parser)]
+pub struct DumpStateCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The block to dump the state at. Defaults to the last executed block.
+    ///
+    /// The state of past blocks is read from the account and storage history, so it must not be
+    /// pruned up to the block.
+    #[arg(long, value_name = "BLOCK_NUMBER", verbatim_doc_comment)]
+    block: Option<BlockNumber>,
+
+    /// The path of the JSONL state dump file.
+    ///
+    /// The first line contains { "root": \<state-root\> }, the following lines contain the
+    /// accounts, in the format read by `init-state`.
+    #[arg(value_name = "STATE_DUMP_FILE", verbatim_doc_comment)]
+    state: PathBuf,
+}
+
+impl DumpStateCommand {
+    /// Execute the `dump-state` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "Reth dump-state starting");
+
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        let block = match self.block {
+            Some(block) => block,
+            None => {
+                provider_factory
+                    .provider()?
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .unwrap_or_default()
+                    .block_number
+            }
+        };
+        info!(target: "reth::cli", block, path = ?self.state, "Dumping state");
+
+        let writer = BufWriter::new(File::create(&self.state)?);
+        let accounts = write_state_dump(&provider_factory, block, writer)?;
+
+        info!(target: "reth::cli", block, accounts, "State dump written");
+        Ok(())
+    }
+}
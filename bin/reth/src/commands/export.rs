@@ -0,0 +1,265 @@
+//! Command that exports canonical blocks to RLP encoded files.
+
+use crate::{
+    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    version::SHORT_VERSION,
+};
+use alloy_rlp::Encodable;
+use clap::{Parser, ValueEnum};
+use reth_primitives::BlockNumber;
+use reth_provider::{BlockNumReader, BlockReader};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// The number of blocks that are read from the database at once.
+const BLOCKS_PER_BATCH: u64 = 1_000;
+
+/// Exports canonical blocks to RLP encoded files that can be imported with `reth import`.
+#[derive(Debug, Parser)]
+pub struct ExportCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK_NUMBER", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export. Defaults to the highest block in the database.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    to: Option<BlockNumber>,
+
+    /// Compresses the exported files.
+    #[arg(long, value_enum, default_value_t = Compression::None)]
+    compression: Compression,
+
+    /// The maximum size of the RLP encoded blocks of a file in bytes.
+    ///
+    /// If set, the blocks are split into files `<EXPORT_PATH>.<index>`, none of which contains
+    /// more than this many bytes of blocks, unless a single block is larger. Blocks are never
+    /// split across files, so each file can be imported on its own.
+    #[arg(long, value_name = "BYTES", verbatim_doc_comment)]
+    max_file_size: Option<u64>,
+
+    /// The path to export the blocks to.
+    ///
+    /// The extension of the compression is appended to the path, if any.
+    #[arg(value_name = "EXPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+/// The compression of the exported files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Compression {
+    /// No compression.
+    None,
+    /// Gzip compression, appends `.gz` to the file names.
+    Gzip,
+    /// Zstandard compression, appends `.zst` to the file names.
+    Zstd,
+}
+
+impl Compression {
+    /// Returns the file extension of the compression.
+    const fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some("gz"),
+            Self::Zstd => Some("zst"),
+        }
+    }
+
+    /// Creates the file at the given path and returns a writer that compresses its input.
+    fn create(&self, path: &Path) -> io::Result<CompressedFile> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(match self {
+            Self::None => CompressedFile::None(file),
+            Self::Gzip => {
+                CompressedFile::Gzip(flate2::write::GzEncoder::new(file, Default::default()))
+            }
+            Self::Zstd => CompressedFile::Zstd(zstd::Encoder::new(file, 0)?),
+        })
+    }
+}
+
+/// A file that compresses its input.
+enum CompressedFile {
+    None(BufWriter<File>),
+    Gzip(flate2::write::GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl CompressedFile {
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Self::None(file) => file.write_all(buf),
+            Self::Gzip(file) => file.write_all(buf),
+            Self::Zstd(file) => file.write_all(buf),
+        }
+    }
+
+    /// Writes the end of the compressed stream, if any, and flushes the file.
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Self::None(mut file) => file.flush(),
+            Self::Gzip(file) => file.finish()?.flush(),
+            Self::Zstd(file) => file.finish()?.flush(),
+        }
+    }
+}
+
+impl ExportCommand {
+    /// Execute `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let to = match self.to {
+            Some(to) => to,
+            None => provider.last_block_number()?,
+        };
+        if to < self.from {
+            eyre::bail!("last block {to} is lower than first block {}", self.from)
+        }
+
+        let mut writer = ChunkedFileWriter::new(self.path, self.compression, self.max_file_size);
+        let mut buf = Vec::new();
+        for start in (self.from..=to).step_by(BLOCKS_PER_BATCH as usize) {
+            let end = (start + BLOCKS_PER_BATCH - 1).min(to);
+            let blocks = provider.block_range(start..=end)?;
+            if blocks.len() as u64 != end - start + 1 {
+                eyre::bail!("blocks {start}..={end} not found")
+            }
+
+            for block in blocks {
+                buf.clear();
+                block.encode(&mut buf);
+                writer.write_block(&buf)?;
+            }
+            info!(target: "reth::cli", block = end, "Exported blocks");
+        }
+        let files = writer.finish()?;
+
+        info!(target: "reth::cli",
+            from = self.from,
+            to,
+            ?files,
+            "Blocks exported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Writes RLP encoded blocks to files of a maximum size.
+struct ChunkedFileWriter {
+    /// The path of the exported blocks.
+    path: PathBuf,
+    /// The compression of the files.
+    compression: Compression,
+    /// The maximum size of the blocks of a file, if the blocks are split into files.
+    max_file_size: Option<u64>,
+    /// The written files.
+    files: Vec<PathBuf>,
+    /// The current file and the size of its blocks.
+    file: Option<(CompressedFile, u64)>,
+}
+
+impl ChunkedFileWriter {
+    const fn new(path: PathBuf, compression: Compression, max_file_size: Option<u64>) -> Self {
+        Self { path, compression, max_file_size, files: Vec::new(), file: None }
+    }
+
+    /// Writes the RLP encoded block to the current file, or to a new file if the block doesn't fit
+    /// into the current one.
+    fn write_block(&mut self, block: &[u8]) -> io::Result<()> {
+        let len = block.len() as u64;
+        let full = match (&self.file, self.max_file_size) {
+            (Some((_, size)), Some(max_file_size)) => *size > 0 && size + len > max_file_size,
+            _ => false,
+        };
+        if full {
+            self.finish_file()?;
+        }
+
+        if self.file.is_none() {
+            let mut path = self.path.clone().into_os_string();
+            if self.max_file_size.is_some() {
+                path.push(format!(".{:05}", self.files.len()));
+            }
+            if let Some(extension) = self.compression.extension() {
+                path.push(format!(".{extension}"));
+            }
+            let path = PathBuf::from(path);
+            self.file = Some((self.compression.create(&path)?, 0));
+            self.files.push(path);
+        }
+
+        let (file, size) = self.file.as_mut().expect("file created");
+        file.write_all(block)?;
+        *size += len;
+        Ok(())
+    }
+
+    /// Finishes the current file.
+    fn finish_file(&mut self) -> io::Result<()> {
+        if let Some((file, _)) = self.file.take() {
+            file.finish()?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the current file and returns the written files.
+    fn finish(mut self) -> io::Result<Vec<PathBuf>> {
+        self.finish_file()?;
+        Ok(self.files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+
+    #[test]
+    fn parse_common_export_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ExportCommand = ExportCommand::parse_from(["reth", "--chain", chain, "."]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_primitives::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+
+    #[test]
+    fn split_blocks_into_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocks.rlp");
+
+        let mut writer = ChunkedFileWriter::new(path.clone(), Compression::None, Some(10));
+        for block in [[1; 4], [2; 4], [3; 4]] {
+            writer.write_block(&block).unwrap();
+        }
+        writer.write_block(&[4; 12]).unwrap();
+        let files = writer.finish().unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("blocks.rlp.00000"),
+                dir.path().join("blocks.rlp.00001"),
+                dir.path().join("blocks.rlp.00002")
+            ]
+        );
+        assert_eq!(std::fs::read(&files[0]).unwrap(), [[1; 4], [2; 4]].concat());
+        assert_eq!(std::fs::read(&files[1]).unwrap(), [3; 4]);
+        assert_eq!(std::fs::read(&files[2]).unwrap(), [4; 12]);
+    }
+}
@@ -0,0 +1,144 @@
+//! Command that exports pre-merge blocks to Era1 archives.
+
+use crate::{
+    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    version::SHORT_VERSION,
+};
+use clap::Parser;
+use reth_db_api::database::Database;
+use reth_downloaders::era1::{era1_file_name, Era1Block, Era1Writer, MAX_ERA1_BLOCKS};
+use reth_primitives::{BlockBody, BlockNumber, B256};
+use reth_provider::{
+    BlockNumReader, BlockReader, DatabaseProviderRO, HeaderProvider, ProviderError, ReceiptProvider,
+};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    ops::RangeInclusive,
+    path::PathBuf,
+};
+use tracing::info;
+
+/// Exports blocks to Era1 archives.
+#[derive(Debug, Parser)]
+pub struct ExportEraCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK_NUMBER", default_value_t = 0)]
+    first_block: BlockNumber,
+
+    /// The last block to export. Defaults to the highest block in the database.
+    ///
+    /// Era1 archives are meant for pre-merge history, so this should not be higher than the last
+    /// pre-merge block.
+    #[arg(long, value_name = "BLOCK_NUMBER", verbatim_doc_comment)]
+    last_block: Option<BlockNumber>,
+
+    /// The network name used in the names of the files. Defaults to the name of the chain.
+    #[arg(long, value_name = "NETWORK")]
+    network: Option<String>,
+
+    /// The directory to write the Era1 files to.
+    ///
+    /// One file is written per epoch of 8192 blocks, named
+    /// `<network>-<epoch>-<short accumulator root>.era1`.
+    #[arg(value_name = "EXPORT_DIR", verbatim_doc_comment)]
+    dir: PathBuf,
+}
+
+impl ExportEraCommand {
+    /// Execute `export-era` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let last_block = match self.last_block {
+            Some(last_block) => last_block,
+            None => provider.last_block_number()?,
+        };
+        if last_block < self.first_block {
+            eyre::bail!("last block {last_block} is lower than first block {}", self.first_block)
+        }
+        let network = self.network.unwrap_or_else(|| self.env.chain.chain.to_string());
+        fs::create_dir_all(&self.dir)?;
+
+        let epoch_len = MAX_ERA1_BLOCKS as u64;
+        for epoch in self.first_block / epoch_len..=last_block / epoch_len {
+            let blocks = (epoch * epoch_len).max(self.first_block)..=
+                (epoch * epoch_len + epoch_len - 1).min(last_block);
+
+            // the name of the file depends on the accumulator root
+            let tmp_path = self.dir.join(format!("{network}-{epoch:05}.era1.tmp"));
+            let root = write_era1(&provider, blocks.clone(), File::create(&tmp_path)?)?;
+            let path = self.dir.join(era1_file_name(&network, epoch, root));
+            fs::rename(tmp_path, &path)?;
+
+            info!(target: "reth::cli",
+                path = %path.display(),
+                ?blocks,
+                accumulator_root = %root,
+                "Era1 file written"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the given blocks to the Era1 file and returns its accumulator root.
+fn write_era1<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    blocks: RangeInclusive<BlockNumber>,
+    file: File,
+) -> eyre::Result<B256> {
+    let mut writer = Era1Writer::new(BufWriter::new(file))?;
+    for number in blocks {
+        let header =
+            provider.sealed_header(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        let block = provider
+            .block(number.into())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+        let receipts = provider
+            .receipts_by_block(number.into())?
+            .ok_or(eyre::eyre!("receipts of block {number} not found"))?;
+        let total_difficulty = provider
+            .header_td_by_number(number)?
+            .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+
+        writer.append_block(&Era1Block {
+            header,
+            body: BlockBody {
+                transactions: block.body,
+                ommers: block.ommers,
+                withdrawals: block.withdrawals,
+                requests: block.requests,
+            },
+            receipts: receipts.into_iter().map(|receipt| receipt.with_bloom()).collect(),
+            total_difficulty,
+        })?;
+    }
+    Ok(writer.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+
+    #[test]
+    fn parse_common_export_era_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ExportEraCommand =
+                ExportEraCommand::parse_from(["reth", "--chain", chain, "."]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_primitives::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+}
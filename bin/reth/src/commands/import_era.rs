@@ -0,0 +1,258 @@
+//! Command that imports pre-merge blocks from Era1 archives.
+
+use crate::{
+    commands::{
+        common::{AccessRights, Environment, EnvironmentArgs},
+        import::build_import_pipeline,
+    },
+    version::SHORT_VERSION,
+};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_downloaders::{
+    era1::{Era1, Era1Block, Era1Reader, MAX_ERA1_BLOCKS},
+    file_client::FileClient,
+};
+use reth_primitives::B256;
+use reth_provider::{BlockNumReader, StageCheckpointReader};
+use reth_prune_types::PruneModes;
+use reth_stages::StageId;
+use reth_static_file::StaticFileProducer;
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, info, warn};
+
+/// Syncs blocks from Era1 archives.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// Disables stages that require state.
+    #[arg(long, verbatim_doc_comment)]
+    no_state: bool,
+
+    /// The path to a file with the canonical header accumulator root of each epoch of the chain,
+    /// one hex encoded root per line, starting with epoch 0.
+    ///
+    /// These are the historical epochs of the pre-merge master accumulator. Each Era1 file must
+    /// contain the blocks of one epoch, and its accumulator root must match the root of the epoch.
+    #[arg(
+        long,
+        value_name = "FILE",
+        required_unless_present = "no_accumulator_check",
+        verbatim_doc_comment
+    )]
+    accumulator_roots: Option<PathBuf>,
+
+    /// Imports the files without checking their accumulator roots against the canonical roots.
+    ///
+    /// Only the consistency of each file is verified, so the files must be trusted.
+    #[arg(long, conflicts_with = "accumulator_roots", verbatim_doc_comment)]
+    no_accumulator_check: bool,
+
+    /// The path to an Era1 file, or to a directory of Era1 files.
+    ///
+    /// The files of a directory are imported in the order of their names. The accumulator root of
+    /// each file is verified before its blocks are passed to the headers and bodies stages, after
+    /// which the remaining stages are executed.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl ImportEraCommand {
+    /// Execute `import-era` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        if self.no_state {
+            info!(target: "reth::cli", "Disabled stages requiring state");
+        }
+
+        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+
+        let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
+        info!(target: "reth::cli", "Consensus engine initialized");
+
+        let accumulator_roots =
+            self.accumulator_roots.as_deref().map(read_accumulator_roots).transpose()?;
+        if accumulator_roots.is_none() {
+            warn!(target: "reth::cli", "Accumulator roots of the Era1 files are not checked against the canonical roots");
+        }
+
+        let files = era1_files(&self.path)?;
+        info!(target: "reth::cli", files = files.len(), "Importing Era1 files");
+
+        let mut total_imported_blocks = 0;
+        for path in files {
+            let era1 = Era1Reader::new(BufReader::new(File::open(&path)?)).read()?;
+            info!(target: "reth::cli",
+                path = %path.display(),
+                start_block = ?era1.start_block(),
+                end_block = ?era1.end_block(),
+                accumulator_root = %era1.accumulator_root,
+                "Era1 file read"
+            );
+            if let Some(roots) = &accumulator_roots {
+                verify_accumulator_root(&era1, roots)
+                    .wrap_err_with(|| format!("invalid Era1 file {}", path.display()))?;
+            }
+
+            // skip the blocks that were imported already
+            let last_block_number = provider_factory.last_block_number()?;
+            let blocks = era1
+                .blocks
+                .into_iter()
+                .filter(|block| block.header.number > last_block_number)
+                .map(Era1Block::into_block)
+                .collect::<Vec<_>>();
+            if blocks.is_empty() {
+                debug!(target: "reth::cli", path = %path.display(), "Era1 file imported already");
+                continue
+            }
+            total_imported_blocks += blocks.len();
+
+            let file_client = FileClient::from_blocks(blocks);
+            let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
+
+            let (mut pipeline, events) = build_import_pipeline(
+                &config,
+                provider_factory.clone(),
+                &consensus,
+                Arc::new(file_client),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+                self.no_state,
+            )
+            .await?;
+
+            // override the tip
+            pipeline.set_tip(tip);
+            debug!(target: "reth::cli", ?tip, "Tip manually set");
+
+            let latest_block_number = provider_factory
+                .provider()?
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|ch| ch.block_number);
+            tokio::spawn(reth_node_events::node::handle_events(
+                None,
+                latest_block_number,
+                events,
+                provider_factory.db_ref().clone(),
+            ));
+
+            // Run pipeline
+            info!(target: "reth::cli", "Starting sync pipeline");
+            tokio::select! {
+                res = pipeline.run() => res?,
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+        }
+
+        info!(target: "reth::cli", total_imported_blocks, "Era1 files imported");
+
+        Ok(())
+    }
+}
+
+/// Reads the canonical accumulator roots of the epochs of the chain, one hex encoded root per line.
+fn read_accumulator_roots(path: &Path) -> eyre::Result<Vec<B256>> {
+    std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read accumulator roots {}", path.display()))?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse().wrap_err_with(|| format!("invalid accumulator root {line}")))
+        .collect()
+}
+
+/// Verifies that the Era1 file starts at the first block of an epoch, and that its accumulator
+/// root is the canonical root of the epoch.
+fn verify_accumulator_root(era1: &Era1, roots: &[B256]) -> eyre::Result<()> {
+    let start_block = era1.start_block().unwrap_or_default();
+    if start_block % MAX_ERA1_BLOCKS as u64 != 0 {
+        eyre::bail!("first block {start_block} is not the first block of an epoch")
+    }
+
+    let epoch = start_block / MAX_ERA1_BLOCKS as u64;
+    let expected = roots
+        .get(epoch as usize)
+        .ok_or_else(|| eyre::eyre!("no canonical accumulator root for epoch {epoch}"))?;
+    if era1.accumulator_root != *expected {
+        eyre::bail!(
+            "accumulator root {} does not match the canonical root {expected} of epoch {epoch}",
+            era1.accumulator_root
+        )
+    }
+    Ok(())
+}
+
+/// Returns the given Era1 file, or the Era1 files of the given directory ordered by name.
+fn era1_files(path: &Path) -> eyre::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()])
+    }
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "era1") {
+            files.push(path);
+        }
+    }
+    files.sort_unstable();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+    use reth_primitives::{Header, SealedHeader, U256};
+
+    #[test]
+    fn parse_common_import_era_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ImportEraCommand = ImportEraCommand::parse_from([
+                "reth",
+                "--chain",
+                chain,
+                "--no-accumulator-check",
+                ".",
+            ]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_primitives::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+
+    #[test]
+    fn verify_canonical_accumulator_root() {
+        let roots = vec![B256::random(), B256::random()];
+        let era1 = |start_block, accumulator_root| Era1 {
+            blocks: vec![Era1Block {
+                header: SealedHeader::new(
+                    Header { number: start_block, ..Default::default() },
+                    B256::ZERO,
+                ),
+                body: Default::default(),
+                receipts: Vec::new(),
+                total_difficulty: U256::ZERO,
+            }],
+            accumulator_root,
+        };
+
+        assert!(verify_accumulator_root(&era1(MAX_ERA1_BLOCKS as u64, roots[1]), &roots).is_ok());
+        assert!(verify_accumulator_root(&era1(0, roots[1]), &roots).is_err());
+        assert!(verify_accumulator_root(&era1(1, roots[0]), &roots).is_err());
+        assert!(
+            verify_accumulator_root(&era1(2 * MAX_ERA1_BLOCKS as u64, roots[0]), &roots).is_err()
+        );
+    }
+}
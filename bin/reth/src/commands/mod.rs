@@ -4,7 +4,11 @@ pub mod config_cmd;
 pub mod db;
 pub mod debug_cmd;
 pub mod dump_genesis;
+pub mod dump_state;
+pub mod export;
+pub mod export_era;
 pub mod import;
+pub mod import_era;
 pub mod import_op;
 pub mod import_receipts_op;
 
@@ -15,6 +19,7 @@ pub mod node;
 pub mod p2p;
 pub mod recover;
 pub mod stage;
+pub mod static_file;
 pub mod test_vectors;
 
 pub mod common;
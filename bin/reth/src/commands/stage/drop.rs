@@ -32,15 +32,20 @@ impl Command {
 
         let tool = DbTool::new(provider_factory)?;
 
-        let static_file_segment = match self.stage {
-            StageEnum::Headers => Some(StaticFileSegment::Headers),
-            StageEnum::Bodies => Some(StaticFileSegment::Transactions),
-            StageEnum::Execution => Some(StaticFileSegment::Receipts),
-            _ => None,
+        let static_file_segments: &[StaticFileSegment] = match self.stage {
+            StageEnum::Headers => &[StaticFileSegment::Headers],
+            StageEnum::Bodies => &[StaticFileSegment::Transactions],
+            StageEnum::Senders => &[StaticFileSegment::TransactionSenders],
+            StageEnum::Execution => &[
+                StaticFileSegment::Receipts,
+                StaticFileSegment::AccountChangeSets,
+                StaticFileSegment::StorageChangeSets,
+            ],
+            _ => &[],
         };
 
         // Delete static file segment data before inserting the genesis header below
-        if let Some(static_file_segment) = static_file_segment {
+        for &static_file_segment in static_file_segments {
             let static_file_provider = tool.provider_factory.static_file_provider();
             let static_files = iter_static_files(static_file_provider.directory())?;
             if let Some(segment_static_files) = static_files.get(&static_file_segment) {
@@ -163,6 +168,15 @@ impl Command {
                 )?;
                 insert_genesis_history(&provider_rw, self.env.chain.genesis.alloc.iter())?;
             }
+            StageEnum::AddressTransactions => {
+                tx.clear::<tables::AddressTransactions>()?;
+                tx.clear::<tables::ContractCreators>()?;
+                // The index is optional, without a checkpoint it's no longer maintained.
+                tx.delete::<tables::StageCheckpoints>(
+                    StageId::IndexAddressTransactions.to_string(),
+                    None,
+                )?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 tx.put::<tables::StageCheckpoints>(
@@ -11,7 +11,9 @@ use crate::{
 use clap::Parser;
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_cli_runner::CliContext;
-use reth_config::config::{HashingConfig, SenderRecoveryConfig, TransactionLookupConfig};
+use reth_config::config::{
+    HashingConfig, IndexAddressTransactionsConfig, SenderRecoveryConfig, TransactionLookupConfig,
+};
 use reth_downloaders::bodies::bodies::BodiesDownloaderBuilder;
 use reth_exex::ExExManagerHandle;
 use reth_provider::{
@@ -20,8 +22,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, ExecutionStageThresholds,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexAddressTransactionsStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -227,6 +229,14 @@ impl Command {
                     )),
                     None,
                 ),
+                StageEnum::AddressTransactions => (
+                    Box::new(IndexAddressTransactionsStage::new(
+                        IndexAddressTransactionsConfig { enabled: true, chunk_size: batch_size },
+                        etl_config,
+                        prune_modes.address_transactions,
+                    )),
+                    None,
+                ),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -0,0 +1,37 @@
+//! Static file maintenance tool
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+
+mod recompress;
+
+/// `reth static-file` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+/// `reth static-file` subcommands
+pub enum Subcommands {
+    /// Rewrites static files of a segment with a different compression and filter configuration
+    Recompress(recompress::Command),
+}
+
+impl Command {
+    /// Execute `static-file` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Recompress(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+        }
+
+        Ok(())
+    }
+}
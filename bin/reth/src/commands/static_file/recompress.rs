@@ -0,0 +1,470 @@
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use rand::Rng;
+use reth_db_api::database::Database;
+use reth_nippy_jar::{
+    compression::{Compressors, Lz4, Zstd},
+    ColumnResult, NippyJar, NippyJarCursor, NippyJarError,
+};
+use reth_primitives::{
+    static_file::{
+        find_fixed_range, Compression, PerfectHashingFunction, SegmentHeader,
+        SegmentRangeInclusive, BLOCKS_PER_STATIC_FILE,
+    },
+    BlockNumber, StaticFileSegment, B256,
+};
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, ProviderFactory, StaticFileProviderFactory,
+    TransactionsProviderExt,
+};
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+/// The arguments for the `reth static-file recompress` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The static file segment to recompress.
+    segment: StaticFileSegment,
+
+    /// The first block of the range to recompress.
+    ///
+    /// Every static file holding blocks of the range is rewritten as a whole.
+    #[arg(long, short, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block of the range to recompress. Defaults to the highest block of the segment.
+    #[arg(long, short)]
+    to: Option<BlockNumber>,
+
+    /// The compression of every column without a `--column-compression`.
+    #[arg(long, short, value_enum, default_value_t = Compression::Lz4)]
+    compression: Compression,
+
+    /// The compression of a single column, as `<COLUMN>=<COMPRESSION>`. Can be used multiple
+    /// times.
+    ///
+    /// Columns are numbered from 0, in the order of the tables of the segment. Every `zstd-dict`
+    /// column gets its own dictionary.
+    #[arg(long, value_name = "COLUMN=COMPRESSION", value_parser = parse_column_compression)]
+    column_compression: Vec<(usize, Compression)>,
+
+    /// The maximum size in bytes of a zstd dictionary.
+    #[arg(long, default_value_t = 5_000_000)]
+    max_dict_size: usize,
+
+    /// The number of most recent rows of a static file used to train its zstd dictionaries.
+    #[arg(long, default_value_t = 1000)]
+    dict_samples: usize,
+
+    /// Builds a cuckoo filter and a perfect hashing function, allowing lookups by hash.
+    #[arg(long, default_value_t = false)]
+    with_filters: bool,
+
+    /// The perfect hashing function built with `--with-filters`.
+    #[arg(long, value_enum, default_value_t = PerfectHashingFunction::Fmph)]
+    phf: PerfectHashingFunction,
+
+    /// The number of random lookups used to measure the latency of the static files.
+    #[arg(long, default_value_t = 1000)]
+    lookups: usize,
+}
+
+impl Command {
+    /// Execute `static-file recompress` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let static_file_provider = provider_factory.static_file_provider();
+
+        let columns = self.segment.columns();
+        if let Some((column, _)) = self.column_compression.iter().find(|(c, _)| *c >= columns) {
+            eyre::bail!(
+                "{} static files have {columns} columns, got column {column}.",
+                self.segment
+            )
+        }
+
+        let Some(highest_block) = static_file_provider.get_highest_static_file_block(self.segment)
+        else {
+            eyre::bail!("There are no {} static files.", self.segment)
+        };
+        let to = self.to.unwrap_or(highest_block).min(highest_block);
+        if self.from > to {
+            eyre::bail!("Invalid block range {}..={to}.", self.from)
+        }
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header([
+            "Block Range",
+            "Rows",
+            "Size Before",
+            "Size After",
+            "By Number Before",
+            "By Number After",
+            "By Key Before",
+            "By Key After",
+        ]);
+
+        for fixed_block_range in (find_fixed_range(self.from).start()..=to)
+            .step_by(BLOCKS_PER_STATIC_FILE as usize)
+            .map(find_fixed_range)
+        {
+            let Some(recompressed) =
+                self.recompress_jar(&static_file_provider, fixed_block_range)?
+            else {
+                continue
+            };
+
+            let mut row = Row::new();
+            row.add_cell(Cell::new(format!(
+                "{}..={}",
+                fixed_block_range.start(),
+                fixed_block_range.end()
+            )))
+            .add_cell(Cell::new(recompressed.rows))
+            .add_cell(Cell::new(human_bytes(recompressed.size_before as f64)))
+            .add_cell(Cell::new(human_bytes(recompressed.size_after as f64)))
+            .add_cell(Cell::new(format!("{:?}", recompressed.latency_before.by_number)))
+            .add_cell(Cell::new(format!("{:?}", recompressed.latency_after.by_number)))
+            .add_cell(Cell::new(format_latency(recompressed.latency_before.by_key)))
+            .add_cell(Cell::new(format_latency(recompressed.latency_after.by_key)));
+            table.add_row(row);
+        }
+
+        println!("{table}");
+
+        Ok(())
+    }
+
+    /// Rewrites the static file of `fixed_block_range` with the requested configuration. Returns
+    /// `None` if there's no such static file, or if it's empty or still being written to.
+    ///
+    /// The new static file is written and verified in a staging directory before it replaces the
+    /// original one, see [`StaticFileProvider::replace_jar`].
+    fn recompress_jar(
+        &self,
+        static_file_provider: &StaticFileProvider,
+        fixed_block_range: SegmentRangeInclusive,
+    ) -> eyre::Result<Option<RecompressedJar>> {
+        let filename = self.segment.filename(&fixed_block_range);
+        let path = static_file_provider.directory().join(&filename);
+        if !path.exists() {
+            return Ok(None)
+        }
+
+        let mut jar = NippyJar::<SegmentHeader>::load(&path)?;
+        if jar.user_header().block_end() != Some(fixed_block_range.end()) {
+            warn!(target: "reth::cli", segment = %self.segment, ?fixed_block_range, "Skipping static file which is still being written to");
+            return Ok(None)
+        }
+        if jar.rows() == 0 {
+            return Ok(None)
+        }
+        if jar.index_path().exists() {
+            jar.load_filters()?;
+        }
+
+        let rows = jar.rows();
+        info!(target: "reth::cli", segment = %self.segment, ?fixed_block_range, rows, "Recompressing static file");
+
+        let keys = if self.with_filters {
+            Some(self.keys(
+                static_file_provider,
+                jar.user_header().start().unwrap_or_default(),
+                rows,
+            )?)
+        } else {
+            None
+        };
+
+        // Staged in the static files directory, so that it can be moved into place
+        let staging_dir = static_file_provider.replacement_dir(self.segment, &fixed_block_range);
+        if staging_dir.exists() {
+            reth_fs_util::remove_dir_all(&staging_dir)?;
+        }
+        reth_fs_util::create_dir_all(&staging_dir)?;
+        let new_jar = match self.stage_jar(&jar, keys.as_deref(), &staging_dir.join(&filename)) {
+            Ok(new_jar) => new_jar,
+            Err(err) => {
+                reth_fs_util::remove_dir_all(&staging_dir)?;
+                return Err(err)
+            }
+        };
+
+        let mut rng = rand::thread_rng();
+        let lookups = (0..self.lookups).map(|_| rng.gen_range(0..rows)).collect::<Vec<_>>();
+        let recompressed = RecompressedJar {
+            rows,
+            size_before: jar_size(&jar),
+            size_after: jar_size(&new_jar),
+            latency_before: Latency::measure(&jar, &lookups, keys.as_deref())?,
+            latency_after: Latency::measure(&new_jar, &lookups, keys.as_deref())?,
+        };
+
+        static_file_provider.replace_jar(self.segment, fixed_block_range)?;
+
+        Ok(Some(recompressed))
+    }
+
+    /// Writes the new static file to `path` and verifies that every row matches the original
+    /// static file.
+    fn stage_jar(
+        &self,
+        jar: &NippyJar<SegmentHeader>,
+        keys: Option<&[B256]>,
+        path: &Path,
+    ) -> eyre::Result<NippyJar<SegmentHeader>> {
+        let rows = jar.rows();
+        let mut new_jar =
+            self.prepare_jar(jar, NippyJar::new(jar.columns(), path, jar.user_header().clone()))?;
+
+        if let Some(keys) = keys {
+            new_jar = new_jar.with_cuckoo_filter(rows);
+            new_jar = match self.phf {
+                PerfectHashingFunction::Fmph => new_jar.with_fmph(),
+                PerfectHashingFunction::GoFmph => new_jar.with_gofmph(),
+            };
+            new_jar
+                .prepare_index(keys.iter().map(|key| -> ColumnResult<B256> { Ok(*key) }), rows)?;
+        }
+
+        // Every column is read with its own cursor, so rows are streamed from the original static
+        // file instead of being held in memory.
+        let reader = Arc::new(jar.open_data_reader()?);
+        let column_values = (0..jar.columns())
+            .map(|column| {
+                let mut cursor = NippyJarCursor::with_reader(jar, reader.clone())?;
+                Ok((0..rows).map(move |row| -> ColumnResult<Vec<u8>> {
+                    let value = cursor
+                        .row_by_number_with_cols(row, 1 << column)?
+                        .ok_or(NippyJarError::UnexpectedMissingValue(row as u64, column as u64))?;
+                    Ok(value[0].to_vec())
+                }))
+            })
+            .collect::<Result<Vec<_>, NippyJarError>>()?;
+        let new_jar = new_jar.freeze(column_values, rows as u64)?;
+
+        // Load the new static file as it will be read by the node
+        let mut new_jar = NippyJar::<SegmentHeader>::load(new_jar.data_path())?;
+        new_jar.load_filters()?;
+        if new_jar.rows() != rows {
+            eyre::bail!(
+                "Recompressed {} static file has {} rows instead of {rows}.",
+                self.segment,
+                new_jar.rows()
+            )
+        }
+
+        // Make sure the recompressed static file holds the same data
+        {
+            let mut cursor = NippyJarCursor::with_reader(jar, reader)?;
+            let mut new_cursor = NippyJarCursor::new(&new_jar)?;
+            for row in 0..rows {
+                if cursor.row_by_number(row)? != new_cursor.row_by_number(row)? {
+                    eyre::bail!(
+                        "Row {row} of the recompressed {} static file doesn't match the original.",
+                        self.segment
+                    )
+                }
+            }
+        }
+
+        Ok(new_jar)
+    }
+
+    /// Sets the compression of the new static file, training the zstd dictionaries on the most
+    /// recent rows of the original static file if necessary.
+    fn prepare_jar(
+        &self,
+        jar: &NippyJar<SegmentHeader>,
+        new_jar: NippyJar<SegmentHeader>,
+    ) -> eyre::Result<NippyJar<SegmentHeader>> {
+        let mut new_jar = if self.column_compression.is_empty() {
+            match self.compression {
+                Compression::Lz4 => new_jar.with_lz4(),
+                Compression::Zstd => new_jar.with_zstd(false, 0),
+                Compression::ZstdWithDictionary => new_jar.with_zstd(true, self.max_dict_size),
+                Compression::Uncompressed => new_jar,
+            }
+        } else {
+            new_jar.with_column_compression(
+                (0..jar.columns())
+                    .map(|column| match self.column_compression(column) {
+                        Compression::Lz4 => Some(Compressors::Lz4(Lz4::default())),
+                        Compression::Zstd => Some(Compressors::Zstd(Zstd::new(false, 0, 1))),
+                        Compression::ZstdWithDictionary => {
+                            Some(Compressors::Zstd(Zstd::new(true, self.max_dict_size, 1)))
+                        }
+                        Compression::Uncompressed => None,
+                    })
+                    .collect(),
+            )
+        };
+
+        if (0..jar.columns()).any(|column| {
+            matches!(self.column_compression(column), Compression::ZstdWithDictionary)
+        }) {
+            let mut dataset = vec![Vec::new(); jar.columns()];
+            let mut cursor = NippyJarCursor::new(jar)?;
+            for row in jar.rows().saturating_sub(self.dict_samples)..jar.rows() {
+                let values = cursor
+                    .row_by_number(row)?
+                    .ok_or(NippyJarError::UnexpectedMissingValue(row as u64, 0))?;
+                for (column, value) in values.into_iter().enumerate() {
+                    dataset[column].push(value.to_vec());
+                }
+            }
+            new_jar.prepare_compression(dataset)?;
+        }
+
+        Ok(new_jar)
+    }
+
+    /// Returns the compression of `column`.
+    fn column_compression(&self, column: usize) -> Compression {
+        self.column_compression
+            .iter()
+            .rev()
+            .find(|(c, _)| *c == column)
+            .map_or(self.compression, |(_, compression)| *compression)
+    }
+
+    /// Returns the keys of the filters & PHF of `rows` rows starting at `start`: canonical block
+    /// hashes for block based segments and transaction hashes for the others.
+    fn keys(
+        &self,
+        static_file_provider: &StaticFileProvider,
+        start: u64,
+        rows: usize,
+    ) -> eyre::Result<Vec<B256>> {
+        let end = start + rows as u64;
+        let keys = if self.segment.is_block_based() {
+            static_file_provider.canonical_hashes_range(start, end)?
+        } else {
+            static_file_provider
+                .transaction_hashes_by_range(start..end)?
+                .into_iter()
+                .map(|(hash, _)| hash)
+                .collect()
+        };
+
+        if keys.len() != rows {
+            eyre::bail!(
+                "Found {} hashes for the {rows} rows of the {} static file. Filters require the headers and transactions static files of the same range.",
+                keys.len(),
+                self.segment
+            )
+        }
+
+        Ok(keys)
+    }
+}
+
+/// Outcome of recompressing a static file.
+#[derive(Debug)]
+struct RecompressedJar {
+    rows: usize,
+    size_before: u64,
+    size_after: u64,
+    latency_before: Latency,
+    latency_after: Latency,
+}
+
+/// Average latency of reading a row of a static file.
+#[derive(Debug)]
+struct Latency {
+    by_number: Duration,
+    /// `None` if the static file has no filters.
+    by_key: Option<Duration>,
+}
+
+impl Latency {
+    /// Measures the average latency of reading `rows` by number and, if the static file has
+    /// filters, by their key in `keys`.
+    fn measure(
+        jar: &NippyJar<SegmentHeader>,
+        rows: &[usize],
+        keys: Option<&[B256]>,
+    ) -> eyre::Result<Self> {
+        let lookups = rows.len().max(1) as u32;
+        let mut cursor = NippyJarCursor::new(jar)?;
+
+        let start = Instant::now();
+        for &row in rows {
+            cursor.row_by_number(row)?;
+        }
+        let by_number = start.elapsed() / lookups;
+
+        let mut by_key = None;
+        if let Some(keys) = keys {
+            let start = Instant::now();
+            for &row in rows {
+                match cursor.row_by_key(keys[row].as_slice()) {
+                    Ok(_) => {}
+                    Err(NippyJarError::UnsupportedFilterQuery) => {
+                        return Ok(Self { by_number, by_key })
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            by_key = Some(start.elapsed() / lookups);
+        }
+
+        Ok(Self { by_number, by_key })
+    }
+}
+
+/// Returns the total size of the files of a static file.
+fn jar_size(jar: &NippyJar<SegmentHeader>) -> u64 {
+    [jar.data_path().to_path_buf(), jar.offsets_path(), jar.index_path(), jar.config_path()]
+        .into_iter()
+        .map(|path| reth_fs_util::metadata(path).map(|metadata| metadata.len()).unwrap_or_default())
+        .sum()
+}
+
+/// Formats a latency, or `-` if it wasn't measured.
+fn format_latency(latency: Option<Duration>) -> String {
+    latency.map_or_else(|| "-".to_string(), |latency| format!("{latency:?}"))
+}
+
+/// Parses a `<COLUMN>=<COMPRESSION>` argument.
+fn parse_column_compression(value: &str) -> Result<(usize, Compression), String> {
+    let (column, compression) =
+        value.split_once('=').ok_or_else(|| format!("expected COLUMN=COMPRESSION, got {value}"))?;
+    let column = column.parse().map_err(|err| format!("invalid column {column}: {err}"))?;
+    let compression = <Compression as clap::ValueEnum>::from_str(compression, true)?;
+    Ok((column, compression))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_recompress_args() {
+        let command = Command::parse_from([
+            "reth",
+            "headers",
+            "--compression",
+            "zstd",
+            "--column-compression",
+            "0=zstd-dict",
+            "--column-compression",
+            "2=uncompressed",
+            "--with-filters",
+        ]);
+        assert!(command.with_filters);
+        assert!(matches!(command.column_compression(0), Compression::ZstdWithDictionary));
+        assert!(matches!(command.column_compression(1), Compression::Zstd));
+        assert!(matches!(command.column_compression(2), Compression::Uncompressed));
+
+        assert!(Command::try_parse_from(["reth", "headers", "--column-compression", "0"]).is_err());
+        assert!(
+            Command::try_parse_from(["reth", "headers", "--column-compression", "0=gzip"]).is_err()
+        );
+    }
+}
@@ -113,6 +113,25 @@ pub trait BlockchainTreeEngine: BlockchainTreeViewer + Send + Sync {
     ///
     /// Returns `Ok` if the blocks were canonicalized, or if the blocks were already canonical.
     fn make_canonical(&self, block_hash: BlockHash) -> Result<CanonicalOutcome, CanonicalError>;
+
+    /// Unwinds the canonical chain down to the given block, removing all blocks above it from the
+    /// database.
+    ///
+    /// The unwound blocks are kept in the tree as a side chain. Nothing is done if the given block
+    /// is at or above the canonical tip.
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError>;
+
+    /// Clears the state cached for the canonical tip.
+    ///
+    /// This has to be called whenever the canonical state is written outside of the tree, for
+    /// example by the pipeline.
+    fn clear_state_cache(&self);
+
+    /// Invalidates the state of the canonical tip after it was changed outside of the tree
+    /// without adding a block, for example by a state override of a dev chain.
+    ///
+    /// This clears the state cache and removes all side chains that fork off the canonical tip.
+    fn invalidate_canonical_tip_state(&self);
 }
 
 /// Represents the kind of validation that should be performed when inserting a block.
@@ -304,6 +304,14 @@ impl BlockIndices {
         self.canonical_chain.retain(|num, _| *num <= unwind_to);
     }
 
+    /// Removes the forks of the canonical tip.
+    ///
+    /// Return list of chains for removal that fork off the canonical tip.
+    pub(crate) fn remove_canonical_tip_forks(&mut self) -> BTreeSet<BlockchainId> {
+        let tip = self.canonical_tip();
+        self.remove_block(tip.number, tip.hash)
+    }
+
     /// Used for finalization of block.
     ///
     /// Return list of chains for removal that depend on finalized canonical chain.
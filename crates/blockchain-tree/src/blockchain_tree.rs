@@ -18,10 +18,10 @@ use reth_primitives::{
     SealedBlockWithSenders, SealedHeader, StaticFileSegment, B256, U256,
 };
 use reth_provider::{
-    BlockExecutionWriter, BlockNumReader, BlockWriter, CanonStateNotification,
-    CanonStateNotificationSender, CanonStateNotifications, Chain, ChainSpecProvider, ChainSplit,
-    ChainSplitTarget, DisplayBlocksChain, ExecutionOutcome, HeaderProvider, ProviderError,
-    StaticFileProviderFactory,
+    providers::StateCache, BlockExecutionWriter, BlockNumReader, BlockWriter,
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications, Chain,
+    ChainSpecProvider, ChainSplit, ChainSplitTarget, DisplayBlocksChain, ExecutionOutcome,
+    HeaderProvider, ProviderError, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_api::{MetricEvent, MetricEventsSender};
@@ -115,10 +115,12 @@ where
     ///   storage space efficiently. It's important to validate this configuration to ensure it does
     ///   not lead to unintended data loss.
     pub fn new(
-        externals: TreeExternals<DB, E>,
+        mut externals: TreeExternals<DB, E>,
         config: BlockchainTreeConfig,
         prune_modes: Option<PruneModes>,
     ) -> ProviderResult<Self> {
+        externals.state_cache = StateCache::new(config.state_cache_size());
+
         let max_reorg_depth = config.max_reorg_depth() as usize;
         // The size of the broadcast is twice the maximum reorg depth, because at maximum reorg
         // depth at least N blocks must be sent at once.
@@ -430,6 +432,7 @@ where
             canonical_chain.inner(),
             parent,
             &self.externals,
+            &self.config,
             block_attachment,
             block_validation_kind,
         )?;
@@ -486,6 +489,7 @@ where
                 block_hashes,
                 canonical_chain.inner(),
                 &self.externals,
+                &self.config,
                 canonical_fork,
                 block_attachment,
                 block_validation_kind,
@@ -502,6 +506,7 @@ where
                 canonical_chain.inner(),
                 canonical_fork,
                 &self.externals,
+                &self.config,
                 block_validation_kind,
             )?;
             self.insert_chain(chain);
@@ -1216,6 +1221,11 @@ where
     ) -> Result<(), CanonicalError> {
         let (blocks, state, chain_trie_updates) = chain.into_inner();
         let hashed_state = state.hash_state_slow();
+        let state_cache_update = self.externals.state_cache.update(
+            blocks.first().parent_hash,
+            blocks.tip().hash(),
+            state.state(),
+        );
 
         // Compute state root or retrieve cached trie updates before opening write transaction.
         let block_hash_numbers =
@@ -1265,6 +1275,7 @@ where
             .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
 
         provider_rw.commit()?;
+        self.externals.state_cache.apply(state_cache_update);
         recorder.record_relative(MakeCanonicalAction::CommitCanonicalChainToDatabase);
 
         Ok(())
@@ -1289,6 +1300,28 @@ where
         Ok(())
     }
 
+    /// Clears the state cached for the canonical tip.
+    ///
+    /// This has to be called whenever the canonical state is written outside of the tree.
+    pub fn clear_state_cache(&self) {
+        self.externals.state_cache.clear();
+    }
+
+    /// Invalidates the state of the canonical tip after it was changed outside of the tree.
+    ///
+    /// This clears the state cache and removes all side chains that fork off the canonical tip,
+    /// since they were executed on top of the previous state.
+    pub fn invalidate_canonical_tip_state(&mut self) {
+        self.clear_state_cache();
+
+        let mut remove_chains = self.state.block_indices.remove_canonical_tip_forks();
+        while let Some(chain_id) = remove_chains.pop_first() {
+            if let Some(chain) = self.state.chains.remove(&chain_id) {
+                remove_chains.extend(self.state.block_indices.remove_chain(&chain));
+            }
+        }
+    }
+
     /// Reverts the canonical chain down to the given block from the database and returns the
     /// unwound chain.
     ///
@@ -1331,6 +1364,8 @@ where
             .map_err(|e| CanonicalError::CanonicalRevert(e.to_string()))?;
 
         provider_rw.commit()?;
+        // the cached state is not the canonical state anymore
+        self.externals.state_cache.clear();
 
         if blocks_and_execution.is_empty() {
             Ok(None)
@@ -2364,16 +2399,16 @@ mod tests {
         // test pops execution results from vector, so order is from last to first.
         let externals =
             setup_externals(vec![exec3.clone(), exec2.clone(), exec1.clone(), exec3, exec2, exec1]);
-        let cloned_externals_1 = TreeExternals {
-            provider_factory: externals.provider_factory.clone(),
-            executor_factory: externals.executor_factory.clone(),
-            consensus: externals.consensus.clone(),
-        };
-        let cloned_externals_2 = TreeExternals {
-            provider_factory: externals.provider_factory.clone(),
-            executor_factory: externals.executor_factory.clone(),
-            consensus: externals.consensus.clone(),
-        };
+        let cloned_externals_1 = TreeExternals::new(
+            externals.provider_factory.clone(),
+            externals.consensus.clone(),
+            externals.executor_factory.clone(),
+        );
+        let cloned_externals_2 = TreeExternals::new(
+            externals.provider_factory.clone(),
+            externals.consensus.clone(),
+            externals.executor_factory.clone(),
+        );
 
         // last finalized block would be number 9.
         setup_genesis(&externals.provider_factory, genesis);
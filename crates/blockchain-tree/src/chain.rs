@@ -4,14 +4,17 @@
 //! blocks, as well as a list of the blocks the chain is composed of.
 
 use super::externals::TreeExternals;
-use crate::BundleStateDataRef;
+use crate::{BlockchainTreeConfig, BundleStateDataRef};
 use reth_blockchain_tree_api::{
     error::{BlockchainTreeError, InsertBlockErrorKind},
     BlockAttachment, BlockValidationKind,
 };
 use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
 use reth_db_api::database::Database;
-use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_evm::{
+    execute::{BlockExecutionOutput, BlockExecutorProvider, Executor},
+    prefetch::PrefetchCache,
+};
 use reth_execution_errors::BlockExecutionError;
 use reth_primitives::{
     BlockHash, BlockNumber, ForkBlock, GotExpected, SealedBlockWithSenders, SealedHeader, U256,
@@ -65,12 +68,14 @@ impl AppendableChain {
     ///
     /// if [`BlockValidationKind::Exhaustive`] is specified, the method will verify the state root
     /// of the block.
+    #[allow(clippy::too_many_arguments)]
     pub fn new_canonical_fork<DB, E>(
         block: SealedBlockWithSenders,
         parent_header: &SealedHeader,
         canonical_block_hashes: &BTreeMap<BlockNumber, BlockHash>,
         canonical_fork: ForkBlock,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
     ) -> Result<Self, InsertBlockErrorKind>
@@ -93,6 +98,7 @@ impl AppendableChain {
             parent_header,
             state_provider,
             externals,
+            config,
             block_attachment,
             block_validation_kind,
         )?;
@@ -103,6 +109,7 @@ impl AppendableChain {
     /// Create a new chain that forks off of an existing sidechain.
     ///
     /// This differs from [`AppendableChain::new_canonical_fork`] in that this starts a new fork.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new_chain_fork<DB, E>(
         &self,
         block: SealedBlockWithSenders,
@@ -110,6 +117,7 @@ impl AppendableChain {
         canonical_block_hashes: &BTreeMap<BlockNumber, BlockHash>,
         canonical_fork: ForkBlock,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         block_validation_kind: BlockValidationKind,
     ) -> Result<Self, InsertBlockErrorKind>
     where
@@ -139,6 +147,7 @@ impl AppendableChain {
             parent,
             bundle_state_data,
             externals,
+            config,
             BlockAttachment::HistoricalFork,
             block_validation_kind,
         )?;
@@ -166,11 +175,14 @@ impl AppendableChain {
     ///   - [`BlockAttachment`] represents if the block extends the canonical chain, and thus we can
     ///     cache the trie state updates.
     ///   - [`BlockValidationKind`] determines if the state root __should__ be validated.
+    ///
+    /// The state of the block is prefetched if enabled in the [`BlockchainTreeConfig`].
     fn validate_and_execute<EDP, DB, E>(
         block: SealedBlockWithSenders,
         parent_block: &SealedHeader,
         bundle_state_data_provider: EDP,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
     ) -> Result<(ExecutionOutcome, Option<TrieUpdates>), BlockExecutionError>
@@ -200,15 +212,44 @@ impl AppendableChain {
             // will be open in parallel. See https://github.com/paradigmxyz/reth/issues/7509.
             .disable_long_read_transaction_safety()
             .state_provider_by_block_number(canonical_fork.number)?;
+        let state_provider = externals.state_cache.provider(state_provider, canonical_fork.hash);
 
         let provider = BundleStateProvider::new(state_provider, bundle_state_data_provider);
 
-        let db = StateProviderDatabase::new(&provider);
-        let executor = externals.executor_factory.executor(db);
         let block_hash = block.hash();
         let block = block.unseal();
 
-        let state = executor.execute((&block, U256::MAX).into())?;
+        let state = if config.prefetch_workers() > 0 {
+            // every worker reads the same state as the executor through its own transaction
+            let workers = (0..config.prefetch_workers())
+                .map(|_| {
+                    let state_provider = consistent_view
+                        .provider_ro()?
+                        .disable_long_read_transaction_safety()
+                        .state_provider_by_block_number(canonical_fork.number)?;
+                    Ok(StateProviderDatabase::new(BundleStateProvider::new(
+                        externals.state_cache.provider(state_provider, canonical_fork.hash),
+                        &provider.block_execution_data_provider,
+                    )))
+                })
+                .collect::<Result<Vec<_>, ProviderError>>()?;
+
+            let cache = PrefetchCache::default();
+            externals.prefetcher.execute_with_prefetch(
+                &externals.executor_factory,
+                &block,
+                U256::MAX,
+                &cache,
+                workers,
+                || {
+                    let db = cache.canonical(StateProviderDatabase::new(&provider));
+                    externals.executor_factory.executor(db).execute((&block, U256::MAX).into())
+                },
+            )?
+        } else {
+            let db = StateProviderDatabase::new(&provider);
+            externals.executor_factory.executor(db).execute((&block, U256::MAX).into())?
+        };
         let BlockExecutionOutput { state, receipts, requests, .. } = state;
         externals
             .consensus
@@ -274,6 +315,7 @@ impl AppendableChain {
         side_chain_block_hashes: BTreeMap<BlockNumber, BlockHash>,
         canonical_block_hashes: &BTreeMap<BlockNumber, BlockHash>,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         canonical_fork: ForkBlock,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
@@ -296,6 +338,7 @@ impl AppendableChain {
             parent_block,
             bundle_state_data,
             externals,
+            config,
             block_attachment,
             block_validation_kind,
         )?;
@@ -1,5 +1,7 @@
 //! Blockchain tree configuration
 
+use reth_provider::providers::DEFAULT_STATE_CACHE_SIZE;
+
 /// The configuration for the blockchain tree.
 #[derive(Clone, Copy, Debug)]
 pub struct BlockchainTreeConfig {
@@ -19,6 +21,12 @@ pub struct BlockchainTreeConfig {
     /// be 256. It covers both number of blocks required for reorg, and number of blocks
     /// required for `BLOCKHASH` EVM opcode.
     num_of_additional_canonical_block_hashes: u64,
+    /// The number of worker threads that speculatively execute the transactions of a block to
+    /// prefetch its state while it is executed, `0` disables prefetching.
+    prefetch_workers: usize,
+    /// The maximum number of accounts and of storage slots in the cache of the canonical state
+    /// that is shared across blocks, `0` disables the cache.
+    state_cache_size: u32,
 }
 
 impl Default for BlockchainTreeConfig {
@@ -33,6 +41,9 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            // prefetching is opt-in
+            prefetch_workers: 0,
+            state_cache_size: DEFAULT_STATE_CACHE_SIZE,
         }
     }
 }
@@ -54,9 +65,25 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            prefetch_workers: 0,
+            state_cache_size: DEFAULT_STATE_CACHE_SIZE,
         }
     }
 
+    /// Sets the number of worker threads that prefetch the state of executed blocks, `0` disables
+    /// prefetching.
+    pub const fn with_prefetch_workers(mut self, prefetch_workers: usize) -> Self {
+        self.prefetch_workers = prefetch_workers;
+        self
+    }
+
+    /// Sets the maximum number of accounts and of storage slots in the cache of the canonical
+    /// state, `0` disables the cache.
+    pub const fn with_state_cache_size(mut self, state_cache_size: u32) -> Self {
+        self.state_cache_size = state_cache_size;
+        self
+    }
+
     /// Return the maximum reorg depth.
     pub const fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
@@ -88,4 +115,15 @@ impl BlockchainTreeConfig {
     pub const fn max_unconnected_blocks(&self) -> u32 {
         self.max_unconnected_blocks
     }
+
+    /// Return the number of worker threads that prefetch the state of executed blocks.
+    pub const fn prefetch_workers(&self) -> usize {
+        self.prefetch_workers
+    }
+
+    /// Return the maximum number of accounts and of storage slots in the cache of the canonical
+    /// state.
+    pub const fn state_cache_size(&self) -> u32 {
+        self.state_cache_size
+    }
 }
@@ -3,10 +3,11 @@
 use reth_consensus::Consensus;
 use reth_db::{static_file::HeaderMask, tables};
 use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
+use reth_evm::prefetch::Prefetcher;
 use reth_primitives::{BlockHash, BlockNumber, StaticFileSegment};
 use reth_provider::{
-    FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory, StaticFileProviderFactory,
-    StatsReader,
+    providers::StateCache, FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory,
+    StaticFileProviderFactory, StatsReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use std::{collections::BTreeMap, sync::Arc};
@@ -19,7 +20,9 @@ use std::{collections::BTreeMap, sync::Arc};
 /// - A handle to the database
 /// - A handle to the consensus engine
 /// - The executor factory to execute blocks with
+/// - The prefetcher to prefetch the state of executed blocks with
 /// - The chain spec
+/// - The cache of the canonical state
 #[derive(Debug)]
 pub struct TreeExternals<DB, E> {
     /// The provider factory, used to commit the canonical chain, or unwind it.
@@ -28,6 +31,10 @@ pub struct TreeExternals<DB, E> {
     pub(crate) consensus: Arc<dyn Consensus>,
     /// The executor factory to execute blocks with.
     pub(crate) executor_factory: E,
+    /// The prefetcher to prefetch the state of executed blocks with.
+    pub(crate) prefetcher: Prefetcher,
+    /// The cache of the canonical state, shared by the executions of all blocks.
+    pub(crate) state_cache: StateCache,
 }
 
 impl<DB, E> TreeExternals<DB, E> {
@@ -37,7 +44,13 @@ impl<DB, E> TreeExternals<DB, E> {
         consensus: Arc<dyn Consensus>,
         executor_factory: E,
     ) -> Self {
-        Self { provider_factory, consensus, executor_factory }
+        Self {
+            provider_factory,
+            consensus,
+            executor_factory,
+            prefetcher: Prefetcher::default(),
+            state_cache: StateCache::disabled(),
+        }
     }
 }
 
@@ -74,6 +74,14 @@ impl BlockchainTreeEngine for NoopBlockchainTree {
     ) -> Result<BTreeMap<BlockNumber, BlockHash>, CanonicalError> {
         Ok(BTreeMap::new())
     }
+
+    fn unwind(&self, _unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        Ok(())
+    }
+
+    fn clear_state_cache(&self) {}
+
+    fn invalidate_canonical_tip_state(&self) {}
 }
 
 impl BlockchainTreeViewer for NoopBlockchainTree {
@@ -104,6 +104,26 @@ where
         tree.update_chains_metrics();
         res
     }
+
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        trace!(target: "blockchain_tree", unwind_to, "Unwinding canonical chain");
+        let mut tree = self.tree.write();
+        let res = tree.unwind(unwind_to);
+        tree.update_chains_metrics();
+        res
+    }
+
+    fn clear_state_cache(&self) {
+        trace!(target: "blockchain_tree", "Clearing state cache");
+        self.tree.read().clear_state_cache();
+    }
+
+    fn invalidate_canonical_tip_state(&self) {
+        trace!(target: "blockchain_tree", "Invalidating canonical tip state");
+        let mut tree = self.tree.write();
+        tree.invalidate_canonical_tip_state();
+        tree.update_chains_metrics();
+    }
 }
 
 impl<DB, E> BlockchainTreeViewer for ShareableBlockchainTree<DB, E>
@@ -2,6 +2,7 @@
 
 use reth_network::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
+use reth_rpc_layer::RateLimitConfig;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     ffi::OsStr,
@@ -25,6 +26,9 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for rate limiting of RPC calls.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_rate_limit: Option<RateLimitConfig>,
 }
 
 impl Config {
@@ -82,6 +86,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexAddressTransactionsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -310,6 +316,25 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Address transactions index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexAddressTransactionsConfig {
+    /// Whether to build and maintain the address transactions index.
+    ///
+    /// Once built, the index is kept up to date with the canonical chain until the stage is
+    /// dropped.
+    pub enabled: bool,
+    /// The maximum number of transactions to process before writing to disk.
+    pub chunk_size: u64,
+}
+
+impl Default for IndexAddressTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, chunk_size: 5_000_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -723,4 +748,26 @@ connect_trusted_nodes_only = true
         let conf: Config = toml::from_str(trusted_nodes_only).unwrap();
         assert!(conf.peers.trusted_nodes_only);
     }
+
+    #[test]
+    fn test_conf_rpc_rate_limit() {
+        let rpc_rate_limit = r#"
+[rpc_rate_limit]
+period = "10s"
+ip_quota = 500
+
+[rpc_rate_limit.api_keys]
+tenant = 5000
+
+[rpc_rate_limit.method_costs]
+"debug_traceBlock*" = 100
+"#;
+        let conf: Config = toml::from_str(rpc_rate_limit).unwrap();
+        let rate_limit = conf.rpc_rate_limit.unwrap();
+        assert_eq!(rate_limit.period, Duration::from_secs(10));
+        assert_eq!(rate_limit.ip_quota, Some(500));
+        assert_eq!(rate_limit.api_keys.get("tenant"), Some(&5000));
+        assert_eq!(rate_limit.method_costs.get("debug_traceBlock*"), Some(&100));
+        assert_eq!(rate_limit.default_cost, 1);
+    }
 }
@@ -0,0 +1,214 @@
+//! A handle to control the [`MiningTask`](crate::MiningTask) of a dev chain.
+
+use crate::{state::AccountChanges, Storage};
+use reth_blockchain_tree_api::error::{CanonicalError, InsertBlockError};
+use reth_execution_errors::BlockExecutionError;
+use reth_primitives::{Address, BlockNumHash, SealedHeader, B256};
+use reth_provider::ProviderError;
+use std::{collections::BTreeMap, time::Duration};
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+
+/// Errors that can occur when sealing blocks or modifying the chain through a [`MiningHandle`].
+#[derive(Debug, thiserror::Error)]
+pub enum MiningError {
+    /// Failed to build and execute the block.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// The blockchain tree rejected the block.
+    #[error(transparent)]
+    InsertBlock(#[from] InsertBlockError),
+    /// Failed to update the canonical chain.
+    #[error(transparent)]
+    Canonical(#[from] CanonicalError),
+    /// Failed to read or write the database.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The engine did not accept the forkchoice update to the sealed block.
+    #[error("forkchoice update to sealed block {0} failed")]
+    ForkchoiceUpdate(B256),
+    /// The mining task is not running anymore.
+    #[error("mining task closed")]
+    TaskClosed,
+}
+
+/// Commands sent from a [`MiningHandle`] to the [`MiningTask`](crate::MiningTask).
+#[derive(Debug)]
+pub(crate) enum MiningCommand {
+    /// Seal a new block with all pending transactions.
+    Seal { timestamp: Option<u64>, tx: oneshot::Sender<Result<SealedHeader, MiningError>> },
+    /// Apply changes to an account and seal them in a new block.
+    SetAccount {
+        address: Address,
+        changes: AccountChanges,
+        tx: oneshot::Sender<Result<SealedHeader, MiningError>>,
+    },
+    /// Snapshot the chain at the current block.
+    Snapshot { tx: oneshot::Sender<u64> },
+    /// Revert the chain to the block of a snapshot.
+    Revert { id: u64, tx: oneshot::Sender<Result<bool, MiningError>> },
+    /// Return whether a block is sealed for every new transaction.
+    IsAutomine { tx: oneshot::Sender<bool> },
+    /// Enable or disable sealing a block for every new transaction.
+    SetAutomine(bool),
+    /// Seal a block every interval, or stop mining if `None`.
+    SetIntervalMining(Option<Duration>),
+}
+
+/// A handle to a running [`MiningTask`](crate::MiningTask).
+///
+/// This can seal blocks on demand, change the mining mode and the parameters of the next blocks,
+/// modify accounts of the latest state and snapshot and revert the chain.
+///
+/// Blocks are only reverted within the range the blockchain tree can unwind, so snapshots are
+/// kept out of the finalized part of the chain.
+#[derive(Debug, Clone)]
+pub struct MiningHandle {
+    to_task: UnboundedSender<MiningCommand>,
+    storage: Storage,
+}
+
+// === impl MiningHandle ===
+
+impl MiningHandle {
+    /// Creates a new handle that sends commands to the task.
+    pub(crate) const fn new(to_task: UnboundedSender<MiningCommand>, storage: Storage) -> Self {
+        Self { to_task, storage }
+    }
+
+    /// Sends a command to the task and waits for its response.
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> MiningCommand,
+    ) -> Result<T, MiningError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(command(tx))?;
+        rx.await.map_err(|_| MiningError::TaskClosed)
+    }
+
+    /// Sends a command to the task.
+    fn send(&self, command: MiningCommand) -> Result<(), MiningError> {
+        self.to_task.send(command).map_err(|_| MiningError::TaskClosed)
+    }
+
+    /// Seals a new block with all pending transactions of the pool, regardless of the mining mode.
+    ///
+    /// The block gets the given timestamp, or the next timestamp of the chain if `None`.
+    pub async fn seal(&self, timestamp: Option<u64>) -> Result<SealedHeader, MiningError> {
+        self.request(|tx| MiningCommand::Seal { timestamp, tx }).await?
+    }
+
+    /// Seals `blocks` new blocks and returns their headers.
+    ///
+    /// If an `interval` is given, the timestamps of the blocks are `interval` seconds apart.
+    pub async fn mine(
+        &self,
+        blocks: u64,
+        interval: Option<u64>,
+    ) -> Result<Vec<SealedHeader>, MiningError> {
+        let mut headers: Vec<SealedHeader> = Vec::new();
+        for _ in 0..blocks {
+            let timestamp = interval
+                .and_then(|interval| headers.last().map(|header| header.timestamp + interval));
+            headers.push(self.seal(timestamp).await?);
+        }
+        Ok(headers)
+    }
+
+    /// Returns `true` if a block is sealed for every new transaction.
+    pub async fn automine(&self) -> Result<bool, MiningError> {
+        self.request(|tx| MiningCommand::IsAutomine { tx }).await
+    }
+
+    /// Enables or disables sealing a block for every new transaction.
+    ///
+    /// Enabling automine stops interval mining, disabling it stops mining altogether.
+    pub fn set_automine(&self, enabled: bool) -> Result<(), MiningError> {
+        self.send(MiningCommand::SetAutomine(enabled))
+    }
+
+    /// Seals a block with all pending transactions every `interval`, or stops mining if `None`.
+    pub fn set_interval_mining(&self, interval: Option<Duration>) -> Result<(), MiningError> {
+        self.send(MiningCommand::SetIntervalMining(interval))
+    }
+
+    /// Changes an account of the latest state and seals the change in a new empty block, so the
+    /// state root of every block matches its state.
+    ///
+    /// Returns the header of the new block.
+    pub async fn set_account(
+        &self,
+        address: Address,
+        changes: AccountChanges,
+    ) -> Result<SealedHeader, MiningError> {
+        self.request(|tx| MiningCommand::SetAccount { address, changes, tx }).await?
+    }
+
+    /// Snapshots the chain at the current block and returns the id of the snapshot.
+    pub async fn snapshot(&self) -> Result<u64, MiningError> {
+        self.request(|tx| MiningCommand::Snapshot { tx }).await
+    }
+
+    /// Reverts the chain to the block of the given snapshot.
+    ///
+    /// The snapshot and all snapshots taken after it are removed. Returns `false` if there is no
+    /// snapshot with the given id.
+    pub async fn revert(&self, id: u64) -> Result<bool, MiningError> {
+        self.request(|tx| MiningCommand::Revert { id, tx }).await?
+    }
+
+    /// Returns the block of every snapshot by snapshot id.
+    pub async fn snapshots(&self) -> BTreeMap<u64, BlockNumHash> {
+        let storage = self.storage.read().await;
+        storage.snapshots.iter().map(|(id, (header, _))| (*id, header.num_hash())).collect()
+    }
+
+    /// Sets the timestamp of the next block. Blocks after it continue from this timestamp.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) {
+        self.storage.write().await.next_timestamp = Some(timestamp);
+    }
+
+    /// Moves the clock of the chain forward and returns the total offset from the system time in
+    /// seconds.
+    pub async fn increase_time(&self, seconds: u64) -> i64 {
+        let mut storage = self.storage.write().await;
+        storage.time_offset = storage.time_offset.saturating_add_unsigned(seconds);
+        storage.time_offset
+    }
+
+    /// Sets the clock of the chain to the given timestamp and returns the offset from the system
+    /// time in seconds.
+    pub async fn set_time(&self, timestamp: u64) -> i64 {
+        let mut storage = self.storage.write().await;
+        storage.time_offset = timestamp as i64 - crate::unix_timestamp() as i64;
+        storage.time_offset
+    }
+
+    /// Sets a fixed interval in seconds between the timestamps of consecutive blocks, or removes
+    /// it if `None`.
+    ///
+    /// Returns `true` if an interval was set before.
+    pub async fn set_block_timestamp_interval(&self, interval: Option<u64>) -> bool {
+        let mut storage = self.storage.write().await;
+        std::mem::replace(&mut storage.timestamp_interval, interval).is_some()
+    }
+
+    /// Sets the beneficiary of the next blocks.
+    pub async fn set_coinbase(&self, coinbase: Address) {
+        self.storage.write().await.coinbase = coinbase;
+    }
+
+    /// Sets the gas limit of the next blocks.
+    pub async fn set_block_gas_limit(&self, gas_limit: u64) {
+        self.storage.write().await.gas_limit = Some(gas_limit);
+    }
+
+    /// Sets the base fee of the next block, if the London hardfork is active.
+    pub async fn set_next_block_base_fee(&self, base_fee: u64) {
+        self.storage.write().await.next_base_fee = Some(base_fee);
+    }
+
+    /// Sets the `prevrandao` value of the next block.
+    pub async fn set_next_block_prev_randao(&self, prev_randao: B256) {
+        self.storage.write().await.next_prev_randao = Some(prev_randao);
+    }
+}
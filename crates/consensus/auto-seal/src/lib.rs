@@ -6,6 +6,10 @@
 //!
 //! These downloaders poll the miner, assemble the block, and return transactions that are ready to
 //! be mined.
+//!
+//! The [`MiningHandle`] of a [`MiningTask`] controls the dev chain, it seals blocks on demand,
+//! changes the parameters of the next blocks, overrides accounts and snapshots and reverts the
+//! chain.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -22,15 +26,17 @@ use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 use reth_primitives::{
     constants::{EMPTY_TRANSACTIONS, ETHEREUM_BLOCK_GAS_LIMIT},
     eip4844::calculate_excess_blob_gas,
-    proofs, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    ChainSpec, Header, Requests, SealedBlock, SealedHeader, TransactionSigned, Withdrawals, B256,
-    U256,
+    proofs, Address, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    ChainSpec, Header, Requests, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    TransactionSigned, Withdrawals, B256, U256,
+};
+use reth_provider::{
+    BlockReaderIdExt, ExecutionOutcome, ProviderFactory, StateProviderFactory, StateRootProvider,
 };
-use reth_provider::{BlockReaderIdExt, ExecutionOutcome, StateProviderFactory, StateRootProvider};
 use reth_revm::database::StateProviderDatabase;
 use reth_transaction_pool::TransactionPool;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -38,12 +44,16 @@ use tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockReadGuard, RwLockWriteGua
 use tracing::trace;
 
 mod client;
+mod handle;
 mod mode;
+mod state;
 mod task;
 
 pub use crate::client::AutoSealClient;
+pub use handle::{MiningError, MiningHandle};
 pub use mode::{FixedBlockTimeMiner, MiningMode, ReadyTransactionMiner};
 use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+pub use state::AccountChanges;
 pub use task::MiningTask;
 
 /// A consensus implementation intended for local development and testing purposes.
@@ -97,8 +107,9 @@ impl Consensus for AutoSealConsensus {
 
 /// Builder type for configuring the setup
 #[derive(Debug)]
-pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig> {
+pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig, DB> {
     client: Client,
+    provider_factory: ProviderFactory<DB>,
     consensus: AutoSealConsensus,
     pool: Pool,
     mode: MiningMode,
@@ -109,7 +120,7 @@ pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig> {
 
 // === impl AutoSealBuilder ===
 
-impl<Client, Pool, Engine, EvmConfig> AutoSealBuilder<Client, Pool, Engine, EvmConfig>
+impl<Client, Pool, Engine, EvmConfig, DB> AutoSealBuilder<Client, Pool, Engine, EvmConfig, DB>
 where
     Client: BlockReaderIdExt,
     Pool: TransactionPool,
@@ -119,6 +130,7 @@ where
     pub fn new(
         chain_spec: Arc<ChainSpec>,
         client: Client,
+        provider_factory: ProviderFactory<DB>,
         pool: Pool,
         to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
         mode: MiningMode,
@@ -133,6 +145,7 @@ where
         Self {
             storage: Storage::new(latest_header),
             client,
+            provider_factory,
             consensus: AutoSealConsensus::new(chain_spec),
             pool,
             mode,
@@ -151,8 +164,17 @@ where
     #[track_caller]
     pub fn build(
         self,
-    ) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool, EvmConfig, Engine>) {
-        let Self { client, consensus, pool, mode, storage, to_engine, evm_config } = self;
+    ) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool, EvmConfig, Engine, DB>) {
+        let Self {
+            client,
+            provider_factory,
+            consensus,
+            pool,
+            mode,
+            storage,
+            to_engine,
+            evm_config,
+        } = self;
         let auto_client = AutoSealClient::new(storage.clone());
         let task = MiningTask::new(
             Arc::clone(&consensus.chain_spec),
@@ -160,6 +182,7 @@ where
             to_engine,
             storage,
             client,
+            provider_factory,
             pool,
             evm_config,
         );
@@ -217,6 +240,24 @@ pub(crate) struct StorageInner {
     pub(crate) best_hash: B256,
     /// The total difficulty of the chain until this block
     pub(crate) total_difficulty: U256,
+    /// The timestamp of the next block, if set explicitly.
+    pub(crate) next_timestamp: Option<u64>,
+    /// The offset in seconds between the clock of the chain and the system time.
+    pub(crate) time_offset: i64,
+    /// A fixed interval between the timestamps of consecutive blocks, if set.
+    pub(crate) timestamp_interval: Option<u64>,
+    /// The base fee of the next block, if set explicitly.
+    pub(crate) next_base_fee: Option<u64>,
+    /// The `prevrandao` value of the next block, if set explicitly.
+    pub(crate) next_prev_randao: Option<B256>,
+    /// The beneficiary of new blocks.
+    pub(crate) coinbase: Address,
+    /// The gas limit of new blocks, [`ETHEREUM_BLOCK_GAS_LIMIT`] if not set.
+    pub(crate) gas_limit: Option<u64>,
+    /// The block and total difficulty of every snapshot, by snapshot id.
+    pub(crate) snapshots: BTreeMap<u64, (SealedHeader, U256)>,
+    /// The id of the next snapshot.
+    pub(crate) next_snapshot_id: u64,
 }
 
 // === impl StorageInner ===
@@ -254,6 +295,76 @@ impl StorageInner {
         self.hash_to_number.insert(self.best_hash, self.best_block);
     }
 
+    /// Returns the header of the best block.
+    pub(crate) fn best_header(&self) -> SealedHeader {
+        let header = self.headers.get(&self.best_block).cloned().unwrap_or_default();
+        header.seal(self.best_hash)
+    }
+
+    /// Returns the timestamp of the next block.
+    ///
+    /// An exact timestamp, either given or set with [`Self::next_timestamp`], moves the clock of
+    /// the chain, so the blocks after it continue from there.
+    fn next_block_timestamp(&mut self, timestamp: Option<u64>) -> u64 {
+        let now = unix_timestamp();
+        if let Some(timestamp) = timestamp.or_else(|| self.next_timestamp.take()) {
+            self.time_offset = timestamp as i64 - now as i64;
+            return timestamp
+        }
+        if let Some(interval) = self.timestamp_interval {
+            if let Some(parent) = self.headers.get(&self.best_block) {
+                return parent.timestamp + interval
+            }
+        }
+        now.saturating_add_signed(self.time_offset)
+    }
+
+    /// Records a snapshot of the best block and returns its id.
+    pub(crate) fn snapshot(&mut self) -> u64 {
+        let id = self.next_snapshot_id;
+        self.next_snapshot_id += 1;
+        self.snapshots.insert(id, (self.best_header(), self.total_difficulty));
+        id
+    }
+
+    /// Returns the header of the block of the given snapshot.
+    pub(crate) fn snapshot_header(&self, id: u64) -> Option<&SealedHeader> {
+        self.snapshots.get(&id).map(|(header, _)| header)
+    }
+
+    /// Returns the header of the block that should be marked as finalized.
+    ///
+    /// This is the block of the earliest snapshot, since the blocks above it can still be
+    /// reverted. Returns `None` if there are no snapshots, in which case every new block is final.
+    pub(crate) fn finalized_header(&self) -> Option<SealedHeader> {
+        self.snapshots.values().next().map(|(header, _)| header.clone())
+    }
+
+    /// Resets the best block to the block of the given snapshot and removes all blocks above it.
+    ///
+    /// The snapshot and all snapshots taken after it are removed.
+    pub(crate) fn revert_to_snapshot(&mut self, id: u64) -> Option<SealedHeader> {
+        let (header, total_difficulty) = self.snapshots.get(&id).cloned()?;
+        self.snapshots.split_off(&id);
+
+        let removed = self
+            .hash_to_number
+            .iter()
+            .filter(|(_, number)| **number > header.number)
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        for hash in removed {
+            self.hash_to_number.remove(&hash);
+            self.bodies.remove(&hash);
+        }
+        self.headers.retain(|number, _| *number <= header.number);
+
+        self.best_block = header.number;
+        self.best_hash = header.hash();
+        self.total_difficulty = total_difficulty;
+        Some(header)
+    }
+
     /// Fills in pre-execution header fields based on the current best block and given
     /// transactions.
     pub(crate) fn build_header_template(
@@ -285,7 +396,7 @@ impl StorageInner {
         let mut header = Header {
             parent_hash: self.best_hash,
             ommers_hash: proofs::calculate_ommers_root(ommers),
-            beneficiary: Default::default(),
+            beneficiary: self.coinbase,
             state_root: Default::default(),
             transactions_root: Default::default(),
             receipts_root: Default::default(),
@@ -293,7 +404,7 @@ impl StorageInner {
             logs_bloom: Default::default(),
             difficulty: U256::from(2),
             number: self.best_block + 1,
-            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            gas_limit: self.gas_limit.unwrap_or(ETHEREUM_BLOCK_GAS_LIMIT),
             gas_used: 0,
             timestamp,
             mix_hash: Default::default(),
@@ -336,23 +447,28 @@ impl StorageInner {
         header
     }
 
-    /// Builds and executes a new block with the given transactions, on the provided executor.
+    /// Builds and executes a new block with the given transactions and their senders, on the
+    /// provided executor.
+    ///
+    /// The block gets the given timestamp, or the next timestamp of the chain if `None`.
     ///
-    /// This returns the header of the executed block, as well as the poststate from execution.
+    /// This returns the executed block, as well as the poststate from execution.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn build_and_execute<Provider, Executor>(
         &mut self,
         transactions: Vec<TransactionSigned>,
+        senders: Vec<Address>,
         ommers: Vec<Header>,
+        timestamp: Option<u64>,
         provider: &Provider,
         chain_spec: Arc<ChainSpec>,
         executor: &Executor,
-    ) -> Result<(SealedHeader, ExecutionOutcome), BlockExecutionError>
+    ) -> Result<(SealedBlockWithSenders, ExecutionOutcome), BlockExecutionError>
     where
         Executor: BlockExecutorProvider,
         Provider: StateProviderFactory,
     {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let timestamp = self.next_block_timestamp(timestamp);
 
         // if shanghai is active, include empty withdrawals
         let withdrawals =
@@ -361,7 +477,7 @@ impl StorageInner {
         let requests =
             chain_spec.is_prague_active_at_timestamp(timestamp).then_some(Requests::default());
 
-        let header = self.build_header_template(
+        let mut header = self.build_header_template(
             timestamp,
             &transactions,
             &ommers,
@@ -369,15 +485,27 @@ impl StorageInner {
             requests.as_ref(),
             chain_spec,
         );
-
-        let block = Block {
-            header,
-            body: transactions,
-            ommers: ommers.clone(),
-            withdrawals: withdrawals.clone(),
-            requests: requests.clone(),
+        if let Some(base_fee) = self.next_base_fee.take() {
+            if header.base_fee_per_gas.is_some() {
+                header.base_fee_per_gas = Some(base_fee);
+            }
         }
-        .with_recovered_senders()
+        if let Some(prev_randao) = self.next_prev_randao.take() {
+            header.mix_hash = prev_randao;
+        }
+
+        // the senders are passed in instead of recovered, so that transactions of impersonated
+        // accounts, which are not signed by the sender, can be included
+        let block = BlockWithSenders::new(
+            Block {
+                header,
+                body: transactions,
+                ommers: ommers.clone(),
+                withdrawals: withdrawals.clone(),
+                requests: requests.clone(),
+            },
+            senders,
+        )
         .ok_or(BlockExecutionError::Validation(BlockValidationError::SenderRecoveryError))?;
 
         trace!(target: "consensus::auto", transactions=?&block.body, "executing transactions");
@@ -400,7 +528,7 @@ impl StorageInner {
         // means we need to extract the requests from the execution output and compute the requests
         // root here
 
-        let Block { mut header, body, .. } = block.block;
+        let BlockWithSenders { block: Block { mut header, body, .. }, senders } = block;
         let body = BlockBody { transactions: body, ommers, withdrawals, requests };
 
         trace!(target: "consensus::auto", ?execution_outcome, ?header, ?body, "executed block, calculating state root and completing header");
@@ -410,11 +538,16 @@ impl StorageInner {
         trace!(target: "consensus::auto", root=?header.state_root, ?body, "calculated root");
 
         // finally insert into storage
-        self.insert_new_block(header.clone(), body);
+        self.insert_new_block(header.clone(), body.clone());
 
-        // set new header with hash that should have been updated by insert_new_block
-        let new_header = header.seal(self.best_hash);
+        // seal the block with the hash that should have been updated by insert_new_block
+        let block = BlockWithSenders { block: body.create_block(header), senders };
 
-        Ok((new_header, execution_outcome))
+        Ok((block.seal(self.best_hash), execution_outcome))
     }
 }
+
+/// Returns the current system time as a unix timestamp in seconds.
+pub(crate) fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
@@ -0,0 +1,117 @@
+//! Direct modifications of the latest state, used to override accounts on a dev chain.
+//!
+//! The changes are attributed to the next block, which is sealed right after them, so the state
+//! root of every sealed block still matches the state after it.
+
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRW, DbDupCursorRO},
+    database::Database,
+    models::{AccountBeforeTx, BlockNumberAddress},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_primitives::{keccak256, Address, Bytecode, Bytes, StorageEntry, B256, U256};
+use reth_provider::{BlockNumReader, DatabaseProviderRW, ProviderResult};
+use reth_trie::{HashedPostState, HashedStorage};
+use std::collections::{BTreeMap, HashMap};
+
+/// Changes to apply to a single account of the latest state.
+///
+/// Fields that are `None` are left untouched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountChanges {
+    /// The new balance of the account.
+    pub balance: Option<U256>,
+    /// The new nonce of the account.
+    pub nonce: Option<u64>,
+    /// The new code of the account. Empty code removes the code of the account.
+    pub code: Option<Bytes>,
+    /// Storage slots to overwrite.
+    pub storage: BTreeMap<B256, U256>,
+}
+
+/// Applies the given changes to the latest state, as part of the next block.
+///
+/// The previous values are recorded in the changesets of the next block, unless they were already
+/// recorded there, so that unwinding the block also reverts these changes. The history indices of
+/// the changesets are written together with the block. The hashed state and the state trie are
+/// updated as well, so the state root of the next block includes the changes.
+///
+/// The next block has to be sealed right after, otherwise the state root of the latest block does
+/// not match the latest state.
+pub(crate) fn apply_account_changes<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    address: Address,
+    changes: AccountChanges,
+) -> ProviderResult<()> {
+    let tx = provider.tx_ref();
+    let block = provider.last_block_number()? + 1;
+
+    let old_account = tx.get::<tables::PlainAccountState>(address)?;
+    let mut account = old_account.unwrap_or_default();
+    if let Some(balance) = changes.balance {
+        account.balance = balance;
+    }
+    if let Some(nonce) = changes.nonce {
+        account.nonce = nonce;
+    }
+    if let Some(code) = changes.code {
+        if code.is_empty() {
+            account.bytecode_hash = None;
+        } else {
+            let hash = keccak256(&code);
+            tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+            account.bytecode_hash = Some(hash);
+        }
+    }
+
+    let mut changesets = tx.cursor_dup_write::<tables::AccountChangeSets>()?;
+    if changesets
+        .seek_by_key_subkey(block, address)?
+        .filter(|entry| entry.address == address)
+        .is_none()
+    {
+        changesets.upsert(block, AccountBeforeTx { address, info: old_account })?;
+    }
+    tx.put::<tables::PlainAccountState>(address, account)?;
+
+    let mut hashed_storage = HashedStorage::new(false);
+    if !changes.storage.is_empty() {
+        let mut storage = tx.cursor_dup_write::<tables::PlainStorageState>()?;
+        let mut changesets = tx.cursor_dup_write::<tables::StorageChangeSets>()?;
+        for (slot, value) in changes.storage {
+            let old_value = match storage.seek_by_key_subkey(address, slot)? {
+                Some(entry) if entry.key == slot => {
+                    storage.delete_current()?;
+                    entry.value
+                }
+                _ => U256::ZERO,
+            };
+            if value != U256::ZERO {
+                storage.upsert(address, StorageEntry { key: slot, value })?;
+            }
+
+            let key = BlockNumberAddress((block, address));
+            if changesets.seek_by_key_subkey(key, slot)?.filter(|entry| entry.key == slot).is_none()
+            {
+                changesets.upsert(key, StorageEntry { key: slot, value: old_value })?;
+            }
+
+            hashed_storage.storage.insert(keccak256(slot), value);
+        }
+    }
+
+    // Update the hashed state and the trie, so the next block computes the right state root.
+    let hashed_address = keccak256(address);
+    let hashed_state = HashedPostState {
+        accounts: HashMap::from([(hashed_address, Some(account))]),
+        storages: HashMap::from([(hashed_address, hashed_storage)]),
+    };
+    let (_, trie_updates) =
+        hashed_state.state_root_with_updates(tx).map_err(DatabaseError::from)?;
+    reth_provider::bundle_state::HashedStateChanges(hashed_state).write_to_db(tx)?;
+    trie_updates.flush(tx)?;
+
+    Ok(())
+}
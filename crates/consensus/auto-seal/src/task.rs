@@ -1,14 +1,21 @@
-use crate::{mode::MiningMode, Storage};
+use crate::{
+    handle::{MiningCommand, MiningError, MiningHandle},
+    mode::MiningMode,
+    state::{apply_account_changes, AccountChanges},
+    Storage,
+};
 use futures_util::{future::BoxFuture, FutureExt};
 use reth_beacon_consensus::{BeaconEngineMessage, ForkchoiceStatus};
+use reth_blockchain_tree_api::{BlockValidationKind, BlockchainTreeEngine};
+use reth_db_api::database::Database;
 use reth_engine_primitives::EngineTypes;
 use reth_evm::execute::BlockExecutorProvider;
-use reth_primitives::{ChainSpec, IntoRecoveredTransaction};
-use reth_provider::{CanonChainTracker, StateProviderFactory};
+use reth_primitives::{Address, ChainSpec, IntoRecoveredTransaction, SealedHeader};
+use reth_provider::{CanonChainTracker, ProviderFactory, StateProviderFactory};
 use reth_rpc_types::engine::ForkchoiceState;
 use reth_stages_api::PipelineEvent;
 use reth_tokio_util::EventStream;
-use reth_transaction_pool::{TransactionPool, ValidPoolTransaction};
+use reth_transaction_pool::{PoolTransaction, TransactionPool, ValidPoolTransaction};
 use std::{
     collections::VecDeque,
     future::Future,
@@ -16,15 +23,20 @@ use std::{
     sync::Arc,
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
 use tracing::{debug, error, warn};
 
 /// A Future that listens for new ready transactions and puts new blocks into storage
-pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTypes> {
+pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTypes, DB> {
     /// The configured chain spec
     chain_spec: Arc<ChainSpec>,
     /// The client used to interact with the state
     client: Client,
+    /// The factory used to modify the latest state
+    provider_factory: ProviderFactory<DB>,
     /// The active miner
     miner: MiningMode,
     /// Single active future that inserts a new block into `storage`
@@ -33,20 +45,24 @@ pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTyp
     storage: Storage,
     /// Pool where transactions are stored
     pool: Pool,
-    /// backlog of sets of transactions ready to be mined
-    queued: VecDeque<Vec<Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>>,
+    /// backlog of jobs, like sets of transactions ready to be mined, executed one at a time
+    queued: VecDeque<Job<<Pool as TransactionPool>::Transaction>>,
     // TODO: ideally this would just be a sender of hashes
     to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     /// The pipeline events to listen on
     pipe_line_events: Option<EventStream<PipelineEvent>>,
     /// The type used for block execution
     block_executor: Executor,
+    /// Sender half of the command channel, cloned into new [`MiningHandle`]s
+    to_task: UnboundedSender<MiningCommand>,
+    /// Commands received from [`MiningHandle`]s
+    commands: UnboundedReceiver<MiningCommand>,
 }
 
 // === impl MiningTask ===
 
-impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
-    MiningTask<Client, Pool, Executor, Engine>
+impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes, DB>
+    MiningTask<Client, Pool, Executor, Engine, DB>
 {
     /// Creates a new instance of the task
     #[allow(clippy::too_many_arguments)]
@@ -56,12 +72,15 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
         to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
         storage: Storage,
         client: Client,
+        provider_factory: ProviderFactory<DB>,
         pool: Pool,
         block_executor: Executor,
     ) -> Self {
+        let (to_task, commands) = unbounded_channel();
         Self {
             chain_spec,
             client,
+            provider_factory,
             miner,
             insert_task: None,
             storage,
@@ -70,6 +89,8 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
             queued: Default::default(),
             pipe_line_events: None,
             block_executor,
+            to_task,
+            commands,
         }
     }
 
@@ -77,15 +98,56 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
     pub fn set_pipeline_events(&mut self, events: EventStream<PipelineEvent>) {
         self.pipe_line_events = Some(events);
     }
+
+    /// Returns a new [`MiningHandle`] to control this task.
+    pub fn handle(&self) -> MiningHandle {
+        MiningHandle::new(self.to_task.clone(), self.storage.clone())
+    }
+}
+
+impl<Executor, Client, Pool, Engine, DB> MiningTask<Client, Pool, Executor, Engine, DB>
+where
+    Pool: TransactionPool,
+    Engine: EngineTypes,
+{
+    /// Handles a command of a [`MiningHandle`], either directly or by queueing a job.
+    fn on_command(&mut self, command: MiningCommand) {
+        match command {
+            MiningCommand::Seal { timestamp, tx } => {
+                self.queued.push_back(Job::Seal { timestamp, tx })
+            }
+            MiningCommand::SetAccount { address, changes, tx } => {
+                self.queued.push_back(Job::SetAccount { address, changes, tx })
+            }
+            MiningCommand::Snapshot { tx } => self.queued.push_back(Job::Snapshot { tx }),
+            MiningCommand::Revert { id, tx } => self.queued.push_back(Job::Revert { id, tx }),
+            MiningCommand::IsAutomine { tx } => {
+                let _ = tx.send(matches!(self.miner, MiningMode::Auto(_)));
+            }
+            MiningCommand::SetAutomine(enabled) => {
+                let is_automine = matches!(self.miner, MiningMode::Auto(_));
+                if enabled && !is_automine {
+                    self.miner = MiningMode::instant(1, self.pool.pending_transactions_listener());
+                } else if !enabled && is_automine {
+                    self.miner = MiningMode::None;
+                }
+            }
+            MiningCommand::SetIntervalMining(interval) => {
+                self.miner = interval.map_or(MiningMode::None, MiningMode::interval);
+            }
+        }
+    }
 }
 
-impl<Executor, Client, Pool, Engine> Future for MiningTask<Client, Pool, Executor, Engine>
+impl<Executor, Client, Pool, Engine, DB> Future for MiningTask<Client, Pool, Executor, Engine, DB>
 where
-    Client: StateProviderFactory + CanonChainTracker + Clone + Unpin + 'static,
+    Client:
+        StateProviderFactory + CanonChainTracker + BlockchainTreeEngine + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     <Pool as TransactionPool>::Transaction: IntoRecoveredTransaction,
     Engine: EngineTypes + 'static,
     Executor: BlockExecutorProvider,
+    DB: Database + 'static,
 {
     type Output = ();
 
@@ -94,9 +156,13 @@ where
 
         // this drives block production and
         loop {
+            while let Poll::Ready(Some(command)) = this.commands.poll_recv(cx) {
+                this.on_command(command);
+            }
+
             if let Poll::Ready(transactions) = this.miner.poll(&this.pool, cx) {
                 // miner returned a set of transaction that we feed to the producer
-                this.queued.push_back(transactions);
+                this.queued.push_back(Job::Mine(transactions));
             }
 
             if this.insert_task.is_none() {
@@ -106,95 +172,22 @@ where
                 }
 
                 // ready to queue in new insert task
-                let storage = this.storage.clone();
-                let transactions = this.queued.pop_front().expect("not empty");
-
-                let to_engine = this.to_engine.clone();
-                let client = this.client.clone();
-                let chain_spec = Arc::clone(&this.chain_spec);
-                let pool = this.pool.clone();
+                let job = this.queued.pop_front().expect("not empty");
+                let ctx = JobContext {
+                    chain_spec: Arc::clone(&this.chain_spec),
+                    client: this.client.clone(),
+                    provider_factory: this.provider_factory.clone(),
+                    storage: this.storage.clone(),
+                    pool: this.pool.clone(),
+                    to_engine: this.to_engine.clone(),
+                    block_executor: this.block_executor.clone(),
+                };
                 let events = this.pipe_line_events.take();
-                let executor = this.block_executor.clone();
 
                 // Create the mining future that creates a block, notifies the engine that drives
                 // the pipeline
                 this.insert_task = Some(Box::pin(async move {
-                    let mut storage = storage.write().await;
-
-                    let transactions: Vec<_> = transactions
-                        .into_iter()
-                        .map(|tx| {
-                            let recovered = tx.to_recovered_transaction();
-                            recovered.into_signed()
-                        })
-                        .collect();
-                    let ommers = vec![];
-
-                    match storage.build_and_execute(
-                        transactions.clone(),
-                        ommers.clone(),
-                        &client,
-                        chain_spec,
-                        &executor,
-                    ) {
-                        Ok((new_header, _bundle_state)) => {
-                            // clear all transactions from pool
-                            pool.remove_transactions(
-                                transactions.iter().map(|tx| tx.hash()).collect(),
-                            );
-
-                            let state = ForkchoiceState {
-                                head_block_hash: new_header.hash(),
-                                finalized_block_hash: new_header.hash(),
-                                safe_block_hash: new_header.hash(),
-                            };
-                            drop(storage);
-
-                            // TODO: make this a future
-                            // await the fcu call rx for SYNCING, then wait for a VALID response
-                            loop {
-                                // send the new update to the engine, this will trigger the engine
-                                // to download and execute the block we just inserted
-                                let (tx, rx) = oneshot::channel();
-                                let _ = to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
-                                    state,
-                                    payload_attrs: None,
-                                    tx,
-                                });
-                                debug!(target: "consensus::auto", ?state, "Sent fork choice update");
-
-                                match rx.await.unwrap() {
-                                    Ok(fcu_response) => {
-                                        match fcu_response.forkchoice_status() {
-                                            ForkchoiceStatus::Valid => break,
-                                            ForkchoiceStatus::Invalid => {
-                                                error!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned invalid response");
-                                                return None
-                                            }
-                                            ForkchoiceStatus::Syncing => {
-                                                debug!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned SYNCING, waiting for VALID");
-                                                // wait for the next fork choice update
-                                                continue
-                                            }
-                                        }
-                                    }
-                                    Err(err) => {
-                                        error!(target: "consensus::auto", %err, "Autoseal fork choice update failed");
-                                        return None
-                                    }
-                                }
-                            }
-
-                            // update canon chain for rpc
-                            client.set_canonical_head(new_header.clone());
-                            client.set_safe(new_header.clone());
-                            client.set_finalized(new_header.clone());
-                        }
-                        Err(err) => {
-                            warn!(target: "consensus::auto", %err, "failed to execute block")
-                        }
-                    }
-
+                    ctx.run(job).await;
                     events
                 }));
             }
@@ -216,10 +209,203 @@ where
     }
 }
 
-impl<Client, Pool: TransactionPool, EvmConfig: std::fmt::Debug, Engine: EngineTypes> std::fmt::Debug
-    for MiningTask<Client, Pool, EvmConfig, Engine>
+impl<Client, Pool: TransactionPool, EvmConfig: std::fmt::Debug, Engine: EngineTypes, DB>
+    std::fmt::Debug for MiningTask<Client, Pool, EvmConfig, Engine, DB>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MiningTask").finish_non_exhaustive()
     }
 }
+
+/// A unit of work of the [`MiningTask`]. Jobs are executed one at a time, in order.
+enum Job<T: PoolTransaction> {
+    /// Seal a block with the transactions returned by the miner.
+    Mine(Vec<Arc<ValidPoolTransaction<T>>>),
+    /// Seal a block with all pending transactions.
+    Seal { timestamp: Option<u64>, tx: oneshot::Sender<Result<SealedHeader, MiningError>> },
+    /// Apply changes to an account and seal them in a new block.
+    SetAccount {
+        address: Address,
+        changes: AccountChanges,
+        tx: oneshot::Sender<Result<SealedHeader, MiningError>>,
+    },
+    /// Snapshot the chain at the current block.
+    Snapshot { tx: oneshot::Sender<u64> },
+    /// Revert the chain to the block of a snapshot.
+    Revert { id: u64, tx: oneshot::Sender<Result<bool, MiningError>> },
+}
+
+/// Everything needed to execute a [`Job`] outside of the [`MiningTask`].
+struct JobContext<Client, Pool, Executor, Engine: EngineTypes, DB> {
+    chain_spec: Arc<ChainSpec>,
+    client: Client,
+    provider_factory: ProviderFactory<DB>,
+    storage: Storage,
+    pool: Pool,
+    to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
+    block_executor: Executor,
+}
+
+impl<Client, Pool, Executor, Engine, DB> JobContext<Client, Pool, Executor, Engine, DB>
+where
+    Client: StateProviderFactory + CanonChainTracker + BlockchainTreeEngine,
+    Pool: TransactionPool,
+    <Pool as TransactionPool>::Transaction: IntoRecoveredTransaction,
+    Engine: EngineTypes,
+    Executor: BlockExecutorProvider,
+    DB: Database,
+{
+    /// Executes the job and sends the result back to the [`MiningHandle`] that requested it.
+    async fn run(self, job: Job<Pool::Transaction>) {
+        match job {
+            Job::Mine(mut transactions) => {
+                // an earlier job may have sealed some of the transactions in the meantime
+                transactions.retain(|tx| self.pool.contains(tx.hash()));
+                if transactions.is_empty() {
+                    return
+                }
+                if let Err(err) = self.seal(Some(transactions), None).await {
+                    warn!(target: "consensus::auto", %err, "failed to seal block")
+                }
+            }
+            Job::Seal { timestamp, tx } => {
+                let _ = tx.send(self.seal(None, timestamp).await);
+            }
+            Job::SetAccount { address, changes, tx } => {
+                let _ = tx.send(self.set_account(address, changes).await);
+            }
+            Job::Snapshot { tx } => {
+                let _ = tx.send(self.storage.write().await.snapshot());
+            }
+            Job::Revert { id, tx } => {
+                let _ = tx.send(self.revert(id).await);
+            }
+        }
+    }
+
+    /// Seals a new block and makes it the canonical head.
+    ///
+    /// If no transactions are given, all pending transactions of the pool are included.
+    async fn seal(
+        &self,
+        transactions: Option<Vec<Arc<ValidPoolTransaction<Pool::Transaction>>>>,
+        timestamp: Option<u64>,
+    ) -> Result<SealedHeader, MiningError> {
+        let mut storage = self.storage.write().await;
+
+        let transactions = transactions.unwrap_or_else(|| self.pool.best_transactions().collect());
+        let (transactions, senders): (Vec<_>, Vec<_>) = transactions
+            .into_iter()
+            .map(|tx| {
+                let recovered = tx.to_recovered_transaction();
+                let signer = recovered.signer();
+                (recovered.into_signed(), signer)
+            })
+            .unzip();
+        let ommers = vec![];
+
+        let (block, _bundle_state) = storage.build_and_execute(
+            transactions,
+            senders,
+            ommers,
+            timestamp,
+            &self.client,
+            Arc::clone(&self.chain_spec),
+            &self.block_executor,
+        )?;
+        let new_header = block.header.clone();
+        let finalized_header = storage.finalized_header().unwrap_or_else(|| new_header.clone());
+        drop(storage);
+
+        // clear all transactions from pool
+        self.pool.remove_transactions(block.body.iter().map(|tx| tx.hash()).collect());
+
+        // insert the block with its senders, since the senders of impersonated transactions can't
+        // be recovered from their signatures
+        self.client.insert_block(block, BlockValidationKind::Exhaustive)?;
+
+        // blocks above the earliest snapshot must not be finalized, so they can still be reverted
+        let state = ForkchoiceState {
+            head_block_hash: new_header.hash(),
+            finalized_block_hash: finalized_header.hash(),
+            safe_block_hash: finalized_header.hash(),
+        };
+
+        // TODO: make this a future
+        // await the fcu call rx for SYNCING, then wait for a VALID response
+        loop {
+            // send the new update to the engine, this will trigger the engine
+            // to make the block we just inserted canonical
+            let (tx, rx) = oneshot::channel();
+            let _ = self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
+                state,
+                payload_attrs: None,
+                tx,
+            });
+            debug!(target: "consensus::auto", ?state, "Sent fork choice update");
+
+            match rx.await.map_err(|_| MiningError::TaskClosed)? {
+                Ok(fcu_response) => match fcu_response.forkchoice_status() {
+                    ForkchoiceStatus::Valid => break,
+                    ForkchoiceStatus::Invalid => {
+                        error!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned invalid response");
+                        return Err(MiningError::ForkchoiceUpdate(new_header.hash()))
+                    }
+                    ForkchoiceStatus::Syncing => {
+                        debug!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned SYNCING, waiting for VALID");
+                        // wait for the next fork choice update
+                        continue
+                    }
+                },
+                Err(err) => {
+                    error!(target: "consensus::auto", %err, "Autoseal fork choice update failed");
+                    return Err(MiningError::ForkchoiceUpdate(new_header.hash()))
+                }
+            }
+        }
+
+        // update canon chain for rpc
+        self.client.set_canonical_head(new_header.clone());
+        self.client.set_safe(finalized_header.clone());
+        self.client.set_finalized(finalized_header);
+
+        Ok(new_header)
+    }
+
+    /// Applies the changes to the account and seals them in a new empty block.
+    ///
+    /// The changes are written as part of the next block before it's sealed, so the state root of
+    /// the new block includes them and unwinding it reverts them.
+    async fn set_account(
+        &self,
+        address: Address,
+        changes: AccountChanges,
+    ) -> Result<SealedHeader, MiningError> {
+        let provider = self.provider_factory.provider_rw()?;
+        apply_account_changes(&provider, address, changes)?;
+        provider.commit()?;
+
+        // The latest state was changed without going through the tree, so the tree's view of it is
+        // stale.
+        self.client.invalidate_canonical_tip_state();
+
+        self.seal(Some(Vec::new()), None).await
+    }
+
+    /// Reverts the chain to the block of the given snapshot.
+    async fn revert(&self, id: u64) -> Result<bool, MiningError> {
+        let mut storage = self.storage.write().await;
+        let Some(header) = storage.snapshot_header(id).cloned() else { return Ok(false) };
+
+        self.client.unwind(header.number)?;
+        storage.revert_to_snapshot(id);
+        let finalized_header = storage.finalized_header().unwrap_or_else(|| header.clone());
+        drop(storage);
+
+        self.client.set_canonical_head(header);
+        self.client.set_safe(finalized_header.clone());
+        self.client.set_finalized(finalized_header);
+
+        Ok(true)
+    }
+}
@@ -16,6 +16,8 @@ pub enum BeaconConsensusEngineEvent {
     LiveSyncProgress(ConsensusEngineLiveSyncProgress),
     /// A block was added to the fork chain.
     ForkBlockAdded(Arc<SealedBlock>),
+    /// A block was rejected as invalid, and the validation error
+    InvalidBlock(Arc<SealedBlock>, String),
 }
 
 /// Progress of the consensus engine during live sync.
@@ -1440,6 +1440,10 @@ where
     /// Updates the internal sync state depending on the pipeline configuration,
     /// the outcome of the pipeline run and the last observed forkchoice state.
     fn on_pipeline_outcome(&mut self, ctrl: ControlFlow) -> RethResult<()> {
+        // The pipeline wrote to the database outside of the tree, so the state cached for the
+        // canonical tip may be stale.
+        self.blockchain.clear_state_cache();
+
         // Pipeline unwound, memorize the invalid block and wait for CL for next sync target.
         if let ControlFlow::Unwind { bad_block, .. } = ctrl {
             warn!(target: "consensus::engine", invalid_hash=?bad_block.hash(), invalid_number=?bad_block.number, "Bad block detected in unwind");
@@ -1665,10 +1669,15 @@ where
                         } else {
                             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
                         };
-                        // keep track of the invalid header
-                        self.invalid_headers.insert(block.header);
+                        // keep track of the invalid header and the rejected block
+                        let validation_error = error.to_string();
+                        self.invalid_headers.insert(block.header.clone());
+                        self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                            Arc::new(block),
+                            validation_error.clone(),
+                        ));
                         PayloadStatus::new(
-                            PayloadStatusEnum::Invalid { validation_error: error.to_string() },
+                            PayloadStatusEnum::Invalid { validation_error },
                             latest_valid_hash,
                         )
                     }
@@ -1775,7 +1784,11 @@ where
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
 
-                            self.invalid_headers.insert(block.header);
+                            self.invalid_headers.insert(block.header.clone());
+                            self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                                Arc::new(block),
+                                err.to_string(),
+                            ));
                         }
                     }
                 }
@@ -101,6 +101,24 @@ where
             stats: BlockExecutorStats::default(),
         }
     }
+
+    fn prefetch_transactions<DB, I>(
+        &self,
+        db: DB,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        transactions: I,
+    ) where
+        DB: Database<Error = ProviderError>,
+        I: Iterator<Item = usize>,
+    {
+        let mut executor = EthBlockExecutor::new(
+            self.chain_spec.clone(),
+            self.evm_config.clone(),
+            State::builder().with_database(db).build(),
+        );
+        executor.prefetch_transactions(block, total_difficulty, transactions);
+    }
 }
 
 /// Helper type for the output of executing a block.
@@ -303,6 +321,33 @@ where
         Ok(output)
     }
 
+    /// Speculatively executes the transactions of the block at the given indices on top of the
+    /// internal state, ignoring transactions that fail.
+    ///
+    /// This does not apply any pre-execution or post-execution changes.
+    fn prefetch_transactions(
+        &mut self,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        transactions: impl Iterator<Item = usize>,
+    ) {
+        self.on_new_block(&block.header);
+
+        let env = self.evm_env_for_block(&block.header, total_difficulty);
+        let mut evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
+        for index in transactions {
+            let (Some(transaction), Some(sender)) =
+                (block.body.get(index), block.senders.get(index))
+            else {
+                break
+            };
+            EvmConfig::fill_tx_env(evm.tx_mut(), transaction, *sender);
+            if let Ok(ResultAndState { state, .. }) = evm.transact() {
+                evm.db_mut().commit(state);
+            }
+        }
+    }
+
     /// Apply settings before a new block is executed.
     pub(crate) fn on_new_block(&mut self, header: &Header) {
         // Set state clear flag if the block is after the Spurious Dragon hardfork.
@@ -461,9 +506,10 @@ mod tests {
         eip4788::{BEACON_ROOTS_ADDRESS, BEACON_ROOTS_CODE, SYSTEM_ADDRESS},
         eip7002::{WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_CODE},
     };
+    use reth_evm::prefetch::{PrefetchCache, Prefetcher};
     use reth_primitives::{
         constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
-        keccak256, public_key_to_address, Account, Block, ChainSpecBuilder, ForkCondition,
+        keccak256, public_key_to_address, Account, Address, Block, ChainSpecBuilder, ForkCondition,
         Transaction, TxKind, TxLegacy, B256,
     };
     use reth_revm::{
@@ -1405,4 +1451,83 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn prefetch_does_not_change_execution() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+        let mut db = StateProviderTest::default();
+
+        let secp = Secp256k1::new();
+        let senders = [
+            Keypair::new(&secp, &mut generators::rng()),
+            Keypair::new(&secp, &mut generators::rng()),
+        ];
+        for key_pair in &senders {
+            db.insert_account(
+                public_key_to_address(key_pair.public_key()),
+                Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+                None,
+                HashMap::new(),
+            );
+        }
+
+        let mut header = chain_spec.genesis_header();
+        header.gas_limit = 1_000_000;
+        let recipient = Address::with_last_byte(0xaa);
+        let body = [(0, 0), (0, 1), (1, 0)]
+            .into_iter()
+            .map(|(sender, nonce)| {
+                sign_tx_with_key_pair(
+                    senders[sender],
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price: header.base_fee_per_gas.unwrap().into(),
+                        gas_limit: 21_000,
+                        to: TxKind::Call(recipient),
+                        value: U256::from(1),
+                        input: Bytes::default(),
+                    }),
+                )
+            })
+            .collect();
+        let block = Block { header, body, ommers: vec![], withdrawals: None, requests: None }
+            .with_recovered_senders()
+            .unwrap();
+
+        let provider = executor_provider(chain_spec);
+        let expected = provider
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+
+        // warm the cache with all transactions first, so the canonical execution reads from it
+        let cache = PrefetchCache::default();
+        provider.prefetch_transactions(
+            cache.warming(StateProviderDatabase::new(&db)),
+            &block,
+            U256::ZERO,
+            0..block.body.len(),
+        );
+        let output = Prefetcher::default().execute_with_prefetch(
+            &provider,
+            &block,
+            U256::ZERO,
+            &cache,
+            vec![StateProviderDatabase::new(&db), StateProviderDatabase::new(&db)],
+            || {
+                provider
+                    .executor(cache.canonical(StateProviderDatabase::new(&db)))
+                    .execute((&block, U256::ZERO).into())
+                    .unwrap()
+            },
+        );
+
+        assert!(cache.stats().hits > 0);
+        assert_eq!(output.state, expected.state);
+        assert_eq!(output.receipts, expected.receipts);
+        assert_eq!(output.requests, expected.requests);
+        assert_eq!(output.gas_used, expected.gas_used);
+        assert_eq!(output.gas_used, 3 * 21_000);
+    }
 }
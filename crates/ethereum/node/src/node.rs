@@ -4,6 +4,7 @@ use crate::{EthEngineTypes, EthEvmConfig};
 use reth_auto_seal_consensus::AutoSealConsensus;
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
 use reth_beacon_consensus::EthBeaconConsensus;
+use reth_ethereum_payload_builder::{BundleSelector, GreedySelector, TransactionSelector};
 use reth_evm_ethereum::execute::EthExecutorProvider;
 use reth_network::NetworkHandle;
 use reth_node_builder::{
@@ -12,7 +13,7 @@ use reth_node_builder::{
         PayloadServiceBuilder, PoolBuilder,
     },
     node::{FullNodeTypes, NodeTypes},
-    BuilderContext, Node, PayloadBuilderConfig,
+    BuilderContext, Node, PayloadBuilderConfig, TransactionSelectorKind,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::CanonStateSubscriptions;
@@ -139,19 +140,35 @@ where
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+            if let Some(snapshot_config) =
+                ctx.config().txpool.pool_snapshot_config(data_dir.txpool_snapshot())
+            {
+                // the snapshot includes the local transactions
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "txpool snapshot task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::pool_snapshot_task(
+                            shutdown,
+                            pool.clone(),
+                            snapshot_config,
+                        )
+                    },
+                );
+            } else {
+                let transactions_backup_config =
+                    reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
 
-            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
-                "local transactions backup task",
-                |shutdown| {
-                    reth_transaction_pool::maintain::backup_local_transactions_task(
-                        shutdown,
-                        pool.clone(),
-                        transactions_backup_config,
-                    )
-                },
-            );
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "local transactions backup task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::backup_local_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            transactions_backup_config,
+                        )
+                    },
+                );
+            }
 
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
@@ -186,30 +203,54 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
-        let conf = ctx.payload_builder_config();
+        match ctx.payload_builder_config().transaction_selector() {
+            TransactionSelectorKind::Greedy => {
+                spawn_payload_service(ctx, pool, GreedySelector::default())
+            }
+            TransactionSelectorKind::Bundle => {
+                spawn_payload_service(ctx, pool, BundleSelector::default())
+            }
+        }
+    }
+}
+
+/// Spawns the payload service with an Ethereum payload builder that selects the transactions of
+/// the payloads with the given [`TransactionSelector`].
+fn spawn_payload_service<Node, Pool, Selector>(
+    ctx: &BuilderContext<Node>,
+    pool: Pool,
+    selector: Selector,
+) -> eyre::Result<PayloadBuilderHandle<Node::Engine>>
+where
+    Node: FullNodeTypes<Engine = EthEngineTypes>,
+    Pool: TransactionPool + Unpin + 'static,
+    Selector: TransactionSelector + Clone + Unpin + 'static,
+{
+    let payload_builder =
+        reth_ethereum_payload_builder::EthereumPayloadBuilder::default().with_selector(selector);
+    let conf = ctx.payload_builder_config();
 
-        let payload_job_config = BasicPayloadJobGeneratorConfig::default()
-            .interval(conf.interval())
-            .deadline(conf.deadline())
-            .max_payload_tasks(conf.max_payload_tasks())
-            .extradata(conf.extradata_bytes());
+    let payload_job_config = BasicPayloadJobGeneratorConfig::default()
+        .interval(conf.interval())
+        .deadline(conf.deadline())
+        .max_payload_tasks(conf.max_payload_tasks())
+        .resolve_grace_period(conf.resolve_grace_period())
+        .extradata(conf.extradata_bytes());
 
-        let payload_generator = BasicPayloadJobGenerator::with_builder(
-            ctx.provider().clone(),
-            pool,
-            ctx.task_executor().clone(),
-            payload_job_config,
-            ctx.chain_spec(),
-            payload_builder,
-        );
-        let (payload_service, payload_builder) =
-            PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
+    let payload_generator = BasicPayloadJobGenerator::with_builder(
+        ctx.provider().clone(),
+        pool,
+        ctx.task_executor().clone(),
+        payload_job_config,
+        ctx.chain_spec(),
+        payload_builder,
+    );
+    let (payload_service, payload_builder) =
+        PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
 
-        ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
+    ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
 
-        Ok(payload_builder)
-    }
+    Ok(payload_builder)
 }
 
 /// A basic ethereum payload service.
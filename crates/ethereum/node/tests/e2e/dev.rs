@@ -1,9 +1,12 @@
 use crate::utils::EthNode;
 use futures::StreamExt;
-use reth::rpc::eth::EthTransactions;
+use reth::rpc::{api::clients::AnvilApiClient, eth::EthTransactions, types::TransactionRequest};
 use reth_e2e_test_utils::setup;
-use reth_primitives::{b256, hex, ChainSpec, Genesis};
-use reth_provider::CanonStateSubscriptions;
+use reth_primitives::{address, b256, hex, Bytes, ChainSpec, Genesis, B256, U256};
+use reth_provider::{
+    BlockNumReader, CanonStateSubscriptions, HeaderProvider, StateProviderFactory,
+    StateRootProvider,
+};
 use std::sync::Arc;
 
 #[tokio::test]
@@ -15,6 +18,122 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_control_dev_node_with_anvil_api() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let (mut nodes, _tasks, _) = setup(1, custom_chain(), true).await?;
+    let node = nodes.pop().unwrap();
+    let provider = node.inner.provider.clone();
+    let client = node.inner.rpc_server_handles.rpc.http_client().unwrap();
+
+    // mine blocks on demand, with timestamps `interval` seconds apart
+    AnvilApiClient::anvil_mine(&client, Some(U256::from(3)), Some(U256::from(10))).await?;
+    assert_eq!(provider.best_block_number()?, 3);
+    let (second, third) =
+        (provider.header_by_number(2)?.unwrap(), provider.header_by_number(3)?.unwrap());
+    assert_eq!(third.timestamp, second.timestamp + 10);
+
+    let timestamp = third.timestamp + 1_000;
+    AnvilApiClient::anvil_set_next_block_timestamp(&client, timestamp).await?;
+    AnvilApiClient::anvil_mine(&client, None, None).await?;
+    assert_eq!(provider.header_by_number(4)?.unwrap().timestamp, timestamp);
+
+    // override an account, every override is sealed in a new block
+    let account = address!("00000000000000000000000000000000000000aa");
+    let code = Bytes::from_static(&[0x60, 0x00]);
+    let slot = B256::with_last_byte(1);
+    AnvilApiClient::anvil_set_balance(&client, account, U256::from(42)).await?;
+    AnvilApiClient::anvil_set_nonce(&client, account, U256::from(7)).await?;
+    AnvilApiClient::anvil_set_code(&client, account, code.clone()).await?;
+    AnvilApiClient::anvil_set_storage_at(&client, account, U256::from(1), B256::with_last_byte(2))
+        .await?;
+
+    assert_eq!(provider.best_block_number()?, 8);
+    let tip = provider.sealed_header(8)?.unwrap();
+    assert_eq!(tip.state_root, provider.latest()?.state_root(&Default::default())?);
+
+    let state = provider.latest()?;
+    assert_eq!(state.account_balance(account)?, Some(U256::from(42)));
+    assert_eq!(state.account_nonce(account)?, Some(7));
+    assert_eq!(state.account_code(account)?.unwrap().original_bytes(), code);
+    assert_eq!(state.storage(account, slot)?, Some(U256::from(2)));
+
+    // the next block is built on top of the overridden state
+    AnvilApiClient::anvil_mine(&client, None, None).await?;
+    assert_eq!(provider.best_block_number()?, 9);
+
+    // revert to a snapshot, which also reverts the overrides made after it
+    let snapshot = AnvilApiClient::anvil_snapshot(&client).await?;
+    AnvilApiClient::anvil_set_balance(&client, account, U256::from(1)).await?;
+    AnvilApiClient::anvil_mine(&client, Some(U256::from(2)), None).await?;
+    assert_eq!(provider.best_block_number()?, 12);
+
+    assert!(AnvilApiClient::anvil_revert(&client, snapshot).await?);
+    assert_eq!(provider.best_block_number()?, 9);
+    assert_eq!(provider.latest()?.account_balance(account)?, Some(U256::from(42)));
+    assert!(!AnvilApiClient::anvil_revert(&client, snapshot).await?);
+
+    // send a transaction from an impersonated account
+    let sender = address!("00000000000000000000000000000000000000bb");
+    AnvilApiClient::anvil_set_balance(&client, sender, U256::from(10).pow(U256::from(18))).await?;
+    AnvilApiClient::anvil_impersonate_account(&client, sender).await?;
+
+    let mut notifications = provider.canonical_state_stream();
+    let eth_api = node.inner.rpc_registry.eth_api();
+    let hash = eth_api
+        .send_transaction(TransactionRequest {
+            from: Some(sender),
+            to: Some(account.into()),
+            value: Some(U256::from(1)),
+            ..Default::default()
+        })
+        .await?;
+
+    let head = notifications.next().await.unwrap();
+    assert_eq!(head.tip().transactions().next().unwrap().hash(), hash);
+    let receipt = eth_api.transaction_receipt(hash).await?.unwrap();
+    assert_eq!(receipt.from, sender);
+    assert_eq!(provider.latest()?.account_balance(account)?, Some(U256::from(43)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dev_node_executes_blocks_on_overridden_state() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let (mut nodes, _tasks, _) = setup(1, custom_chain(), true).await?;
+    let node = nodes.pop().unwrap();
+    let provider = node.inner.provider.clone();
+    let client = node.inner.rpc_server_handles.rpc.http_client().unwrap();
+    let eth_api = node.inner.rpc_registry.eth_api();
+
+    let sender = address!("00000000000000000000000000000000000000cc");
+    let recipient = address!("00000000000000000000000000000000000000dd");
+    AnvilApiClient::anvil_set_balance(&client, sender, U256::from(10).pow(U256::from(18))).await?;
+    AnvilApiClient::anvil_impersonate_account(&client, sender).await?;
+    let mut notifications = provider.canonical_state_stream();
+    let transfer = TransactionRequest {
+        from: Some(sender),
+        to: Some(recipient.into()),
+        value: Some(U256::from(1)),
+        ..Default::default()
+    };
+
+    // insert a block that touches the recipient
+    eth_api.send_transaction(transfer.clone()).await?;
+    notifications.next().await.unwrap();
+    assert_eq!(provider.latest()?.account_balance(recipient)?, Some(U256::from(1)));
+
+    // override the recipient in a new block, the next block has to be executed on top of it
+    AnvilApiClient::anvil_set_balance(&client, recipient, U256::from(100)).await?;
+    notifications.next().await.unwrap();
+    eth_api.send_transaction(transfer).await?;
+    notifications.next().await.unwrap();
+    assert_eq!(provider.latest()?.account_balance(recipient)?, Some(U256::from(101)));
+
+    Ok(())
+}
+
 async fn assert_chain_advances(mut node: EthNode) {
     let mut notifications = node.inner.provider.canonical_state_stream();
 
@@ -0,0 +1,217 @@
+//! Simulation of the bundles of the bundle pool during payload building.
+
+use reth_evm::ConfigureEvm;
+use reth_payload_builder::error::PayloadBuilderError;
+use reth_primitives::{
+    revm::env::tx_env_with_recovered,
+    revm_primitives::{
+        db::{Database, DatabaseRef},
+        AccountInfo, Address, Bytecode, B256,
+    },
+    TransactionSignedEcRecovered, U256,
+};
+use reth_transaction_pool::MevBundle;
+use revm::{
+    db::CacheDB,
+    primitives::{
+        BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, ExecutionResult,
+        ResultAndState, State as EvmState,
+    },
+    DatabaseCommit,
+};
+use std::{cell::RefCell, collections::HashSet};
+use tracing::trace;
+
+/// A bundle that was executed on top of the state of the block that is being built.
+#[derive(Debug)]
+pub(crate) struct ExecutedBundle {
+    /// The transactions of the bundle with their results and state changes, in order.
+    pub(crate) transactions: Vec<(TransactionSignedEcRecovered, ExecutionResult, EvmState)>,
+    /// The gas used by all transactions of the bundle.
+    pub(crate) gas_used: u64,
+    /// The increase of the balance of the coinbase.
+    pub(crate) coinbase_profit: U256,
+    /// The balance of the coinbase before the bundle.
+    pub(crate) coinbase_before: U256,
+}
+
+impl ExecutedBundle {
+    /// Returns the profit of the bundle per unit of gas.
+    pub(crate) fn profit_per_gas(&self) -> U256 {
+        self.coinbase_profit.checked_div(U256::from(self.gas_used)).unwrap_or_default()
+    }
+
+    /// Returns `true` if the transactions of the bundle still fit into the given gas limit, with
+    /// the same check [`simulate_bundle`] applies before executing each transaction.
+    pub(crate) fn fits(&self, gas_limit: u64) -> bool {
+        let mut gas_used = 0;
+        self.transactions.iter().all(|(tx, result, _)| {
+            let fits = gas_used + tx.gas_limit() <= gas_limit;
+            gas_used += result.gas_used();
+            fits
+        })
+    }
+
+    /// Moves the coinbase balances of the state changes of the bundle onto the given balance of
+    /// the coinbase, as if the bundle had been executed on top of it.
+    pub(crate) fn rebase_coinbase(&mut self, coinbase: Address, coinbase_before: U256) {
+        for (_, _, state) in &mut self.transactions {
+            if let Some(account) = state.get_mut(&coinbase) {
+                let balance = &mut account.info.balance;
+                *balance = if coinbase_before >= self.coinbase_before {
+                    balance.saturating_add(coinbase_before - self.coinbase_before)
+                } else {
+                    balance.saturating_sub(self.coinbase_before - coinbase_before)
+                };
+            }
+        }
+        self.coinbase_before = coinbase_before;
+    }
+}
+
+/// The state changed by the transactions that were committed to the block so far.
+///
+/// A bundle that was simulated before these transactions can be included without simulating it
+/// again if it didn't read any of the changed state. Every transaction pays the coinbase, so the
+/// balance of the coinbase is not tracked and the simulation is moved onto the current balance
+/// with [`ExecutedBundle::rebase_coinbase`] instead. Bundles that read the balance of the coinbase
+/// are not detected.
+#[derive(Debug, Default)]
+pub(crate) struct ChangedState {
+    /// The accounts whose balance, nonce or code changed.
+    accounts: HashSet<Address>,
+    /// The storage slots that changed.
+    slots: HashSet<(Address, U256)>,
+}
+
+impl ChangedState {
+    /// Records the state changes of a committed transaction.
+    pub(crate) fn record(
+        &mut self,
+        tx: &TransactionSignedEcRecovered,
+        state: &EvmState,
+        coinbase: Address,
+    ) {
+        for (address, account) in state {
+            if !account.is_touched() {
+                continue
+            }
+            // the nonce of the coinbase only changes if it sends the transaction
+            if *address != coinbase || tx.signer() == coinbase {
+                self.accounts.insert(*address);
+            }
+            self.slots.extend(
+                account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, _)| (*address, *key)),
+            );
+        }
+    }
+
+    /// Returns `true` if the simulation of the bundle read any of the changed state.
+    pub(crate) fn affects(&self, bundle: &ExecutedBundle) -> bool {
+        bundle.transactions.iter().any(|(_, _, state)| {
+            state.iter().any(|(address, account)| {
+                self.accounts.contains(address) ||
+                    account.storage.keys().any(|key| self.slots.contains(&(*address, *key)))
+            })
+        })
+    }
+}
+
+/// Executes the transactions of the bundle on top of the given database, without committing the
+/// state changes.
+///
+/// Returns `None` if the bundle can't be included: if one of its transactions is invalid, reverts
+/// without being allowed to, or doesn't fit into the remaining gas of the block. Blob transactions
+/// are not supported in bundles.
+pub(crate) fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    db: &mut DB,
+    cfg: &CfgEnvWithHandlerCfg,
+    block_env: &BlockEnv,
+    bundle: &MevBundle,
+    gas_limit: u64,
+) -> Result<Option<ExecutedBundle>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm,
+    DB: Database<Error = reth_errors::ProviderError>,
+{
+    // state changes of a transaction must be visible to the next transactions of the bundle, so the
+    // bundle is executed on a cache on top of the database
+    let mut cache = CacheDB::new(DatabaseRefAdapter { inner: RefCell::new(db) });
+    let coinbase = block_env.coinbase;
+    let coinbase_before = cache.basic(coinbase)?.map(|account| account.balance).unwrap_or_default();
+
+    let mut transactions = Vec::with_capacity(bundle.transactions.len());
+    let mut gas_used = 0;
+    for tx in &bundle.transactions {
+        let transaction = &tx.transaction;
+        if transaction.is_eip4844() || gas_used + transaction.gas_limit() > gas_limit {
+            return Ok(None)
+        }
+
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg.clone(),
+            block_env.clone(),
+            tx_env_with_recovered(transaction),
+        );
+        let mut evm = evm_config.evm_with_env(&mut cache, env);
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(EVMError::Transaction(err)) => {
+                trace!(target: "payload_builder", %err, tx=?transaction.hash(), "skipping bundle with invalid transaction");
+                return Ok(None)
+            }
+            Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+        };
+        drop(evm);
+
+        if !result.is_success() && !tx.can_revert {
+            trace!(target: "payload_builder", tx=?transaction.hash(), "skipping bundle with reverted transaction");
+            return Ok(None)
+        }
+
+        gas_used += result.gas_used();
+        cache.commit(state.clone());
+        transactions.push((transaction.clone(), result, state));
+    }
+
+    let coinbase_after = cache.basic(coinbase)?.map(|account| account.balance).unwrap_or_default();
+    Ok(Some(ExecutedBundle {
+        transactions,
+        gas_used,
+        coinbase_profit: coinbase_after.saturating_sub(coinbase_before),
+        coinbase_before,
+    }))
+}
+
+/// Wraps a mutable [`Database`] to be used as a [`DatabaseRef`].
+///
+/// All reads go through the wrapped database, so the accounts and slots the bundle touches are
+/// loaded into its cache.
+struct DatabaseRefAdapter<'a, DB> {
+    inner: RefCell<&'a mut DB>,
+}
+
+impl<'a, DB: Database> DatabaseRef for DatabaseRefAdapter<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.inner.borrow_mut().basic(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.inner.borrow_mut().code_by_hash(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.inner.borrow_mut().storage(address, index)
+    }
+
+    fn block_hash_ref(&self, number: U256) -> Result<B256, Self::Error> {
+        self.inner.borrow_mut().block_hash(number)
+    }
+}
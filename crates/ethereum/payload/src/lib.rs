@@ -9,6 +9,16 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
+mod bundle;
+mod metrics;
+mod prefix;
+mod selector;
+
+pub use selector::{BestTransactionsFor, BundleSelector, GreedySelector, TransactionSelector};
+
+use bundle::{simulate_bundle, ChangedState, ExecutedBundle};
+use metrics::TransactionSelectorMetrics;
+use prefix::{ExecutionPrefix, InvalidTransactionExecution};
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, post_block_withdrawal_requests_contract_call,
     pre_block_beacon_root_contract_call, BuildArguments, BuildOutcome, PayloadBuilder,
@@ -18,7 +28,8 @@ use reth_errors::RethError;
 use reth_evm::ConfigureEvm;
 use reth_evm_ethereum::{eip6110::parse_deposits_from_receipts, EthEvmConfig};
 use reth_payload_builder::{
-    error::PayloadBuilderError, EthBuiltPayload, EthPayloadBuilderAttributes,
+    database::CachedExecution, error::PayloadBuilderError, EthBuiltPayload,
+    EthPayloadBuilderAttributes,
 };
 use reth_primitives::{
     constants::{
@@ -35,21 +46,30 @@ use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
-    DatabaseCommit, State,
+    Database, DatabaseCommit, State,
 };
 use tracing::{debug, trace, warn};
 
 /// Ethereum payload builder
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig, Selector = BundleSelector> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// The strategy that selects the transactions of the payloads.
+    selector: Selector,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { evm_config }
+        Self { evm_config, selector: BundleSelector }
+    }
+}
+
+impl<EvmConfig, Selector> EthereumPayloadBuilder<EvmConfig, Selector> {
+    /// Sets the [`TransactionSelector`] that selects the transactions of the payloads.
+    pub fn with_selector<S>(self, selector: S) -> EthereumPayloadBuilder<EvmConfig, S> {
+        EthereumPayloadBuilder { evm_config: self.evm_config, selector }
     }
 }
 
@@ -60,9 +80,11 @@ impl Default for EthereumPayloadBuilder {
 }
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<EvmConfig, Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder<EvmConfig>
+impl<EvmConfig, Selector, Pool, Client> PayloadBuilder<Pool, Client>
+    for EthereumPayloadBuilder<EvmConfig, Selector>
 where
     EvmConfig: ConfigureEvm,
+    Selector: TransactionSelector + Clone,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
@@ -73,7 +95,7 @@ where
         &self,
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload_builder(self.evm_config.clone(), args)
+        default_ethereum_payload_builder(self.evm_config.clone(), &self.selector, args)
     }
 
     fn build_empty_payload(
@@ -236,22 +258,26 @@ where
     }
 }
 
-/// Constructs an Ethereum transaction payload using the best transactions from the pool.
+/// Constructs an Ethereum transaction payload using the bundles and transactions chosen by the
+/// [`TransactionSelector`].
 ///
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
 #[inline]
-pub fn default_ethereum_payload_builder<EvmConfig, Pool, Client>(
+pub fn default_ethereum_payload_builder<EvmConfig, Selector, Pool, Client>(
     evm_config: EvmConfig,
+    selector: &Selector,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
     EvmConfig: ConfigureEvm,
+    Selector: TransactionSelector,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
     let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload } = args;
+    let mut prefix = ExecutionPrefix::new(cached_reads.take_executions());
 
     let state_provider = client.state_by_block_hash(config.parent_block.hash())?;
     let state = StateProviderDatabase::new(state_provider);
@@ -275,10 +301,16 @@ where
 
     let mut executed_txs = Vec::new();
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
-        base_fee,
-        initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    let mut best_txs = selector.best_transactions(
+        &pool,
+        BestTransactionsAttributes::new(
+            base_fee,
+            initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
+        ),
+    );
+    let mut included_bundles = 0;
+    let mut included_txs = 0;
+    let mut skipped_txs = 0;
 
     let mut total_fees = U256::ZERO;
 
@@ -305,6 +337,91 @@ where
     .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
     let mut receipts = Vec::new();
+
+    // include the most profitable bundles of the selector at the top of the block
+    let mut bundles = Vec::new();
+    for bundle in selector.bundles(&pool, block_number, attributes.timestamp) {
+        if let Some(executed) = simulate_bundle(
+            &evm_config,
+            &mut db,
+            &initialized_cfg,
+            &initialized_block_env,
+            &bundle,
+            block_gas_limit,
+        )? {
+            if executed.coinbase_profit > U256::ZERO {
+                bundles.push((executed.profit_per_gas(), bundle, executed));
+            }
+        }
+    }
+    bundles.sort_unstable_by(|(a, ..), (b, ..)| b.cmp(a));
+
+    let coinbase = initialized_block_env.coinbase;
+    let mut changed_state = ChangedState::default();
+    for (_, bundle, mut simulated) in bundles {
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        let bundle_hash = bundle.hash();
+        let execution = match prefix.reuse(bundle_hash) {
+            Some(execution) => execution,
+            None => {
+                let remaining_gas = block_gas_limit - cumulative_gas_used;
+                let executed =
+                    if changed_state.affects(&simulated) || !simulated.fits(remaining_gas) {
+                        // bundles included before changed the state the bundle read, or it no
+                        // longer fits
+                        let Some(executed) = simulate_bundle(
+                            &evm_config,
+                            &mut db,
+                            &initialized_cfg,
+                            &initialized_block_env,
+                            &bundle,
+                            remaining_gas,
+                        )?
+                        else {
+                            continue
+                        };
+                        executed
+                    } else {
+                        let coinbase_before =
+                            db.basic(coinbase)?.map(|account| account.balance).unwrap_or_default();
+                        simulated.rebase_coinbase(coinbase, coinbase_before);
+                        simulated
+                    };
+                let ExecutedBundle { transactions, coinbase_profit, .. } = executed;
+                CachedExecution { hash: bundle_hash, transactions, fees: coinbase_profit }
+            }
+        };
+        let coinbase_profit = execution.fees;
+        if coinbase_profit == U256::ZERO {
+            continue
+        }
+
+        trace!(target: "payload_builder", bundle=?bundle_hash, ?coinbase_profit, "including bundle");
+        for (tx, result, state) in execution.transactions.clone() {
+            changed_state.record(&tx, &state, coinbase);
+            db.commit(state);
+            cumulative_gas_used += result.gas_used();
+
+            #[allow(clippy::needless_update)] // side-effect of optimism fields
+            receipts.push(Some(Receipt {
+                tx_type: tx.tx_type(),
+                success: result.is_success(),
+                cumulative_gas_used,
+                logs: result.into_logs().into_iter().map(Into::into).collect(),
+                ..Default::default()
+            }));
+            executed_txs.push(tx.into_signed());
+        }
+        // the profit of the bundle includes the priority fees of its transactions
+        total_fees += coinbase_profit;
+        included_bundles += 1;
+        prefix.push(execution);
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -312,6 +429,7 @@ where
             // which also removes all dependent transaction from the iterator before we can
             // continue
             best_txs.mark_invalid(&pool_tx);
+            skipped_txs += 1;
             continue
         }
 
@@ -334,47 +452,61 @@ where
                 // for regular transactions above.
                 trace!(target: "payload_builder", tx=?tx.hash, ?sum_blob_gas_used, ?tx_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
                 best_txs.mark_invalid(&pool_tx);
+                skipped_txs += 1;
                 continue
             }
         }
 
-        let env = EnvWithHandlerCfg::new_with_cfg_env(
-            initialized_cfg.clone(),
-            initialized_block_env.clone(),
-            tx_env_with_recovered(&tx),
-        );
-
-        // Configure the environment for the block.
-        let mut evm = evm_config.evm_with_env(&mut db, env);
-
-        let ResultAndState { result, state } = match evm.transact() {
-            Ok(res) => res,
-            Err(err) => {
-                match err {
-                    EVMError::Transaction(err) => {
-                        if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
-                            // if the nonce is too low, we can skip this transaction
-                            trace!(target: "payload_builder", %err, ?tx, "skipping nonce too low transaction");
-                        } else {
-                            // if the transaction is invalid, we can skip it and all of its
-                            // descendants
-                            trace!(target: "payload_builder", %err, ?tx, "skipping invalid transaction and its descendants");
-                            best_txs.mark_invalid(&pool_tx);
-                        }
+        // the previous attempt executed the same transactions on top of the same state up to this
+        // transaction, so its result can be reused
+        let (result, state) = if let Some(execution) = prefix.reuse(tx.hash) {
+            trace!(target: "payload_builder", tx=?tx.hash, "reusing execution of previous attempt");
+            let mut transactions = execution.transactions.into_iter();
+            match (transactions.next(), transactions.next()) {
+                (Some((_, result, state)), None) => (result, state),
+                _ => return Err(PayloadBuilderError::other(InvalidTransactionExecution(tx.hash))),
+            }
+        } else {
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                initialized_cfg.clone(),
+                initialized_block_env.clone(),
+                tx_env_with_recovered(&tx),
+            );
 
-                        continue
-                    }
-                    err => {
-                        // this is an error that we should treat as fatal for this attempt
-                        return Err(PayloadBuilderError::EvmExecutionError(err))
+            // Configure the environment for the block.
+            let mut evm = evm_config.evm_with_env(&mut db, env);
+
+            let ResultAndState { result, state } = match evm.transact() {
+                Ok(res) => res,
+                Err(err) => {
+                    match err {
+                        EVMError::Transaction(err) => {
+                            if matches!(err, InvalidTransaction::NonceTooLow { .. }) {
+                                // if the nonce is too low, we can skip this transaction
+                                trace!(target: "payload_builder", %err, ?tx, "skipping nonce too low transaction");
+                            } else {
+                                // if the transaction is invalid, we can skip it and all of its
+                                // descendants
+                                trace!(target: "payload_builder", %err, ?tx, "skipping invalid transaction and its descendants");
+                                best_txs.mark_invalid(&pool_tx);
+                            }
+
+                            skipped_txs += 1;
+                            continue
+                        }
+                        err => {
+                            // this is an error that we should treat as fatal for this attempt
+                            return Err(PayloadBuilderError::EvmExecutionError(err))
+                        }
                     }
                 }
-            }
+            };
+            // drop evm so db is released.
+            drop(evm);
+            (result, state)
         };
-        // drop evm so db is released.
-        drop(evm);
         // commit changes
-        db.commit(state);
+        db.commit(state.clone());
 
         // add to the total blob gas used if the transaction successfully executed
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
@@ -392,6 +524,20 @@ where
         // add gas used by the transaction to cumulative gas used, before creating the receipt
         cumulative_gas_used += gas_used;
 
+        // update add to total fees
+        let miner_fee = tx
+            .effective_tip_per_gas(Some(base_fee))
+            .expect("fee is always valid; execution succeeded");
+        let fees = U256::from(miner_fee) * U256::from(gas_used);
+        total_fees += fees;
+
+        // record the execution for the next attempt
+        prefix.push(CachedExecution {
+            hash: tx.hash,
+            transactions: vec![(tx.clone(), result.clone(), state)],
+            fees,
+        });
+
         // Push transaction changeset and calculate header bloom filter for receipt.
         #[allow(clippy::needless_update)] // side-effect of optimism fields
         receipts.push(Some(Receipt {
@@ -402,19 +548,15 @@ where
             ..Default::default()
         }));
 
-        // update add to total fees
-        let miner_fee = tx
-            .effective_tip_per_gas(Some(base_fee))
-            .expect("fee is always valid; execution succeeded");
-        total_fees += U256::from(miner_fee) * U256::from(gas_used);
-
         // append transaction to the list of executed transactions
         executed_txs.push(tx.into_signed());
+        included_txs += 1;
     }
 
     // check if we have a better block
     if !is_better_payload(best_payload.as_ref(), total_fees) {
         // can skip building the block
+        cached_reads.set_executions(prefix.into_executions());
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
@@ -518,10 +660,18 @@ where
     let sealed_block = block.seal_slow();
     debug!(target: "payload_builder", ?sealed_block, "sealed built block");
 
+    let metrics = TransactionSelectorMetrics::new_with_labels(&[("selector", selector.name())]);
+    metrics.built_payloads.increment(1);
+    metrics.included_bundles.increment(included_bundles);
+    metrics.included_transactions.increment(included_txs);
+    metrics.skipped_transactions.increment(skipped_txs);
+    metrics.payload_gas_used.record(cumulative_gas_used as f64);
+
     let mut payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees);
 
     // extend the payload with the blob sidecars from the executed txs
     payload.extend_sidecars(blob_sidecars);
 
+    cached_reads.set_executions(prefix.into_executions());
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
@@ -0,0 +1,22 @@
+//! Metrics of the transaction selection strategies.
+
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+
+/// Metrics of a [`TransactionSelector`](crate::TransactionSelector), labeled with its name.
+#[derive(Metrics)]
+#[metrics(scope = "payloads.transaction_selector")]
+pub(crate) struct TransactionSelectorMetrics {
+    /// Total number of payloads built with the strategy
+    pub(crate) built_payloads: Counter,
+    /// Total number of bundles included in built payloads
+    pub(crate) included_bundles: Counter,
+    /// Total number of pool transactions included in built payloads
+    pub(crate) included_transactions: Counter,
+    /// Total number of pool transactions that were skipped while building payloads
+    pub(crate) skipped_transactions: Counter,
+    /// Gas used by built payloads
+    pub(crate) payload_gas_used: Histogram,
+}
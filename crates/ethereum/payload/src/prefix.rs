@@ -0,0 +1,53 @@
+//! Reuse of the executions of the previous build attempt of a payload job.
+
+use reth_payload_builder::database::CachedExecution;
+use reth_primitives::B256;
+
+/// The execution of the previous attempt that is reused for a single transaction doesn't hold
+/// exactly one transaction.
+#[derive(Debug, thiserror::Error)]
+#[error("reused execution of transaction {0} doesn't hold exactly one transaction")]
+pub(crate) struct InvalidTransactionExecution(pub(crate) B256);
+
+/// Tracks the executions of a build attempt and hands out the executions of the previous attempt
+/// of the same job while both attempts execute the same transactions and bundles in the same
+/// order.
+///
+/// Once the attempts diverge, the state differs from the one the previous executions were based
+/// on, so none of the remaining previous executions are reused.
+#[derive(Debug)]
+pub(crate) struct ExecutionPrefix {
+    /// The remaining executions of the previous attempt, `None` once the attempts diverged.
+    previous: Option<std::vec::IntoIter<CachedExecution>>,
+    /// The executions of this attempt, in order.
+    executions: Vec<CachedExecution>,
+}
+
+impl ExecutionPrefix {
+    /// Creates a new prefix on top of the executions of the previous attempt.
+    pub(crate) fn new(previous: Vec<CachedExecution>) -> Self {
+        Self { previous: Some(previous.into_iter()), executions: Vec::new() }
+    }
+
+    /// Returns the execution of the previous attempt at the current position if it executed the
+    /// transaction or bundle with the given hash.
+    ///
+    /// The returned execution must be committed and recorded with [`Self::push`].
+    pub(crate) fn reuse(&mut self, hash: B256) -> Option<CachedExecution> {
+        let execution = self.previous.as_mut()?.next().filter(|execution| execution.hash == hash);
+        if execution.is_none() {
+            self.previous = None;
+        }
+        execution
+    }
+
+    /// Records a committed execution of this attempt.
+    pub(crate) fn push(&mut self, execution: CachedExecution) {
+        self.executions.push(execution);
+    }
+
+    /// Returns the executions of this attempt, in order.
+    pub(crate) fn into_executions(self) -> Vec<CachedExecution> {
+        self.executions
+    }
+}
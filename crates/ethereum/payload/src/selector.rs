@@ -0,0 +1,81 @@
+//! Strategies that select the transactions of a payload.
+
+use reth_transaction_pool::{
+    BestTransactions, BestTransactionsAttributes, MevBundle, TransactionPool, ValidPoolTransaction,
+};
+use std::{fmt, sync::Arc};
+
+/// The iterator over the pool transactions of a [`TransactionPool`] that are tried in order.
+pub type BestTransactionsFor<Pool> = Box<
+    dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
+>;
+
+/// A strategy that selects the transactions of a payload.
+///
+/// The payload builder first simulates the bundles returned by [`TransactionSelector::bundles`]
+/// and includes the profitable ones at the top of the block, ordered by their profit per gas. The
+/// remaining gas is filled with the pool transactions yielded by
+/// [`TransactionSelector::best_transactions`], in order.
+///
+/// Strategies that need state for a single payload, like a cap on the number of transactions per
+/// sender, can keep it in the iterator they return, for example in the predicate passed to
+/// [`BestTransactions::filter`]. Transactions that are filtered out are marked as invalid, which
+/// also skips their descendants.
+///
+/// The default implementations select no bundles and the pool transactions in pool order.
+pub trait TransactionSelector: fmt::Debug + Send + Sync {
+    /// Returns the name of the strategy, used as label of its metrics.
+    fn name(&self) -> &'static str;
+
+    /// Returns the bundles that are considered for the top of the block with the given number and
+    /// timestamp.
+    fn bundles<Pool: TransactionPool>(
+        &self,
+        _pool: &Pool,
+        _block_number: u64,
+        _timestamp: u64,
+    ) -> Vec<MevBundle> {
+        Vec::new()
+    }
+
+    /// Returns the pool transactions in the order they are tried.
+    fn best_transactions<Pool: TransactionPool>(
+        &self,
+        pool: &Pool,
+        attributes: BestTransactionsAttributes,
+    ) -> BestTransactionsFor<Pool> {
+        pool.best_transactions_with_attributes(attributes)
+    }
+}
+
+/// Selects the pool transactions greedily in pool order and ignores bundles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GreedySelector;
+
+impl TransactionSelector for GreedySelector {
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+}
+
+/// Includes the profitable bundles of the bundle pool at the top of the block and fills the rest
+/// of the block greedily with the pool transactions in pool order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BundleSelector;
+
+impl TransactionSelector for BundleSelector {
+    fn name(&self) -> &'static str {
+        "bundle"
+    }
+
+    fn bundles<Pool: TransactionPool>(
+        &self,
+        pool: &Pool,
+        block_number: u64,
+        timestamp: u64,
+    ) -> Vec<MevBundle> {
+        pool.bundle_pool().bundles_at(block_number, timestamp)
+    }
+}
@@ -5,7 +5,7 @@ use crate::execute::{
 };
 use reth_execution_errors::BlockExecutionError;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
+use reth_primitives::{BlockNumber, BlockWithSenders, Receipt, U256};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
 use revm_primitives::db::Database;
@@ -41,6 +41,22 @@ where
             Self::Right(b) => Either::Right(b.batch_executor(db, prune_modes)),
         }
     }
+
+    fn prefetch_transactions<DB, I>(
+        &self,
+        db: DB,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        transactions: I,
+    ) where
+        DB: Database<Error = ProviderError>,
+        I: Iterator<Item = usize>,
+    {
+        match self {
+            Self::Left(a) => a.prefetch_transactions(db, block, total_difficulty, transactions),
+            Self::Right(b) => b.prefetch_transactions(db, block, total_difficulty, transactions),
+        }
+    }
 }
 
 impl<A, B, DB> Executor<DB> for Either<A, B>
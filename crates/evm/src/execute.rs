@@ -171,6 +171,26 @@ pub trait BlockExecutorProvider: Send + Sync + Clone + Unpin + 'static {
     fn batch_executor<DB>(&self, db: DB, prune_modes: PruneModes) -> Self::BatchExecutor<DB>
     where
         DB: Database<Error = ProviderError>;
+
+    /// Speculatively executes the transactions of the block at the given indices on top of the
+    /// given database, to load the state they access into the database.
+    ///
+    /// The changes of the transactions are discarded and execution errors are ignored. This is
+    /// used to warm caches before the block is executed, see [`crate::prefetch`].
+    ///
+    /// The default implementation does nothing.
+    fn prefetch_transactions<DB, I>(
+        &self,
+        db: DB,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        transactions: I,
+    ) where
+        DB: Database<Error = ProviderError>,
+        I: Iterator<Item = usize>,
+    {
+        let _ = (db, block, total_difficulty, transactions);
+    }
 }
 
 #[cfg(test)]
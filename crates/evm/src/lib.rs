@@ -16,8 +16,11 @@ use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId,
 
 pub mod either;
 pub mod execute;
+mod metrics;
 pub mod noop;
+pub mod prefetch;
 pub mod provider;
+pub mod witness;
 
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking executor
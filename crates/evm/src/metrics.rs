@@ -0,0 +1,33 @@
+//! Executor metrics.
+
+use crate::prefetch::PrefetchStats;
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+
+/// Metrics of block execution.
+#[derive(Metrics)]
+#[metrics(scope = "executor")]
+pub(crate) struct ExecutorMetrics {
+    /// Total number of state reads of prefetched blocks that were served from the prefetch cache
+    pub(crate) prefetch_hits: Counter,
+    /// Total number of state reads of prefetched blocks that were served from the database
+    pub(crate) prefetch_misses: Counter,
+    /// The share of state reads served from the prefetch cache in the last prefetched block
+    pub(crate) prefetch_hit_rate: Gauge,
+    /// Estimated execution time saved per block by prefetching, in seconds
+    pub(crate) prefetch_time_saved: Histogram,
+}
+
+impl ExecutorMetrics {
+    /// Records the statistics of a prefetched block.
+    pub(crate) fn record_prefetch(&self, stats: PrefetchStats) {
+        self.prefetch_hits.increment(stats.hits);
+        self.prefetch_misses.increment(stats.misses);
+        if let Some(hit_rate) = stats.hit_rate() {
+            self.prefetch_hit_rate.set(hit_rate);
+        }
+        self.prefetch_time_saved.record(stats.time_saved());
+    }
+}
@@ -0,0 +1,297 @@
+//! Speculative prefetching of the state of a block.
+//!
+//! Block execution is dominated by cold state reads. [`Prefetcher::execute_with_prefetch`]
+//! speculatively executes the transactions of a block on worker threads while the block is
+//! executed, each on top of the state before the block. The state read by the workers is kept in a
+//! shared [`PrefetchCache`], from which the canonical execution reads it instead of the database.
+//!
+//! Only values read from the database are cached, never the changes of speculative executions, so
+//! the canonical execution reads the same state with and without prefetching as long as the
+//! databases of the workers and the canonical execution read the same state.
+
+use crate::{execute::BlockExecutorProvider, metrics::ExecutorMetrics};
+use parking_lot::RwLock;
+use reth_primitives::{Address, BlockWithSenders, B256, U256};
+use reth_storage_errors::provider::ProviderError;
+use revm::{db::DatabaseRef, Database};
+use revm_primitives::{AccountInfo, Bytecode};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// The state read by the speculative executions of a block.
+///
+/// Hits and misses are only counted for reads of the canonical execution.
+#[derive(Debug, Default)]
+pub struct PrefetchCache {
+    /// Cached accounts, `None` if the account does not exist.
+    accounts: RwLock<HashMap<Address, Option<AccountInfo>>>,
+    /// Cached storage slots.
+    storage: RwLock<HashMap<(Address, U256), U256>>,
+    /// Cached bytecodes by code hash.
+    contracts: RwLock<HashMap<B256, Bytecode>>,
+    /// Number of canonical reads served from the cache.
+    hits: AtomicU64,
+    /// Number of canonical reads served from the database.
+    misses: AtomicU64,
+    /// Total time spent on canonical reads served from the database, in nanoseconds.
+    miss_nanos: AtomicU64,
+}
+
+impl PrefetchCache {
+    /// Returns a database for speculative executions that caches all values read from `db`.
+    pub const fn warming<DB>(&self, db: DB) -> PrefetchDatabase<'_, DB> {
+        PrefetchDatabase { cache: self, db, canonical: false }
+    }
+
+    /// Returns a database for the canonical execution that reads cached values from the cache and
+    /// all other values from `db`.
+    pub const fn canonical<DB>(&self, db: DB) -> PrefetchDatabase<'_, DB> {
+        PrefetchDatabase { cache: self, db, canonical: true }
+    }
+
+    /// Returns the statistics of the canonical reads so far.
+    pub fn stats(&self) -> PrefetchStats {
+        PrefetchStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            miss_duration: Duration::from_nanos(self.miss_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Statistics of the canonical reads of a [`PrefetchCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefetchStats {
+    /// Number of reads served from the cache.
+    pub hits: u64,
+    /// Number of reads served from the database.
+    pub misses: u64,
+    /// Total time spent on reads served from the database.
+    pub miss_duration: Duration,
+}
+
+impl PrefetchStats {
+    /// Returns the share of reads served from the cache, or `None` if nothing was read.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let reads = self.hits + self.misses;
+        (reads > 0).then(|| self.hits as f64 / reads as f64)
+    }
+
+    /// Returns the estimated time saved by the reads served from the cache, assuming they would
+    /// have taken as long as the reads served from the database on average.
+    pub fn time_saved(&self) -> Duration {
+        if self.misses == 0 {
+            return Duration::ZERO
+        }
+        self.miss_duration.mul_f64(self.hits as f64 / self.misses as f64)
+    }
+}
+
+/// A [`Database`] that reads through a [`PrefetchCache`].
+///
+/// Created with [`PrefetchCache::warming`] or [`PrefetchCache::canonical`].
+#[derive(Debug)]
+pub struct PrefetchDatabase<'a, DB> {
+    cache: &'a PrefetchCache,
+    db: DB,
+    /// Whether the reads are the ones of the canonical execution.
+    canonical: bool,
+}
+
+impl<'a, DB: DatabaseRef> PrefetchDatabase<'a, DB> {
+    /// Returns the cached value for the key, or reads it with `read`.
+    ///
+    /// Values read by speculative executions are cached, reads of the canonical execution are
+    /// counted.
+    fn read<K, V>(
+        &self,
+        map: &RwLock<HashMap<K, V>>,
+        key: K,
+        read: impl FnOnce(&DB) -> Result<V, DB::Error>,
+    ) -> Result<V, DB::Error>
+    where
+        K: Eq + Hash,
+        V: Clone,
+    {
+        if let Some(value) = map.read().get(&key).cloned() {
+            if self.canonical {
+                self.cache.hits.fetch_add(1, Ordering::Relaxed);
+            }
+            return Ok(value)
+        }
+
+        let start = Instant::now();
+        let value = read(&self.db)?;
+        if self.canonical {
+            self.cache.misses.fetch_add(1, Ordering::Relaxed);
+            self.cache.miss_nanos.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        } else {
+            map.write().insert(key, value.clone());
+        }
+        Ok(value)
+    }
+}
+
+impl<'a, DB: DatabaseRef> DatabaseRef for PrefetchDatabase<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.read(&self.cache.accounts, address, |db| db.basic_ref(address))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.read(&self.cache.contracts, code_hash, |db| db.code_by_hash_ref(code_hash))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.read(&self.cache.storage, (address, index), |db| db.storage_ref(address, index))
+    }
+
+    fn block_hash_ref(&self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number)
+    }
+}
+
+impl<'a, DB: DatabaseRef> Database for PrefetchDatabase<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+/// Executes blocks while their state is prefetched.
+///
+/// The statistics of the canonical reads of all prefetched blocks are recorded in the executor
+/// metrics, so a single instance should be kept for all executions.
+#[derive(Debug, Default)]
+pub struct Prefetcher {
+    metrics: ExecutorMetrics,
+}
+
+impl Prefetcher {
+    /// Runs the canonical execution of a block with `execute` while the transactions of the block
+    /// are speculatively executed with [`BlockExecutorProvider::prefetch_transactions`] on one
+    /// worker thread per database in `workers`.
+    ///
+    /// The workers fill the given cache, `execute` is expected to read the state through
+    /// [`PrefetchCache::canonical`]. Workers take the transactions in block order and stop once
+    /// `execute` returns, after finishing the transaction they execute at that time.
+    pub fn execute_with_prefetch<E, W, T>(
+        &self,
+        executor_provider: &E,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        cache: &PrefetchCache,
+        workers: Vec<W>,
+        execute: impl FnOnce() -> T,
+    ) -> T
+    where
+        E: BlockExecutorProvider,
+        W: DatabaseRef<Error = ProviderError> + Send,
+    {
+        let next = AtomicUsize::new(0);
+        let done = AtomicBool::new(false);
+
+        let output = std::thread::scope(|scope| {
+            for db in workers {
+                let (next, done) = (&next, &done);
+                scope.spawn(move || {
+                    let transactions = std::iter::from_fn(|| {
+                        if done.load(Ordering::Relaxed) {
+                            return None
+                        }
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        (index < block.body.len()).then_some(index)
+                    });
+                    executor_provider.prefetch_transactions(
+                        cache.warming(db),
+                        block,
+                        total_difficulty,
+                        transactions,
+                    );
+                });
+            }
+
+            let output = execute();
+            done.store(true, Ordering::Relaxed);
+            output
+        });
+
+        self.metrics.record_prefetch(cache.stats());
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutorProvider;
+    use reth_primitives::{Block, TransactionSigned};
+    use revm::db::{CacheDB, EmptyDB, EmptyDBTyped};
+
+    #[test]
+    fn canonical_reads_cached_values() {
+        let address = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(address, AccountInfo { nonce: 1, ..Default::default() });
+        db.insert_account_storage(address, U256::from(1), U256::from(2)).unwrap();
+
+        let cache = PrefetchCache::default();
+        assert_eq!(cache.warming(&db).storage_ref(address, U256::from(1)).unwrap(), U256::from(2));
+
+        let canonical = cache.canonical(&db);
+        assert_eq!(canonical.storage_ref(address, U256::from(1)).unwrap(), U256::from(2));
+        assert_eq!(canonical.basic_ref(address).unwrap().unwrap().nonce, 1);
+        // canonical reads are not cached
+        assert_eq!(canonical.basic_ref(address).unwrap().unwrap().nonce, 1);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+        assert_eq!(stats.hit_rate(), Some(1.0 / 3.0));
+    }
+
+    #[test]
+    fn execute_without_prefetching_transactions() {
+        let address = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDBTyped::<ProviderError>::new());
+        db.insert_account_info(address, AccountInfo { nonce: 1, ..Default::default() });
+
+        let block = BlockWithSenders {
+            block: Block { body: vec![TransactionSigned::default(); 2], ..Default::default() },
+            senders: vec![Address::ZERO; 2],
+        };
+
+        // the mock executor provider doesn't implement prefetching, so the workers read nothing
+        let cache = PrefetchCache::default();
+        let nonce = Prefetcher::default().execute_with_prefetch(
+            &MockExecutorProvider::default(),
+            &block,
+            U256::ZERO,
+            &cache,
+            vec![&db, &db],
+            || cache.canonical(&db).basic_ref(address).map(|account| account.unwrap().nonce),
+        );
+        assert_eq!(nonce, Ok(1));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (0, 1));
+        assert!(cache.accounts.read().is_empty());
+    }
+}
@@ -0,0 +1,410 @@
+//! Execution witness for stateless block execution.
+//!
+//! The [`RecordingDatabase`] wraps the database used during block execution and records every
+//! piece of pre-state that is accessed. Combined with a state multiproof for the accessed keys
+//! this yields an [`ExecutionWitness`], which is sufficient to re-execute the block without access
+//! to the full state, see [`execute_with_witness`].
+
+use crate::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_execution_errors::BlockExecutionError;
+use reth_primitives::{
+    keccak256, proofs::MultiProof, Address, BlockNumber, BlockWithSenders, Bytes, Header, Receipt,
+    B256, KECCAK_EMPTY, U256,
+};
+use reth_storage_errors::provider::ProviderError;
+use reth_trie_types::Nibbles;
+use revm_primitives::{db::Database, AccountInfo, Bytecode};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::RangeInclusive,
+};
+
+pub use reth_primitives::{ExecutionWitness, WitnessAccount, WitnessVerificationError};
+
+/// Converts the trie nodes keyed by [`Nibbles`] into serializable nodes.
+fn nodes_by_bytes(nodes: BTreeMap<Nibbles, Bytes>) -> BTreeMap<Bytes, Bytes> {
+    nodes.into_iter().map(|(path, node)| (Bytes::copy_from_slice(&path), node)).collect()
+}
+
+/// Errors that can occur when executing a block from an [`ExecutionWitness`].
+#[derive(Debug, thiserror::Error)]
+pub enum WitnessError {
+    /// The witness failed verification against the parent of the executed block.
+    #[error(transparent)]
+    Verification(#[from] WitnessVerificationError),
+    /// Block execution failed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+}
+
+/// A [Database] wrapper that records all state read from the inner database.
+///
+/// Since the executor caches all loaded state, the first read of every key corresponds to its
+/// value before the execution.
+#[derive(Debug)]
+pub struct RecordingDatabase<DB> {
+    /// The inner database.
+    inner: DB,
+    /// Accessed accounts.
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    /// Accessed storage slots.
+    storage: HashMap<Address, HashMap<U256, U256>>,
+    /// Accessed bytecodes.
+    codes: HashMap<B256, Bytecode>,
+    /// Accessed block hashes.
+    block_hashes: BTreeMap<u64, B256>,
+}
+
+impl<DB> RecordingDatabase<DB> {
+    /// Creates a new recording database on top of the given database.
+    pub fn new(inner: DB) -> Self {
+        Self {
+            inner,
+            accounts: HashMap::default(),
+            storage: HashMap::default(),
+            codes: HashMap::default(),
+            block_hashes: BTreeMap::default(),
+        }
+    }
+
+    /// Returns the multiproof targets for the accessed state, mapping hashed addresses to the
+    /// sets of hashed storage slots.
+    pub fn proof_targets(&self) -> HashMap<B256, HashSet<B256>> {
+        self.accounts
+            .keys()
+            .chain(self.storage.keys())
+            .map(|address| {
+                let slots = self
+                    .storage
+                    .get(address)
+                    .map(|storage| {
+                        storage
+                            .keys()
+                            .map(|slot| keccak256(B256::new(slot.to_be_bytes())))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (keccak256(address), slots)
+            })
+            .collect()
+    }
+
+    /// Returns the range of ancestor headers required to verify the accessed block hashes, from
+    /// the oldest accessed block up to the given parent block.
+    pub fn ancestor_range(&self, parent_number: BlockNumber) -> RangeInclusive<BlockNumber> {
+        let oldest = self.block_hashes.keys().next().copied().unwrap_or(parent_number);
+        oldest.min(parent_number)..=parent_number
+    }
+
+    /// Consumes the recorder and builds the [`ExecutionWitness`] for the recorded state from the
+    /// given multiproof and ancestor headers. The multiproof is expected to be generated for
+    /// [`RecordingDatabase::proof_targets`], the headers for [`RecordingDatabase::ancestor_range`]
+    /// in any order.
+    pub fn into_witness(
+        self,
+        mut multiproof: MultiProof,
+        mut headers: Vec<Header>,
+    ) -> ExecutionWitness {
+        let addresses = self.accounts.keys().chain(self.storage.keys()).copied();
+        let mut accounts = addresses
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|address| {
+                let hashed_address = keccak256(address);
+                let storage_multiproof =
+                    multiproof.storages.remove(&hashed_address).unwrap_or_default();
+                WitnessAccount {
+                    address,
+                    info: multiproof.accounts.get(&hashed_address).copied(),
+                    storage_root: storage_multiproof.root,
+                    storage: self
+                        .storage
+                        .get(&address)
+                        .map(|storage| {
+                            storage
+                                .iter()
+                                .map(|(slot, value)| (B256::new(slot.to_be_bytes()), *value))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    storage_trie: nodes_by_bytes(storage_multiproof.subtree),
+                }
+            })
+            .collect::<Vec<_>>();
+        accounts.sort_unstable_by_key(|account| account.address);
+
+        let mut codes = self
+            .codes
+            .into_iter()
+            .filter(|(code_hash, _)| *code_hash != KECCAK_EMPTY)
+            .collect::<Vec<_>>();
+        codes.sort_unstable_by_key(|(code_hash, _)| *code_hash);
+        headers.sort_unstable_by_key(|header| std::cmp::Reverse(header.number));
+
+        ExecutionWitness {
+            accounts,
+            codes: codes.into_iter().map(|(_, code)| code.original_bytes()).collect(),
+            block_hashes: self.block_hashes,
+            headers,
+            account_trie: nodes_by_bytes(multiproof.account_subtree),
+        }
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.inner.basic(address)?;
+        self.accounts.entry(address).or_insert_with(|| info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.inner.code_by_hash(code_hash)?;
+        self.codes.entry(code_hash).or_insert_with(|| code.clone());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.inner.storage(address, index)?;
+        self.storage.entry(address).or_default().entry(index).or_insert(value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let hash = self.inner.block_hash(number)?;
+        self.block_hashes.insert(number.saturating_to(), hash);
+        Ok(hash)
+    }
+}
+
+/// A [Database] that serves state exclusively from an [`ExecutionWitness`].
+///
+/// Any access to state that is not part of the witness results in a
+/// [`ProviderError::MissingWitnessState`] error.
+#[derive(Debug)]
+pub struct WitnessDatabase<'a> {
+    /// Accounts of the witness by address.
+    accounts: HashMap<Address, &'a WitnessAccount>,
+    /// Bytecodes of the witness by code hash.
+    codes: HashMap<B256, Bytecode>,
+    /// Block hashes of the witness by number.
+    block_hashes: &'a BTreeMap<u64, B256>,
+}
+
+impl<'a> WitnessDatabase<'a> {
+    /// Creates a new database from the given witness.
+    ///
+    /// The witness is not verified, see [`ExecutionWitness::verify`].
+    pub fn new(witness: &'a ExecutionWitness) -> Self {
+        Self {
+            accounts: witness.accounts.iter().map(|account| (account.address, account)).collect(),
+            codes: witness
+                .codes
+                .iter()
+                .map(|code| (keccak256(code), Bytecode::new_raw(code.clone())))
+                .collect(),
+            block_hashes: &witness.block_hashes,
+        }
+    }
+
+    fn account(&self, address: Address) -> Result<&'a WitnessAccount, ProviderError> {
+        self.accounts
+            .get(&address)
+            .copied()
+            .ok_or_else(|| ProviderError::MissingWitnessState(format!("account {address}")))
+    }
+}
+
+impl<'a> Database for WitnessDatabase<'a> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        Ok(self.account(address)?.info.map(|account| AccountInfo {
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+            code: None,
+        }))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::default())
+        }
+        self.codes
+            .get(&code_hash)
+            .cloned()
+            .ok_or_else(|| ProviderError::MissingWitnessState(format!("bytecode {code_hash}")))
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let account = self.account(address)?;
+        if account.info.is_none() {
+            return Ok(U256::ZERO)
+        }
+        let slot = B256::new(index.to_be_bytes());
+        account.storage.get(&slot).copied().ok_or_else(|| {
+            ProviderError::MissingWitnessState(format!("storage slot {slot} of account {address}"))
+        })
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let number: u64 =
+            number.try_into().map_err(|_| ProviderError::BlockNumberOverflow(number))?;
+        self.block_hashes
+            .get(&number)
+            .copied()
+            .ok_or_else(|| ProviderError::MissingWitnessState(format!("block hash #{number}")))
+    }
+}
+
+/// Re-executes the block using only the state contained in the [`ExecutionWitness`].
+///
+/// The witness is verified against the parent hash of the block before execution, see
+/// [`ExecutionWitness::verify`]. Note that the post-state root is not validated, this is up to the
+/// caller.
+pub fn execute_with_witness<E: BlockExecutorProvider>(
+    executor_provider: &E,
+    block: &BlockWithSenders,
+    total_difficulty: U256,
+    witness: &ExecutionWitness,
+) -> Result<BlockExecutionOutput<Receipt>, WitnessError> {
+    witness.verify(block.parent_hash)?;
+    let db = WitnessDatabase::new(witness);
+    Ok(executor_provider.executor(db).execute((block, total_difficulty).into())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_primitives::{proofs::IntoTrieAccount, Account};
+    use reth_trie_types::{proof::ProofRetainer, HashBuilder};
+    use revm::db::{CacheDB, EmptyDB};
+
+    /// Builds a state trie for the given accounts without storage and returns its root and the
+    /// multiproof for all of them.
+    fn state_multiproof(accounts: &[(Address, Account)]) -> (B256, MultiProof) {
+        let mut leaves = accounts
+            .iter()
+            .map(|(address, account)| (Nibbles::unpack(keccak256(address)), *account))
+            .collect::<Vec<_>>();
+        leaves.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let retainer = ProofRetainer::from_iter(leaves.iter().map(|(path, _)| path.clone()));
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        for (path, account) in &leaves {
+            let mut account_rlp = Vec::new();
+            IntoTrieAccount::to_trie_account((
+                *account,
+                reth_primitives::constants::EMPTY_ROOT_HASH,
+            ))
+            .encode(&mut account_rlp);
+            hash_builder.add_leaf(path.clone(), &account_rlp);
+        }
+        let root = hash_builder.root();
+
+        let multiproof = MultiProof {
+            account_subtree: hash_builder.take_proofs(),
+            accounts: accounts
+                .iter()
+                .map(|(address, account)| (keccak256(address), *account))
+                .collect(),
+            storages: HashMap::default(),
+        };
+        (root, multiproof)
+    }
+
+    #[test]
+    fn record_and_verify_witness() {
+        let existing = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        let missing = Address::with_last_byte(3);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let (root, multiproof) = state_multiproof(&[(existing, account), (other, account)]);
+
+        let mut cache = CacheDB::new(EmptyDB::default());
+        cache.insert_account_info(
+            existing,
+            AccountInfo { nonce: 1, balance: U256::from(10), ..Default::default() },
+        );
+
+        let mut db = RecordingDatabase::new(cache);
+        assert!(db.basic(existing).unwrap().is_some());
+        assert!(db.basic(missing).unwrap().is_none());
+
+        let targets = db.proof_targets();
+        assert_eq!(targets.len(), 2);
+        assert!(targets.contains_key(&keccak256(existing)));
+
+        let parent = Header { number: 1, state_root: root, ..Default::default() };
+        let witness = db.into_witness(multiproof, vec![parent.clone()]);
+        assert_eq!(witness.accounts.len(), 2);
+        assert_eq!(witness.verify(parent.hash_slow()).map_err(|err| err.to_string()), Ok(()));
+        assert!(witness.verify(B256::random()).is_err());
+        assert!(witness.verify_state(B256::random()).is_err());
+
+        // The witness can be serialized and restored.
+        let json = serde_json::to_string(&witness).unwrap();
+        assert_eq!(serde_json::from_str::<ExecutionWitness>(&json).unwrap(), witness);
+
+        let mut db = WitnessDatabase::new(&witness);
+        assert_eq!(db.basic(existing).unwrap().map(|info| info.nonce), Some(1));
+        assert_eq!(db.basic(missing).unwrap(), None);
+        assert_eq!(db.storage(missing, U256::from(1)).unwrap(), U256::ZERO);
+        assert!(matches!(db.basic(other), Err(ProviderError::MissingWitnessState(_))));
+        assert!(matches!(
+            db.storage(existing, U256::from(1)),
+            Err(ProviderError::MissingWitnessState(_))
+        ));
+    }
+
+    #[test]
+    fn verify_witness_block_hashes() {
+        let grandparent = Header { number: 1, ..Default::default() };
+        let parent =
+            Header { number: 2, parent_hash: grandparent.hash_slow(), ..Default::default() };
+
+        let mut cache = CacheDB::new(EmptyDB::default());
+        cache.block_hashes.insert(U256::from(1), grandparent.hash_slow());
+        let mut db = RecordingDatabase::new(cache);
+        assert_eq!(db.block_hash(U256::from(1)).unwrap(), grandparent.hash_slow());
+        assert_eq!(db.ancestor_range(parent.number), 1..=2);
+
+        let witness =
+            db.into_witness(MultiProof::default(), vec![grandparent.clone(), parent.clone()]);
+        assert_eq!(witness.headers, vec![parent.clone(), grandparent.clone()]);
+        assert_eq!(witness.verify(parent.hash_slow()).map_err(|err| err.to_string()), Ok(()));
+
+        // a forged block hash is rejected
+        let mut forged = witness.clone();
+        forged.block_hashes.insert(1, B256::random());
+        assert!(matches!(
+            forged.verify(parent.hash_slow()),
+            Err(WitnessVerificationError::InvalidBlockHash(1))
+        ));
+
+        // a block hash without a header is rejected
+        let mut forged = witness.clone();
+        forged.headers.pop();
+        assert!(matches!(
+            forged.verify(parent.hash_slow()),
+            Err(WitnessVerificationError::InvalidBlockHash(1))
+        ));
+
+        // a header that is not an ancestor is rejected
+        let mut forged = witness.clone();
+        forged.headers[1].gas_limit = 1;
+        assert!(matches!(
+            forged.verify(parent.hash_slow()),
+            Err(WitnessVerificationError::InvalidHeader(1))
+        ));
+
+        assert!(matches!(
+            ExecutionWitness::default().verify(parent.hash_slow()),
+            Err(WitnessVerificationError::MissingParentHeader)
+        ));
+    }
+}
@@ -23,6 +23,14 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Replay
+//!
+//! If the node is started with a write-ahead log (see [`ExExManager::with_wal`]), notifications
+//! are persisted before they are sent to the `ExEx`'s. A notification stays in the log until the
+//! `ExEx` received it and emitted an `ExExEvent::FinishedHeight` event at or above its tip (or, for
+//! reverts, below the reverted blocks). After a restart, all notifications that were not
+//! acknowledged yet, including reorgs and reverts, are sent to the `ExEx` again.
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
@@ -46,6 +54,9 @@ pub use manager::*;
 mod notification;
 pub use notification::*;
 
+mod wal;
+pub use wal::*;
+
 // Re-export exex types
 #[doc(inline)]
 pub use reth_exex_types::*;
@@ -1,4 +1,4 @@
-use crate::{ExExEvent, ExExNotification, FinishedExExHeight};
+use crate::{ExExEvent, ExExNotification, FinishedExExHeight, Wal};
 use metrics::Gauge;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::BlockNumber;
@@ -6,6 +6,7 @@ use reth_tracing::tracing::debug;
 use std::{
     collections::VecDeque,
     future::{poll_fn, Future},
+    path::Path,
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -47,6 +48,12 @@ pub struct ExExHandle {
     receiver: UnboundedReceiver<ExExEvent>,
     /// The ID of the next notification to send to this `ExEx`.
     next_notification_id: usize,
+    /// The ID of the last notification that was sent to this `ExEx`.
+    last_sent_id: Option<usize>,
+    /// The notifications with a lower ID were received by the `ExEx` before the manager last
+    /// drained its events, so the `FinishedHeight` events received afterwards were sent after the
+    /// `ExEx` received them.
+    acknowledgeable_id: usize,
 
     /// The finished block number of the `ExEx`.
     ///
@@ -70,6 +77,8 @@ impl ExExHandle {
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
                 next_notification_id: 0,
+                last_sent_id: None,
+                acknowledgeable_id: 0,
                 finished_height: None,
             },
             event_tx,
@@ -131,12 +140,25 @@ impl ExExHandle {
         match self.sender.send_item(notification.clone()) {
             Ok(()) => {
                 self.next_notification_id = notification_id + 1;
+                self.last_sent_id = Some(*notification_id);
                 self.metrics.notifications_sent_total.increment(1);
                 Poll::Ready(Ok(()))
             }
             Err(err) => Poll::Ready(Err(err)),
         }
     }
+
+    /// Returns the ID below which all notifications were received by the `ExEx`, or skipped.
+    ///
+    /// Only the last sent notification can still be in the channel.
+    fn received_id(&self) -> usize {
+        let queued =
+            self.sender.get_ref().map_or(true, |sender| sender.capacity() < sender.max_capacity());
+        match self.last_sent_id {
+            Some(last_sent_id) if queued => last_sent_id,
+            _ => self.next_notification_id,
+        }
+    }
 }
 
 /// Metrics for the `ExEx` manager.
@@ -194,6 +216,11 @@ pub struct ExExManager {
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Sender<FinishedExExHeight>,
 
+    /// Write-ahead log of the notifications that were not acknowledged by all `ExEx`'s yet.
+    ///
+    /// If this is `None`, notifications are only kept in memory.
+    wal: Option<Wal>,
+
     /// A handle to the `ExEx` manager.
     handle: ExExManagerHandle,
     /// Metrics for the `ExEx` manager.
@@ -239,6 +266,8 @@ impl ExExManager {
             is_ready: is_ready_tx,
             finished_height: finished_height_tx,
 
+            wal: None,
+
             handle: ExExManagerHandle {
                 exex_tx: handle_tx,
                 num_exexs,
@@ -251,6 +280,39 @@ impl ExExManager {
         }
     }
 
+    /// Create a new [`ExExManager`] that persists notifications in a [`Wal`] in the given
+    /// directory.
+    ///
+    /// Notifications that were not acknowledged by an `ExEx` before the node was stopped are sent
+    /// to the `ExEx` again. `ExEx`'s that were not present before start with the next new
+    /// notification.
+    ///
+    /// See [`ExExManager::new`] for the other arguments.
+    pub fn with_wal(
+        handles: Vec<ExExHandle>,
+        max_capacity: usize,
+        wal_dir: impl AsRef<Path>,
+    ) -> eyre::Result<Self> {
+        let (mut wal, notifications) = Wal::open(wal_dir)?;
+        let exex_ids = handles.iter().map(|handle| handle.id.clone()).collect::<Vec<_>>();
+        let unacknowledged = wal.register(&exex_ids)?;
+        let first_id = wal.first_id();
+
+        let mut manager = Self::new(handles, max_capacity);
+        for (exex, id) in manager.exex_handles.iter_mut().zip(unacknowledged) {
+            exex.next_notification_id = id.max(first_id);
+        }
+        manager.buffer.extend(notifications.into_iter().filter(|(id, _)| *id >= first_id));
+        debug!(notifications = %manager.buffer.len(), "Replaying notifications from WAL");
+
+        manager.min_id = first_id;
+        manager.next_id = wal.next_id();
+        manager.wal = Some(wal);
+        manager.update_capacity();
+
+        Ok(manager)
+    }
+
     /// Returns the handle to the manager.
     pub fn handle(&self) -> ExExManagerHandle {
         self.handle.clone()
@@ -271,10 +333,16 @@ impl ExExManager {
 
     /// Pushes a new notification into the managers internal buffer, assigning the notification a
     /// unique ID.
-    fn push_notification(&mut self, notification: ExExNotification) {
+    ///
+    /// If the manager has a [`Wal`], the notification is written to it first.
+    fn push_notification(&mut self, notification: ExExNotification) -> eyre::Result<()> {
         let next_id = self.next_id;
+        if let Some(wal) = &mut self.wal {
+            wal.append(next_id, &notification)?;
+        }
         self.buffer.push_back((next_id, notification));
         self.next_id += 1;
+        Ok(())
     }
 }
 
@@ -290,7 +358,11 @@ impl Future for ExExManager {
                     reverted_tip = ?notification.reverted_chain().map(|chain| chain.tip().number),
                     "Received new notification"
                 );
-                self.push_notification(notification);
+                if let Err(err) = self.push_notification(notification) {
+                    // the notification could not be persisted, which is irrecoverable for the
+                    // manager
+                    return Poll::Ready(Err(err))
+                }
                 continue
             }
             break
@@ -329,14 +401,34 @@ impl Future for ExExManager {
         self.update_capacity();
 
         // handle incoming exex events
-        for exex in &mut self.exex_handles {
+        let this = &mut *self;
+        for exex in &mut this.exex_handles {
+            // observed before draining the events, so that the events received by the next poll
+            // were sent after the exex received these notifications
+            let received_id = exex.received_id();
+
+            let mut finished_height = None;
             while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
                 debug!(exex_id = %exex.id, ?event, "Received event from exex");
                 exex.metrics.events_sent_total.increment(1);
                 match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::FinishedHeight(height) => {
+                        exex.finished_height = Some(height);
+                        finished_height = Some(height);
+                    }
                 }
             }
+
+            // acknowledge the notifications the exex had received before it sent the finished
+            // height
+            if let (Some(wal), Some(finished_height)) = (&mut this.wal, finished_height) {
+                if let Err(err) =
+                    wal.acknowledge(&exex.id, exex.acknowledgeable_id, finished_height)
+                {
+                    return Poll::Ready(Err(err))
+                }
+            }
+            exex.acknowledgeable_id = received_id;
         }
 
         // update watch channel block number
@@ -481,6 +573,65 @@ impl Clone for ExExManagerHandle {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlockWithSenders, SealedHeader};
+    use reth_provider::{Chain, ExecutionOutcome};
+
+    fn chain(numbers: std::ops::RangeInclusive<BlockNumber>) -> Arc<Chain> {
+        let blocks = numbers.map(|number| {
+            let mut block = SealedBlockWithSenders::default();
+            block.block.header =
+                SealedHeader::new(Header { number, ..Default::default() }, Default::default());
+            block
+        });
+        Arc::new(Chain::new(blocks, ExecutionOutcome::default(), None))
+    }
+
+    async fn poll_once(manager: &mut ExExManager) {
+        poll_fn(|cx| {
+            assert!(Pin::new(&mut *manager).poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn wal_acknowledges_received_notifications() {
+        let dir = tempfile::tempdir().unwrap();
+        let (exex, events, mut notifications) = ExExHandle::new("exex".to_string());
+        let mut manager = ExExManager::with_wal(vec![exex], 10, dir.path()).unwrap();
+        let handle = manager.handle();
+        let first_id = |manager: &ExExManager| manager.wal.as_ref().unwrap().first_id();
+
+        let committed = ExExNotification::ChainCommitted { new: chain(1..=2) };
+        handle.send(committed.clone()).unwrap();
+        poll_once(&mut manager).await;
+        assert_eq!(notifications.recv().await, Some(committed));
+
+        // the first event may have been sent before the exex received the commit
+        events.send(ExExEvent::FinishedHeight(2)).unwrap();
+        poll_once(&mut manager).await;
+        assert_eq!(first_id(&manager), 0);
+        events.send(ExExEvent::FinishedHeight(2)).unwrap();
+        poll_once(&mut manager).await;
+        assert_eq!(first_id(&manager), 1);
+
+        // the height of the revert was finished before the revert was received
+        let reverted = ExExNotification::ChainReverted { old: chain(2..=2) };
+        handle.send(reverted.clone()).unwrap();
+        events.send(ExExEvent::FinishedHeight(2)).unwrap();
+        poll_once(&mut manager).await;
+        assert_eq!(first_id(&manager), 1);
+
+        assert_eq!(notifications.recv().await, Some(reverted));
+        events.send(ExExEvent::FinishedHeight(1)).unwrap();
+        poll_once(&mut manager).await;
+        assert_eq!(first_id(&manager), 1);
+        events.send(ExExEvent::FinishedHeight(1)).unwrap();
+        poll_once(&mut manager).await;
+        assert_eq!(first_id(&manager), 2);
+    }
+
     #[tokio::test]
     async fn delivers_events() {}
 
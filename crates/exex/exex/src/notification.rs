@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
-use reth_provider::{CanonStateNotification, Chain};
+use reth_execution_types::Chain;
+use reth_provider::CanonStateNotification;
+use serde::{Deserialize, Serialize};
 
 /// Notifications sent to an `ExEx`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExExNotification {
     /// Chain got committed without a reorg, and only the new chain is returned.
     ChainCommitted {
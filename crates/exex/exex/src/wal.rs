@@ -0,0 +1,280 @@
+//! Write-ahead log of [`ExExNotification`]s.
+//!
+//! Every notification received by the [`ExExManager`](crate::ExExManager) is written to the WAL
+//! before it is sent to the `ExEx`'s, so that notifications that were not processed yet survive a
+//! restart of the node.
+//!
+//! A notification is acknowledged by an `ExEx` once the `ExEx` received it, and afterwards emitted
+//! an [`ExExEvent::FinishedHeight`](crate::ExExEvent::FinishedHeight) at or above the
+//! [acknowledgement height](acknowledgement_height) of the notification. The height of a revert is
+//! usually finished before the revert is received, so only events sent after the notification was
+//! received acknowledge it. Notifications that were acknowledged by all `ExEx`'s are removed from
+//! the WAL, the others are delivered again after a restart.
+//!
+//! The WAL is a directory with one file per notification, named after the notification ID, and
+//! a file with the ID of the first notification each `ExEx` has not acknowledged yet.
+
+use crate::ExExNotification;
+use reth_primitives::BlockNumber;
+use reth_tracing::tracing::debug;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// The extension of the files that contain notifications.
+const NOTIFICATION_FILE_EXTENSION: &str = "wal";
+
+/// The name of the file that contains the acknowledged notifications of each `ExEx`.
+const ACKNOWLEDGEMENTS_FILE_NAME: &str = "acknowledgements.json";
+
+/// Returns the height an `ExEx` has to finish to acknowledge the notification.
+///
+/// This is the tip of the committed chain, or the block the reverted chain was forked from if
+/// there is no committed chain.
+pub fn acknowledgement_height(notification: &ExExNotification) -> BlockNumber {
+    match notification.committed_chain() {
+        Some(chain) => chain.tip().number,
+        None => notification
+            .reverted_chain()
+            .map(|chain| chain.first().number.saturating_sub(1))
+            .unwrap_or_default(),
+    }
+}
+
+/// A write-ahead log of [`ExExNotification`]s, see the [module docs](self).
+#[derive(Debug)]
+pub struct Wal {
+    /// The directory of the WAL.
+    dir: PathBuf,
+    /// The acknowledgement heights of the notifications in the WAL, by notification ID.
+    heights: BTreeMap<usize, BlockNumber>,
+    /// The ID of the first notification each `ExEx` has not acknowledged yet.
+    acknowledgements: BTreeMap<String, usize>,
+    /// The ID of the next notification.
+    next_id: usize,
+}
+
+impl Wal {
+    /// Opens the WAL in the given directory, creating it if it doesn't exist.
+    ///
+    /// Returns the WAL and the notifications it contains, ordered by ID.
+    pub fn open(dir: impl AsRef<Path>) -> eyre::Result<(Self, Vec<(usize, ExExNotification)>)> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut notifications = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(NOTIFICATION_FILE_EXTENSION) {
+                continue
+            }
+            let Some(id) = path.file_stem().and_then(|id| id.to_str()?.parse::<usize>().ok())
+            else {
+                continue
+            };
+            let notification: ExExNotification = serde_json::from_slice(&fs::read(&path)?)?;
+            notifications.push((id, notification));
+        }
+        notifications.sort_unstable_by_key(|(id, _)| *id);
+
+        let acknowledgements_path = dir.join(ACKNOWLEDGEMENTS_FILE_NAME);
+        let acknowledgements = if acknowledgements_path.exists() {
+            serde_json::from_slice(&fs::read(acknowledgements_path)?)?
+        } else {
+            BTreeMap::new()
+        };
+
+        let heights = notifications
+            .iter()
+            .map(|(id, notification)| (*id, acknowledgement_height(notification)))
+            .collect::<BTreeMap<_, _>>();
+        let next_id = heights
+            .last_key_value()
+            .map(|(id, _)| id + 1)
+            .or_else(|| acknowledgements.values().max().copied())
+            .unwrap_or_default();
+        debug!(target: "exex::wal", ?dir, notifications = %notifications.len(), %next_id, "Opened WAL");
+
+        Ok((Self { dir, heights, acknowledgements, next_id }, notifications))
+    }
+
+    /// Returns the ID of the next notification.
+    pub const fn next_id(&self) -> usize {
+        self.next_id
+    }
+
+    /// Returns the ID of the first notification in the WAL, or the ID of the next notification if
+    /// the WAL is empty.
+    pub fn first_id(&self) -> usize {
+        self.heights.first_key_value().map(|(id, _)| *id).unwrap_or(self.next_id)
+    }
+
+    /// Registers the given `ExEx`'s and returns the ID of the first notification each of them has
+    /// not acknowledged yet.
+    ///
+    /// `ExEx`'s that are not known to the WAL have not seen any notification in the WAL, and start
+    /// with the next notification. `ExEx`'s that are known to the WAL but not given are removed.
+    pub fn register(&mut self, exex_ids: &[String]) -> eyre::Result<Vec<usize>> {
+        let next_id = self.next_id;
+        self.acknowledgements.retain(|id, _| exex_ids.contains(id));
+        let unacknowledged = exex_ids
+            .iter()
+            .map(|id| *self.acknowledgements.entry(id.clone()).or_insert(next_id))
+            .collect();
+        self.write_acknowledgements()?;
+        self.prune()?;
+        Ok(unacknowledged)
+    }
+
+    /// Appends the notification to the WAL.
+    ///
+    /// The ID of the notification must be equal to [`Wal::next_id`].
+    pub fn append(&mut self, id: usize, notification: &ExExNotification) -> eyre::Result<()> {
+        debug_assert_eq!(id, self.next_id, "notification appended out of order");
+        write_atomic(&self.notification_path(id), &serde_json::to_vec(notification)?)?;
+        self.heights.insert(id, acknowledgement_height(notification));
+        self.next_id = id + 1;
+        Ok(())
+    }
+
+    /// Acknowledges the notifications that were received by the `ExEx`, up to the given finished
+    /// height of the `ExEx`.
+    ///
+    /// All notifications with an ID lower than `received_id` must have been received by the `ExEx`
+    /// before it sent the finished height. Notifications that were acknowledged by all `ExEx`'s
+    /// are removed from the WAL.
+    pub fn acknowledge(
+        &mut self,
+        exex_id: &str,
+        received_id: usize,
+        finished_height: BlockNumber,
+    ) -> eyre::Result<()> {
+        let Some(&unacknowledged) = self.acknowledgements.get(exex_id) else { return Ok(()) };
+        if received_id <= unacknowledged {
+            return Ok(())
+        }
+
+        let mut acknowledged = unacknowledged;
+        for (id, height) in self.heights.range(unacknowledged..received_id) {
+            // Notifications are acknowledged in order.
+            if *height > finished_height {
+                break
+            }
+            acknowledged = id + 1;
+        }
+        if acknowledged == unacknowledged {
+            return Ok(())
+        }
+
+        debug!(target: "exex::wal", %exex_id, %acknowledged, %finished_height, "Acknowledged notifications");
+        self.acknowledgements.insert(exex_id.to_string(), acknowledged);
+        self.write_acknowledgements()?;
+        self.prune()
+    }
+
+    /// Removes the notifications that were acknowledged by all `ExEx`'s.
+    fn prune(&mut self) -> eyre::Result<()> {
+        let acknowledged = self.acknowledgements.values().min().copied().unwrap_or(self.next_id);
+
+        let retained = self.heights.split_off(&acknowledged);
+        let pruned = std::mem::replace(&mut self.heights, retained);
+        for id in pruned.keys() {
+            fs::remove_file(self.notification_path(*id))?;
+        }
+        if !pruned.is_empty() {
+            debug!(target: "exex::wal", pruned = %pruned.len(), "Pruned WAL");
+        }
+        Ok(())
+    }
+
+    fn write_acknowledgements(&self) -> eyre::Result<()> {
+        write_atomic(
+            &self.dir.join(ACKNOWLEDGEMENTS_FILE_NAME),
+            &serde_json::to_vec(&self.acknowledgements)?,
+        )
+    }
+
+    fn notification_path(&self, id: usize) -> PathBuf {
+        self.dir.join(format!("{id:020}.{NOTIFICATION_FILE_EXTENSION}"))
+    }
+}
+
+/// Writes the file by writing to a temporary file first and renaming it, so that the file is never
+/// left partially written.
+///
+/// The temporary file is synced before the rename and the directory after it, so that the file is
+/// durable once this returns.
+fn write_atomic(path: &Path, contents: &[u8]) -> eyre::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(tmp_path, path)?;
+    if let Some(dir) = path.parent() {
+        fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlockWithSenders, SealedHeader};
+    use reth_provider::{Chain, ExecutionOutcome};
+    use std::sync::Arc;
+
+    fn chain(numbers: std::ops::RangeInclusive<BlockNumber>) -> Arc<Chain> {
+        let blocks = numbers.map(|number| {
+            let mut block = SealedBlockWithSenders::default();
+            block.block.header =
+                SealedHeader::new(Header { number, ..Default::default() }, Default::default());
+            block
+        });
+        Arc::new(Chain::new(blocks, ExecutionOutcome::default(), None))
+    }
+
+    #[test]
+    fn acknowledge_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let exexs = vec!["first".to_string(), "second".to_string()];
+
+        let (mut wal, notifications) = Wal::open(dir.path()).unwrap();
+        assert!(notifications.is_empty());
+        assert_eq!(wal.register(&exexs).unwrap(), vec![0, 0]);
+
+        let committed = ExExNotification::ChainCommitted { new: chain(1..=2) };
+        let reorged = ExExNotification::ChainReorged { old: chain(2..=2), new: chain(2..=3) };
+        let reverted = ExExNotification::ChainReverted { old: chain(3..=3) };
+        wal.append(0, &committed).unwrap();
+        wal.append(1, &reorged).unwrap();
+        wal.append(2, &reverted).unwrap();
+        assert_eq!(wal.heights.values().copied().collect::<Vec<_>>(), vec![2, 3, 2]);
+
+        // the reorg was not delivered to the first exex yet
+        wal.acknowledge("first", 1, 3).unwrap();
+        wal.acknowledge("second", 3, 2).unwrap();
+        assert_eq!(
+            wal.acknowledgements,
+            BTreeMap::from([("first".to_string(), 1), ("second".to_string(), 1)])
+        );
+        assert_eq!(wal.first_id(), 1);
+
+        // replay after a restart
+        let (mut wal, notifications) = Wal::open(dir.path()).unwrap();
+        assert_eq!(notifications, vec![(1, reorged), (2, reverted)]);
+        assert_eq!(wal.next_id(), 3);
+        assert_eq!(wal.register(&exexs).unwrap(), vec![1, 1]);
+
+        // a new exex starts with the next notification, a removed exex doesn't prevent pruning
+        assert_eq!(wal.register(&["second".to_string(), "third".to_string()]).unwrap(), vec![1, 3]);
+        wal.acknowledge("second", 3, 3).unwrap();
+        assert_eq!(wal.first_id(), 3);
+        let (_, notifications) = Wal::open(dir.path()).unwrap();
+        assert!(notifications.is_empty());
+    }
+}
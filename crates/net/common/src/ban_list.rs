@@ -104,6 +104,11 @@ impl BanList {
         self.banned_peers.contains_key(peer_id)
     }
 
+    /// Returns an iterator over the peers that are banned until a timestamp.
+    pub fn banned_peers_until(&self) -> impl Iterator<Item = (PeerId, Instant)> + '_ {
+        self.banned_peers.iter().filter_map(|(peer, until)| until.map(|until| (*peer, until)))
+    }
+
     /// Unbans the ip address
     pub fn unban_ip(&mut self, ip: &IpAddr) {
         self.banned_ips.remove(ip);
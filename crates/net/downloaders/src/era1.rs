@@ -0,0 +1,548 @@
+use alloy_rlp::{Decodable, Encodable};
+use reth_primitives::{
+    hex, BlockBody, BlockNumber, Header, ReceiptWithBloom, SealedBlock, SealedHeader, B256, U256,
+};
+use sha2::{Digest, Sha256};
+use std::io::{self, Read, Write};
+use thiserror::Error;
+use tracing::trace;
+
+/// The maximum number of blocks in an Era1 file, which is the number of blocks of an epoch.
+pub const MAX_ERA1_BLOCKS: usize = 8192;
+
+/// The depth of the merkle tree of the header accumulator of an epoch.
+const ACCUMULATOR_DEPTH: usize = MAX_ERA1_BLOCKS.trailing_zeros() as usize;
+
+/// The length of the header of an e2store entry: type (2 bytes), length (4 bytes) and reserved (2
+/// bytes).
+const ENTRY_HEADER_LEN: u64 = 8;
+
+/// The maximum length of a compressed header, body or receipts entry, or of an entry of an unknown
+/// type. A pre-merge block and its receipts are far smaller, even uncompressed.
+const MAX_COMPRESSED_ENTRY_LEN: usize = 16 * 1024 * 1024;
+
+/// The e2store entry types of an Era1 file.
+const VERSION: u16 = 0x3265;
+const COMPRESSED_HEADER: u16 = 0x03;
+const COMPRESSED_BODY: u16 = 0x04;
+const COMPRESSED_RECEIPTS: u16 = 0x05;
+const TOTAL_DIFFICULTY: u16 = 0x06;
+const ACCUMULATOR: u16 = 0x07;
+const BLOCK_INDEX: u16 = 0x3266;
+
+/// An error that can occur when reading or writing an Era1 file.
+#[derive(Debug, Error)]
+pub enum Era1Error {
+    /// An error occurred when reading or writing the file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// An error occurred when decoding a header, body or receipts.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+
+    /// The file contains an entry of an unexpected type.
+    #[error("unexpected entry type {got:#06x}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// The type of the entry in the file.
+        got: u16,
+        /// The expected type of the entry.
+        expected: u16,
+    },
+
+    /// The file contains an entry with an invalid length.
+    #[error("entry of type {0:#06x} has invalid length {1}")]
+    InvalidEntryLength(u16, usize),
+
+    /// The file contains no blocks.
+    #[error("era1 file contains no blocks")]
+    Empty,
+
+    /// The file contains more blocks than an epoch.
+    #[error("era1 file contains more than {MAX_ERA1_BLOCKS} blocks")]
+    TooManyBlocks,
+
+    /// The blocks of the file are not consecutive.
+    #[error("block {got} does not follow block {}", expected - 1)]
+    NonConsecutiveBlock {
+        /// The number of the block in the file.
+        got: BlockNumber,
+        /// The expected block number.
+        expected: BlockNumber,
+    },
+
+    /// The block index does not match the blocks of the file.
+    #[error("invalid block index: {0}")]
+    InvalidBlockIndex(&'static str),
+
+    /// The accumulator root of the file does not match the root of the blocks of the file.
+    #[error("accumulator root mismatch: got {got}, expected {expected}")]
+    AccumulatorRootMismatch {
+        /// The accumulator root computed from the blocks of the file.
+        got: B256,
+        /// The accumulator root in the file.
+        expected: B256,
+    },
+}
+
+/// A block of an Era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1Block {
+    /// The header of the block.
+    pub header: SealedHeader,
+    /// The body of the block.
+    pub body: BlockBody,
+    /// The receipts of the transactions of the block.
+    pub receipts: Vec<ReceiptWithBloom>,
+    /// The total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl Era1Block {
+    /// Returns the block.
+    pub fn into_block(self) -> SealedBlock {
+        SealedBlock {
+            header: self.header,
+            body: self.body.transactions,
+            ommers: self.body.ommers,
+            withdrawals: self.body.withdrawals,
+            requests: self.body.requests,
+        }
+    }
+}
+
+/// The content of an Era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1 {
+    /// The consecutive blocks of the file.
+    pub blocks: Vec<Era1Block>,
+    /// The root of the header accumulator of the blocks.
+    pub accumulator_root: B256,
+}
+
+impl Era1 {
+    /// Returns the number of the first block of the file.
+    pub fn start_block(&self) -> Option<BlockNumber> {
+        self.blocks.first().map(|block| block.header.number)
+    }
+
+    /// Returns the number of the last block of the file.
+    pub fn end_block(&self) -> Option<BlockNumber> {
+        self.blocks.last().map(|block| block.header.number)
+    }
+}
+
+/// Reads an [`Era1`] file.
+///
+/// The file is read sequentially. The block index must match the offsets of the blocks and the
+/// accumulator root must match the root of the header hashes and total difficulties of the
+/// blocks.
+#[derive(Debug)]
+pub struct Era1Reader<R> {
+    /// The reader of the file.
+    reader: R,
+    /// The number of bytes read so far.
+    position: u64,
+}
+
+impl<R: Read> Era1Reader<R> {
+    /// Creates a new reader of the given Era1 file.
+    pub const fn new(reader: R) -> Self {
+        Self { reader, position: 0 }
+    }
+
+    /// Reads and verifies the whole file.
+    pub fn read(mut self) -> Result<Era1, Era1Error> {
+        let version = self.expect_entry(VERSION)?;
+        if !version.is_empty() {
+            return Err(Era1Error::InvalidEntryLength(VERSION, version.len()))
+        }
+
+        let mut blocks = Vec::new();
+        let mut offsets = Vec::new();
+        let accumulator_root = loop {
+            let offset = self.position;
+            let (ty, data) =
+                self.next_entry()?.ok_or(io::Error::from(io::ErrorKind::UnexpectedEof))?;
+            match ty {
+                COMPRESSED_HEADER => {
+                    if blocks.len() == MAX_ERA1_BLOCKS {
+                        return Err(Era1Error::TooManyBlocks)
+                    }
+                    let block = self.read_block(&data)?;
+                    if let Some(previous) = blocks.last().map(|b: &Era1Block| b.header.number) {
+                        if block.header.number != previous + 1 {
+                            return Err(Era1Error::NonConsecutiveBlock {
+                                got: block.header.number,
+                                expected: previous + 1,
+                            })
+                        }
+                    }
+                    trace!(target: "downloaders::era1", number = block.header.number, "Read block");
+                    offsets.push(offset);
+                    blocks.push(block);
+                }
+                ACCUMULATOR => {
+                    if data.len() != 32 {
+                        return Err(Era1Error::InvalidEntryLength(ACCUMULATOR, data.len()))
+                    }
+                    break B256::from_slice(&data)
+                }
+                // other entries are allowed between the blocks and the accumulator
+                _ => continue,
+            }
+        };
+        if blocks.is_empty() {
+            return Err(Era1Error::Empty)
+        }
+
+        let index_offset = self.position;
+        let index = self.expect_entry(BLOCK_INDEX)?;
+        if index.len() != 16 + blocks.len() * 8 {
+            return Err(Era1Error::InvalidEntryLength(BLOCK_INDEX, index.len()))
+        }
+        let read_u64 = |at: usize| u64::from_le_bytes(index[at..at + 8].try_into().unwrap());
+        if read_u64(0) != blocks[0].header.number {
+            return Err(Era1Error::InvalidBlockIndex("start block mismatch"))
+        }
+        if read_u64(index.len() - 8) != blocks.len() as u64 {
+            return Err(Era1Error::InvalidBlockIndex("block count mismatch"))
+        }
+        for (i, offset) in offsets.iter().enumerate() {
+            // offsets are relative to the start of the block index
+            let relative = read_u64(8 + i * 8) as i64;
+            if index_offset as i64 + relative != *offset as i64 {
+                return Err(Era1Error::InvalidBlockIndex("block offset mismatch"))
+            }
+        }
+
+        let root = accumulator_root(
+            blocks.iter().map(|block| (block.header.hash(), block.total_difficulty)),
+        );
+        if root != accumulator_root {
+            return Err(Era1Error::AccumulatorRootMismatch { got: root, expected: accumulator_root })
+        }
+
+        Ok(Era1 { blocks, accumulator_root })
+    }
+
+    /// Reads the block whose compressed header was just read.
+    fn read_block(&mut self, compressed_header: &[u8]) -> Result<Era1Block, Era1Error> {
+        let header = Header::decode(&mut decompress(compressed_header)?.as_slice())?.seal_slow();
+        let body =
+            BlockBody::decode(&mut decompress(&self.expect_entry(COMPRESSED_BODY)?)?.as_slice())?;
+        let receipts = Vec::<ReceiptWithBloom>::decode(
+            &mut decompress(&self.expect_entry(COMPRESSED_RECEIPTS)?)?.as_slice(),
+        )?;
+        let total_difficulty = self.expect_entry(TOTAL_DIFFICULTY)?;
+        if total_difficulty.len() != 32 {
+            return Err(Era1Error::InvalidEntryLength(TOTAL_DIFFICULTY, total_difficulty.len()))
+        }
+
+        Ok(Era1Block {
+            header,
+            body,
+            receipts,
+            total_difficulty: U256::from_le_slice(&total_difficulty),
+        })
+    }
+
+    /// Reads the next entry, which must be of the given type.
+    fn expect_entry(&mut self, expected: u16) -> Result<Vec<u8>, Era1Error> {
+        let (ty, data) = self.next_entry()?.ok_or(io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        if ty != expected {
+            return Err(Era1Error::UnexpectedEntry { got: ty, expected })
+        }
+        Ok(data)
+    }
+
+    /// Reads the next entry, or returns `None` at the end of the file.
+    fn next_entry(&mut self) -> Result<Option<(u16, Vec<u8>)>, Era1Error> {
+        let mut header = [0; ENTRY_HEADER_LEN as usize];
+        match self.reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err.into()),
+        }
+
+        let ty = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+        if header[6..] != [0, 0] || len > max_entry_len(ty) {
+            return Err(Era1Error::InvalidEntryLength(ty, len))
+        }
+
+        // the buffer grows with the data that is actually read, so a truncated file can't make
+        // the reader allocate the full length
+        let mut data = Vec::new();
+        (&mut self.reader).take(len as u64).read_to_end(&mut data)?;
+        if data.len() != len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+        }
+        self.position += ENTRY_HEADER_LEN + len as u64;
+        Ok(Some((ty, data)))
+    }
+}
+
+/// Returns the maximum length of an entry of the given type.
+const fn max_entry_len(ty: u16) -> usize {
+    match ty {
+        VERSION => 0,
+        TOTAL_DIFFICULTY | ACCUMULATOR => 32,
+        BLOCK_INDEX => 16 + MAX_ERA1_BLOCKS * 8,
+        _ => MAX_COMPRESSED_ENTRY_LEN,
+    }
+}
+
+/// Writes an [`Era1`] file.
+///
+/// Blocks must be appended in order. The accumulator root and the block index are written when
+/// the writer is finished.
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    /// The writer of the file.
+    writer: W,
+    /// The number of bytes written so far.
+    position: u64,
+    /// The offsets of the written blocks.
+    offsets: Vec<u64>,
+    /// The header hashes and total difficulties of the written blocks.
+    records: Vec<(B256, U256)>,
+    /// The number of the first written block.
+    start_block: Option<BlockNumber>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer and writes the version entry of the file.
+    pub fn new(writer: W) -> Result<Self, Era1Error> {
+        let mut this = Self {
+            writer,
+            position: 0,
+            offsets: Vec::new(),
+            records: Vec::new(),
+            start_block: None,
+        };
+        this.write_entry(VERSION, &[])?;
+        Ok(this)
+    }
+
+    /// Returns the number of blocks written so far.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no blocks were written yet.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Appends the block to the file.
+    pub fn append_block(&mut self, block: &Era1Block) -> Result<(), Era1Error> {
+        if self.records.len() == MAX_ERA1_BLOCKS {
+            return Err(Era1Error::TooManyBlocks)
+        }
+        let start_block = *self.start_block.get_or_insert(block.header.number);
+        let expected = start_block + self.records.len() as u64;
+        if block.header.number != expected {
+            return Err(Era1Error::NonConsecutiveBlock { got: block.header.number, expected })
+        }
+
+        self.offsets.push(self.position);
+        self.write_entry(COMPRESSED_HEADER, &compress(&alloy_rlp::encode(block.header.header()))?)?;
+        self.write_entry(COMPRESSED_BODY, &compress(&alloy_rlp::encode(&block.body))?)?;
+        let mut receipts = Vec::new();
+        block.receipts.encode(&mut receipts);
+        self.write_entry(COMPRESSED_RECEIPTS, &compress(&receipts)?)?;
+        self.write_entry(TOTAL_DIFFICULTY, &block.total_difficulty.to_le_bytes::<32>())?;
+
+        self.records.push((block.header.hash(), block.total_difficulty));
+        Ok(())
+    }
+
+    /// Writes the accumulator root and the block index, and returns the accumulator root.
+    pub fn finish(mut self) -> Result<B256, Era1Error> {
+        let Some(start_block) = self.start_block else { return Err(Era1Error::Empty) };
+
+        let root = accumulator_root(self.records.iter().copied());
+        self.write_entry(ACCUMULATOR, root.as_slice())?;
+
+        // offsets are relative to the start of the block index
+        let index_offset = self.position as i64;
+        let mut index = Vec::with_capacity(16 + self.offsets.len() * 8);
+        index.extend_from_slice(&start_block.to_le_bytes());
+        for offset in &self.offsets {
+            index.extend_from_slice(&(*offset as i64 - index_offset).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        self.write_entry(BLOCK_INDEX, &index)?;
+
+        self.writer.flush()?;
+        Ok(root)
+    }
+
+    fn write_entry(&mut self, ty: u16, data: &[u8]) -> Result<(), Era1Error> {
+        let len =
+            u32::try_from(data.len()).map_err(|_| Era1Error::InvalidEntryLength(ty, data.len()))?;
+        self.writer.write_all(&ty.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+        self.writer.write_all(data)?;
+        self.position += ENTRY_HEADER_LEN + data.len() as u64;
+        Ok(())
+    }
+}
+
+/// Returns the name of the Era1 file of the given network and epoch:
+/// `<network>-<epoch>-<short accumulator root>.era1`.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator_root: B256) -> String {
+    format!("{network}-{epoch:05}-{}.era1", hex::encode(&accumulator_root[..4]))
+}
+
+/// Returns the root of the header accumulator of the given header hashes and total difficulties.
+///
+/// This is the SSZ hash tree root of `List[HeaderRecord, 8192]`, where `HeaderRecord` is the
+/// container `{block_hash: Bytes32, total_difficulty: uint256}`.
+pub fn accumulator_root(records: impl IntoIterator<Item = (B256, U256)>) -> B256 {
+    let mut layer = records
+        .into_iter()
+        .map(|(hash, total_difficulty)| {
+            sha256_pair(hash.as_slice(), &total_difficulty.to_le_bytes::<32>())
+        })
+        .collect::<Vec<_>>();
+    let len = layer.len() as u64;
+
+    let mut zero = B256::ZERO;
+    for _ in 0..ACCUMULATOR_DEPTH {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer
+            .chunks(2)
+            .map(|pair| sha256_pair(pair[0].as_slice(), pair[1].as_slice()))
+            .collect();
+        zero = sha256_pair(zero.as_slice(), zero.as_slice());
+    }
+    let root = layer.first().copied().unwrap_or(zero);
+
+    // mix in the length of the list
+    let mut length = [0; 32];
+    length[..8].copy_from_slice(&len.to_le_bytes());
+    sha256_pair(root.as_slice(), &length)
+}
+
+fn sha256_pair(left: &[u8], right: &[u8]) -> B256 {
+    B256::from_slice(&Sha256::new().chain_update(left).chain_update(right).finalize())
+}
+
+/// Compresses the data with the snappy framed format.
+fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(data)?;
+    encoder.into_inner().map_err(|err| err.into_error())
+}
+
+/// Decompresses data in the snappy framed format.
+fn decompress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    snap::read::FrameDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Receipt, TxType};
+    use reth_testing_utils::generators::{self, random_block_range};
+
+    fn blocks() -> Vec<Era1Block> {
+        let mut rng = generators::rng();
+        random_block_range(&mut rng, 0..=9, B256::ZERO, 0..3)
+            .into_iter()
+            .scan(U256::ZERO, |total_difficulty, block| {
+                *total_difficulty += block.difficulty;
+                let receipts = (0..block.body.len() as u64)
+                    .map(|i| {
+                        Receipt {
+                            tx_type: TxType::Eip1559,
+                            success: true,
+                            cumulative_gas_used: 21_000 * (i + 1),
+                            ..Default::default()
+                        }
+                        .with_bloom()
+                    })
+                    .collect();
+                let (header, body) = block.split_header_body();
+                Some(Era1Block { header, body, receipts, total_difficulty: *total_difficulty })
+            })
+            .collect()
+    }
+
+    fn write(blocks: &[Era1Block]) -> (Vec<u8>, B256) {
+        let mut file = Vec::new();
+        let mut writer = Era1Writer::new(&mut file).unwrap();
+        for block in blocks {
+            writer.append_block(block).unwrap();
+        }
+        let root = writer.finish().unwrap();
+        (file, root)
+    }
+
+    #[test]
+    fn write_and_read() {
+        let blocks = blocks();
+        let (file, root) = write(&blocks);
+        assert_eq!(
+            root,
+            accumulator_root(
+                blocks.iter().map(|block| (block.header.hash(), block.total_difficulty))
+            )
+        );
+
+        let era1 = Era1Reader::new(file.as_slice()).read().unwrap();
+        assert_eq!(era1, Era1 { blocks, accumulator_root: root });
+        assert_eq!(era1.start_block(), Some(0));
+        assert_eq!(era1.end_block(), Some(9));
+    }
+
+    #[test]
+    fn reject_invalid_accumulator_root() {
+        let mut blocks = blocks();
+        let (_, root) = write(&blocks);
+
+        // the file contains a valid accumulator root for different total difficulties
+        blocks[3].total_difficulty += U256::from(1);
+        let (mut file, _) = write(&blocks);
+        let index_len = ENTRY_HEADER_LEN as usize + 16 + blocks.len() * 8;
+        let root_start = file.len() - index_len - 32;
+        file[root_start..root_start + 32].copy_from_slice(root.as_slice());
+
+        assert!(matches!(
+            Era1Reader::new(file.as_slice()).read(),
+            Err(Era1Error::AccumulatorRootMismatch { expected, .. }) if expected == root
+        ));
+    }
+
+    #[test]
+    fn reject_oversized_entry() {
+        let (mut file, _) = write(&blocks());
+
+        // the length of the first compressed header, after the version entry
+        let len_start = ENTRY_HEADER_LEN as usize + 2;
+        file[len_start..len_start + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert!(matches!(
+            Era1Reader::new(file.as_slice()).read(),
+            Err(Era1Error::InvalidEntryLength(COMPRESSED_HEADER, len)) if len == u32::MAX as usize
+        ));
+    }
+
+    #[test]
+    fn reject_non_consecutive_blocks() {
+        let blocks = blocks();
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        writer.append_block(&blocks[0]).unwrap();
+        assert!(matches!(
+            writer.append_block(&blocks[2]),
+            Err(Era1Error::NonConsecutiveBlock { got: 2, expected: 1 })
+        ));
+    }
+}
@@ -11,7 +11,7 @@ use reth_network_p2p::{
 use reth_network_peers::PeerId;
 use reth_primitives::{
     BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, BytesMut, Header, HeadersDirection,
-    SealedHeader, B256,
+    SealedBlock, SealedHeader, B256,
 };
 use std::{collections::HashMap, io, path::Path};
 use thiserror::Error;
@@ -89,6 +89,23 @@ impl FileClient {
         Ok(Self::from_reader(&reader[..], file_len).await?.0)
     }
 
+    /// Create a new file client from blocks that were read from a file.
+    pub fn from_blocks(blocks: impl IntoIterator<Item = SealedBlock>) -> Self {
+        let mut headers = HashMap::new();
+        let mut hash_to_number = HashMap::new();
+        let mut bodies = HashMap::new();
+
+        for block in blocks {
+            let block_hash = block.hash();
+            let (header, body) = block.split_header_body();
+            hash_to_number.insert(block_hash, header.number);
+            headers.insert(header.number, header.unseal());
+            bodies.insert(block_hash, body);
+        }
+
+        Self { headers, hash_to_number, bodies }
+    }
+
     /// Get the tip hash of the chain.
     pub fn tip(&self) -> Option<B256> {
         self.headers.get(&self.max_block()?).map(|h| h.hash_slow())
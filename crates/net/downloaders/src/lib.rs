@@ -41,6 +41,13 @@ pub mod file_client;
 /// made for op-erigon's import needs).
 pub mod receipt_file_client;
 
+/// Module for reading and writing [Era1](https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md)
+/// archives of pre-merge blocks.
+///
+/// Contains [`Era1Reader`](era1::Era1Reader) and [`Era1Writer`](era1::Era1Writer), which verify
+/// and compute the header accumulator root of an epoch.
+pub mod era1;
+
 /// Module with a codec for reading and encoding block bodies in files.
 ///
 /// Enables decoding and encoding `Block` types within file contexts.
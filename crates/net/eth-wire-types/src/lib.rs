@@ -12,7 +12,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod status;
-pub use status::{Status, StatusBuilder};
+pub use status::{BlockRangeUpdate, Status, Status69, StatusBuilder};
 
 pub mod version;
 pub use version::EthVersion;
@@ -2,7 +2,7 @@
 
 use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
 use reth_codecs_derive::derive_arbitrary;
-use reth_primitives::{ReceiptWithBloom, B256};
+use reth_primitives::{Receipt, ReceiptWithBloom, B256};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -32,9 +32,52 @@ pub struct Receipts(
     pub Vec<Vec<ReceiptWithBloom>>,
 );
 
+impl From<Receipts69> for Receipts {
+    fn from(receipts: Receipts69) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(Receipt::with_bloom).collect())
+                .collect(),
+        )
+    }
+}
+
+/// The `eth/69` response to [`GetReceipts`], containing receipt lists that correspond to each
+/// block requested.
+///
+/// Unlike [`Receipts`], the receipts don't include the logs bloom, which the receiving peer can
+/// compute from the logs.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Receipts69(
+    /// Each receipt list should correspond to a block hash in the request.
+    #[cfg_attr(
+        any(test, feature = "arbitrary"),
+        proptest(
+            strategy = "proptest::collection::vec(proptest::collection::vec(proptest::arbitrary::any::<Receipt>(), 0..=50), 0..=5)"
+        )
+    )]
+    pub Vec<Vec<Receipt>>,
+);
+
+impl From<Receipts> for Receipts69 {
+    fn from(receipts: Receipts) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.receipt).collect())
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{message::RequestPair, GetReceipts, Receipts};
+    use crate::{message::RequestPair, GetReceipts, Receipts, Receipts69};
     use alloy_rlp::{Decodable, Encodable};
     use reth_primitives::{hex, Log, Receipt, ReceiptWithBloom, TxType};
 
@@ -54,6 +97,37 @@ mod tests {
         assert_eq!(receipts, decoded);
     }
 
+    #[test]
+    fn roundtrip_receipts69() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(
+                hex!("0000000000000000000000000000000000000011").into(),
+                vec![
+                    hex!("000000000000000000000000000000000000000000000000000000000000dead").into()
+                ],
+                hex!("0100ff")[..].into(),
+            )],
+            ..Default::default()
+        };
+        let receipts = RequestPair {
+            request_id: 1111,
+            message: Receipts69(vec![vec![receipt.clone()], vec![]]),
+        };
+
+        let mut out = vec![];
+        receipts.encode(&mut out);
+        let decoded = RequestPair::<Receipts69>::decode(&mut out.as_slice()).unwrap();
+        assert_eq!(receipts, decoded);
+
+        // the blooms are computed from the logs
+        let with_bloom = Receipts::from(decoded.message);
+        assert_eq!(with_bloom, Receipts(vec![vec![receipt.with_bloom()], vec![]]));
+        assert_eq!(Receipts69::from(with_bloom), receipts.message);
+    }
+
     #[test]
     // Test vector from: https://eips.ethereum.org/EIPS/eip-2481
     fn encode_get_receipts() {
@@ -148,6 +148,94 @@ impl Default for Status {
     }
 }
 
+/// The `eth/69` status message, which replaces the total difficulty and best block hash of
+/// [`Status`] with the range of blocks the peer can serve.
+///
+/// See also <https://eips.ethereum.org/EIPS/eip-7642>
+#[derive_arbitrary(rlp)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Status69 {
+    /// The current protocol version, 69 or higher.
+    pub version: u8,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier, see [`Status::forkid`].
+    pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest_block: u64,
+
+    /// The latest block the peer can serve.
+    pub latest_block: u64,
+
+    /// The hash of the latest block.
+    pub latest_block_hash: B256,
+}
+
+impl Status69 {
+    /// Creates the `eth/69` status message from the given [`Status`] and block range.
+    ///
+    /// The total difficulty and block hash of the [`Status`] are replaced by the block range.
+    pub const fn new(status: Status, range: BlockRangeUpdate) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            genesis: status.genesis,
+            forkid: status.forkid,
+            earliest_block: range.earliest,
+            latest_block: range.latest,
+            latest_block_hash: range.latest_hash,
+        }
+    }
+
+    /// Returns the block range announced in the status message.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest_block,
+            latest: self.latest_block,
+            latest_hash: self.latest_block_hash,
+        }
+    }
+}
+
+impl From<Status69> for Status {
+    /// Converts the `eth/69` status message into a [`Status`] with zero total difficulty and the
+    /// latest block hash as block hash.
+    fn from(status: Status69) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            total_difficulty: U256::ZERO,
+            blockhash: status.latest_block_hash,
+            genesis: status.genesis,
+            forkid: status.forkid,
+        }
+    }
+}
+
+/// Announces the range of blocks a peer can serve, introduced in `eth/69`.
+///
+/// Peers send this message when their range of available blocks changes, e.g. after their
+/// latest block advanced.
+#[derive_arbitrary(rlp)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockRangeUpdate {
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+    /// The latest block the peer can serve.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
 /// Builder for [`Status`] messages.
 ///
 /// # Example
@@ -227,7 +315,7 @@ impl StatusBuilder {
 
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status};
+    use crate::{BlockRangeUpdate, EthVersion, Status, Status69};
     use alloy_rlp::{Decodable, Encodable};
     use rand::Rng;
     use reth_primitives::{
@@ -404,4 +492,41 @@ mod tests {
         assert_eq!(status.blockhash, head_hash);
         assert_eq!(status.genesis, genesis_hash);
     }
+
+    #[test]
+    fn roundtrip_eth69_status_message() {
+        let status = Status::builder()
+            .version(EthVersion::Eth69 as u8)
+            .chain(Chain::mainnet())
+            .total_difficulty(U256::from(100))
+            .blockhash(B256::with_last_byte(1))
+            .build();
+        let range =
+            BlockRangeUpdate { earliest: 0, latest: 1_000, latest_hash: B256::with_last_byte(2) };
+        let status69 = Status69::new(status, range);
+        assert_eq!(status69.block_range(), range);
+
+        let mut rlp_status = vec![];
+        status69.encode(&mut rlp_status);
+        let decoded = Status69::decode(&mut &rlp_status[..]).unwrap();
+        assert_eq!(decoded, status69);
+
+        // the total difficulty is not part of the eth/69 status
+        let converted = Status::from(decoded);
+        assert_eq!(converted.total_difficulty, U256::ZERO);
+        assert_eq!(converted.blockhash, range.latest_hash);
+        assert_eq!(converted.genesis, status.genesis);
+        assert_eq!(converted.forkid, status.forkid);
+    }
+
+    #[test]
+    fn roundtrip_block_range_update() {
+        let range =
+            BlockRangeUpdate { earliest: 10, latest: 1_000, latest_hash: B256::with_last_byte(2) };
+
+        let mut rlp_range = vec![];
+        range.encode(&mut rlp_range);
+        assert_eq!(rlp_range.len(), range.length());
+        assert_eq!(BlockRangeUpdate::decode(&mut &rlp_range[..]).unwrap(), range);
+    }
 }
@@ -34,6 +34,14 @@ pub enum EthStreamError {
         /// The number of transaction sizes.
         sizes_len: usize,
     },
+    #[error("invalid block range: earliest {earliest} > latest {latest}")]
+    /// Received a block range update whose earliest block is higher than its latest block.
+    InvalidBlockRange {
+        /// The earliest block of the range.
+        earliest: u64,
+        /// The latest block of the range.
+        latest: u64,
+    },
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
@@ -98,4 +106,15 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("no block range set for the eth/69 status message")]
+    /// The `eth/69` status message can't be sent without the block range of the node.
+    MissingBlockRange,
+    #[error("invalid block range in status message: earliest {earliest} > latest {latest}")]
+    /// The earliest block of the announced block range is higher than the latest block.
+    InvalidBlockRange {
+        /// The earliest block of the range.
+        earliest: u64,
+        /// The latest block of the range.
+        latest: u64,
+    },
 }
@@ -2,7 +2,8 @@ use crate::{
     errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
     p2pstream::HANDSHAKE_TIMEOUT,
-    CanDisconnect, DisconnectReason, EthMessage, EthVersion, ProtocolMessage, Status,
+    BlockRangeUpdate, CanDisconnect, DisconnectReason, EthMessage, EthVersion, ProtocolMessage,
+    Status, Status69,
 };
 use futures::{ready, Sink, SinkExt, StreamExt};
 use pin_project::pin_project;
@@ -30,12 +31,23 @@ pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 pub struct UnauthedEthStream<S> {
     #[pin]
     inner: S,
+    /// The block range announced in the `eth/69` status message.
+    block_range: Option<BlockRangeUpdate>,
 }
 
 impl<S> UnauthedEthStream<S> {
     /// Create a new `UnauthedEthStream` from a type `S` which implements `Stream` and `Sink`.
     pub const fn new(inner: S) -> Self {
-        Self { inner }
+        Self { inner, block_range: None }
+    }
+
+    /// Sets the block range that is announced if `eth/69` or higher was negotiated.
+    ///
+    /// This is required for `eth/69`, the handshake fails with
+    /// [`EthHandshakeError::MissingBlockRange`] if it is not set.
+    pub const fn with_block_range(mut self, block_range: BlockRangeUpdate) -> Self {
+        self.block_range = Some(block_range);
+        self
     }
 
     /// Consumes the type and returns the wrapped stream
@@ -52,6 +64,10 @@ where
     /// Consumes the [`UnauthedEthStream`] and returns an [`EthStream`] after the `Status`
     /// handshake is completed successfully. This also returns the `Status` message sent by the
     /// remote peer.
+    ///
+    /// If the version of the given `Status` is `eth/69` or higher, the [`Status69`] message is
+    /// exchanged instead, and the peer's message is returned as `Status` without total
+    /// difficulty.
     pub async fn handshake(
         self,
         status: Status,
@@ -83,11 +99,19 @@ where
             "sending eth status to peer"
         );
 
+        let version = EthVersion::try_from(status.version)?;
+        let our_status = if version >= EthVersion::Eth69 {
+            let Some(block_range) = self.block_range else {
+                return Err(EthHandshakeError::MissingBlockRange.into())
+            };
+            EthMessage::Status69(Status69::new(status, block_range))
+        } else {
+            EthMessage::Status(status)
+        };
+
         // we need to encode and decode here on our own because we don't have an `EthStream` yet
         // The max length for a status with TTD is: <msg id = 1 byte> + <rlp(status) = 88 byte>
-        self.inner
-            .send(alloy_rlp::encode(ProtocolMessage::from(EthMessage::Status(status))).into())
-            .await?;
+        self.inner.send(alloy_rlp::encode(ProtocolMessage::from(our_status)).into()).await?;
 
         let their_msg_res = self.inner.next().await;
 
@@ -104,7 +128,6 @@ where
             return Err(EthStreamError::MessageTooBig(their_msg.len()))
         }
 
-        let version = EthVersion::try_from(status.version)?;
         let msg = match ProtocolMessage::decode_message(version, &mut their_msg.as_ref()) {
             Ok(m) => m,
             Err(err) => {
@@ -114,71 +137,82 @@ where
             }
         };
 
-        // The following checks should match the checks in go-ethereum:
-        // https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/handshake.go#L87-L89
-        match msg.message {
-            EthMessage::Status(resp) => {
-                trace!(
-                    status=%resp,
-                    "validating incoming eth status from peer"
-                );
-                if status.genesis != resp.genesis {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedGenesis(
-                        GotExpected { expected: status.genesis, got: resp.genesis }.into(),
-                    )
-                    .into())
-                }
-
-                if status.version != resp.version {
+        let resp = match msg.message {
+            EthMessage::Status(resp) => resp,
+            EthMessage::Status69(resp) => {
+                if resp.earliest_block > resp.latest_block {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
-                        got: resp.version,
-                        expected: status.version,
-                    })
-                    .into())
-                }
-
-                if status.chain != resp.chain {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedChain(GotExpected {
-                        got: resp.chain,
-                        expected: status.chain,
-                    })
-                    .into())
-                }
-
-                // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
-                // larger, it will still fit within 100 bits
-                if status.total_difficulty.bit_len() > 100 {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
-                        got: status.total_difficulty.bit_len(),
-                        maximum: 100,
+                    return Err(EthHandshakeError::InvalidBlockRange {
+                        earliest: resp.earliest_block,
+                        latest: resp.latest_block,
                     }
                     .into())
                 }
-
-                if let Err(err) =
-                    fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
-                {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(err.into())
-                }
-
-                // now we can create the `EthStream` because the peer has successfully completed
-                // the handshake
-                let stream = EthStream::new(version, self.inner);
-
-                Ok((stream, resp))
+                resp.into()
             }
             _ => {
                 self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                Err(EthStreamError::EthHandshakeError(
+                return Err(EthStreamError::EthHandshakeError(
                     EthHandshakeError::NonStatusMessageInHandshake,
                 ))
             }
+        };
+
+        trace!(
+            status=%resp,
+            "validating incoming eth status from peer"
+        );
+
+        // The following checks should match the checks in go-ethereum:
+        // https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/handshake.go#L87-L89
+        if status.genesis != resp.genesis {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedGenesis(
+                GotExpected { expected: status.genesis, got: resp.genesis }.into(),
+            )
+            .into())
         }
+
+        if status.version != resp.version {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
+                got: resp.version,
+                expected: status.version,
+            })
+            .into())
+        }
+
+        if status.chain != resp.chain {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedChain(GotExpected {
+                got: resp.chain,
+                expected: status.chain,
+            })
+            .into())
+        }
+
+        // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
+        // larger, it will still fit within 100 bits
+        if status.total_difficulty.bit_len() > 100 {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
+                got: status.total_difficulty.bit_len(),
+                maximum: 100,
+            }
+            .into())
+        }
+
+        if let Err(err) = fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
+        {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(err.into())
+        }
+
+        // now we can create the `EthStream` because the peer has successfully completed
+        // the handshake
+        let stream = EthStream::new(version, self.inner);
+
+        Ok((stream, resp))
     }
 }
 
@@ -281,7 +315,7 @@ where
             }
         };
 
-        if matches!(msg.message, EthMessage::Status(_)) {
+        if matches!(msg.message, EthMessage::Status(_) | EthMessage::Status69(_)) {
             return Poll::Ready(Some(Err(EthStreamError::EthHandshakeError(
                 EthHandshakeError::StatusNotInHandshake,
             ))))
@@ -303,7 +337,7 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: EthMessage) -> Result<(), Self::Error> {
-        if matches!(item, EthMessage::Status(_)) {
+        if matches!(item, EthMessage::Status(_) | EthMessage::Status69(_)) {
             // TODO: to disconnect here we would need to do something similar to P2PStream's
             // start_disconnect, which would ideally be a part of the CanDisconnect trait, or at
             // least similar.
@@ -349,7 +383,8 @@ mod tests {
         broadcast::BlockHashNumber,
         errors::{EthHandshakeError, EthStreamError},
         p2pstream::{ProtocolVersion, UnauthedP2PStream},
-        EthMessage, EthStream, EthVersion, HelloMessageWithProtocols, PassthroughCodec, Status,
+        BlockRangeUpdate, EthMessage, EthStream, EthVersion, HelloMessageWithProtocols,
+        PassthroughCodec, Status,
     };
     use futures::{SinkExt, StreamExt};
     use reth_discv4::DEFAULT_DISCOVERY_PORT;
@@ -408,6 +443,95 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_handshake_eth69() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = Status {
+            version: EthVersion::Eth69 as u8,
+            chain: NamedChain::Mainnet.into(),
+            total_difficulty: U256::from(100),
+            blockhash: B256::random(),
+            genesis,
+            // Pass the current fork id.
+            forkid: fork_filter.current(),
+        };
+        let range = BlockRangeUpdate { earliest: 0, latest: 10, latest_hash: status.blockhash };
+        let other_range = BlockRangeUpdate { earliest: 5, latest: 20, latest_hash: B256::random() };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let status_clone = status;
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let (mut stream, their_status) = UnauthedEthStream::new(stream)
+                .with_block_range(other_range)
+                .handshake(status_clone, fork_filter_clone)
+                .await
+                .unwrap();
+
+            // the total difficulty is not exchanged in eth/69
+            assert_eq!(their_status.total_difficulty, U256::ZERO);
+            assert_eq!(their_status.blockhash, range.latest_hash);
+            assert_eq!(stream.version(), EthVersion::Eth69);
+
+            let msg = stream.next().await.unwrap().unwrap();
+            assert_eq!(msg, EthMessage::BlockRangeUpdate(range));
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let (mut stream, their_status) = UnauthedEthStream::new(sink)
+            .with_block_range(range)
+            .handshake(status, fork_filter)
+            .await
+            .unwrap();
+        assert_eq!(their_status.total_difficulty, U256::ZERO);
+        assert_eq!(their_status.blockhash, other_range.latest_hash);
+        assert_eq!(their_status.genesis, genesis);
+
+        // block range updates can be sent after the handshake
+        stream.send(EthMessage::BlockRangeUpdate(range)).await.unwrap();
+
+        // wait for it to finish
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn eth69_handshake_requires_block_range() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = Status {
+            version: EthVersion::Eth69 as u8,
+            chain: NamedChain::Mainnet.into(),
+            total_difficulty: U256::ZERO,
+            blockhash: B256::random(),
+            genesis,
+            forkid: fork_filter.current(),
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let _handle = tokio::spawn(async move {
+            let (_incoming, _) = listener.accept().await.unwrap();
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let handshake_res = UnauthedEthStream::new(sink).handshake(status, fork_filter).await;
+        assert!(matches!(
+            handshake_res,
+            Err(EthStreamError::EthHandshakeError(EthHandshakeError::MissingBlockRange))
+        ));
+    }
+
     #[tokio::test]
     async fn pass_handshake_on_low_td_bitlen() {
         let genesis = B256::random();
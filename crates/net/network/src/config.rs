@@ -11,7 +11,7 @@ use crate::{
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOVERY_ADDRESS};
 use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
-use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
+use reth_eth_wire::{BlockRangeUpdate, HelloMessage, HelloMessageWithProtocols, Status};
 use reth_network_peers::{pk2id, PeerId};
 use reth_primitives::{
     mainnet_nodes, sepolia_nodes, ChainSpec, ForkFilter, Head, TrustedPeer, MAINNET,
@@ -73,6 +73,8 @@ pub struct NetworkConfig<C> {
     pub executor: Box<dyn TaskSpawner>,
     /// The `Status` message to send to peers at the beginning.
     pub status: Status,
+    /// The range of blocks announced to `eth/69` peers at the beginning.
+    pub block_range: BlockRangeUpdate,
     /// Sets the hello message for the p2p handshake in `RLPx`
     pub hello_message: HelloMessageWithProtocols,
     /// Additional protocols to announce and handle in `RLPx`
@@ -170,6 +172,8 @@ pub struct NetworkConfigBuilder {
     extra_protocols: RlpxSubProtocols,
     /// Head used to start set for the fork filter and status.
     head: Option<Head>,
+    /// The earliest block that is available, announced to `eth/69` peers.
+    earliest_block: u64,
     /// Whether tx gossip is disabled
     tx_gossip_disabled: bool,
     /// The block importer type
@@ -200,6 +204,7 @@ impl NetworkConfigBuilder {
             hello_message: None,
             extra_protocols: Default::default(),
             head: None,
+            earliest_block: 0,
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
@@ -241,6 +246,17 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the earliest block for which the node still has the full block data.
+    ///
+    /// This is announced to `eth/69` peers as the start of the served block range and should be
+    /// set if the node pruned historical blocks or receipts.
+    ///
+    /// If not set, this defaults to the genesis block.
+    pub const fn earliest_block(mut self, earliest_block: u64) -> Self {
+        self.earliest_block = earliest_block;
+        self
+    }
+
     /// Sets the `HelloMessage` to send when connecting to peers.
     ///
     /// ```
@@ -495,6 +511,7 @@ impl NetworkConfigBuilder {
             hello_message,
             extra_protocols,
             head,
+            earliest_block,
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
@@ -517,6 +534,13 @@ impl NetworkConfigBuilder {
         // set the status
         let status = Status::spec_builder(&chain_spec, &head).build();
 
+        // set the block range, the earliest block can't be ahead of the head
+        let block_range = BlockRangeUpdate {
+            earliest: earliest_block.min(head.number),
+            latest: head.number,
+            latest_hash: head.hash,
+        };
+
         // set a fork filter based on the chain spec and head
         let fork_filter = chain_spec.fork_filter(head);
 
@@ -547,6 +571,7 @@ impl NetworkConfigBuilder {
             network_mode,
             executor: executor.unwrap_or_else(|| Box::<TokioTaskExecutor>::default()),
             status,
+            block_range,
             hello_message,
             extra_protocols,
             fork_filter,
@@ -585,7 +610,7 @@ mod tests {
     use super::*;
     use rand::thread_rng;
     use reth_dns_discovery::tree::LinkEntry;
-    use reth_primitives::{Chain, ForkHash};
+    use reth_primitives::{Chain, ForkHash, B256};
     use reth_provider::test_utils::NoopProvider;
     use std::collections::BTreeMap;
 
@@ -632,4 +657,19 @@ mod tests {
         assert_eq!(status.forkid.hash, genesis_fork_hash);
         assert_eq!(fork_filter.current().hash, genesis_fork_hash);
     }
+
+    #[test]
+    fn test_network_block_range() {
+        let head = Head { number: 100, hash: B256::random(), ..Default::default() };
+
+        let config = builder().set_head(head).earliest_block(50).build(NoopProvider::default());
+        assert_eq!(
+            config.block_range,
+            BlockRangeUpdate { earliest: 50, latest: 100, latest_hash: head.hash }
+        );
+
+        // the earliest block can't be ahead of the head
+        let config = builder().set_head(head).earliest_block(150).build(NoopProvider::default());
+        assert_eq!(config.block_range.earliest, 100);
+    }
 }
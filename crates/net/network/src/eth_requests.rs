@@ -8,11 +8,11 @@ use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{
     BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetReceipts, NodeData,
-    Receipts,
+    Receipts, Receipts69,
 };
 use reth_network_p2p::error::RequestResult;
 use reth_network_peers::PeerId;
-use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection};
+use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection, Receipt};
 use reth_provider::{BlockReader, HeaderProvider, ReceiptProvider};
 use std::{
     future::Future,
@@ -196,6 +196,31 @@ where
     ) {
         self.metrics.eth_receipts_requests_received_total.increment(1);
 
+        let receipts = self.get_receipts_response(request, Receipt::with_bloom);
+
+        let _ = response.send(Ok(Receipts(receipts)));
+    }
+
+    /// Handles a receipts request of an `eth/69` peer, which doesn't include the logs bloom.
+    fn on_receipts69_request(
+        &self,
+        _peer_id: PeerId,
+        request: GetReceipts,
+        response: oneshot::Sender<RequestResult<Receipts69>>,
+    ) {
+        self.metrics.eth_receipts_requests_received_total.increment(1);
+
+        let receipts = self.get_receipts_response(request, |receipt| receipt);
+
+        let _ = response.send(Ok(Receipts69(receipts)));
+    }
+
+    /// Returns the receipts of the requested blocks, transformed into their wire format.
+    fn get_receipts_response<T: Encodable>(
+        &self,
+        request: GetReceipts,
+        transform: impl Fn(Receipt) -> T,
+    ) -> Vec<Vec<T>> {
         let mut receipts = Vec::new();
 
         let mut total_bytes = 0;
@@ -204,10 +229,7 @@ where
             if let Some(receipts_by_block) =
                 self.client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
             {
-                let receipt = receipts_by_block
-                    .into_iter()
-                    .map(|receipt| receipt.with_bloom())
-                    .collect::<Vec<_>>();
+                let receipt = receipts_by_block.into_iter().map(&transform).collect::<Vec<_>>();
 
                 total_bytes += receipt.length();
                 receipts.push(receipt);
@@ -224,7 +246,7 @@ where
             }
         }
 
-        let _ = response.send(Ok(Receipts(receipts)));
+        receipts
     }
 }
 
@@ -261,6 +283,9 @@ where
                     IncomingEthRequest::GetReceipts { peer_id, request, response } => {
                         this.on_receipts_request(peer_id, request, response)
                     }
+                    IncomingEthRequest::GetReceipts69 { peer_id, request, response } => {
+                        this.on_receipts69_request(peer_id, request, response)
+                    }
                 }
             },
         );
@@ -325,4 +350,15 @@ pub enum IncomingEthRequest {
         /// The channel sender for the response containing receipts.
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
+    /// Request Receipts without logs bloom from an `eth/69` peer.
+    ///
+    /// The response should be sent through the channel.
+    GetReceipts69 {
+        /// The ID of the peer to request receipts from.
+        peer_id: PeerId,
+        /// The specific receipts requested.
+        request: GetReceipts,
+        /// The channel sender for the response containing receipts.
+        response: oneshot::Sender<RequestResult<Receipts69>>,
+    },
 }
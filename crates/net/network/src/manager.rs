@@ -26,7 +26,7 @@ use crate::{
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
     metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     network::{NetworkHandle, NetworkHandleMessage},
-    peers::{PeersHandle, PeersManager},
+    peers::{PeersHandle, PeersManager, PersistedPeers},
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
     session::SessionManager,
@@ -184,6 +184,7 @@ where
             executor,
             hello_message,
             status,
+            block_range,
             fork_filter,
             dns_discovery_config,
             extra_protocols,
@@ -232,6 +233,7 @@ where
             sessions_config,
             executor,
             status,
+            block_range,
             hello_message,
             fork_filter,
             extra_protocols,
@@ -334,6 +336,11 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns the state of the peer set that should be persisted across restarts.
+    pub fn persisted_peers(&self) -> PersistedPeers {
+        self.swarm.state().peers().persisted_peers()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -433,6 +440,13 @@ where
                     response,
                 })
             }
+            PeerRequest::GetReceipts69 { request, response } => {
+                self.delegate_eth_request(IncomingEthRequest::GetReceipts69 {
+                    peer_id,
+                    request,
+                    response,
+                })
+            }
             PeerRequest::GetPooledTransactions { request, response } => {
                 self.notify_tx_manager(NetworkTransactionEvent::GetPooledTransactions {
                     peer_id,
@@ -501,6 +515,10 @@ where
                     this.block_import.on_new_block(peer_id, block);
                 });
             }
+            PeerMessage::BlockRangeUpdate(range) => {
+                // update the peer's best block, the range was validated by the session
+                self.swarm.state_mut().update_peer_block(&peer_id, range.latest_hash, range.latest);
+            }
             PeerMessage::PooledTransactions(msg) => {
                 self.notify_tx_manager(NetworkTransactionEvent::IncomingPooledTransactionHashes {
                     peer_id,
@@ -601,6 +619,9 @@ where
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
             }
+            NetworkHandleMessage::EarliestBlockUpdate(block) => {
+                self.swarm.sessions_mut().on_earliest_block_update(block);
+            }
             NetworkHandleMessage::GetPeerInfos(tx) => {
                 let _ = tx.send(self.swarm.sessions_mut().get_peer_info());
             }
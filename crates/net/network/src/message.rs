@@ -5,15 +5,15 @@
 
 use futures::FutureExt;
 use reth_eth_wire::{
-    capability::RawCapabilityMessage, message::RequestPair, BlockBodies, BlockHeaders, EthMessage,
-    GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts, NewBlock,
-    NewBlockHashes, NewPooledTransactionHashes, NodeData, PooledTransactions, Receipts,
-    SharedTransactions, Transactions,
+    capability::RawCapabilityMessage, message::RequestPair, BlockBodies, BlockHeaders,
+    BlockRangeUpdate, EthMessage, GetBlockBodies, GetBlockHeaders, GetNodeData,
+    GetPooledTransactions, GetReceipts, NewBlock, NewBlockHashes, NewPooledTransactionHashes,
+    NodeData, PooledTransactions, Receipts, Receipts69, SharedTransactions, Transactions,
 };
 use reth_network_p2p::error::{RequestError, RequestResult};
 use reth_network_peers::PeerId;
 use reth_primitives::{
-    BlockBody, Bytes, Header, PooledTransactionsElement, ReceiptWithBloom, B256,
+    BlockBody, Bytes, Header, PooledTransactionsElement, Receipt, ReceiptWithBloom, B256,
 };
 use std::{
     fmt,
@@ -54,6 +54,8 @@ pub enum PeerMessage {
     SendTransactions(SharedTransactions),
     /// Send new pooled transactions
     PooledTransactions(NewPooledTransactionHashes),
+    /// Announce the range of available blocks, only exchanged with `eth/69` peers.
+    BlockRangeUpdate(BlockRangeUpdate),
     /// All `eth` request variants.
     EthRequest(PeerRequest),
     /// Other than eth namespace message
@@ -122,6 +124,15 @@ pub enum PeerRequest {
         /// The channel to send the response for receipts.
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
+    /// Requests receipts without logs bloom from the peer, as used by `eth/69`.
+    ///
+    /// The response should be sent through the channel.
+    GetReceipts69 {
+        /// The request for receipts.
+        request: GetReceipts,
+        /// The channel to send the response for receipts.
+        response: oneshot::Sender<RequestResult<Receipts69>>,
+    },
 }
 
 // === impl PeerRequest ===
@@ -140,6 +151,7 @@ impl PeerRequest {
             Self::GetPooledTransactions { response, .. } => response.send(Err(err)).ok(),
             Self::GetNodeData { response, .. } => response.send(Err(err)).ok(),
             Self::GetReceipts { response, .. } => response.send(Err(err)).ok(),
+            Self::GetReceipts69 { response, .. } => response.send(Err(err)).ok(),
         };
     }
 
@@ -161,7 +173,7 @@ impl PeerRequest {
             Self::GetNodeData { request, .. } => {
                 EthMessage::GetNodeData(RequestPair { request_id, message: request.clone() })
             }
-            Self::GetReceipts { request, .. } => {
+            Self::GetReceipts { request, .. } | Self::GetReceipts69 { request, .. } => {
                 EthMessage::GetReceipts(RequestPair { request_id, message: request.clone() })
             }
         }
@@ -204,6 +216,11 @@ pub enum PeerResponse {
         /// The receiver channel for the response to a receipts request.
         response: oneshot::Receiver<RequestResult<Receipts>>,
     },
+    /// Represents a response to a request for receipts without logs bloom.
+    Receipts69 {
+        /// The receiver channel for the response to a receipts request.
+        response: oneshot::Receiver<RequestResult<Receipts69>>,
+    },
 }
 
 // === impl PeerResponse ===
@@ -236,6 +253,9 @@ impl PeerResponse {
             Self::Receipts { response } => {
                 poll_request!(response, Receipts, cx)
             }
+            Self::Receipts69 { response } => {
+                poll_request!(response, Receipts69, cx)
+            }
         };
         Poll::Ready(res)
     }
@@ -254,6 +274,8 @@ pub enum PeerResponseResult {
     NodeData(RequestResult<Vec<Bytes>>),
     /// Represents a result containing receipts or an error.
     Receipts(RequestResult<Vec<Vec<ReceiptWithBloom>>>),
+    /// Represents a result containing receipts without logs bloom or an error.
+    Receipts69(RequestResult<Vec<Vec<Receipt>>>),
 }
 
 // === impl PeerResponseResult ===
@@ -288,6 +310,9 @@ impl PeerResponseResult {
             Self::Receipts(resp) => {
                 to_message!(resp, Receipts, id)
             }
+            Self::Receipts69(resp) => {
+                to_message!(resp, Receipts69, id)
+            }
         }
     }
 
@@ -299,6 +324,7 @@ impl PeerResponseResult {
             Self::PooledTransactions(res) => res.as_ref().err(),
             Self::NodeData(res) => res.as_ref().err(),
             Self::Receipts(res) => res.as_ref().err(),
+            Self::Receipts69(res) => res.as_ref().err(),
         }
     }
 
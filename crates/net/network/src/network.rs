@@ -115,6 +115,14 @@ impl NetworkHandle {
         self.send_message(NetworkHandleMessage::StatusUpdate { head });
     }
 
+    /// Updates the earliest block for which the node still has the full block data, for example
+    /// after pruning.
+    ///
+    /// The new block range is announced to all `eth/69` peers.
+    pub fn update_earliest_block(&self, block: u64) {
+        self.send_message(NetworkHandleMessage::EarliestBlockUpdate(block));
+    }
+
     /// Announce a block over devp2p
     ///
     /// Caution: in `PoS` this is a noop because new blocks are no longer announced over devp2p.
@@ -458,6 +466,8 @@ pub(crate) enum NetworkHandleMessage {
         /// The head status to apply.
         head: Head,
     },
+    /// Updates the earliest block announced to `eth/69` peers.
+    EarliestBlockUpdate(u64),
     /// Retrieves the current status via a oneshot sender.
     GetStatus(oneshot::Sender<NetworkStatus>),
     /// Gets `PeerInfo` for the specified peer IDs.
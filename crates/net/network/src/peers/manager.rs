@@ -4,8 +4,9 @@ use crate::{
         reputation::{
             is_banned_reputation, DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE,
         },
-        ReputationChangeWeights, DEFAULT_MAX_COUNT_CONCURRENT_OUTBOUND_DIALS,
-        DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND,
+        ReputationChangeCounts, ReputationChangeWeights,
+        DEFAULT_MAX_COUNT_CONCURRENT_OUTBOUND_DIALS, DEFAULT_MAX_COUNT_PEERS_INBOUND,
+        DEFAULT_MAX_COUNT_PEERS_OUTBOUND,
     },
     session::{Direction, PendingSessionHandshakeError},
     swarm::NetworkConnectionState,
@@ -23,7 +24,7 @@ use std::{
     net::{IpAddr, SocketAddr},
     path::Path,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::{
@@ -135,12 +136,13 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            mut ban_list,
             ban_duration,
             backoff_durations,
             trusted_nodes,
             trusted_nodes_only,
             basic_nodes,
+            persisted_peers,
             max_backoff_count,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
@@ -161,6 +163,31 @@ impl PeersManager {
             peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
         }
 
+        let PersistedPeers { peers: persisted_peers, banned_peers } = persisted_peers;
+        for PersistedPeer { record, reputation, last_seen, reputation_changes } in persisted_peers {
+            let NodeRecord { address, tcp_port, udp_port: _, id } = record;
+            let peer =
+                peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
+            peer.reputation = reputation;
+            peer.last_seen = last_seen.map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+            peer.reputation_changes = reputation_changes;
+        }
+
+        // restore the bans that haven't expired yet
+        for PersistedBan { peer_id, banned_until } in banned_peers {
+            let banned_until = UNIX_EPOCH + Duration::from_secs(banned_until);
+            if let Ok(remaining) = banned_until.duration_since(SystemTime::now()) {
+                ban_list.ban_peer_until(peer_id, std::time::Instant::now() + remaining);
+            }
+        }
+
+        // peers whose ban expired while the node was offline are unbanned
+        for (peer_id, peer) in &mut peers {
+            if peer.is_banned() && !ban_list.is_banned_peer(peer_id) {
+                peer.unban();
+            }
+        }
+
         Self {
             peers,
             trusted_peer_ids,
@@ -198,6 +225,36 @@ impl PeersManager {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
     }
 
+    /// Returns the state of the peer set that is persisted across restarts.
+    ///
+    /// See also [`PeersConfig::with_basic_nodes_from_file`].
+    pub(crate) fn persisted_peers(&self) -> PersistedPeers {
+        let peers = self
+            .peers
+            .iter()
+            .map(|(peer_id, peer)| PersistedPeer {
+                record: NodeRecord::new(peer.addr, *peer_id),
+                reputation: peer.reputation,
+                last_seen: peer.last_seen.and_then(unix_timestamp),
+                reputation_changes: peer.reputation_changes,
+            })
+            .collect();
+
+        let now = std::time::Instant::now();
+        let system_now = SystemTime::now();
+        let banned_peers = self
+            .ban_list
+            .banned_peers_until()
+            .filter_map(|(peer_id, until)| {
+                let banned_until =
+                    unix_timestamp(system_now + until.saturating_duration_since(now))?;
+                Some(PersistedBan { peer_id, banned_until })
+            })
+            .collect();
+
+        PersistedPeers { peers, banned_peers }
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -322,6 +379,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.last_seen = Some(SystemTime::now());
 
                 is_trusted = is_trusted || peer.is_trusted();
 
@@ -339,6 +397,7 @@ impl PeersManager {
                 // disconnect, because we only know the outgoing port
                 let mut peer = Peer::with_state(addr, PeerConnectionState::In);
                 peer.remove_after_disconnect = true;
+                peer.last_seen = Some(SystemTime::now());
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
 
@@ -423,6 +482,8 @@ impl PeersManager {
     /// trusted peer, it will also be less strict with the reputation slashing.
     pub(crate) fn apply_reputation_change(&mut self, peer_id: &PeerId, rep: ReputationChangeKind) {
         let outcome = if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.reputation_changes.record(rep);
+
             // First check if we should reset the reputation
             if rep.is_reset() {
                 peer.reset_reputation()
@@ -503,6 +564,7 @@ impl PeersManager {
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
+                    entry.get_mut().last_seen = Some(SystemTime::now());
                     return
                 }
             }
@@ -518,6 +580,7 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.last_seen = Some(SystemTime::now());
         }
     }
 
@@ -603,10 +666,15 @@ impl PeersManager {
                     backoff_until = Some(backoff_time);
                 } else {
                     // If the error was not a backoff error, we reduce the peer's reputation
+                    peer.reputation_changes.record(reputation_change);
                     let reputation_change = self.reputation_weights.change(reputation_change);
                     peer.reputation = peer.reputation.saturating_add(reputation_change.as_i32());
                 };
 
+                if peer.state.is_connected() {
+                    // the session was active until now
+                    peer.last_seen = Some(SystemTime::now());
+                }
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
 
@@ -1031,6 +1099,10 @@ pub struct Peer {
     backed_off: bool,
     /// Counts number of times the peer was backed off due to a severe [`BackoffKind`].
     severe_backoff_counter: u8,
+    /// When a session with the peer was last established or closed, if ever.
+    last_seen: Option<SystemTime>,
+    /// Counts the reputation changes that were applied to the peer.
+    reputation_changes: ReputationChangeCounts,
 }
 
 // === impl Peer ===
@@ -1049,6 +1121,16 @@ impl Peer {
         self.reputation
     }
 
+    /// Returns when a session with the peer was last established or closed, if ever.
+    pub const fn last_seen(&self) -> Option<SystemTime> {
+        self.last_seen
+    }
+
+    /// Returns the counts of the reputation changes that were applied to the peer.
+    pub const fn reputation_changes(&self) -> &ReputationChangeCounts {
+        &self.reputation_changes
+    }
+
     fn with_state(addr: SocketAddr, state: PeerConnectionState) -> Self {
         Self {
             addr,
@@ -1059,6 +1141,8 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            last_seen: None,
+            reputation_changes: Default::default(),
         }
     }
 
@@ -1289,6 +1373,9 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// Peers and bans restored from a previous run.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: PersistedPeers,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -1319,6 +1406,7 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            persisted_peers: Default::default(),
             max_backoff_count: 5,
         }
     }
@@ -1401,6 +1489,12 @@ impl PeersConfig {
         self
     }
 
+    /// Peers and bans restored from a previous run.
+    pub fn with_persisted_peers(mut self, persisted_peers: PersistedPeers) -> Self {
+        self.persisted_peers = persisted_peers;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -1427,7 +1521,9 @@ impl PeersConfig {
         self.connection_info.max_outbound + self.connection_info.max_inbound
     }
 
-    /// Read from file nodes available at launch. Ignored if None.
+    /// Read from file the peers available at launch, see [`PersistedPeers`]. Ignored if None.
+    ///
+    /// Files that only contain a list of node records are loaded as basic nodes.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -1439,8 +1535,10 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        Ok(match serde_json::from_reader(reader)? {
+            PeersFile::Peers(persisted_peers) => self.with_persisted_peers(persisted_peers),
+            PeersFile::Nodes(nodes) => self.with_basic_nodes(nodes),
+        })
     }
 
     /// Returns settings for testing
@@ -1454,6 +1552,58 @@ impl PeersConfig {
     }
 }
 
+/// The state of the peer set that is persisted across restarts, so that bans and the reputation
+/// of known peers survive a restart of the node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PersistedPeers {
+    /// The known peers.
+    pub peers: Vec<PersistedPeer>,
+    /// The peers that are banned temporarily.
+    pub banned_peers: Vec<PersistedBan>,
+}
+
+/// A known peer that is persisted across restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedPeer {
+    /// Where to reach the peer.
+    pub record: NodeRecord,
+    /// Reputation of the peer.
+    pub reputation: i32,
+    /// Unix timestamp in seconds of when a session with the peer was last established or closed.
+    pub last_seen: Option<u64>,
+    /// Counts the reputation changes that were applied to the peer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reputation_changes: ReputationChangeCounts,
+}
+
+/// A temporary ban of a peer that is persisted across restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedBan {
+    /// The banned peer.
+    pub peer_id: PeerId,
+    /// Unix timestamp in seconds of when the ban expires.
+    pub banned_until: u64,
+}
+
+/// The contents of a peers file, see [`PeersConfig::with_basic_nodes_from_file`].
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+enum PeersFile {
+    /// Only the node records of the peers.
+    Nodes(HashSet<NodeRecord>),
+    /// Peers with their reputation and bans.
+    Peers(PersistedPeers),
+}
+
+/// Returns the unix timestamp in seconds of the given time, if it's after the unix epoch.
+fn unix_timestamp(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|duration| duration.as_secs())
+}
+
 /// The durations to use when a backoff should be applied to a peer.
 ///
 /// See also [`BackoffKind`].
@@ -2839,4 +2989,45 @@ mod tests {
         // no more pending outbound connections
         assert_eq!(peer_manager.connection_info.num_pending_out, 0);
     }
+
+    #[tokio::test]
+    async fn test_persisted_peers() {
+        let good = PeerId::random();
+        let good_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let bad = PeerId::random();
+        let bad_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer(good, good_addr, None);
+        peers.add_peer(bad, bad_addr, None);
+        peers.apply_reputation_change(&good, ReputationChangeKind::Timeout);
+        peers.apply_reputation_change(&bad, ReputationChangeKind::BadProtocol);
+        assert!(peers.ban_list.is_banned_peer(&bad));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known-peers.json");
+        let mut persisted = peers.persisted_peers();
+        std::fs::write(&path, serde_json::to_string(&persisted).unwrap()).unwrap();
+
+        let config = PeersConfig::test().with_basic_nodes_from_file(Some(&path)).unwrap();
+        let restored = PeersManager::new(config);
+        let peer = restored.peers.get(&good).unwrap();
+        assert_eq!(peer.addr, good_addr);
+        assert_eq!(peer.reputation, peers.get_reputation(&good).unwrap());
+        assert_eq!(peer.reputation_changes.timeout, 1);
+        assert!(restored.peers.get(&bad).unwrap().is_banned());
+        assert!(restored.ban_list.is_banned_peer(&bad));
+
+        // peers whose ban expired are unbanned
+        persisted.banned_peers[0].banned_until = 0;
+        let restored = PeersManager::new(PeersConfig::test().with_persisted_peers(persisted));
+        assert_eq!(restored.get_reputation(&bad), Some(DEFAULT_REPUTATION));
+        assert!(!restored.ban_list.is_banned_peer(&bad));
+
+        // files that only contain node records are loaded as basic nodes
+        let nodes = HashSet::from([NodeRecord::new(good_addr, good)]);
+        std::fs::write(&path, serde_json::to_string(&nodes).unwrap()).unwrap();
+        let config = PeersConfig::test().with_basic_nodes_from_file(Some(&path)).unwrap();
+        assert_eq!(config.basic_nodes, nodes);
+        assert_eq!(config.persisted_peers, Default::default());
+    }
 }
@@ -4,8 +4,11 @@ mod manager;
 mod reputation;
 
 pub(crate) use manager::InboundConnectionError;
-pub use manager::{ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager};
-pub use reputation::ReputationChangeWeights;
+pub use manager::{
+    ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager, PersistedBan,
+    PersistedPeer, PersistedPeers,
+};
+pub use reputation::{ReputationChangeCounts, ReputationChangeWeights};
 pub use reth_network_api::PeerKind;
 
 /// Maximum number of available slots for outbound sessions.
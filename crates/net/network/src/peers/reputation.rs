@@ -138,3 +138,54 @@ impl From<Reputation> for ReputationChange {
         Self(value)
     }
 }
+
+/// Counts the [`ReputationChangeKind`]s that were applied to a peer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ReputationChangeCounts {
+    /// Number of [`ReputationChangeKind::BadMessage`]
+    pub bad_message: u32,
+    /// Number of [`ReputationChangeKind::BadBlock`]
+    pub bad_block: u32,
+    /// Number of [`ReputationChangeKind::BadTransactions`]
+    pub bad_transactions: u32,
+    /// Number of [`ReputationChangeKind::AlreadySeenTransaction`]
+    pub already_seen_transactions: u32,
+    /// Number of [`ReputationChangeKind::Timeout`]
+    pub timeout: u32,
+    /// Number of [`ReputationChangeKind::BadProtocol`]
+    pub bad_protocol: u32,
+    /// Number of [`ReputationChangeKind::FailedToConnect`]
+    pub failed_to_connect: u32,
+    /// Number of [`ReputationChangeKind::Dropped`]
+    pub dropped: u32,
+    /// Number of [`ReputationChangeKind::BadAnnouncement`]
+    pub bad_announcement: u32,
+    /// Number of [`ReputationChangeKind::Reset`]
+    pub reset: u32,
+    /// Number of [`ReputationChangeKind::Other`]
+    pub other: u32,
+}
+
+// === impl ReputationChangeCounts ===
+
+impl ReputationChangeCounts {
+    /// Increments the count of the given [`ReputationChangeKind`].
+    pub fn record(&mut self, kind: ReputationChangeKind) {
+        let count = match kind {
+            ReputationChangeKind::BadMessage => &mut self.bad_message,
+            ReputationChangeKind::BadBlock => &mut self.bad_block,
+            ReputationChangeKind::BadTransactions => &mut self.bad_transactions,
+            ReputationChangeKind::AlreadySeenTransaction => &mut self.already_seen_transactions,
+            ReputationChangeKind::Timeout => &mut self.timeout,
+            ReputationChangeKind::BadProtocol => &mut self.bad_protocol,
+            ReputationChangeKind::FailedToConnect => &mut self.failed_to_connect,
+            ReputationChangeKind::Dropped => &mut self.dropped,
+            ReputationChangeKind::BadAnnouncement => &mut self.bad_announcement,
+            ReputationChangeKind::Reset => &mut self.reset,
+            ReputationChangeKind::Other(_) => &mut self.other,
+        };
+        *count = count.saturating_add(1);
+    }
+}
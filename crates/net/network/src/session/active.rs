@@ -16,7 +16,7 @@ use reth_eth_wire::{
     capability::Capabilities,
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    DisconnectP2P, DisconnectReason, EthMessage,
+    DisconnectP2P, DisconnectReason, EthMessage, EthVersion, Receipts,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_p2p::error::RequestError;
@@ -170,10 +170,26 @@ impl ActiveSession {
         }
 
         match msg {
-            message @ EthMessage::Status(_) => OnIncomingMessageOutcome::BadMessage {
-                error: EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake),
-                message,
-            },
+            message @ (EthMessage::Status(_) | EthMessage::Status69(_)) => {
+                OnIncomingMessageOutcome::BadMessage {
+                    error: EthStreamError::EthHandshakeError(
+                        EthHandshakeError::StatusNotInHandshake,
+                    ),
+                    message,
+                }
+            }
+            EthMessage::BlockRangeUpdate(msg) => {
+                if msg.earliest > msg.latest {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidBlockRange {
+                            earliest: msg.earliest,
+                            latest: msg.latest,
+                        },
+                        message: EthMessage::BlockRangeUpdate(msg),
+                    }
+                }
+                self.try_emit_broadcast(PeerMessage::BlockRangeUpdate(msg)).into()
+            }
             EthMessage::NewBlockHashes(msg) => {
                 self.try_emit_broadcast(PeerMessage::NewBlockHashes(msg)).into()
             }
@@ -226,11 +242,23 @@ impl ActiveSession {
                 on_response!(resp, GetNodeData)
             }
             EthMessage::GetReceipts(req) => {
-                on_request!(req, Receipts, GetReceipts)
+                if self.conn.version() >= EthVersion::Eth69 {
+                    on_request!(req, Receipts69, GetReceipts69)
+                } else {
+                    on_request!(req, Receipts, GetReceipts)
+                }
             }
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::Receipts69(resp) => {
+                // eth/69 receipts don't include the logs bloom, which is computed here
+                let resp = RequestPair {
+                    request_id: resp.request_id,
+                    message: Receipts::from(resp.message),
+                };
+                on_response!(resp, GetReceipts)
+            }
         }
     }
 
@@ -251,10 +279,20 @@ impl ActiveSession {
     fn on_internal_peer_message(&mut self, msg: PeerMessage) {
         match msg {
             PeerMessage::NewBlockHashes(msg) => {
-                self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                // block announcements were removed in eth/69
+                if self.conn.version() < EthVersion::Eth69 {
+                    self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                }
             }
             PeerMessage::NewBlock(msg) => {
-                self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                if self.conn.version() < EthVersion::Eth69 {
+                    self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                }
+            }
+            PeerMessage::BlockRangeUpdate(msg) => {
+                if self.conn.version() >= EthVersion::Eth69 {
+                    self.queued_outgoing.push_back(EthMessage::BlockRangeUpdate(msg).into());
+                }
             }
             PeerMessage::PooledTransactions(msg) => {
                 if msg.is_valid_for_version(self.conn.version()) {
@@ -765,11 +803,11 @@ mod tests {
     };
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
-        EthStream, GetBlockBodies, HelloMessageWithProtocols, P2PStream, Status, StatusBuilder,
-        UnauthedEthStream, UnauthedP2PStream,
+        BlockRangeUpdate, EthStream, GetBlockBodies, GetReceipts, HelloMessageWithProtocols,
+        P2PStream, Receipts69, Status, StatusBuilder, UnauthedEthStream, UnauthedP2PStream,
     };
     use reth_network_peers::pk2id;
-    use reth_primitives::{ForkFilter, Hardfork, MAINNET};
+    use reth_primitives::{ForkFilter, Hardfork, B256, MAINNET};
     use secp256k1::{SecretKey, SECP256K1};
     use tokio::{
         net::{TcpListener, TcpStream},
@@ -811,7 +849,7 @@ mod tests {
             F: FnOnce(EthStream<P2PStream<ECIESStream<TcpStream>>>) -> O + Send + 'static,
             O: Future<Output = ()> + Send + Sync,
         {
-            let status = self.status;
+            let mut status = self.status;
             let fork_filter = self.fork_filter.clone();
             let local_peer_id = self.local_peer_id;
             let mut hello = self.hello.clone();
@@ -823,7 +861,11 @@ mod tests {
 
                 let (p2p_stream, _) = UnauthedP2PStream::new(sink).handshake(hello).await.unwrap();
 
+                status.set_eth_version(p2p_stream.shared_capabilities().eth_version().unwrap());
+                let block_range =
+                    BlockRangeUpdate { earliest: 0, latest: 0, latest_hash: status.blockhash };
                 let (client_stream, _) = UnauthedEthStream::new(p2p_stream)
+                    .with_block_range(block_range)
                     .handshake(status, fork_filter)
                     .await
                     .unwrap();
@@ -846,6 +888,7 @@ mod tests {
                 self.secret_key,
                 self.hello.clone(),
                 self.status,
+                Default::default(),
                 self.fork_filter.clone(),
                 Default::default(),
             ));
@@ -1137,6 +1180,66 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_eth69_messages() {
+        reth_tracing::init_test_tracing();
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let range = BlockRangeUpdate { earliest: 0, latest: 100, latest_hash: B256::random() };
+        let receipts = Receipts69(vec![vec![Default::default()]]);
+
+        let expected_receipts = receipts.clone();
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            assert_eq!(client_stream.version(), EthVersion::Eth69);
+            client_stream.send(EthMessage::BlockRangeUpdate(range)).await.unwrap();
+            client_stream
+                .send(EthMessage::GetReceipts(RequestPair {
+                    request_id: 1,
+                    message: GetReceipts(vec![range.latest_hash]),
+                }))
+                .await
+                .unwrap();
+
+            // receipts are served without logs bloom
+            let msg = client_stream.next().await.unwrap().unwrap();
+            assert_eq!(
+                msg,
+                EthMessage::Receipts69(RequestPair { request_id: 1, message: expected_receipts })
+            );
+        });
+        let client = tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let session = builder.connect_incoming(incoming).await;
+        tokio::task::spawn(session);
+
+        match builder.active_session_rx.next().await.unwrap() {
+            ActiveSessionMessage::ValidMessage {
+                message: PeerMessage::BlockRangeUpdate(update),
+                ..
+            } => {
+                assert_eq!(update, range);
+            }
+            ev => unreachable!("{ev:?}"),
+        }
+
+        match builder.active_session_rx.next().await.unwrap() {
+            ActiveSessionMessage::ValidMessage {
+                message: PeerMessage::EthRequest(PeerRequest::GetReceipts69 { request, response }),
+                ..
+            } => {
+                assert_eq!(request, GetReceipts(vec![range.latest_hash]));
+                response.send(Ok(receipts)).unwrap();
+            }
+            ev => unreachable!("{ev:?}"),
+        }
+
+        client.await.unwrap();
+    }
+
     #[test]
     fn timeout_calculation_sanity_tests() {
         let rtt = Duration::from_secs(5);
@@ -0,0 +1,58 @@
+//! clap [Args](clap::Args) for engine configuration
+
+use clap::Args;
+use reth_provider::providers::DEFAULT_STATE_CACHE_SIZE;
+
+/// Parameters for configuring the engine
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Engine")]
+pub struct EngineArgs {
+    /// Number of worker threads that speculatively execute the transactions of new blocks to
+    /// prefetch their state while the blocks are executed.
+    ///
+    /// Prefetching is disabled if set to 0.
+    #[arg(long = "engine.prefetch-workers", value_name = "WORKERS", default_value_t = 0)]
+    pub prefetch_workers: usize,
+
+    /// Maximum number of accounts and of storage slots in the cache of the canonical state that
+    /// is shared across the executions of new blocks.
+    ///
+    /// The cache is disabled if set to 0.
+    #[arg(long = "engine.state-cache-size", value_name = "ENTRIES", default_value_t = DEFAULT_STATE_CACHE_SIZE)]
+    pub state_cache_size: u32,
+}
+
+impl Default for EngineArgs {
+    fn default() -> Self {
+        Self { prefetch_workers: 0, state_cache_size: DEFAULT_STATE_CACHE_SIZE }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_engine_args() {
+        let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, EngineArgs::default());
+
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.prefetch-workers", "4"])
+                .args;
+        assert_eq!(args, EngineArgs { prefetch_workers: 4, ..Default::default() });
+
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.state-cache-size", "0"])
+                .args;
+        assert_eq!(args, EngineArgs { state_cache_size: 0, ..Default::default() });
+    }
+}
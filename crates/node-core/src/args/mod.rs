@@ -29,7 +29,7 @@ pub use secret_key::{get_secret_key, SecretKeyError};
 
 /// `PayloadBuilderArgs` struct for configuring the payload builder
 mod payload_builder;
-pub use payload_builder::PayloadBuilderArgs;
+pub use payload_builder::{PayloadBuilderArgs, TransactionSelectorKind};
 
 /// Stage related arguments
 mod stage;
@@ -47,6 +47,10 @@ pub use txpool::TxPoolArgs;
 mod dev;
 pub use dev::DevArgs;
 
+/// EngineArgs for configuring the engine
+mod engine;
+pub use engine::EngineArgs;
+
 /// PruneArgs for configuring the pruning and full node
 mod pruning;
 pub use pruning::PruningArgs;
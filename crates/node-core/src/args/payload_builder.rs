@@ -4,8 +4,9 @@ use crate::{
 };
 use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
-    Arg, Args, Command,
+    Arg, Args, Command, ValueEnum,
 };
+use humantime::parse_duration;
 use reth_primitives::constants::{
     ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
 };
@@ -23,7 +24,9 @@ pub struct PayloadBuilderArgs {
     #[arg(long = "builder.gaslimit", default_value = "30000000", value_name = "GAS_LIMIT")]
     pub max_gas_limit: u64,
 
-    /// The interval at which the job should build a new payload after the last (in seconds).
+    /// The minimum interval between two builds of a payload (in seconds).
+    ///
+    /// New builds are triggered by new pending transactions.
     #[arg(long = "builder.interval", value_parser = parse_duration_from_secs, default_value = "1", value_name = "SECONDS")]
     pub interval: Duration,
 
@@ -34,6 +37,29 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// How long resolving a payload waits for an in-flight build that is expected to finish
+    /// before returning the best payload so far.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --builder.resolve-grace-period 200ms
+    #[arg(long = "builder.resolve-grace-period", value_parser = parse_duration, default_value = "0s", value_name = "DURATION", verbatim_doc_comment)]
+    pub resolve_grace_period: Duration,
+
+    /// The strategy that selects the transactions of built payloads.
+    #[arg(long = "builder.tx-selector", value_enum, default_value_t = TransactionSelectorKind::Bundle)]
+    pub transaction_selector: TransactionSelectorKind,
+}
+
+/// The strategy that selects the transactions of built payloads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TransactionSelectorKind {
+    /// Includes the pool transactions greedily in pool order and ignores bundles
+    Greedy,
+    /// Includes the profitable bundles at the top of the block, followed by the pool transactions
+    /// in pool order
+    #[default]
+    Bundle,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -44,6 +70,8 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            resolve_grace_period: Duration::ZERO,
+            transaction_selector: TransactionSelectorKind::Bundle,
         }
     }
 }
@@ -68,6 +96,14 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn resolve_grace_period(&self) -> Duration {
+        self.resolve_grace_period
+    }
+
+    fn transaction_selector(&self) -> TransactionSelectorKind {
+        self.transaction_selector
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -127,6 +163,34 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_resolve_grace_period() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.resolve-grace-period",
+            "200ms",
+        ])
+        .args;
+        assert_eq!(args.resolve_grace_period, Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_args_with_transaction_selector() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.tx-selector",
+            "greedy",
+        ])
+        .args;
+        assert_eq!(args.transaction_selector, TransactionSelectorKind::Greedy);
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.tx-selector",
+            "unknown"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_default_extradata() {
         let extradata = default_extradata();
@@ -50,4 +50,8 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The address transactions stage within the pipeline.
+    ///
+    /// Manages the optional index of transactions by the addresses they touch.
+    AddressTransactions,
 }
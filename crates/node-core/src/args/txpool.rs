@@ -2,13 +2,20 @@
 
 use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
+use humantime::parse_duration;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, validate::DEFAULT_MAX_TX_INPUT_BYTES,
+    blobstore::{
+        disk::DEFAULT_MAX_CACHED_BLOBS, BlobSidecarArchiveConfig, DEFAULT_BLOB_SIDECAR_RETENTION,
+    },
+    maintain::PoolSnapshotConfig,
+    validate::DEFAULT_MAX_TX_INPUT_BYTES,
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::{path::PathBuf, time::Duration};
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -63,6 +70,46 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+
+    /// Snapshot all subpools, including blob sidecars, periodically and on shutdown, and
+    /// revalidate and reinsert the transactions on startup.
+    ///
+    /// This replaces the backup of local transactions.
+    #[arg(long = "txpool.snapshot")]
+    pub snapshot: bool,
+
+    /// How often the pool snapshot is written while the node is running.
+    ///
+    /// Only used with `--txpool.snapshot`.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --txpool.snapshot-interval 10m
+    #[arg(
+        long = "txpool.snapshot-interval",
+        value_parser = parse_duration,
+        default_value = "5m",
+        verbatim_doc_comment
+    )]
+    pub snapshot_interval: Duration,
+
+    /// Keep the blob sidecars of canonical transactions in an archive after they are finalized,
+    /// and serve them with `reth_getBlobSidecars`.
+    #[arg(long = "blobpool.archive")]
+    pub blob_archive: bool,
+
+    /// How long archived blob sidecars are kept, relative to the timestamp of the chain tip.
+    ///
+    /// Only used with `--blobpool.archive`.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --blobpool.archive-retention 18days
+    #[arg(
+        long = "blobpool.archive-retention",
+        value_parser = parse_duration,
+        default_value = "18days 4h 54m 24s",
+        verbatim_doc_comment
+    )]
+    pub blob_archive_retention: Duration,
 }
 
 impl Default for TxPoolArgs {
@@ -82,10 +129,29 @@ impl Default for TxPoolArgs {
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            snapshot: false,
+            snapshot_interval: PoolSnapshotConfig::DEFAULT_INTERVAL,
+            blob_archive: false,
+            blob_archive_retention: DEFAULT_BLOB_SIDECAR_RETENTION,
         }
     }
 }
 
+impl TxPoolArgs {
+    /// Returns the configuration of the pool snapshot written to the given file, if it is enabled.
+    pub fn pool_snapshot_config(&self, snapshot_path: PathBuf) -> Option<PoolSnapshotConfig> {
+        self.snapshot
+            .then(|| PoolSnapshotConfig::new(snapshot_path).with_interval(self.snapshot_interval))
+    }
+
+    /// Returns the configuration of the blob sidecar archive, if it is enabled.
+    pub fn blob_archive_config(&self) -> Option<BlobSidecarArchiveConfig> {
+        self.blob_archive.then(|| {
+            BlobSidecarArchiveConfig::default().with_retention(self.blob_archive_retention)
+        })
+    }
+}
+
 impl RethTransactionPoolConfig for TxPoolArgs {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig {
@@ -138,4 +204,43 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_args_snapshot() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.snapshot",
+            "--txpool.snapshot-interval",
+            "1m",
+        ])
+        .args;
+        assert_eq!(
+            args.pool_snapshot_config("snapshot.rlp".into()),
+            Some(PoolSnapshotConfig {
+                snapshot_path: "snapshot.rlp".into(),
+                interval: Duration::from_secs(60)
+            })
+        );
+
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.pool_snapshot_config("snapshot.rlp".into()), None);
+    }
+
+    #[test]
+    fn txpool_args_blob_archive() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--blobpool.archive",
+            "--blobpool.archive-retention",
+            "2days",
+        ])
+        .args;
+        assert_eq!(
+            args.blob_archive_config(),
+            Some(BlobSidecarArchiveConfig { retention: Duration::from_secs(2 * 24 * 60 * 60) })
+        );
+
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.blob_archive_config(), None);
+    }
 }
@@ -1,5 +1,6 @@
 //! Config traits for various node components.
 
+use crate::args::TransactionSelectorKind;
 use reth_network::protocol::IntoRlpxSubProtocol;
 use reth_primitives::Bytes;
 use reth_transaction_pool::PoolConfig;
@@ -18,7 +19,7 @@ pub trait PayloadBuilderConfig {
         self.extradata().as_bytes().to_vec().into()
     }
 
-    /// The interval at which the job should build a new payload after the last.
+    /// The minimum interval between two builds of a payload.
     fn interval(&self) -> Duration;
 
     /// The deadline for when the payload builder job should resolve.
@@ -29,6 +30,12 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// How long resolving a payload waits for an in-flight build that is expected to finish.
+    fn resolve_grace_period(&self) -> Duration;
+
+    /// The strategy that selects the transactions of built payloads.
+    fn transaction_selector(&self) -> TransactionSelectorKind;
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
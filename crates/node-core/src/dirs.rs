@@ -323,6 +323,14 @@ impl<D> ChainPath<D> {
         self.data_dir().join("blobstore")
     }
 
+    /// Returns the path to the blob archive directory for this chain where blobs of canonical
+    /// transactions are kept after finalization.
+    ///
+    /// `<DIR>/<CHAIN_ID>/blob-archive`
+    pub fn blob_archive(&self) -> PathBuf {
+        self.data_dir().join("blob-archive")
+    }
+
     /// Returns the path to the local transactions backup file
     ///
     /// `<DIR>/<CHAIN_ID>/txpool-transactions-backup.rlp`
@@ -330,6 +338,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the snapshot of all subpools
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-snapshot.rlp`
+    pub fn txpool_snapshot(&self) -> PathBuf {
+        self.data_dir().join("txpool-snapshot.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -343,6 +358,13 @@ impl<D> ChainPath<D> {
     pub fn jwt(&self) -> PathBuf {
         self.data_dir().join("jwt.hex")
     }
+
+    /// Returns the path to the `ExEx` write-ahead log directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/exex/wal`
+    pub fn exex_wal(&self) -> PathBuf {
+        self.data_dir().join("exex").join("wal")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
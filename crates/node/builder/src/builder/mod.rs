@@ -33,7 +33,10 @@ use reth_node_core::{
     utils::write_peers_to_file,
 };
 use reth_primitives::{constants::eip4844::MAINNET_KZG_TRUSTED_SETUP, ChainSpec};
-use reth_provider::{providers::BlockchainProvider, ChainSpecProvider};
+use reth_provider::{
+    providers::BlockchainProvider, ChainSpecProvider, DatabaseProviderFactory,
+    PruneCheckpointReader,
+};
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolConfig, TransactionPool};
 use secp256k1::SecretKey;
@@ -529,12 +532,18 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     pub fn network_config_builder(&self) -> eyre::Result<NetworkConfigBuilder> {
         let secret_key = self.network_secret(&self.config().datadir())?;
         let default_peers_path = self.config().datadir().known_peers();
-        Ok(self.config().network.network_config(
-            self.reth_config(),
-            self.config().chain.clone(),
-            secret_key,
-            default_peers_path,
-        ))
+        // the range of blocks we can serve starts after the highest pruned block
+        let earliest_block = self.provider().database_provider_ro()?.earliest_available_block()?;
+        Ok(self
+            .config()
+            .network
+            .network_config(
+                self.reth_config(),
+                self.config().chain.clone(),
+                secret_key,
+                default_peers_path,
+            )
+            .earliest_block(earliest_block))
     }
 
     /// Get the network secret from the given data dir
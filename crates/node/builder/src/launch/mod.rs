@@ -10,7 +10,7 @@ use crate::{
 use futures::{future::Either, stream, stream_select, StreamExt};
 use reth_beacon_consensus::{
     hooks::{EngineHooks, PruneHook, StaticFileHook},
-    BeaconConsensusEngine,
+    BeaconConsensusEngine, BeaconConsensusEngineEvent,
 };
 use reth_blockchain_tree::{
     noop::NoopBlockchainTree, BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree,
@@ -30,12 +30,15 @@ use reth_node_core::{
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
 
 use reth_primitives::format_ether;
-use reth_provider::providers::BlockchainProvider;
+use reth_provider::{
+    providers::BlockchainProvider, CanonStateSubscriptions, PruneCheckpointReader,
+};
+use reth_prune::PrunerEvent;
 use reth_rpc_engine_api::EngineApi;
 use reth_rpc_types::engine::ClientVersionV1;
 use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::{debug, info};
-use reth_transaction_pool::TransactionPool;
+use reth_tracing::tracing::{debug, info, warn};
+use reth_transaction_pool::{blobstore::BlobSidecarArchive, TransactionPool};
 use std::{future::Future, sync::Arc};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -139,7 +142,9 @@ where
         let head = ctx.lookup_head()?;
 
         // Configure the blockchain tree for the node
-        let tree_config = BlockchainTreeConfig::default();
+        let tree_config = BlockchainTreeConfig::default()
+            .with_prefetch_workers(ctx.node_config().engine.prefetch_workers)
+            .with_state_cache_size(ctx.node_config().engine.state_cache_size);
 
         // NOTE: This is a temporary workaround to provide the canon state notification sender to the components builder because there's a cyclic dependency between the blockchain provider and the tree component. This will be removed once the Blockchain provider no longer depends on an instance of the tree: <https://github.com/paradigmxyz/reth/issues/7154>
         let (canon_state_notification_sender, _receiver) =
@@ -198,7 +203,7 @@ where
         let exex_manager_handle =
             ExExLauncher::new(head, node_adapter.clone(), installed_exex, ctx.configs().clone())
                 .launch()
-                .await;
+                .await?;
 
         // create pipeline
         let network_client = node_adapter.network().fetch_client().await?;
@@ -227,7 +232,7 @@ where
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
-        let (pipeline, client) = if ctx.is_dev() {
+        let (pipeline, client, mining_handle) = if ctx.is_dev() {
             info!(target: "reth::cli", "Starting Reth in dev mode");
 
             for (idx, (address, alloc)) in ctx.chain_spec().genesis.alloc.iter().enumerate() {
@@ -242,6 +247,7 @@ where
             let (_, client, mut task) = reth_auto_seal_consensus::AutoSealBuilder::new(
                 ctx.chain_spec(),
                 blockchain_db.clone(),
+                ctx.provider_factory().clone(),
                 node_adapter.components.pool().clone(),
                 consensus_engine_tx.clone(),
                 mining_mode,
@@ -267,10 +273,11 @@ where
 
             let pipeline_events = pipeline.events();
             task.set_pipeline_events(pipeline_events);
+            let mining_handle = task.handle();
             debug!(target: "reth::cli", "Spawning auto mine task");
             ctx.task_executor().spawn(Box::pin(task));
 
-            (pipeline, Either::Left(client))
+            (pipeline, Either::Left(client), Some(mining_handle))
         } else {
             let pipeline = crate::setup::build_networked_pipeline(
                 ctx.node_config(),
@@ -288,7 +295,7 @@ where
             )
             .await?;
 
-            (pipeline, Either::Right(network_client.clone()))
+            (pipeline, Either::Right(network_client.clone()), None)
         };
 
         let pipeline_events = pipeline.events();
@@ -305,7 +312,24 @@ where
         let pruner = pruner_builder.build(ctx.provider_factory().clone());
 
         let pruner_events = pruner.events();
+        let mut network_pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
+
+        // keep the block range announced to eth/69 peers in sync with the pruned blocks
+        let network = node_adapter.components.network().clone();
+        let provider_factory = ctx.provider_factory().clone();
+        ctx.task_executor().spawn(Box::pin(async move {
+            while let Some(event) = network_pruner_events.next().await {
+                if let PrunerEvent::Finished { .. } = event {
+                    match provider_factory.earliest_available_block() {
+                        Ok(block) => network.update_earliest_block(block),
+                        Err(err) => {
+                            warn!(target: "reth::cli", %err, "Failed to read earliest available block")
+                        }
+                    }
+                }
+            }
+        }));
         hooks.add(PruneHook::new(pruner, Box::new(ctx.task_executor().clone())));
 
         // Configure the consensus engine
@@ -351,6 +375,9 @@ where
             ),
         );
 
+        // subscribe to invalid blocks before the handle is moved into the engine API
+        let mut invalid_block_events = beacon_engine_handle.event_listener();
+
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
             name: NAME_CLIENT.to_string(),
@@ -370,6 +397,24 @@ where
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // keep the blob sidecars of canonical transactions after they leave the pool
+        let blob_archive = if let Some(config) = ctx.node_config().txpool.blob_archive_config() {
+            let archive = BlobSidecarArchive::open(ctx.data_dir().blob_archive(), config)?;
+            info!(target: "reth::cli", path = ?ctx.data_dir().blob_archive(), retention = ?config.retention, "Blob sidecar archive opened");
+            ctx.task_executor().spawn_critical(
+                "blob sidecar archive task",
+                reth_transaction_pool::maintain::archive_blob_sidecars_task(
+                    blockchain_db.clone(),
+                    node_adapter.components.pool().clone(),
+                    archive.clone(),
+                    blockchain_db.canonical_state_stream(),
+                ),
+            );
+            Some(archive)
+        } else {
+            None
+        };
+
         // Start RPC servers
         let (rpc_server_handles, mut rpc_registry) = crate::rpc::launch_rpc_servers(
             node_adapter.clone(),
@@ -377,6 +422,9 @@ where
             ctx.node_config(),
             jwt_secret,
             rpc,
+            mining_handle,
+            ctx.toml_config().rpc_rate_limit.clone(),
+            blob_archive,
         )
         .await?;
 
@@ -385,6 +433,16 @@ where
             rpc_registry.eth_api().with_dev_accounts();
         }
 
+        // keep track of blocks rejected by the engine for `debug_getBadBlocks`
+        let bad_blocks = rpc_registry.bad_blocks().clone();
+        ctx.task_executor().spawn(Box::pin(async move {
+            while let Some(event) = invalid_block_events.next().await {
+                if let BeaconConsensusEngineEvent::InvalidBlock(block, validation_error) = event {
+                    bad_blocks.insert(block, validation_error);
+                }
+            }
+        }));
+
         // Run consensus engine to completion
         let (tx, rx) = oneshot::channel();
         info!(target: "reth::cli", "Starting consensus engine");
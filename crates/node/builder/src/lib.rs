@@ -39,6 +39,9 @@ pub use reth_node_core::cli::config::{
     PayloadBuilderConfig, RethNetworkConfig, RethTransactionPoolConfig,
 };
 
+/// Re-export the transaction selection strategies of the [`PayloadBuilderConfig`].
+pub use reth_node_core::args::TransactionSelectorKind;
+
 // re-export the core config for convenience
 pub use reth_node_core::node_config::NodeConfig;
 
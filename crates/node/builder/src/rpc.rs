@@ -1,18 +1,24 @@
 //! Builder support for rpc components.
 
 use futures::TryFutureExt;
+use reth_auto_seal_consensus::MiningHandle;
 use reth_network::NetworkHandle;
 use reth_node_api::FullNodeComponents;
-use reth_node_core::{node_config::NodeConfig, rpc::api::EngineApiServer};
+use reth_node_core::{
+    node_config::NodeConfig,
+    rpc::api::{AnvilApiServer, EngineApiServer, GanacheApiServer, HardhatApiServer},
+};
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_rpc::AnvilApi;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
     RethModuleRegistry, RpcModuleBuilder, RpcServerHandle, TransportRpcModules,
 };
-use reth_rpc_layer::JwtSecret;
+use reth_rpc_layer::{JwtSecret, RateLimitConfig, RateLimiter};
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
+use reth_transaction_pool::blobstore::BlobSidecarArchive;
 use std::{
     fmt,
     ops::{Deref, DerefMut},
@@ -155,6 +161,7 @@ pub struct RpcRegistry<Node: FullNodeComponents> {
         TaskExecutor,
         Node::Provider,
         Node::Evm,
+        Node::Executor,
     >,
 }
 
@@ -166,6 +173,7 @@ impl<Node: FullNodeComponents> Deref for RpcRegistry<Node> {
         TaskExecutor,
         Node::Provider,
         Node::Evm,
+        Node::Executor,
     >;
 
     fn deref(&self) -> &Self::Target {
@@ -253,6 +261,9 @@ pub(crate) async fn launch_rpc_servers<Node, Engine>(
     config: &NodeConfig,
     jwt_secret: JwtSecret,
     hooks: RpcHooks<Node>,
+    mining: Option<MiningHandle>,
+    rate_limit: Option<RateLimitConfig>,
+    blob_archive: Option<BlobSidecarArchive>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node>)>
 where
     Node: FullNodeComponents + Clone,
@@ -264,16 +275,31 @@ where
     let module_config = config.rpc.transport_rpc_module_config();
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
-    let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
+    let mut module_builder = RpcModuleBuilder::default()
         .with_provider(node.provider().clone())
         .with_pool(node.pool().clone())
         .with_network(node.network().clone())
         .with_events(node.provider().clone())
         .with_executor(node.task_executor().clone())
         .with_evm_config(node.evm_config().clone())
-        .build_with_auth_server(module_config, engine_api);
+        .with_block_executor(node.block_executor().clone());
+    if let Some(blob_archive) = blob_archive {
+        module_builder = module_builder.with_blob_archive(blob_archive);
+    }
+    let (mut modules, mut auth_module, registry) =
+        module_builder.build_with_auth_server(module_config, engine_api);
 
     let mut registry = RpcRegistry { registry };
+
+    // on dev chains, install the `anvil`, `hardhat` and `evm` namespaces to control the chain
+    if let Some(mining) = mining {
+        let anvil = AnvilApi::new(node.provider().clone(), node.pool().clone(), mining);
+        registry.eth_api().with_impersonated_accounts(anvil.impersonated_accounts().clone());
+        modules.merge_configured(AnvilApiServer::into_rpc(anvil.clone()))?;
+        modules.merge_configured(HardhatApiServer::into_rpc(anvil.clone()))?;
+        modules.merge_configured(GanacheApiServer::into_rpc(anvil))?;
+    }
+
     let ctx = RpcContext {
         node: node.clone(),
         config,
@@ -284,7 +310,8 @@ where
 
     extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-    let server_config = config.rpc.rpc_server_config();
+    let rate_limiter = rate_limit.map(RateLimiter::new).transpose()?;
+    let server_config = config.rpc.rpc_server_config().with_rate_limiter(rate_limiter);
     let launch_rpc = modules.clone().start_server(server_config).map_ok(|handle| {
         if let Some(path) = handle.ipc_endpoint() {
             info!(target: "reth::cli", %path, "RPC IPC server started");
@@ -298,6 +298,9 @@ impl<DB> NodeState<DB> {
             BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
                 info!(number=block.number, hash=?block.hash(), "Block added to fork chain");
             }
+            BeaconConsensusEngineEvent::InvalidBlock(_, _) => {
+                // invalid blocks are already reported by the engine
+            }
         }
     }
 
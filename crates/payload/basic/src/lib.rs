@@ -18,7 +18,8 @@ use reth_payload_builder::{
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_primitives::{
     constants::{EMPTY_WITHDRAWALS, RETH_CLIENT_VERSION, SLOT_DURATION},
-    proofs, BlockNumberOrTag, Bytes, ChainSpec, Request, SealedBlock, Withdrawals, B256, U256,
+    proofs, BlockNumberOrTag, Bytes, ChainSpec, Request, SealedBlock, TxHash, Withdrawals, B256,
+    U256,
 };
 use reth_provider::{
     BlockReaderIdExt, BlockSource, CanonStateNotification, ProviderError, StateProviderFactory,
@@ -28,7 +29,7 @@ use reth_revm::state_change::{
     post_block_withdrawals_balance_increments,
 };
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{TransactionListenerKind, TransactionPool};
 use revm::{
     primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg},
     Database, DatabaseCommit, Evm, State,
@@ -43,8 +44,8 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{oneshot, Semaphore},
-    time::{Interval, Sleep},
+    sync::{mpsc::Receiver, oneshot, Semaphore},
+    time::{Instant, Interval, MissedTickBehavior, Sleep},
 };
 use tracing::{debug, trace, warn};
 
@@ -180,15 +181,28 @@ where
 
         let cached_reads = self.maybe_pre_cached(config.parent_block.hash());
 
+        // the first build is started right away, so the next one can start after the interval
+        let mut interval =
+            tokio::time::interval_at(Instant::now() + self.config.interval, self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
         let mut job = BasicPayloadJob {
             config,
             client: self.client.clone(),
             pool: self.pool.clone(),
             executor: self.executor.clone(),
             deadline,
-            interval: tokio::time::interval(self.config.interval),
+            interval,
+            pending_transactions: self
+                .pool
+                .pending_transactions_listener_for(TransactionListenerKind::All),
+            new_bundles: self.pool.bundle_pool().new_bundles_listener(),
+            new_transactions: false,
+            resolve_grace_period: self.config.resolve_grace_period,
             best_payload: None,
             pending_block: None,
+            build_started: Instant::now(),
+            build_duration: None,
             cached_reads,
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
@@ -258,7 +272,9 @@ impl PayloadTaskGuard {
 pub struct BasicPayloadJobGeneratorConfig {
     /// Data to include in the block's extra data field.
     extradata: Bytes,
-    /// The interval at which the job should build a new payload after the last.
+    /// The minimum interval between two builds of a job.
+    ///
+    /// New builds are triggered by new pending transactions.
     interval: Duration,
     /// The deadline for when the payload builder job should resolve.
     ///
@@ -266,12 +282,15 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// How long resolving a payload waits for an in-flight build that is expected to finish
+    /// before returning the best payload so far.
+    resolve_grace_period: Duration,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
 
 impl BasicPayloadJobGeneratorConfig {
-    /// Sets the interval at which the job should build a new payload after the last.
+    /// Sets the minimum interval between two builds of a job.
     pub const fn interval(mut self, interval: Duration) -> Self {
         self.interval = interval;
         self
@@ -301,6 +320,15 @@ impl BasicPayloadJobGeneratorConfig {
         self.extradata = extradata;
         self
     }
+
+    /// Sets how long resolving a payload waits for an in-flight build that is expected to finish
+    /// before returning the best payload so far.
+    ///
+    /// Defaults to zero, which resolves the best payload right away.
+    pub const fn resolve_grace_period(mut self, resolve_grace_period: Duration) -> Self {
+        self.resolve_grace_period = resolve_grace_period;
+        self
+    }
 }
 
 impl Default for BasicPayloadJobGeneratorConfig {
@@ -311,11 +339,16 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            resolve_grace_period: Duration::ZERO,
         }
     }
 }
 
 /// A basic payload job that continuously builds a payload with the best transactions from the pool.
+///
+/// A new build is started whenever new transactions were added to the pending pool or new bundles
+/// targeting the payload's block were added to the bundle pool, but at most once per interval and
+/// only if no build is in progress.
 #[derive(Debug)]
 pub struct BasicPayloadJob<Client, Pool, Tasks, Builder>
 where
@@ -331,12 +364,24 @@ where
     executor: Tasks,
     /// The deadline when this job should resolve.
     deadline: Pin<Box<Sleep>>,
-    /// The interval at which the job should build a new payload after the last.
+    /// The minimum interval between two builds.
     interval: Interval,
+    /// Receiver for the hashes of new pending transactions of the pool.
+    pending_transactions: Receiver<TxHash>,
+    /// Receiver for the hashes of new bundles of the bundle pool.
+    new_bundles: Receiver<B256>,
+    /// Whether new pending transactions or bundles were added since the last build started.
+    new_transactions: bool,
+    /// How long resolving waits for an in-flight build that is expected to finish.
+    resolve_grace_period: Duration,
     /// The best payload so far.
     best_payload: Option<Builder::BuiltPayload>,
     /// Receiver for the block that is currently being built.
     pending_block: Option<PendingPayload<Builder::BuiltPayload>>,
+    /// When the last build started.
+    build_started: Instant,
+    /// How long the last finished build took.
+    build_duration: Option<Duration>,
     /// Restricts how many generator tasks can be executed at once.
     payload_task_guard: PayloadTaskGuard,
     /// Caches all disk reads for the state the new payloads builds on
@@ -373,6 +418,8 @@ where
         let payload_config = self.config.clone();
         let best_payload = self.best_payload.clone();
         self.metrics.inc_initiated_payload_builds();
+        self.new_transactions = false;
+        self.build_started = Instant::now();
         let cached_reads = self.cached_reads.take().unwrap_or_default();
         let builder = self.builder.clone();
         self.executor.spawn_blocking(Box::pin(async move {
@@ -414,11 +461,26 @@ where
             return Poll::Ready(Ok(()))
         }
 
-        // check if the interval is reached
-        while this.interval.poll_tick(cx).is_ready() {
-            // start a new job if there is no pending block and we haven't reached the deadline
-            if this.pending_block.is_none() {
-                this.spawn_build_job();
+        // check if new transactions were added to the pending pool
+        while let Poll::Ready(Some(_)) = this.pending_transactions.poll_recv(cx) {
+            this.new_transactions = true;
+        }
+
+        // check if new bundles that can be included in this payload were added
+        let mut new_bundles = Vec::new();
+        while let Poll::Ready(Some(hash)) = this.new_bundles.poll_recv(cx) {
+            new_bundles.push(hash);
+        }
+        if !new_bundles.is_empty() {
+            let bundle_pool = this.pool.bundle_pool();
+            let block_number = this.config.parent_block.number + 1;
+            let timestamp = this.config.attributes.timestamp();
+            if new_bundles.iter().any(|hash| {
+                bundle_pool
+                    .get(hash)
+                    .is_some_and(|bundle| bundle.is_valid_at(block_number, timestamp))
+            }) {
+                this.new_transactions = true;
             }
         }
 
@@ -426,7 +488,7 @@ where
         if let Some(mut fut) = this.pending_block.take() {
             match fut.poll_unpin(cx) {
                 Poll::Ready(Ok(outcome)) => {
-                    this.interval.reset();
+                    this.build_duration = Some(this.build_started.elapsed());
                     match outcome {
                         BuildOutcome::Better { payload, cached_reads } => {
                             this.cached_reads = Some(cached_reads);
@@ -446,6 +508,7 @@ where
                     // job failed, but we simply try again next interval
                     debug!(target: "payload_builder", %error, "payload build attempt failed");
                     this.metrics.inc_failed_payload_builds();
+                    this.new_transactions = true;
                 }
                 Poll::Pending => {
                     this.pending_block = Some(fut);
@@ -453,6 +516,15 @@ where
             }
         }
 
+        // start a new build if there are new transactions, no build is in progress and the
+        // interval since the last build passed
+        if this.new_transactions &&
+            this.pending_block.is_none() &&
+            this.interval.poll_tick(cx).is_ready()
+        {
+            this.spawn_build_job();
+        }
+
         Poll::Pending
     }
 }
@@ -496,6 +568,17 @@ where
             self.spawn_build_job();
         }
 
+        // wait for the in-flight build if it is expected to finish within the grace period, the
+        // duration of the last build is the best guess for the duration of this one
+        let mut grace_period = None;
+        if best_payload.is_some() && self.pending_block.is_some() {
+            let expected_end = self.build_duration.map(|duration| self.build_started + duration);
+            let until = Instant::now() + self.resolve_grace_period;
+            if expected_end.map_or(true, |expected_end| expected_end <= until) {
+                grace_period = Some(Box::pin(tokio::time::sleep_until(until)));
+            }
+        }
+
         let maybe_better = self.pending_block.take();
         let mut empty_payload = None;
 
@@ -544,7 +627,7 @@ where
             };
         }
 
-        let fut = ResolveBestPayload { best_payload, maybe_better, empty_payload };
+        let fut = ResolveBestPayload { best_payload, maybe_better, empty_payload, grace_period };
 
         (fut, KeepPayloadJobAlive::No)
     }
@@ -555,7 +638,8 @@ where
 /// This returns the payload that's supposed to be sent to the CL.
 ///
 /// If payload has been built so far, it will return that, but it will check if there's a better
-/// payload available from an in progress build job. If so it will return that.
+/// payload available from an in progress build job. If so it will return that. If the in progress
+/// build job is expected to finish soon, it waits for it until the grace period elapsed.
 ///
 /// If no payload has been built so far, it will either return an empty payload or the result of the
 /// in progress build job, whatever finishes first.
@@ -567,6 +651,9 @@ pub struct ResolveBestPayload<Payload> {
     pub maybe_better: Option<PendingPayload<Payload>>,
     /// The empty payload building job in progress, if any.
     pub empty_payload: Option<oneshot::Receiver<Result<Payload, PayloadBuilderError>>>,
+    /// The end of the grace period in which the in progress job may still beat the best payload,
+    /// if it is waited for.
+    pub grace_period: Option<Pin<Box<Sleep>>>,
 }
 
 impl<Payload> ResolveBestPayload<Payload> {
@@ -595,9 +682,17 @@ where
             }
         }
 
-        if let Some(best) = this.best_payload.take() {
+        if this.best_payload.is_some() &&
+            this.maybe_better.is_some() &&
+            this.grace_period.as_mut().is_some_and(|grace| grace.as_mut().poll(cx).is_pending())
+        {
+            // the in progress job may still beat the best payload
+            return Poll::Pending
+        }
+
+        if let Some(best_payload) = this.best_payload.take() {
             debug!(target: "payload_builder", "resolving best payload");
-            return Poll::Ready(Ok(best))
+            return Poll::Ready(Ok(best_payload))
         }
 
         if let Some(fut) = Pin::new(&mut this.empty_payload).as_pin_mut() {
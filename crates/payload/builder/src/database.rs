@@ -3,9 +3,9 @@
 use reth_primitives::{
     revm_primitives::{
         db::{Database, DatabaseRef},
-        AccountInfo, Address, Bytecode, B256,
+        AccountInfo, Address, Bytecode, ExecutionResult, State as EvmState, B256,
     },
-    U256,
+    TransactionSignedEcRecovered, U256,
 };
 use std::{
     cell::RefCell,
@@ -17,6 +17,9 @@ use std::{
 /// This is intended to be used in conjunction with `revm::db::State`
 /// during payload building which repeatedly accesses the same data.
 ///
+/// Next to the reads, it holds the executions of the last build attempt of a payload job, see
+/// [`CachedExecution`].
+///
 /// # Example
 ///
 /// ```
@@ -36,6 +39,7 @@ pub struct CachedReads {
     accounts: HashMap<Address, CachedAccount>,
     contracts: HashMap<B256, Bytecode>,
     block_hashes: HashMap<U256, B256>,
+    executions: Vec<CachedExecution>,
 }
 
 // === impl CachedReads ===
@@ -59,6 +63,33 @@ impl CachedReads {
     ) {
         self.accounts.insert(address, CachedAccount { info: Some(info), storage });
     }
+
+    /// Takes the executions of the last build attempt, in the order of its block.
+    pub fn take_executions(&mut self) -> Vec<CachedExecution> {
+        std::mem::take(&mut self.executions)
+    }
+
+    /// Sets the executions of a build attempt, in the order of its block, so the next attempt can
+    /// reuse them.
+    pub fn set_executions(&mut self, executions: Vec<CachedExecution>) {
+        self.executions = executions;
+    }
+}
+
+/// The execution of a transaction, or of the transactions of a bundle, by a build attempt of a
+/// payload job.
+///
+/// All attempts of a job build on top of the same state with the same block environment. An
+/// attempt that executes the same transactions in the same order as the previous attempt can reuse
+/// their results instead of executing them again, until the first transaction that differs.
+#[derive(Debug, Clone)]
+pub struct CachedExecution {
+    /// The hash of the executed transaction or bundle.
+    pub hash: B256,
+    /// The executed transactions with their results and state changes, in order.
+    pub transactions: Vec<(TransactionSignedEcRecovered, ExecutionResult, EvmState)>,
+    /// The fees that the execution paid to the coinbase.
+    pub fees: U256,
 }
 
 /// A [Database] that caches reads inside [`CachedReads`].
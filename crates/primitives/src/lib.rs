@@ -43,6 +43,7 @@ pub use reth_static_file_types as static_file;
 mod storage;
 pub mod transaction;
 mod withdrawal;
+mod witness;
 pub use account::{Account, Bytecode};
 #[cfg(any(test, feature = "arbitrary"))]
 pub use block::{generate_valid_header, valid_header_strategy};
@@ -77,6 +78,7 @@ pub use receipt::{
 pub use request::Requests;
 pub use static_file::StaticFileSegment;
 pub use storage::StorageEntry;
+pub use witness::{ExecutionWitness, WitnessAccount, WitnessVerificationError};
 
 pub use transaction::{
     BlobTransaction, BlobTransactionSidecar, FromRecoveredPooledTransaction,
@@ -7,7 +7,7 @@ use crate::{
 use reth_trie_types::{hash_builder::HashBuilder, Nibbles};
 
 mod types;
-pub use types::{AccountProof, StorageProof};
+pub use types::{AccountProof, MultiProof, StorageMultiProof, StorageProof};
 mod traits;
 pub use traits::IntoTrieAccount;
 
@@ -7,6 +7,89 @@ use alloy_trie::{
     proof::{verify_proof, ProofVerificationError},
     EMPTY_ROOT_HASH,
 };
+use std::collections::{BTreeMap, HashMap};
+
+/// The state multiproof of target accounts and multiproofs of their storage tries.
+///
+/// Branch nodes shared between the targets are included only once. Individual account and storage
+/// proofs can be extracted with [`MultiProof::account_proof`].
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct MultiProof {
+    /// State trie multiproof for requested accounts.
+    pub account_subtree: BTreeMap<Nibbles, Bytes>,
+    /// Target accounts that exist in the state, keyed by hashed address.
+    pub accounts: HashMap<B256, Account>,
+    /// Storage trie multiproofs of the target accounts, keyed by hashed address.
+    pub storages: HashMap<B256, StorageMultiProof>,
+}
+
+impl MultiProof {
+    /// Construct the account proof for the given address and storage slots from the multiproof.
+    ///
+    /// The address and slots are expected to be part of the targets the multiproof was generated
+    /// for, otherwise the resulting proof will fail verification.
+    pub fn account_proof(&self, address: Address, slots: &[B256]) -> AccountProof {
+        let hashed_address = keccak256(address);
+        let nibbles = Nibbles::unpack(hashed_address);
+
+        let mut proof = AccountProof::new(address);
+        proof.set_proof(
+            self.account_subtree
+                .iter()
+                .filter(|(path, _)| nibbles.starts_with(path))
+                .map(|(_, node)| node.clone())
+                .collect(),
+        );
+
+        if let Some(info) = self.accounts.get(&hashed_address) {
+            let (storage_root, storage_proofs) = match self.storages.get(&hashed_address) {
+                Some(storage) => {
+                    (storage.root, slots.iter().map(|slot| storage.storage_proof(*slot)).collect())
+                }
+                None => (EMPTY_ROOT_HASH, slots.iter().copied().map(StorageProof::new).collect()),
+            };
+            proof.set_account(*info, storage_root, storage_proofs);
+        }
+
+        proof
+    }
+}
+
+/// The merkle multiproof of storage trie.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StorageMultiProof {
+    /// Storage trie root.
+    pub root: B256,
+    /// Storage multiproof for requested slots.
+    pub subtree: BTreeMap<Nibbles, Bytes>,
+    /// Non-zero values of the target slots, keyed by hashed slot.
+    pub values: HashMap<B256, U256>,
+}
+
+impl Default for StorageMultiProof {
+    fn default() -> Self {
+        Self { root: EMPTY_ROOT_HASH, subtree: BTreeMap::default(), values: HashMap::default() }
+    }
+}
+
+impl StorageMultiProof {
+    /// Construct the storage proof for the given slot from the multiproof.
+    pub fn storage_proof(&self, slot: B256) -> StorageProof {
+        let hashed_slot = keccak256(slot);
+        let nibbles = Nibbles::unpack(hashed_slot);
+        let nodes = self
+            .subtree
+            .iter()
+            .filter(|(path, _)| nibbles.starts_with(path))
+            .map(|(_, node)| node.clone())
+            .collect();
+
+        let mut proof = StorageProof::new_with_nibbles(slot, nibbles);
+        proof.set_value(self.values.get(&hashed_slot).copied().unwrap_or_default());
+        proof.set_proof(nodes);
+        proof
+    }
+}
 
 /// The merkle proof with the relevant account info.
 #[derive(PartialEq, Eq, Debug)]
@@ -0,0 +1,158 @@
+//! The witness required to execute a block statelessly.
+
+use crate::{
+    keccak256,
+    proofs::{MultiProof, StorageMultiProof},
+    Account, Address, BlockNumber, Bytes, Header, B256, U256,
+};
+use alloy_trie::proof::ProofVerificationError;
+use reth_trie_types::Nibbles;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// The witness required to execute a block statelessly.
+///
+/// Contains the pre-state of all accounts, storage slots, bytecodes and block hashes accessed
+/// during execution, and the trie nodes that prove the accessed state against the state root of
+/// the parent block. Trie nodes are RLP encoded and keyed by their path in the trie, where every
+/// byte of the path is a single nibble.
+///
+/// The ancestor headers prove the parent state root and the accessed block hashes against the
+/// parent hash of the executed block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionWitness {
+    /// Pre-state of all accessed accounts.
+    pub accounts: Vec<WitnessAccount>,
+    /// Bytecodes of all accessed contracts.
+    pub codes: Vec<Bytes>,
+    /// Hashes of all block headers accessed via the `BLOCKHASH` opcode.
+    pub block_hashes: BTreeMap<u64, B256>,
+    /// Ancestor headers of the block, from the parent down to the oldest block accessed via the
+    /// `BLOCKHASH` opcode.
+    pub headers: Vec<Header>,
+    /// Account trie nodes on the paths to all accessed accounts.
+    pub account_trie: BTreeMap<Bytes, Bytes>,
+}
+
+/// Pre-state of an account accessed during execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WitnessAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// Account info, `None` if the account did not exist.
+    pub info: Option<Account>,
+    /// The storage root of the account.
+    pub storage_root: B256,
+    /// Values of all accessed storage slots.
+    pub storage: BTreeMap<B256, U256>,
+    /// Storage trie nodes on the paths to all accessed storage slots.
+    pub storage_trie: BTreeMap<Bytes, Bytes>,
+}
+
+impl ExecutionWitness {
+    /// Returns the state multiproof contained in the witness.
+    pub fn multiproof(&self) -> MultiProof {
+        let mut multiproof =
+            MultiProof { account_subtree: nodes_by_path(&self.account_trie), ..Default::default() };
+        for account in &self.accounts {
+            let Some(info) = account.info else { continue };
+            let hashed_address = keccak256(account.address);
+            multiproof.accounts.insert(hashed_address, info);
+            multiproof.storages.insert(
+                hashed_address,
+                StorageMultiProof {
+                    root: account.storage_root,
+                    subtree: nodes_by_path(&account.storage_trie),
+                    values: account
+                        .storage
+                        .iter()
+                        .map(|(slot, value)| (keccak256(slot), *value))
+                        .collect(),
+                },
+            );
+        }
+        multiproof
+    }
+
+    /// Verifies the witness against the parent hash of the executed block.
+    ///
+    /// The ancestor headers must form a chain ending in the parent, all block hashes must match
+    /// these headers, and all accounts and storage slots must be proven against the state root of
+    /// the parent header.
+    pub fn verify(&self, parent_hash: B256) -> Result<(), WitnessVerificationError> {
+        let parent = self.verify_headers(parent_hash)?;
+        self.verify_state(parent.state_root)
+    }
+
+    /// Verifies that the ancestor headers form a chain ending in the given parent hash and that
+    /// all block hashes are part of this chain. Returns the parent header.
+    fn verify_headers(&self, parent_hash: B256) -> Result<&Header, WitnessVerificationError> {
+        let parent = self.headers.first().ok_or(WitnessVerificationError::MissingParentHeader)?;
+
+        let mut hashes = HashMap::with_capacity(self.headers.len());
+        let mut expected_hash = parent_hash;
+        for header in &self.headers {
+            let hash = header.hash_slow();
+            if hash != expected_hash {
+                return Err(WitnessVerificationError::InvalidHeader(header.number))
+            }
+            hashes.insert(header.number, hash);
+            expected_hash = header.parent_hash;
+        }
+
+        for (number, hash) in &self.block_hashes {
+            if hashes.get(number) != Some(hash) {
+                return Err(WitnessVerificationError::InvalidBlockHash(*number))
+            }
+        }
+        Ok(parent)
+    }
+
+    /// Verifies all accounts and storage slots of the witness against the given state root.
+    pub fn verify_state(&self, state_root: B256) -> Result<(), WitnessVerificationError> {
+        let multiproof = self.multiproof();
+        for account in &self.accounts {
+            if account.info.is_none() && !account.storage.is_empty() {
+                return Err(WitnessVerificationError::StorageOfMissingAccount(account.address))
+            }
+
+            let slots = account.storage.keys().copied().collect::<Vec<_>>();
+            multiproof.account_proof(account.address, &slots).verify(state_root).map_err(
+                |error| WitnessVerificationError::InvalidProof { address: account.address, error },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts the serializable trie nodes into nodes keyed by [`Nibbles`].
+fn nodes_by_path(nodes: &BTreeMap<Bytes, Bytes>) -> BTreeMap<Nibbles, Bytes> {
+    nodes.iter().map(|(path, node)| (Nibbles::from_nibbles_unchecked(path), node.clone())).collect()
+}
+
+/// Errors that can occur when verifying an [`ExecutionWitness`].
+#[derive(Debug, thiserror::Error)]
+pub enum WitnessVerificationError {
+    /// The witness state does not match the state root.
+    #[error("invalid witness proof for account {address}: {error:?}")]
+    InvalidProof {
+        /// The address of the account.
+        address: Address,
+        /// The proof verification error.
+        error: ProofVerificationError,
+    },
+    /// The witness contains storage for an account that does not exist.
+    #[error("witness contains storage of non-existent account {0}")]
+    StorageOfMissingAccount(Address),
+    /// The witness does not contain the header of the parent block.
+    #[error("witness is missing the parent header")]
+    MissingParentHeader,
+    /// An ancestor header of the witness is not part of the chain of the executed block.
+    #[error("witness header #{0} is not an ancestor of the block")]
+    InvalidHeader(BlockNumber),
+    /// A block hash of the witness does not match the ancestor headers.
+    #[error("witness block hash #{0} does not match the ancestor headers")]
+    InvalidBlockHash(BlockNumber),
+}
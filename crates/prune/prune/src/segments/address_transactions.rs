@@ -0,0 +1,141 @@
+use crate::{
+    segments::{
+        history::prune_history_indices, PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
+    },
+    PrunerError,
+};
+use reth_db::tables;
+use reth_db_api::{database::Database, models::ShardedKey};
+use reth_provider::DatabaseProviderRW;
+use reth_prune_types::{PruneMode, PruneProgress, PruneSegment};
+use tracing::{instrument, trace};
+
+/// Prunes the [`tables::AddressTransactions`] index.
+///
+/// The shards of the index contain transaction numbers, so they are pruned up to the last
+/// transaction of the target block. [`tables::ContractCreators`] is left untouched.
+#[derive(Debug)]
+pub struct AddressTransactions {
+    mode: PruneMode,
+}
+
+impl AddressTransactions {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for AddressTransactions {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressTransactions
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let tx_range = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address transactions to prune");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let tx_range_end = *tx_range.end();
+
+        let (processed, pruned) = prune_history_indices::<DB, tables::AddressTransactions, _>(
+            provider,
+            tx_range_end,
+            |a, b| a.key == b.key,
+            |key| ShardedKey::last(key.key),
+        )?;
+        trace!(target: "pruner", %processed, %pruned, "Pruned address transactions");
+
+        Ok(PruneOutput {
+            progress: PruneProgress::new(true, &input.limiter),
+            pruned,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: Some(input.to_block),
+                tx_number: Some(tx_range_end),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressTransactions, PruneInput, PruneOutput, Segment};
+    use assert_matches::assert_matches;
+    use reth_db::{tables, BlockNumberList};
+    use reth_db_api::{models::ShardedKey, transaction::DbTxMut};
+    use reth_primitives::{Address, B256};
+    use reth_provider::PruneCheckpointReader;
+    use reth_prune_types::{PruneLimiter, PruneMode, PruneProgress, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::{generators, generators::random_block_range};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 2..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let first = Address::with_last_byte(1);
+        let second = Address::with_last_byte(2);
+        db.commit(|tx| {
+            tx.put::<tables::AddressTransactions>(
+                ShardedKey::new(first, 5),
+                BlockNumberList::new_pre_sorted([1, 3, 5]),
+            )?;
+            tx.put::<tables::AddressTransactions>(
+                ShardedKey::last(first),
+                BlockNumberList::new_pre_sorted([7, 12, 19]),
+            )?;
+            tx.put::<tables::AddressTransactions>(
+                ShardedKey::last(second),
+                BlockNumberList::new_pre_sorted([0, 4]),
+            )?;
+            Ok(())
+        })
+        .expect("insert address transactions");
+
+        let prune_mode = PruneMode::Before(6);
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+        let segment = AddressTransactions::new(prune_mode);
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(
+            result,
+            PruneOutput { progress: PruneProgress::Finished, pruned: 2, checkpoint: Some(_) }
+        );
+        segment
+            .save_checkpoint(&provider, result.checkpoint.unwrap().as_prune_checkpoint(prune_mode))
+            .unwrap();
+        provider.commit().expect("commit");
+
+        // blocks contain two transactions each, so the last transaction of block 5 is 11
+        assert_eq!(
+            db.table::<tables::AddressTransactions>().unwrap(),
+            vec![(ShardedKey::last(first), BlockNumberList::new_pre_sorted([12, 19]))]
+        );
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::AddressTransactions)
+                .unwrap()
+                .and_then(|checkpoint| checkpoint.tx_number),
+            Some(11)
+        );
+    }
+}
@@ -1,16 +1,21 @@
+mod account_changesets;
 mod account_history;
+mod address_transactions;
 mod headers;
 pub(super) mod history;
 mod receipts;
 mod receipts_by_logs;
 mod sender_recovery;
 mod set;
+mod storage_changesets;
 mod storage_history;
 mod transaction_lookup;
 mod transactions;
 
 use crate::PrunerError;
+pub use account_changesets::AccountChangeSets;
 pub use account_history::AccountHistory;
+pub use address_transactions::AddressTransactions;
 use alloy_primitives::{BlockNumber, TxNumber};
 pub use headers::Headers;
 pub use receipts::Receipts;
@@ -25,6 +30,7 @@ use reth_prune_types::{
 pub use sender_recovery::SenderRecovery;
 pub use set::SegmentSet;
 use std::{fmt::Debug, ops::RangeInclusive};
+pub use storage_changesets::StorageChangeSets;
 pub use storage_history::StorageHistory;
 use tracing::error;
 pub use transaction_lookup::TransactionLookup;
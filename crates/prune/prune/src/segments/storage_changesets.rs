@@ -0,0 +1,156 @@
+use crate::{
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
+    PrunerError,
+};
+use reth_db::tables;
+use reth_db_api::{database::Database, models::BlockNumberAddress};
+use reth_provider::DatabaseProviderRW;
+use reth_prune_types::{PruneMode, PruneProgress, PruneSegment};
+use tracing::{instrument, trace};
+
+/// Deletes the [`tables::StorageChangeSets`] rows that have been moved to static files.
+///
+/// Unlike [`StorageHistory`](crate::segments::StorageHistory), the history indices are left
+/// untouched, because the changesets are still available from static files.
+#[derive(Debug)]
+pub struct StorageChangeSets {
+    mode: PruneMode,
+}
+
+impl StorageChangeSets {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for StorageChangeSets {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::StorageChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No storage changesets to prune");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+
+        let mut last_pruned_block = None;
+        let (pruned, done) = provider.prune_table_with_range::<tables::StorageChangeSets>(
+            BlockNumberAddress::range(range),
+            &mut limiter,
+            |_| false,
+            |row| last_pruned_block = Some(row.0.block_number()),
+        )?;
+        trace!(target: "pruner", %pruned, %done, "Pruned storage changesets");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more storage changesets to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its storage changesets on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(PruneOutput {
+            progress,
+            pruned,
+            checkpoint: Some(PruneOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, Segment, StorageChangeSets};
+    use reth_db::tables;
+    use reth_db_api::models::{StorageBeforeTx, StoredBlockStorageChanges};
+    use reth_primitives::{StaticFileSegment, B256};
+    use reth_provider::{providers::StaticFileWriter, StaticFileProviderFactory};
+    use reth_prune_types::{PruneLimiter, PruneMode, PruneProgress};
+    use reth_stages::test_utils::TestStageDB;
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block_range, random_changeset_range, random_eoa_accounts},
+    };
+
+    #[test]
+    fn prune_after_copy_to_static_files() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 0..1);
+        let accounts = random_eoa_accounts(&mut rng, 3);
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            1..3,
+            0..16,
+        );
+        db.insert_changesets(changesets, None).expect("insert changesets");
+        let changesets = db.table::<tables::StorageChangeSets>().unwrap();
+
+        // Copy the changesets of blocks 0..=5 to static files, like the static file producer does
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=5 {
+            let changes = changesets
+                .iter()
+                .filter(|(key, _)| key.block_number() == block)
+                .map(|(key, entry)| StorageBeforeTx {
+                    address: key.address(),
+                    key: entry.key,
+                    value: entry.value,
+                })
+                .collect();
+            writer.append_storage_changeset(block, StoredBlockStorageChanges { changes }).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let segment = StorageChangeSets::new(PruneMode::before_inclusive(5));
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_eq!(result.progress, PruneProgress::Finished);
+        assert_eq!(
+            result.pruned,
+            changesets.iter().filter(|(key, _)| key.block_number() <= 5).count()
+        );
+        assert_eq!(result.checkpoint.and_then(|checkpoint| checkpoint.block_number), Some(5));
+        provider.commit().expect("commit");
+
+        // Only the changesets that were not copied are left in the database, and all of them can
+        // still be read
+        assert_eq!(
+            db.table::<tables::StorageChangeSets>().unwrap(),
+            changesets
+                .iter()
+                .filter(|(key, _)| key.block_number() > 5)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(db.factory.provider().unwrap().storage_changesets(0..=9).unwrap(), changesets);
+    }
+}
@@ -1,12 +1,13 @@
 use reth_primitives::{
-    keccak256, proofs::AccountProof, Account, Address, BlockNumber, Bytecode, Bytes, StorageKey,
-    B256, U256,
+    keccak256,
+    proofs::{AccountProof, MultiProof},
+    Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, B256, U256,
 };
 use reth_storage_api::{AccountReader, BlockHashReader, StateProvider, StateRootProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::updates::TrieUpdates;
 use revm::db::BundleState;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Mock state for testing
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -93,4 +94,8 @@ impl StateProvider for StateProviderTest {
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         unimplemented!("proof generation is not supported")
     }
+
+    fn multiproof(&self, _targets: &HashMap<B256, HashSet<B256>>) -> ProviderResult<MultiProof> {
+        unimplemented!("proof generation is not supported")
+    }
 }
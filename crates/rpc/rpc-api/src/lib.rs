@@ -42,12 +42,15 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
         bundle::{EthBundleApiServer, EthCallBundleApiServer},
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
         eth::EthApiServer,
         eth_filter::EthFilterApiServer,
         eth_pubsub::EthPubSubApiServer,
+        ganache::GanacheApiServer,
+        hardhat::HardhatApiServer,
         mev::MevApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
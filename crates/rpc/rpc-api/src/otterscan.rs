@@ -83,6 +83,9 @@ pub trait Otterscan {
     ) -> RpcResult<Option<Transaction>>;
 
     /// Gets the transaction hash and the address who created a contract.
+    ///
+    /// For contracts deployed by another contract with `CREATE` or `CREATE2`, the creator is the
+    /// deploying contract. These are found by tracing and require historical state.
     #[method(name = "getContractCreator")]
     async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>>;
 }
@@ -1,5 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlockId, B256, U256};
+use reth_rpc_types::{AddressTransactionsPage, AddressTransactionsRequest, ArchivedBlobSidecar};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +13,36 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns a page of the transactions the address appeared in, as the sender, the recipient,
+    /// the created contract, the emitter of an event or an indexed address argument of a
+    /// well-known event.
+    ///
+    /// Requires the address transactions index to be enabled.
+    #[method(name = "getAddressTransactions")]
+    async fn reth_get_address_transactions(
+        &self,
+        address: Address,
+        request: Option<AddressTransactionsRequest>,
+    ) -> RpcResult<AddressTransactionsPage>;
+
+    /// Returns the archived blob sidecars of the blob transactions in a block.
+    ///
+    /// Requires the blob sidecar archive to be enabled. Sidecars of blocks older than the
+    /// retention period of the archive, or of transactions the node has never seen, are not
+    /// returned.
+    #[method(name = "getBlobSidecars")]
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<ArchivedBlobSidecar>>;
+
+    /// Returns the archived blob sidecar that contains the blob with the given versioned hash.
+    ///
+    /// Requires the blob sidecar archive to be enabled.
+    #[method(name = "getBlobSidecarByVersionedHash")]
+    async fn reth_get_blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<ArchivedBlobSidecar>>;
 }
@@ -92,6 +92,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .eth_proof_window(self.rpc_eth_proof_window)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
     }
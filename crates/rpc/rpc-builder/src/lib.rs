@@ -17,28 +17,32 @@
 //! Configure only an http server with a selection of [`RethRpcModule`]s
 //!
 //! ```
-//! use reth_evm::ConfigureEvm;
+//! use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, StageCheckpointReader,
+//!     StateProviderFactory,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
 //! };
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::TransactionPool;
-//! pub async fn launch<Provider, Pool, Network, Events, EvmConfig>(
+//! pub async fn launch<Provider, Pool, Network, Events, EvmConfig, BlockExecutor>(
 //!     provider: Provider,
 //!     pool: Pool,
 //!     network: Network,
 //!     events: Events,
 //!     evm_config: EvmConfig,
+//!     block_executor: BlockExecutor,
 //! ) where
 //!     Provider: AccountReader
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + StageCheckpointReader
+//!         + AddressTransactionsReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -48,6 +52,7 @@
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions + Clone + 'static,
 //!     EvmConfig: ConfigureEvm + 'static,
+//!     BlockExecutor: BlockExecutorProvider,
 //! {
 //!     // configure the rpc module per transport
 //!     let transports = TransportRpcModuleConfig::default().with_http(vec![
@@ -63,6 +68,7 @@
 //!         TokioTaskExecutor::default(),
 //!         events,
 //!         evm_config,
+//!         block_executor,
 //!     )
 //!     .build(transports);
 //!     let handle = RpcServerConfig::default()
@@ -78,11 +84,12 @@
 //!
 //! ```
 //! use reth_engine_primitives::EngineTypes;
-//! use reth_evm::ConfigureEvm;
+//! use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, StageCheckpointReader,
+//!     StateProviderFactory,
 //! };
 //! use reth_rpc_api::EngineApiServer;
 //! use reth_rpc_builder::{
@@ -93,18 +100,30 @@
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::TransactionPool;
 //! use tokio::try_join;
-//! pub async fn launch<Provider, Pool, Network, Events, EngineApi, EngineT, EvmConfig>(
+//! pub async fn launch<
+//!     Provider,
+//!     Pool,
+//!     Network,
+//!     Events,
+//!     EngineApi,
+//!     EngineT,
+//!     EvmConfig,
+//!     BlockExecutor,
+//! >(
 //!     provider: Provider,
 //!     pool: Pool,
 //!     network: Network,
 //!     events: Events,
 //!     engine_api: EngineApi,
 //!     evm_config: EvmConfig,
+//!     block_executor: BlockExecutor,
 //! ) where
 //!     Provider: AccountReader
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + StageCheckpointReader
+//!         + AddressTransactionsReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -116,6 +135,7 @@
 //!     EngineApi: EngineApiServer<EngineT>,
 //!     EngineT: EngineTypes + 'static,
 //!     EvmConfig: ConfigureEvm + 'static,
+//!     BlockExecutor: BlockExecutorProvider,
 //! {
 //!     // configure the rpc module per transport
 //!     let transports = TransportRpcModuleConfig::default().with_http(vec![
@@ -131,6 +151,7 @@
 //!         TokioTaskExecutor::default(),
 //!         events,
 //!         evm_config,
+//!         block_executor,
 //!     );
 //!
 //!     // configure the server modules
@@ -163,16 +184,20 @@ use error::{ConflictingModules, RpcError, ServerKind};
 use http::{header::AUTHORIZATION, HeaderMap};
 use jsonrpsee::{
     core::RegisterMethodError,
-    server::{AlreadyStoppedError, IdProvider, RpcServiceBuilder, Server, ServerHandle},
+    server::{
+        serve_with_graceful_shutdown, stop_channel, AlreadyStoppedError, IdProvider,
+        RpcServiceBuilder, ServerHandle, TowerServiceBuilder,
+    },
     Methods, RpcModule,
 };
 use reth_engine_primitives::EngineTypes;
-use reth_evm::ConfigureEvm;
+use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_ipc::server::IpcServer;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReader, BlockReaderIdExt,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
+    StageCheckpointReader, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -182,16 +207,22 @@ use reth_rpc::{
         traits::RawTransactionForwarder,
         EthBundle, FeeHistoryCache,
     },
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider,
-    NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    AdminApi, BadBlockStore, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub,
+    EthSubscriptionIdProvider, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi,
+    ValidationApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
-use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
+use reth_rpc_layer::{
+    AuthLayer, Claims, JwtAuthValidator, JwtSecret, RateLimitClientLayer, RateLimitLayer,
+    RateLimiter,
+};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner, TokioTaskExecutor,
 };
-use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
+use reth_transaction_pool::{
+    blobstore::BlobSidecarArchive, noop::NoopTransactionPool, TransactionPool,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
@@ -200,8 +231,10 @@ use std::{
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
+use tokio::net::TcpListener;
+use tower::Service;
 use tower_http::cors::CorsLayer;
-use tracing::{instrument, trace};
+use tracing::{debug, instrument, trace};
 
 // re-export for convenience
 pub use jsonrpsee::server::ServerBuilder;
@@ -232,7 +265,7 @@ mod metrics;
 
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
-pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig>(
+pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>(
     provider: Provider,
     pool: Pool,
     network: Network,
@@ -241,6 +274,7 @@ pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig>(
     executor: Tasks,
     events: Events,
     evm_config: EvmConfig,
+    block_executor: BlockExecutor,
 ) -> Result<RpcServerHandle, RpcError>
 where
     Provider: BlockReaderIdExt
@@ -249,6 +283,8 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + StageCheckpointReader
+        + AddressTransactionsReader
         + Clone
         + Unpin
         + 'static,
@@ -257,10 +293,11 @@ where
     Tasks: TaskSpawner + Clone + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm + 'static,
+    BlockExecutor: BlockExecutorProvider,
 {
     let module_config = module_config.into();
     let server_config = server_config.into();
-    RpcModuleBuilder::new(provider, pool, network, executor, events, evm_config)
+    RpcModuleBuilder::new(provider, pool, network, executor, events, evm_config, block_executor)
         .build(module_config)
         .start_server(server_config)
         .await
@@ -270,7 +307,7 @@ where
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
 #[derive(Debug, Clone)]
-pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig> {
+pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor> {
     /// The Provider type to when creating all rpc handlers
     provider: Provider,
     /// The Pool type to when creating all rpc handlers
@@ -283,12 +320,16 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig> {
     events: Events,
     /// Defines how the EVM should be configured before execution.
     evm_config: EvmConfig,
+    /// The type that can create block executors, for example to re-execute blocks.
+    block_executor: BlockExecutor,
+    /// The archive serving the blob sidecar endpoints of the `reth` namespace, if enabled.
+    blob_archive: Option<BlobSidecarArchive>,
 }
 
 // === impl RpcBuilder ===
 
-impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
-    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig>
+impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
 {
     /// Create a new instance of the builder
     pub const fn new(
@@ -298,32 +339,71 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         executor: Tasks,
         events: Events,
         evm_config: EvmConfig,
+        block_executor: BlockExecutor,
     ) -> Self {
-        Self { provider, pool, network, executor, events, evm_config }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive: None,
+        }
     }
 
     /// Configure the provider instance.
     pub fn with_provider<P>(
         self,
         provider: P,
-    ) -> RpcModuleBuilder<P, Pool, Network, Tasks, Events, EvmConfig>
+    ) -> RpcModuleBuilder<P, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            pool, network, executor, events, evm_config, block_executor, blob_archive, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        }
     }
 
     /// Configure the transaction pool instance.
     pub fn with_pool<P>(
         self,
         pool: P,
-    ) -> RpcModuleBuilder<Provider, P, Network, Tasks, Events, EvmConfig>
+    ) -> RpcModuleBuilder<Provider, P, Network, Tasks, Events, EvmConfig, BlockExecutor>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        }
     }
 
     /// Configure a [`NoopTransactionPool`] instance.
@@ -333,14 +413,33 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     /// which requires a [`TransactionPool`] implementation.
     pub fn with_noop_pool(
         self,
-    ) -> RpcModuleBuilder<Provider, NoopTransactionPool, Network, Tasks, Events, EvmConfig> {
-        let Self { provider, executor, events, network, evm_config, .. } = self;
+    ) -> RpcModuleBuilder<
+        Provider,
+        NoopTransactionPool,
+        Network,
+        Tasks,
+        Events,
+        EvmConfig,
+        BlockExecutor,
+    > {
+        let Self {
+            provider,
+            executor,
+            events,
+            network,
+            evm_config,
+            block_executor,
+            blob_archive,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             executor,
             events,
             network,
             evm_config,
+            block_executor,
+            blob_archive,
             pool: NoopTransactionPool::default(),
         }
     }
@@ -349,12 +448,23 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_network<N>(
         self,
         network: N,
-    ) -> RpcModuleBuilder<Provider, Pool, N, Tasks, Events, EvmConfig>
+    ) -> RpcModuleBuilder<Provider, Pool, N, Tasks, Events, EvmConfig, BlockExecutor>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider, pool, executor, events, evm_config, block_executor, blob_archive, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        }
     }
 
     /// Configure a [`NoopNetwork`] instance.
@@ -364,8 +474,11 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     /// which requires a [`NetworkInfo`] implementation.
     pub fn with_noop_network(
         self,
-    ) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events, EvmConfig> {
-        let Self { provider, pool, executor, events, evm_config, .. } = self;
+    ) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events, EvmConfig, BlockExecutor>
+    {
+        let Self {
+            provider, pool, executor, events, evm_config, block_executor, blob_archive, ..
+        } = self;
         RpcModuleBuilder {
             provider,
             pool,
@@ -373,6 +486,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             events,
             network: NoopNetwork::default(),
             evm_config,
+            block_executor,
+            blob_archive,
         }
     }
 
@@ -380,12 +495,23 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_executor<T>(
         self,
         executor: T,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, T, Events, EvmConfig>
+    ) -> RpcModuleBuilder<Provider, Pool, Network, T, Events, EvmConfig, BlockExecutor>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            pool, network, provider, events, evm_config, block_executor, blob_archive, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        }
     }
 
     /// Configure [`TokioTaskExecutor`] as the task executor to use for additional tasks.
@@ -394,8 +520,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     /// [`TokioTaskExecutor`].
     pub fn with_tokio_executor(
         self,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, TokioTaskExecutor, Events, EvmConfig> {
-        let Self { pool, network, provider, events, evm_config, .. } = self;
+    ) -> RpcModuleBuilder<
+        Provider,
+        Pool,
+        Network,
+        TokioTaskExecutor,
+        Events,
+        EvmConfig,
+        BlockExecutor,
+    > {
+        let Self {
+            pool, network, provider, events, evm_config, block_executor, blob_archive, ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -403,6 +539,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             events,
             executor: TokioTaskExecutor::default(),
             evm_config,
+            block_executor,
+            blob_archive,
         }
     }
 
@@ -410,29 +548,85 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_events<E>(
         self,
         events: E,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, E, EvmConfig>
+    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, E, EvmConfig, BlockExecutor>
     where
         E: CanonStateSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider,
+            pool,
+            executor,
+            network,
+            evm_config,
+            block_executor,
+            blob_archive,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        }
     }
 
     /// Configure the evm configuration type
     pub fn with_evm_config<E>(
         self,
         evm_config: E,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, E>
+    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, E, BlockExecutor>
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self {
+            provider, pool, executor, network, events, block_executor, blob_archive, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        }
+    }
+
+    /// Configure the block executor provider
+    pub fn with_block_executor<B>(
+        self,
+        block_executor: B,
+    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, B>
+    where
+        B: BlockExecutorProvider,
+    {
+        let Self { provider, pool, executor, network, events, evm_config, blob_archive, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        }
+    }
+
+    /// Configure the archive serving the blob sidecar endpoints of the `reth` namespace.
+    pub fn with_blob_archive(mut self, blob_archive: BlobSidecarArchive) -> Self {
+        self.blob_archive = Some(blob_archive);
+        self
     }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
-    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig>
+impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
 where
     Provider: BlockReaderIdExt
         + AccountReader
@@ -440,6 +634,8 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + StageCheckpointReader
+        + AddressTransactionsReader
         + Clone
         + Unpin
         + 'static,
@@ -448,6 +644,7 @@ where
     Tasks: TaskSpawner + Clone + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm + 'static,
+    BlockExecutor: BlockExecutorProvider,
 {
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
     /// be used to start the transport server(s).
@@ -462,18 +659,36 @@ where
     ) -> (
         TransportRpcModules,
         AuthRpcModule,
-        RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>,
+        RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>,
     )
     where
         EngineT: EngineTypes + 'static,
         EngineApi: EngineApiServer<EngineT>,
     {
-        let Self { provider, pool, network, executor, events, evm_config } = self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
 
-        let mut registry =
-            RethModuleRegistry::new(provider, pool, network, executor, events, config, evm_config);
+        let mut registry = RethModuleRegistry::new(
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            config,
+            evm_config,
+            block_executor,
+        );
+        registry.blob_archive = blob_archive;
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -490,14 +705,18 @@ where
     /// # Example
     ///
     /// ```no_run
-    /// use reth_evm::ConfigureEvm;
+    /// use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
     /// use reth_network_api::noop::NoopNetwork;
     /// use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
     /// use reth_rpc_builder::RpcModuleBuilder;
     /// use reth_tasks::TokioTaskExecutor;
     /// use reth_transaction_pool::noop::NoopTransactionPool;
     ///
-    /// fn init<Evm: ConfigureEvm + 'static>(evm: Evm) {
+    /// fn init<Evm, Executor>(evm: Evm, block_executor: Executor)
+    /// where
+    ///     Evm: ConfigureEvm + 'static,
+    ///     Executor: BlockExecutorProvider,
+    /// {
     ///     let mut registry = RpcModuleBuilder::default()
     ///         .with_provider(NoopProvider::default())
     ///         .with_pool(NoopTransactionPool::default())
@@ -505,6 +724,7 @@ where
     ///         .with_executor(TokioTaskExecutor::default())
     ///         .with_events(TestCanonStateSubscriptions::default())
     ///         .with_evm_config(evm)
+    ///         .with_block_executor(block_executor)
     ///         .into_registry(Default::default());
     ///
     ///     let eth_api = registry.eth_api();
@@ -513,9 +733,29 @@ where
     pub fn into_registry(
         self,
         config: RpcModuleConfig,
-    ) -> RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig> {
-        let Self { provider, pool, network, executor, events, evm_config } = self;
-        RethModuleRegistry::new(provider, pool, network, executor, events, config, evm_config)
+    ) -> RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor> {
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        } = self;
+        let mut registry = RethModuleRegistry::new(
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            config,
+            evm_config,
+            block_executor,
+        );
+        registry.blob_archive = blob_archive;
+        registry
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -525,7 +765,16 @@ where
     pub fn build(self, module_config: TransportRpcModuleConfig) -> TransportRpcModules<()> {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events, evm_config } = self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            blob_archive,
+        } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -538,7 +787,9 @@ where
                 events,
                 config.unwrap_or_default(),
                 evm_config,
+                block_executor,
             );
+            registry.blob_archive = blob_archive;
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -550,9 +801,9 @@ where
     }
 }
 
-impl Default for RpcModuleBuilder<(), (), (), (), (), ()> {
+impl Default for RpcModuleBuilder<(), (), (), (), (), (), ()> {
     fn default() -> Self {
-        Self::new((), (), (), (), (), ())
+        Self::new((), (), (), (), (), (), ())
     }
 }
 
@@ -626,7 +877,7 @@ impl RpcModuleConfigBuilder {
 
 /// A Helper type the holds instances of the configured modules.
 #[derive(Debug, Clone)]
-pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig> {
+pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor> {
     provider: Provider,
     pool: Pool,
     network: Network,
@@ -634,12 +885,18 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
     events: Events,
     /// Defines how to configure the EVM before execution.
     evm_config: EvmConfig,
+    /// The type that can create block executors.
+    block_executor: BlockExecutor,
     /// Additional settings for handlers.
     config: RpcModuleConfig,
     /// Holds a clone of all the eth namespace handlers
     eth: Option<EthHandlers<Provider, Pool, Network, Events, EvmConfig>>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// Recently rejected blocks served by the `debug` namespace
+    bad_blocks: BadBlockStore,
+    /// The archive serving the blob sidecar endpoints of the `reth` namespace, if enabled
+    blob_archive: Option<BlobSidecarArchive>,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
     /// Optional forwarder for `eth_sendRawTransaction`
@@ -649,8 +906,8 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
 
 // === impl RethModuleRegistry ===
 
-impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
-    RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
+impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+    RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
 {
     /// Creates a new, empty instance.
     pub fn new(
@@ -661,16 +918,20 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         events: Events,
         config: RpcModuleConfig,
         evm_config: EvmConfig,
+        block_executor: BlockExecutor,
     ) -> Self {
         Self {
             provider,
             pool,
             network,
             evm_config,
+            block_executor,
             eth: None,
             executor,
             modules: Default::default(),
             blocking_pool_guard: BlockingTaskGuard::new(config.eth.max_tracing_requests),
+            bad_blocks: BadBlockStore::new(constants::DEFAULT_MAX_BAD_BLOCKS),
+            blob_archive: None,
             config,
             events,
             eth_raw_transaction_forwarder: None,
@@ -711,6 +972,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         &self.provider
     }
 
+    /// Returns the store of rejected blocks served by `debug_getBadBlocks`.
+    ///
+    /// Invalid blocks should be reported to this store, for example by the consensus engine.
+    pub const fn bad_blocks(&self) -> &BadBlockStore {
+        &self.bad_blocks
+    }
+
+    /// Returns the archive serving the blob sidecar endpoints of the `reth` namespace, if enabled.
+    pub const fn blob_archive(&self) -> Option<&BlobSidecarArchive> {
+        self.blob_archive.as_ref()
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -726,8 +999,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     }
 }
 
-impl<Provider: ChainSpecProvider, Pool, Network, Tasks, Events, EvmConfig>
-    RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
+impl<Provider: ChainSpecProvider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+    RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
 where
     Network: NetworkInfo + Peers + Clone + 'static,
 {
@@ -756,8 +1029,8 @@ where
     }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
-    RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
+impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+    RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
 where
     Provider: BlockReaderIdExt
         + AccountReader
@@ -765,6 +1038,8 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + StageCheckpointReader
+        + AddressTransactionsReader
         + Clone
         + Unpin
         + 'static,
@@ -773,6 +1048,7 @@ where
     Tasks: TaskSpawner + Clone + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm + 'static,
+    BlockExecutor: BlockExecutorProvider,
 {
     /// Register Eth Namespace
     ///
@@ -866,6 +1142,27 @@ where
         self
     }
 
+    /// Register Mev namespace, including the bundle methods of the `eth_` namespace
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn register_mev(&mut self) -> &mut Self {
+        let bundle_api = self.bundle_api();
+        let mut module = EthBundleApiServer::into_rpc(bundle_api.clone());
+        module.remove_method("eth_callBundle");
+        module.merge(MevApiServer::into_rpc(bundle_api)).expect("No conflicting methods");
+        self.modules.insert(RethRpcModule::Mev, module.into());
+        self
+    }
+
+    /// Register Flashbots namespace
+    pub fn register_flashbots(&mut self) -> &mut Self {
+        let validation_api = self.validation_api();
+        self.modules.insert(RethRpcModule::Flashbots, validation_api.into_rpc().into());
+        self
+    }
+
     /// Helper function to create a [`RpcModule`] if it's not `None`
     fn maybe_module(&mut self, config: Option<&RpcModuleSelection>) -> Option<RpcModule<()>> {
         config.map(|config| self.module_for(config))
@@ -939,6 +1236,9 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.block_executor.clone(),
+                            self.bad_blocks.clone(),
+                            self.config.eth.eth_proof_window,
                         )
                         .into_rpc()
                         .into(),
@@ -972,17 +1272,47 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Reth => RethApi::new(
+                            self.provider.clone(),
+                            Box::new(self.executor.clone()),
+                            self.blob_archive.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
+                            EthCallBundleApiServer::into_rpc(EthBundle::new(
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                                self.pool.bundle_pool(),
+                            ))
+                            .into()
+                        }
+                        RethRpcModule::Mev => {
+                            let bundle_api = EthBundle::new(
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                                self.pool.bundle_pool(),
+                            );
+                            let mut module = EthBundleApiServer::into_rpc(bundle_api.clone());
+                            // `eth_callBundle` is served by the `eth-call-bundle` module
+                            module.remove_method("eth_callBundle");
+                            module
+                                .merge(MevApiServer::into_rpc(bundle_api))
+                                .expect("No conflicting methods");
+                            module.into()
                         }
+                        RethRpcModule::Flashbots => ValidationApi::new(
+                            self.provider.clone(),
+                            self.block_executor.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                     })
                     .clone()
             })
@@ -1055,6 +1385,7 @@ where
             cache.clone(),
             gas_oracle,
             self.config.eth.rpc_gas_cap,
+            self.config.eth.eth_proof_window,
             executor.clone(),
             blocking_task_pool.clone(),
             fee_history_cache,
@@ -1117,7 +1448,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn bundle_api(&mut self) -> EthBundle<EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        EthBundle::new(eth_api, self.blocking_pool_guard.clone())
+        EthBundle::new(eth_api, self.blocking_pool_guard.clone(), self.pool.bundle_pool())
     }
 
     /// Instantiates `OtterscanApi`
@@ -1125,9 +1456,11 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn otterscan_api(&mut self) -> OtterscanApi<EthApi<Provider, Pool, Network, EvmConfig>> {
+    pub fn otterscan_api(
+        &mut self,
+    ) -> OtterscanApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 
     /// Instantiates `DebugApi`
@@ -1135,9 +1468,18 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn debug_api(&mut self) -> DebugApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
+    pub fn debug_api(
+        &mut self,
+    ) -> DebugApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>, BlockExecutor> {
         let eth_api = self.eth_api();
-        DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.blocking_pool_guard.clone(),
+            self.block_executor.clone(),
+            self.bad_blocks.clone(),
+            self.config.eth.eth_proof_window,
+        )
     }
 
     /// Instantiates `NetApi`
@@ -1152,7 +1494,20 @@ where
 
     /// Instantiates `RethApi`
     pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+        RethApi::new(
+            self.provider.clone(),
+            Box::new(self.executor.clone()),
+            self.blob_archive.clone(),
+        )
+    }
+
+    /// Instantiates `ValidationApi`
+    pub fn validation_api(&self) -> ValidationApi<Provider, BlockExecutor> {
+        ValidationApi::new(
+            self.provider.clone(),
+            self.block_executor.clone(),
+            Box::new(self.executor.clone()),
+        )
     }
 }
 
@@ -1187,6 +1542,8 @@ pub struct RpcServerConfig {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Rate limiter for http and ws calls
+    rate_limiter: Option<RateLimiter>,
 }
 
 // === impl RpcServerConfig ===
@@ -1304,6 +1661,14 @@ impl RpcServerConfig {
         self
     }
 
+    /// Configures the rate limiter for http and ws calls.
+    ///
+    /// All http and ws servers share the quotas of the rate limiter.
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be be launched on [`RpcServerConfig::start`].
@@ -1343,6 +1708,16 @@ impl RpcServerConfig {
         self.jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`RateLimitClientLayer`] if any
+    fn maybe_rate_limit_client_layer(&self) -> Option<RateLimitClientLayer> {
+        self.rate_limiter.as_ref().map(RateLimiter::client_layer)
+    }
+
+    /// Creates the [`RateLimitLayer`], which does not limit any calls without a rate limiter
+    fn rate_limit_layer(&self) -> RateLimitLayer {
+        self.rate_limiter.as_ref().map(RateLimiter::rpc_layer).unwrap_or_default()
+    }
+
     /// Builds the ws and http server(s).
     ///
     /// If both are on the same port, they are combined into one server.
@@ -1386,23 +1761,26 @@ impl RpcServerConfig {
             modules.config.ensure_ws_http_identical()?;
 
             let builder = self.http_server_config.take().expect("http_server_config is Some");
-            let server = builder
+            let builder = builder
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(cors)?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_rate_limit_client_layer()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules
-                            .http
-                            .as_ref()
-                            .or(modules.ws.as_ref())
-                            .map(RpcRequestMetrics::same_port)
-                            .unwrap_or_default(),
-                    ),
-                )
-                .build(http_socket_addr)
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .or(modules.ws.as_ref())
+                                .map(RpcRequestMetrics::same_port)
+                                .unwrap_or_default(),
+                        )
+                        .layer(self.rate_limit_layer()),
+                );
+            let server = WsHttpServerKind::bind(builder, http_socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr)))?;
             let addr = server
@@ -1422,18 +1800,20 @@ impl RpcServerConfig {
         let mut ws_local_addr = None;
         let mut ws_server = None;
         if let Some(builder) = self.ws_server_config.take() {
-            let server = builder
+            let builder = builder
                 .ws_only()
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_rate_limit_client_layer()),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::new()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
-                )
-                .build(ws_socket_addr)
+                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(self.rate_limit_layer()),
+                );
+            let server = WsHttpServerKind::bind(builder, ws_socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
             let addr = server
@@ -1445,19 +1825,22 @@ impl RpcServerConfig {
         }
 
         if let Some(builder) = self.http_server_config.take() {
-            let server = builder
+            let builder = builder
                 .http_only()
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_rate_limit_client_layer()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
-                )
-                .build(http_socket_addr)
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(self.rate_limit_layer()),
+                );
+            let server = WsHttpServerKind::bind(builder, http_socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             let local_addr = server
@@ -1723,15 +2106,83 @@ struct WsHttpServer {
     jwt_secret: Option<JwtSecret>,
 }
 
-// Define the type alias with detailed type complexity
-type WsHttpServerKind = Server<
+/// The http middleware of the ws and http servers.
+type WsHttpMiddleware = Stack<
+    tower::util::Either<RateLimitClientLayer, Identity>,
     Stack<
         tower::util::Either<AuthLayer<JwtAuthValidator>, Identity>,
         Stack<tower::util::Either<CorsLayer, Identity>, Identity>,
     >,
-    Stack<RpcRequestMetrics, Identity>,
 >;
 
+/// The rpc middleware of the ws and http servers.
+type WsHttpRpcMiddleware = Stack<RateLimitLayer, Stack<RpcRequestMetrics, Identity>>;
+
+/// A bound ws and/or http server that has not been started yet.
+///
+/// The server accepts the connections itself, so that the remote address of each connection can be
+/// inserted into the extensions of its requests for the [`RateLimitClientLayer`].
+struct WsHttpServerKind {
+    /// The listener for incoming connections.
+    listener: TcpListener,
+    /// Builds the service that serves the requests of a connection.
+    builder: TowerServiceBuilder<WsHttpRpcMiddleware, WsHttpMiddleware>,
+}
+
+impl WsHttpServerKind {
+    /// Binds the server to the given address.
+    async fn bind(
+        builder: ServerBuilder<WsHttpMiddleware, WsHttpRpcMiddleware>,
+        addr: SocketAddr,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self { listener, builder: builder.to_service_builder() })
+    }
+
+    /// Returns the address the server is bound to.
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Spawns the server with the given module and returns its handle.
+    fn start(self, module: RpcModule<()>) -> ServerHandle {
+        let Self { listener, builder } = self;
+        let methods = Methods::from(module);
+        let (stop_handle, server_handle) = stop_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, remote_addr) = tokio::select! {
+                    res = listener.accept() => match res {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            debug!(target: "rpc", %err, "failed to accept connection");
+                            continue
+                        }
+                    },
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+                let _ = stream.set_nodelay(true);
+
+                let service = builder.clone().build(methods.clone(), stop_handle.clone());
+                let service =
+                    tower::service_fn(move |mut req: http::Request<hyper::body::Incoming>| {
+                        req.extensions_mut().insert(remote_addr);
+                        service.clone().call(req)
+                    });
+                let stopped = stop_handle.clone().shutdown();
+                tokio::spawn(async move {
+                    if let Err(err) = serve_with_graceful_shutdown(stream, service, stopped).await {
+                        debug!(target: "rpc", %err, %remote_addr, "connection failed");
+                    }
+                });
+            }
+        });
+
+        server_handle
+    }
+}
+
 /// Enum for holding the http and ws servers in all possible combinations.
 enum WsHttpServers {
     /// Both servers are on the same port
@@ -0,0 +1,85 @@
+//! Rate limiting of the http and ws servers
+
+use crate::utils::{test_address, test_rpc_builder};
+use http::{HeaderMap, HeaderValue};
+use jsonrpsee::{core::client::Error, http_client::HttpClientBuilder, ws_client::WsClientBuilder};
+use reth_rpc_api::Web3ApiClient;
+use reth_rpc_builder::{RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig};
+use reth_rpc_layer::{RateLimitConfig, RateLimiter, RATE_LIMIT_EXCEEDED_CODE};
+use reth_rpc_server_types::RethRpcModule;
+use std::{collections::BTreeMap, time::Duration};
+
+/// A limiter that allows a single call per hour with the API key `tenant`, and plenty of calls
+/// without it.
+fn test_rate_limiter() -> RateLimiter {
+    RateLimiter::new(RateLimitConfig {
+        period: Duration::from_secs(3600),
+        ip_quota: Some(100),
+        api_keys: BTreeMap::from([("tenant".to_string(), 1)]),
+        method_costs: BTreeMap::new(),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+async fn launch_rate_limited_http_ws() -> RpcServerHandle {
+    let server = test_rpc_builder().build(
+        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Web3])
+            .with_ws(vec![RethRpcModule::Web3]),
+    );
+    server
+        .start_server(
+            RpcServerConfig::http(Default::default())
+                .with_http_address(test_address())
+                .with_ws(Default::default())
+                .with_ws_address(test_address())
+                .with_rate_limiter(Some(test_rate_limiter())),
+        )
+        .await
+        .unwrap()
+}
+
+fn api_key_headers() -> HeaderMap {
+    HeaderMap::from_iter([("x-api-key".parse().unwrap(), HeaderValue::from_static("tenant"))])
+}
+
+fn is_rate_limited(err: Error) -> bool {
+    matches!(err, Error::Call(err) if err.code() == RATE_LIMIT_EXCEEDED_CODE)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_rate_limit_by_api_key() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_rate_limited_http_ws().await;
+    let url = handle.http_url().unwrap();
+    let client = HttpClientBuilder::default().set_headers(api_key_headers()).build(&url).unwrap();
+
+    // the second call is only rejected if the client of the request reached the rpc middleware,
+    // unidentified clients would be limited by the ip quota
+    Web3ApiClient::client_version(&client).await.unwrap();
+    assert!(is_rate_limited(Web3ApiClient::client_version(&client).await.unwrap_err()));
+
+    // clients without the key are limited by their address
+    let client = HttpClientBuilder::default().build(&url).unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_rate_limit_by_api_key() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_rate_limited_http_ws().await;
+    let url = handle.ws_url().unwrap();
+    let client =
+        WsClientBuilder::default().set_headers(api_key_headers()).build(&url).await.unwrap();
+
+    // the client is identified once per connection, by the upgrade request
+    Web3ApiClient::client_version(&client).await.unwrap();
+    assert!(is_rate_limited(Web3ApiClient::client_version(&client).await.unwrap_err()));
+
+    let client = WsClientBuilder::default().build(&url).await.unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+}
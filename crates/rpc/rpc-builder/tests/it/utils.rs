@@ -1,6 +1,6 @@
 use reth_beacon_consensus::BeaconConsensusEngineHandle;
 use reth_ethereum_engine_primitives::EthEngineTypes;
-use reth_evm_ethereum::EthEvmConfig;
+use reth_evm_ethereum::{execute::EthExecutorProvider, EthEvmConfig};
 use reth_network_api::noop::NoopNetwork;
 use reth_payload_builder::test_utils::spawn_test_payload_service;
 use reth_primitives::MAINNET;
@@ -111,6 +111,7 @@ pub fn test_rpc_builder() -> RpcModuleBuilder<
     TokioTaskExecutor,
     TestCanonStateSubscriptions,
     EthEvmConfig,
+    EthExecutorProvider,
 > {
     RpcModuleBuilder::default()
         .with_provider(NoopProvider::default())
@@ -119,4 +120,5 @@ pub fn test_rpc_builder() -> RpcModuleBuilder<
         .with_executor(TokioTaskExecutor::default())
         .with_events(TestCanonStateSubscriptions::default())
         .with_evm_config(EthEvmConfig::default())
+        .with_block_executor(EthExecutorProvider::mainnet())
 }
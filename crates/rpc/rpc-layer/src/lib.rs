@@ -14,6 +14,7 @@ use jsonrpsee_http_client::HttpResponse;
 mod auth_client_layer;
 mod auth_layer;
 mod jwt_validator;
+mod rate_limit;
 
 // Export alloy JWT types
 pub use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
@@ -21,6 +22,10 @@ pub use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
 pub use auth_client_layer::{secret_to_bearer_header, AuthClientLayer, AuthClientService};
 pub use auth_layer::AuthLayer;
 pub use jwt_validator::JwtAuthValidator;
+pub use rate_limit::{
+    ClientId, RateLimitClientLayer, RateLimitClientService, RateLimitConfig, RateLimitConfigError,
+    RateLimitLayer, RateLimitService, RateLimiter, RATE_LIMIT_EXCEEDED_CODE,
+};
 
 /// General purpose trait to validate Http Authorization headers. It's supposed to be integrated as
 /// a validator trait into an [`AuthLayer`].
@@ -0,0 +1,565 @@
+//! Rate limiting of RPC calls per client.
+
+use futures_util::future::Either;
+use http::HeaderMap;
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use jsonrpsee_http_client::{HttpRequest, HttpResponse};
+use parking_lot::Mutex;
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::{ready, Ready},
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::task::futures::TaskLocalFuture;
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// The error code of calls rejected because the client exceeded its quota.
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The maximum number of tracked clients, above which the least recently seen client is dropped.
+const MAX_TRACKED_CLIENTS: u32 = 100_000;
+
+tokio::task_local! {
+    /// The client of the HTTP request or WS connection that is currently served.
+    static CLIENT: ClientId;
+}
+
+/// Configuration of the rate limits of RPC calls.
+///
+/// Every call has a cost, [`RateLimitConfig::default_cost`] unless a different cost is configured
+/// in [`RateLimitConfig::method_costs`]. A client may spend its quota of cost units per
+/// [`RateLimitConfig::period`]. The quota refills continuously.
+///
+/// Configured in the `[rpc_rate_limit]` section of `reth.toml`:
+///
+/// ```toml
+/// [rpc_rate_limit]
+/// period = "1s"
+/// ip_quota = 500
+/// api_key_header = "x-api-key"
+/// trusted_proxies = ["127.0.0.1"]
+///
+/// [rpc_rate_limit.api_keys]
+/// tenant-a = 5000
+///
+/// [rpc_rate_limit.method_costs]
+/// eth_getLogs = 20
+/// "debug_traceBlock*" = 100
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// The period in which a client may spend its quota.
+    #[serde(with = "humantime_serde")]
+    pub period: Duration,
+    /// The quota of clients without a known API key, per IP address. `None` for no limit.
+    pub ip_quota: Option<u64>,
+    /// The header that carries the API key of a client.
+    pub api_key_header: String,
+    /// The addresses of reverse proxies whose `X-Forwarded-For` and `X-Real-IP` headers are
+    /// trusted.
+    ///
+    /// Clients are identified by the remote address of their connection, unless it is a trusted
+    /// proxy. Then the client is the rightmost address in `X-Forwarded-For` that is not a trusted
+    /// proxy itself, or the address in `X-Real-IP` without `X-Forwarded-For`.
+    pub trusted_proxies: Vec<IpAddr>,
+    /// The quotas of clients by API key.
+    ///
+    /// Clients that send a listed key are limited by the quota of the key instead of the quota of
+    /// their IP address.
+    pub api_keys: BTreeMap<String, u64>,
+    /// The cost of methods without a configured cost.
+    pub default_cost: u64,
+    /// The costs of methods by name.
+    ///
+    /// Names ending in `*` match all methods starting with the preceding prefix. Exact names take
+    /// precedence over prefixes, and longer prefixes over shorter ones. Configured costs replace
+    /// the default costs of heavy methods.
+    pub method_costs: BTreeMap<String, u64>,
+}
+
+impl RateLimitConfig {
+    /// Validates the configuration.
+    ///
+    /// Returns an error if a method costs more than any quota, because such a method could never
+    /// be called by the clients of that quota.
+    pub fn validate(&self) -> Result<(), RateLimitConfigError> {
+        let Some(quota) = self.ip_quota.into_iter().chain(self.api_keys.values().copied()).min()
+        else {
+            return Ok(())
+        };
+        let costs = std::iter::once(("*", self.default_cost))
+            .chain(self.method_costs.iter().map(|(method, cost)| (method.as_str(), *cost)));
+        for (method, cost) in costs {
+            if cost > quota {
+                return Err(RateLimitConfigError::CostExceedsQuota {
+                    method: method.to_string(),
+                    cost,
+                    quota,
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Errors of an invalid [`RateLimitConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RateLimitConfigError {
+    /// A method costs more than the smallest quota.
+    #[error("cost {cost} of method {method} exceeds the quota {quota}")]
+    CostExceedsQuota {
+        /// The method, `*` for the default cost.
+        method: String,
+        /// The cost of the method.
+        cost: u64,
+        /// The smallest configured quota.
+        quota: u64,
+    },
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            period: Duration::from_secs(1),
+            ip_quota: None,
+            api_key_header: "x-api-key".to_string(),
+            trusted_proxies: Vec::new(),
+            api_keys: BTreeMap::new(),
+            default_cost: 1,
+            method_costs: BTreeMap::from([
+                ("eth_getLogs".to_string(), 20),
+                ("debug_traceBlock*".to_string(), 100),
+                ("debug_traceCall*".to_string(), 20),
+                ("debug_traceTransaction".to_string(), 20),
+                ("trace_block".to_string(), 100),
+                ("trace_filter".to_string(), 100),
+                ("trace_replayBlockTransactions".to_string(), 100),
+            ]),
+        }
+    }
+}
+
+/// The client an RPC call is attributed to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientId {
+    /// A client with a configured API key.
+    ApiKey(String),
+    /// A client identified by its IP address.
+    Ip(IpAddr),
+    /// A client that could not be identified.
+    ///
+    /// This is only the case if the remote address of the connection is unknown, because the
+    /// server did not insert it into the request or the [`RateLimitClientLayer`] is not installed.
+    /// All unidentified clients share the quota of a single IP address.
+    Unknown,
+}
+
+impl ClientId {
+    /// Identifies the client of an HTTP request or WS upgrade request by its API key header, or
+    /// by the remote address of its connection.
+    ///
+    /// The forwarding headers are only considered if the remote address is a trusted proxy, see
+    /// [`RateLimitConfig::trusted_proxies`].
+    fn from_request(
+        remote_ip: Option<IpAddr>,
+        headers: &HeaderMap,
+        config: &RateLimitConfig,
+    ) -> Self {
+        if let Some(key) = headers.get(config.api_key_header.as_str()).and_then(|v| v.to_str().ok())
+        {
+            if config.api_keys.contains_key(key) {
+                return Self::ApiKey(key.to_string())
+            }
+        }
+
+        let Some(mut ip) = remote_ip else { return Self::Unknown };
+        if !config.trusted_proxies.contains(&ip) {
+            return Self::Ip(ip)
+        }
+
+        let forwarded = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .collect::<Vec<_>>();
+        if forwarded.is_empty() {
+            if let Some(real_ip) =
+                headers.get("x-real-ip").and_then(|v| v.to_str().ok()?.trim().parse().ok())
+            {
+                ip = real_ip;
+            }
+            return Self::Ip(ip)
+        }
+
+        // every proxy appends the address it received the request from, so the hops left of the
+        // last untrusted one may have been made up by the client
+        for hop in forwarded.into_iter().rev() {
+            let Ok(hop) = hop.trim().parse() else { break };
+            ip = hop;
+            if !config.trusted_proxies.contains(&ip) {
+                break
+            }
+        }
+        Self::Ip(ip)
+    }
+}
+
+/// A token bucket of a client.
+#[derive(Debug)]
+struct Bucket {
+    /// The cost units left.
+    tokens: f64,
+    /// When the tokens were last refilled.
+    updated: Instant,
+}
+
+/// Enforces the quotas of a [`RateLimitConfig`].
+///
+/// The buckets of at most `MAX_TRACKED_CLIENTS` clients are kept, beyond that the bucket of the
+/// least recently seen client is dropped, which refills its quota.
+///
+/// Cheap to clone, all clones share the same buckets.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    inner: Arc<RateLimiterInner>,
+}
+
+#[derive(Debug)]
+struct RateLimiterInner {
+    config: RateLimitConfig,
+    /// The costs of exactly named methods.
+    exact_costs: HashMap<String, u64>,
+    /// The costs of methods by prefix, longest prefix first.
+    prefix_costs: Vec<(String, u64)>,
+    buckets: Mutex<LruMap<ClientId, Bucket, ByLength>>,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter with the given configuration.
+    ///
+    /// Returns an error if the configuration is invalid, see [`RateLimitConfig::validate`].
+    pub fn new(config: RateLimitConfig) -> Result<Self, RateLimitConfigError> {
+        config.validate()?;
+
+        let mut exact_costs = HashMap::new();
+        let mut prefix_costs = Vec::new();
+        for (method, cost) in &config.method_costs {
+            match method.strip_suffix('*') {
+                Some(prefix) => prefix_costs.push((prefix.to_string(), *cost)),
+                None => {
+                    exact_costs.insert(method.clone(), *cost);
+                }
+            }
+        }
+        prefix_costs.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+        Ok(Self {
+            inner: Arc::new(RateLimiterInner {
+                config,
+                exact_costs,
+                prefix_costs,
+                buckets: Mutex::new(LruMap::new(ByLength::new(MAX_TRACKED_CLIENTS))),
+            }),
+        })
+    }
+
+    /// Returns the configuration of the rate limiter.
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.inner.config
+    }
+
+    /// Returns the HTTP middleware layer that identifies the clients of requests.
+    pub fn client_layer(&self) -> RateLimitClientLayer {
+        RateLimitClientLayer { limiter: self.clone() }
+    }
+
+    /// Returns the RPC middleware layer that enforces the quotas.
+    pub fn rpc_layer(&self) -> RateLimitLayer {
+        RateLimitLayer { limiter: Some(self.clone()) }
+    }
+
+    /// Returns the cost of a call of the given method.
+    pub fn cost(&self, method: &str) -> u64 {
+        let inner = &self.inner;
+        inner
+            .exact_costs
+            .get(method)
+            .or_else(|| {
+                inner
+                    .prefix_costs
+                    .iter()
+                    .find(|(prefix, _)| method.starts_with(prefix.as_str()))
+                    .map(|(_, cost)| cost)
+            })
+            .copied()
+            .unwrap_or(inner.config.default_cost)
+    }
+
+    /// Returns the quota of the client, or `None` if the client is not limited.
+    fn quota(&self, client: &ClientId) -> Option<u64> {
+        match client {
+            ClientId::ApiKey(key) => self.inner.config.api_keys.get(key).copied(),
+            ClientId::Ip(_) | ClientId::Unknown => self.inner.config.ip_quota,
+        }
+    }
+
+    /// Charges the client for a call of the given method.
+    ///
+    /// Returns `false` if the client exceeded its quota, in which case nothing is charged.
+    pub fn try_acquire(&self, client: &ClientId, method: &str) -> bool {
+        let Some(quota) = self.quota(client) else { return true };
+        let cost = self.cost(method) as f64;
+        let quota = quota as f64;
+        let period = self.inner.config.period;
+        let rate = quota / period.as_secs_f64().max(f64::EPSILON);
+        let now = Instant::now();
+
+        let mut buckets = self.inner.buckets.lock();
+        let Some(bucket) =
+            buckets.get_or_insert(client.clone(), || Bucket { tokens: quota, updated: now })
+        else {
+            return true
+        };
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(quota);
+        bucket.updated = now;
+
+        if bucket.tokens < cost {
+            debug!(target: "rpc::rate_limit", ?client, %method, "Rate limit exceeded");
+            return false
+        }
+        bucket.tokens -= cost;
+        true
+    }
+}
+
+/// HTTP middleware layer that identifies the client of each HTTP request and WS connection, so
+/// that the [`RateLimitLayer`] can charge its calls.
+///
+/// The client is identified by the API key header configured in [`RateLimitConfig`], falling back
+/// to the remote address of the connection, which the server must insert into the extensions of
+/// each request as a [`SocketAddr`]. If the remote address is a trusted proxy, the client address
+/// is taken from the forwarding headers instead.
+#[derive(Debug, Clone)]
+pub struct RateLimitClientLayer {
+    limiter: RateLimiter,
+}
+
+impl<S> Layer<S> for RateLimitClientLayer {
+    type Service = RateLimitClientService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitClientService { limiter: self.limiter.clone(), inner }
+    }
+}
+
+/// The HTTP middleware service of the [`RateLimitClientLayer`].
+#[derive(Debug, Clone)]
+pub struct RateLimitClientService<S> {
+    limiter: RateLimiter,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RateLimitClientService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<ClientId, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        let remote_ip = req.extensions().get::<SocketAddr>().map(SocketAddr::ip);
+        let client = ClientId::from_request(remote_ip, req.headers(), self.limiter.config());
+        // Set while the inner service is called and while its future is polled, so that the
+        // client is known wherever the server creates the RPC middleware of the request or the WS
+        // connection.
+        let future = CLIENT.sync_scope(client.clone(), || self.inner.call(req));
+        CLIENT.scope(client, future)
+    }
+}
+
+/// RPC middleware layer that rejects calls of clients that exceeded their quota.
+///
+/// The client of a call is the one identified by the [`RateLimitClientLayer`] for the HTTP request
+/// or WS connection of the call, or [`ClientId::Unknown`] without it.
+///
+/// The default layer does not limit any calls.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitLayer {
+    limiter: Option<RateLimiter>,
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let client = CLIENT.try_with(Clone::clone).unwrap_or(ClientId::Unknown);
+        RateLimitService { limiter: self.limiter.clone(), client, inner }
+    }
+}
+
+/// The RPC middleware service of the [`RateLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    limiter: Option<RateLimiter>,
+    client: ClientId,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RateLimitService<S>
+where
+    S: RpcServiceT<'a>,
+{
+    type Future = Either<S::Future, Ready<MethodResponse>>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        if let Some(limiter) = &self.limiter {
+            if !limiter.try_acquire(&self.client, req.method_name()) {
+                return Either::Right(ready(MethodResponse::error(
+                    req.id,
+                    ErrorObject::owned(RATE_LIMIT_EXCEEDED_CODE, "rate limit exceeded", None::<()>),
+                )))
+            }
+        }
+        Either::Left(self.inner.call(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    #[test]
+    fn method_costs() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            method_costs: BTreeMap::from([
+                ("debug_*".to_string(), 10),
+                ("debug_traceBlock*".to_string(), 100),
+                ("debug_traceBlockByHash".to_string(), 50),
+            ]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(limiter.cost("eth_blockNumber"), 1);
+        assert_eq!(limiter.cost("debug_traceTransaction"), 10);
+        assert_eq!(limiter.cost("debug_traceBlockByNumber"), 100);
+        assert_eq!(limiter.cost("debug_traceBlockByHash"), 50);
+    }
+
+    #[test]
+    fn quotas() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            period: Duration::from_secs(3600),
+            ip_quota: Some(30),
+            api_keys: BTreeMap::from([("tenant".to_string(), 100)]),
+            method_costs: BTreeMap::from([("eth_getLogs".to_string(), 20)]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let ip = ClientId::Ip([127, 0, 0, 1].into());
+        assert!(limiter.try_acquire(&ip, "eth_getLogs"));
+        assert!(!limiter.try_acquire(&ip, "eth_getLogs"));
+        assert!(limiter.try_acquire(&ip, "eth_blockNumber"));
+        assert!(limiter.try_acquire(&ClientId::Ip([127, 0, 0, 2].into()), "eth_getLogs"));
+
+        let key = ClientId::ApiKey("tenant".to_string());
+        for _ in 0..5 {
+            assert!(limiter.try_acquire(&key, "eth_getLogs"));
+        }
+        assert!(!limiter.try_acquire(&key, "eth_getLogs"));
+    }
+
+    #[test]
+    fn cost_exceeds_quota() {
+        let config = RateLimitConfig {
+            ip_quota: Some(50),
+            api_keys: BTreeMap::from([("tenant".to_string(), 10)]),
+            method_costs: BTreeMap::from([("eth_getLogs".to_string(), 20)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            RateLimiter::new(config.clone()).unwrap_err(),
+            RateLimitConfigError::CostExceedsQuota {
+                method: "eth_getLogs".to_string(),
+                cost: 20,
+                quota: 10
+            }
+        );
+
+        let config = RateLimitConfig { api_keys: BTreeMap::new(), ..config };
+        assert!(RateLimiter::new(config.clone()).is_ok());
+        assert!(RateLimiter::new(RateLimitConfig { default_cost: 51, ..config }).is_err());
+
+        // the default costs of heavy methods are validated as well
+        assert!(
+            RateLimiter::new(RateLimitConfig { ip_quota: Some(50), ..Default::default() }).is_err()
+        );
+        assert!(RateLimiter::new(RateLimitConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn client_from_request() {
+        let config = RateLimitConfig {
+            api_keys: BTreeMap::from([("tenant".to_string(), 100)]),
+            trusted_proxies: vec![[10, 0, 0, 1].into(), [10, 0, 0, 2].into()],
+            ..Default::default()
+        };
+        let client = IpAddr::from([192, 168, 0, 1]);
+        let proxy = IpAddr::from([10, 0, 0, 1]);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(ClientId::from_request(None, &headers, &config), ClientId::Unknown);
+        assert_eq!(ClientId::from_request(Some(client), &headers, &config), ClientId::Ip(client));
+        assert_eq!(ClientId::from_request(Some(proxy), &headers, &config), ClientId::Ip(proxy));
+
+        // forwarding headers of untrusted peers are ignored
+        headers.insert("x-real-ip", HeaderValue::from_static("172.16.0.1"));
+        assert_eq!(ClientId::from_request(Some(client), &headers, &config), ClientId::Ip(client));
+        assert_eq!(
+            ClientId::from_request(Some(proxy), &headers, &config),
+            ClientId::Ip([172, 16, 0, 1].into())
+        );
+
+        // the rightmost untrusted hop is the client, hops left of it may be spoofed
+        headers.insert("x-forwarded-for", HeaderValue::from_static("1.1.1.1, 192.168.0.1"));
+        headers.append("x-forwarded-for", HeaderValue::from_static("10.0.0.2"));
+        assert_eq!(ClientId::from_request(Some(client), &headers, &config), ClientId::Ip(client));
+        assert_eq!(ClientId::from_request(Some(proxy), &headers, &config), ClientId::Ip(client));
+
+        // if all hops are trusted, the leftmost one is the client
+        headers.insert("x-forwarded-for", HeaderValue::from_static("10.0.0.2, 10.0.0.1"));
+        assert_eq!(
+            ClientId::from_request(Some(proxy), &headers, &config),
+            ClientId::Ip([10, 0, 0, 2].into())
+        );
+
+        headers.insert("x-api-key", HeaderValue::from_static("unknown"));
+        assert_eq!(ClientId::from_request(Some(client), &headers, &config), ClientId::Ip(client));
+
+        headers.insert("x-api-key", HeaderValue::from_static("tenant"));
+        assert_eq!(
+            ClientId::from_request(Some(client), &headers, &config),
+            ClientId::ApiKey("tenant".to_string())
+        );
+    }
+}
@@ -15,6 +15,17 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum number of blocks for which historical `eth_getProof` requests are served.
+/// Proofs are only generated for the latest block by default.
+pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
+
+/// Maximum eth historical proof window. Equivalent to roughly four weeks of data on a 12s block
+/// time chain.
+pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 12;
+
+/// The default maximum number of invalid blocks kept for `debug_getBadBlocks`.
+pub const DEFAULT_MAX_BAD_BLOCKS: usize = 32;
+
 /// The default maximum number tracing requests we're allowing concurrently.
 /// Tracing is mostly CPU bound so we're limiting the number of concurrent requests to something
 /// lower that the number of cores, in order to minimize the impact on the rest of the system.
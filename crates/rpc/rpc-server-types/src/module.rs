@@ -260,6 +260,11 @@ pub enum RethRpcModule {
     /// This is separate from [`RethRpcModule::Eth`] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// `flashbots_` module
+    Flashbots,
+    /// `mev_` module, including the bundle methods of the `eth_` namespace except for
+    /// `eth_callBundle`
+    Mev,
 }
 
 // === impl RethRpcModule ===
@@ -309,6 +314,8 @@ impl FromStr for RethRpcModule {
             "reth" => Self::Reth,
             "ots" => Self::Ots,
             "eth-call-bundle" | "eth_callBundle" => Self::EthCallBundle,
+            "flashbots" => Self::Flashbots,
+            "mev" => Self::Mev,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -0,0 +1,17 @@
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types::RichBlock;
+use serde::{Deserialize, Serialize};
+
+/// A block that was rejected as invalid, as returned by `debug_getBadBlocks`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The block in its JSON representation.
+    pub block: RichBlock,
+    /// The RLP encoded block.
+    pub rlp: Bytes,
+    /// The reason why the block was rejected.
+    pub validation_error: String,
+}
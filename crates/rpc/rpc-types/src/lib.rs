@@ -10,10 +10,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+mod debug;
 mod eth;
 mod mev;
 mod net;
 mod peer;
+mod reth;
 mod rpc;
 
 // re-export for convenience
@@ -43,7 +45,9 @@ pub use eth::{
     transaction::{self, TransactionRequest, TypedTransactionRequest},
 };
 
+pub use debug::*;
 pub use mev::*;
 pub use net::*;
 pub use peer::*;
+pub use reth::*;
 pub use rpc::*;
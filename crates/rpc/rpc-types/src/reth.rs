@@ -0,0 +1,81 @@
+//! Types for the `reth_` namespace.
+
+use alloy_primitives::{TxHash, B256};
+use alloy_rpc_types::BlobTransactionSidecar;
+use serde::{Deserialize, Serialize};
+
+/// Selects a page of the transactions an address appeared in, as requested by
+/// `reth_getAddressTransactions`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsRequest {
+    /// The cursor returned with the previous page. The first page is returned if not set.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::num::u64_opt_via_ruint",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cursor: Option<u64>,
+    /// The maximum number of transactions on the page.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::num::u64_opt_via_ruint",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub page_size: Option<u64>,
+    /// Whether to return the most recent transactions first.
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+/// A transaction an address appeared in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransaction {
+    /// The hash of the transaction.
+    pub transaction_hash: TxHash,
+    /// The hash of the block that contains the transaction.
+    pub block_hash: B256,
+    /// The number of the block that contains the transaction.
+    #[serde(with = "alloy_rpc_types::serde_helpers::num::u64_via_ruint")]
+    pub block_number: u64,
+    /// The index of the transaction in the block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::num::u64_via_ruint")]
+    pub transaction_index: u64,
+}
+
+/// A page of the transactions an address appeared in, as returned by
+/// `reth_getAddressTransactions`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsPage {
+    /// The transactions on the page, in the requested order.
+    pub transactions: Vec<AddressTransaction>,
+    /// The cursor to request the next page with, or `None` if this is the last page.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::num::u64_opt_via_ruint",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub next_cursor: Option<u64>,
+}
+
+/// An archived blob sidecar of a canonical transaction, as returned by `reth_getBlobSidecars`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedBlobSidecar {
+    /// The hash of the transaction.
+    pub transaction_hash: TxHash,
+    /// The hash of the block that contains the transaction.
+    pub block_hash: B256,
+    /// The number of the block that contains the transaction.
+    #[serde(with = "alloy_rpc_types::serde_helpers::num::u64_via_ruint")]
+    pub block_number: u64,
+    /// The index of the transaction in the block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::num::u64_via_ruint")]
+    pub transaction_index: u64,
+    /// The versioned hashes of the blobs of the transaction.
+    pub blob_versioned_hashes: Vec<B256>,
+    /// The blobs, commitments and proofs of the transaction.
+    pub sidecar: BlobTransactionSidecar,
+}
@@ -0,0 +1,538 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_auto_seal_consensus::{AccountChanges, MiningHandle};
+use reth_primitives::{Address, Bytes, SealedHeader, B256, U256};
+use reth_provider::{
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider, TransactionVariant,
+};
+use reth_rpc_api::{AnvilApiServer, GanacheApiServer, HardhatApiServer};
+use reth_rpc_types::{
+    anvil::{Forking, Metadata, MineOptions, NodeInfo},
+    Block, BlockTransactionsKind,
+};
+use reth_rpc_types_compat::block::from_block;
+use reth_transaction_pool::TransactionPool;
+use std::{collections::BTreeMap, time::Duration};
+use tracing::trace;
+
+use crate::{
+    eth::{error::EthApiError, ImpersonatedAccounts},
+    result::{internal_rpc_err, invalid_params_rpc_err, ToRpcResult},
+};
+
+/// The maximum number of blocks that can be mined with a single request.
+const MAX_MINE_BLOCKS: u64 = 10_000;
+
+/// `anvil`, `hardhat` and `evm` (ganache) API implementation for dev chains.
+///
+/// Blocks are sealed by the auto-seal mining task of the dev node, which this API controls through
+/// its [`MiningHandle`]. Methods that depend on forking, tracing or other features of anvil that
+/// reth does not have return an error.
+#[derive(Debug, Clone)]
+pub struct AnvilApi<Provider, Pool> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// An interface to interact with the pool
+    pool: Pool,
+    /// The handle to the mining task of the dev chain.
+    mining: MiningHandle,
+    /// Accounts that transactions can be sent from without their private key.
+    impersonated_accounts: ImpersonatedAccounts,
+    /// The identifier of this instance, reported by `anvil_metadata`.
+    instance_id: B256,
+}
+
+impl<Provider, Pool> AnvilApi<Provider, Pool> {
+    /// Creates a new instance of `AnvilApi`.
+    pub fn new(provider: Provider, pool: Pool, mining: MiningHandle) -> Self {
+        Self {
+            provider,
+            pool,
+            mining,
+            impersonated_accounts: Default::default(),
+            instance_id: B256::random(),
+        }
+    }
+
+    /// Returns the accounts impersonated through this API.
+    ///
+    /// These have to be registered with the `eth` API, so it can send transactions on their
+    /// behalf.
+    pub fn impersonated_accounts(&self) -> &ImpersonatedAccounts {
+        &self.impersonated_accounts
+    }
+}
+
+impl<Provider, Pool> AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Applies the changes to the account in the latest state, sealing them in a new block.
+    async fn set_account(&self, address: Address, changes: AccountChanges) -> RpcResult<()> {
+        self.mining.set_account(address, changes).await.to_rpc_result()?;
+        Ok(())
+    }
+
+    /// Seals the blocks requested by the [`MineOptions`].
+    async fn mine_with_options(&self, opts: Option<MineOptions>) -> RpcResult<Vec<SealedHeader>> {
+        let (timestamp, blocks) = match opts {
+            Some(MineOptions::Options { timestamp, blocks }) => (timestamp, blocks),
+            Some(MineOptions::Timestamp(timestamp)) => (timestamp, None),
+            None => (None, None),
+        };
+        let blocks = blocks_to_mine(blocks)?;
+        if let Some(timestamp) = timestamp {
+            self.mining.set_next_block_timestamp(timestamp).await;
+        }
+        self.mining.mine(blocks, None).await.to_rpc_result()
+    }
+
+    /// Returns the full blocks of the given sealed headers.
+    fn blocks(&self, headers: Vec<SealedHeader>) -> Result<Vec<Block>, EthApiError> {
+        let mut blocks = Vec::with_capacity(headers.len());
+        for header in headers {
+            let block = self
+                .provider
+                .block_with_senders(header.hash().into(), TransactionVariant::WithHash)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            let total_difficulty = self
+                .provider
+                .header_td_by_number(header.number)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            blocks.push(from_block(
+                block,
+                total_difficulty,
+                BlockTransactionsKind::Full,
+                Some(header.hash()),
+            )?);
+        }
+        Ok(blocks)
+    }
+
+    /// Removes the transaction from the pool and returns `true` if it was in the pool.
+    fn drop_transaction(&self, tx_hash: B256) -> bool {
+        !self.pool.remove_transactions(vec![tx_hash]).is_empty()
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> AnvilApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_impersonateAccount");
+        self.impersonated_accounts.impersonate(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_stopImpersonatingAccount");
+        self.impersonated_accounts.stop_impersonating(&address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", enabled, "Serving anvil_autoImpersonateAccount");
+        self.impersonated_accounts.set_impersonate_all(enabled);
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_getAutomine");
+        self.mining.automine().await.to_rpc_result()
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?blocks, ?interval, "Serving anvil_mine");
+        let blocks = blocks_to_mine(blocks.map(|blocks| blocks.saturating_to()))?;
+        let interval = interval.map(|interval| interval.saturating_to());
+        self.mining.mine(blocks, interval).await.to_rpc_result()?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", enabled, "Serving anvil_setAutomine");
+        self.mining.set_automine(enabled).to_rpc_result()
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", interval, "Serving anvil_setIntervalMining");
+        // an interval of 0 disables interval mining
+        let interval = (interval > 0).then(|| Duration::from_secs(interval));
+        self.mining.set_interval_mining(interval).to_rpc_result()
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        trace!(target: "rpc::anvil", ?tx_hash, "Serving anvil_dropTransaction");
+        Ok(self.drop_transaction(tx_hash).then_some(tx_hash))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, ?balance, "Serving anvil_setBalance");
+        self.set_account(address, AccountChanges { balance: Some(balance), ..Default::default() })
+            .await
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_setCode");
+        self.set_account(address, AccountChanges { code: Some(code), ..Default::default() }).await
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, ?nonce, "Serving anvil_setNonce");
+        self.set_account(
+            address,
+            AccountChanges { nonce: Some(nonce.saturating_to()), ..Default::default() },
+        )
+        .await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?address, ?slot, ?value, "Serving anvil_setStorageAt");
+        let storage =
+            BTreeMap::from([(B256::new(slot.to_be_bytes()), U256::from_be_bytes(value.0))]);
+        self.set_account(address, AccountChanges { storage, ..Default::default() }).await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_setCoinbase");
+        self.mining.set_coinbase(address).await;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?base_fee, "Serving anvil_setNextBlockBaseFeePerGas");
+        self.mining.set_next_block_base_fee(base_fee.saturating_to()).await;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        trace!(target: "rpc::anvil", timestamp, "Serving anvil_setTime");
+        Ok(self.mining.set_time(timestamp).await.unsigned_abs())
+    }
+
+    /// Handler for `anvil_dumpState`
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_loadState`
+    async fn anvil_load_state(&self, _state: Bytes) -> RpcResult<bool> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        trace!(target: "rpc::anvil", "Serving anvil_metadata");
+        let latest = self
+            .provider
+            .latest_header()
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("latest block not found"))?;
+        let snapshots = self
+            .mining
+            .snapshots()
+            .await
+            .into_iter()
+            .map(|(id, block)| (U256::from(id), (block.number, block.hash)))
+            .collect();
+
+        Ok(Metadata {
+            client_version: format!("reth/v{}", env!("CARGO_PKG_VERSION")),
+            chain_id: self.provider.chain_spec().chain.id(),
+            instance_id: self.instance_id,
+            latest_block_number: latest.number,
+            latest_block_hash: latest.hash(),
+            forked_network: None,
+            snapshots,
+        })
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        trace!(target: "rpc::anvil", "Serving anvil_snapshot");
+        self.mining.snapshot().await.map(U256::from).to_rpc_result()
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?id, "Serving anvil_revert");
+        let Ok(id) = id.try_into() else { return Ok(false) };
+        self.mining.revert(id).await.to_rpc_result()
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        trace!(target: "rpc::anvil", ?seconds, "Serving anvil_increaseTime");
+        Ok(self.mining.increase_time(seconds.saturating_to()).await)
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", seconds, "Serving anvil_setNextBlockTimestamp");
+        self.mining.set_next_block_timestamp(seconds).await;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, gas_limit: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?gas_limit, "Serving anvil_setBlockGasLimit");
+        self.mining.set_block_gas_limit(gas_limit.saturating_to()).await;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", seconds, "Serving anvil_setBlockTimestampInterval");
+        self.mining.set_block_timestamp_interval(Some(seconds)).await;
+        Ok(())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", "Serving anvil_removeBlockTimestampInterval");
+        Ok(self.mining.set_block_timestamp_interval(None).await)
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        trace!(target: "rpc::anvil", ?opts, "Serving anvil_mine_detailed");
+        let headers = self.mine_with_options(opts).await?;
+        Ok(self.blocks(headers)?)
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(internal_rpc_err("unimplemented"))
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_removePoolTransactions");
+        let hashes = self
+            .pool
+            .get_transactions_by_sender(address)
+            .into_iter()
+            .map(|tx| *tx.hash())
+            .collect();
+        self.pool.remove_transactions(hashes);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> HardhatApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `hardhat_dropTransaction`
+    async fn hardhat_drop_transaction(&self, tx_hash: B256) -> RpcResult<bool> {
+        trace!(target: "rpc::hardhat", ?tx_hash, "Serving hardhat_dropTransaction");
+        Ok(self.drop_transaction(tx_hash))
+    }
+
+    /// Handler for `hardhat_impersonateAccount`
+    async fn hardhat_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        AnvilApiServer::anvil_impersonate_account(self, address).await
+    }
+
+    /// Handler for `hardhat_getAutomine`
+    async fn hardhat_get_automine(&self) -> RpcResult<bool> {
+        AnvilApiServer::anvil_get_automine(self).await
+    }
+
+    /// Handler for `hardhat_metadata`
+    async fn hardhat_metadata(&self) -> RpcResult<Metadata> {
+        AnvilApiServer::anvil_metadata(self).await
+    }
+
+    /// Handler for `hardhat_mine`
+    async fn hardhat_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        AnvilApiServer::anvil_mine(self, blocks, interval).await
+    }
+
+    /// Handler for `hardhat_reset`
+    async fn hardhat_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        AnvilApiServer::anvil_reset(self, fork).await
+    }
+
+    /// Handler for `hardhat_setBalance`
+    async fn hardhat_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_balance(self, address, balance).await
+    }
+
+    /// Handler for `hardhat_setCode`
+    async fn hardhat_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_code(self, address, code).await
+    }
+
+    /// Handler for `hardhat_setCoinbase`
+    async fn hardhat_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_coinbase(self, address).await
+    }
+
+    /// Handler for `hardhat_setLoggingEnabled`
+    async fn hardhat_set_logging_enabled(&self, enabled: bool) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_logging_enabled(self, enabled).await
+    }
+
+    /// Handler for `hardhat_setMinGasPrice`
+    async fn hardhat_set_min_gas_price(&self, gas_price: U256) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_min_gas_price(self, gas_price).await
+    }
+
+    /// Handler for `hardhat_setNextBlockBaseFeePerGas`
+    async fn hardhat_set_next_block_base_fee_per_gas(
+        &self,
+        base_fee_per_gas: U256,
+    ) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_next_block_base_fee_per_gas(self, base_fee_per_gas).await
+    }
+
+    /// Handler for `hardhat_setPrevRandao`
+    async fn hardhat_set_prev_randao(&self, prev_randao: B256) -> RpcResult<()> {
+        trace!(target: "rpc::hardhat", ?prev_randao, "Serving hardhat_setPrevRandao");
+        self.mining.set_next_block_prev_randao(prev_randao).await;
+        Ok(())
+    }
+
+    /// Handler for `hardhat_setNonce`
+    async fn hardhat_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_nonce(self, address, nonce).await
+    }
+
+    /// Handler for `hardhat_setStorageAt`
+    async fn hardhat_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<()> {
+        AnvilApiServer::anvil_set_storage_at(self, address, slot, value).await?;
+        Ok(())
+    }
+
+    /// Handler for `hardhat_stopImpersonatingAccount`
+    async fn hardhat_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        AnvilApiServer::anvil_stop_impersonating_account(self, address).await
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> GanacheApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `evm_increaseTime`
+    async fn evm_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        AnvilApiServer::anvil_increase_time(self, seconds).await
+    }
+
+    /// Handler for `evm_mine`
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String> {
+        trace!(target: "rpc::evm", ?opts, "Serving evm_mine");
+        self.mine_with_options(opts).await?;
+        Ok("0x0".to_string())
+    }
+
+    /// Handler for `evm_revert`
+    async fn evm_revert(&self, snapshot_id: U256) -> RpcResult<bool> {
+        AnvilApiServer::anvil_revert(self, snapshot_id).await
+    }
+
+    /// Handler for `evm_setTime`
+    async fn evm_set_time(&self, timestamp: u64) -> RpcResult<bool> {
+        trace!(target: "rpc::evm", timestamp, "Serving evm_setTime");
+        self.mining.set_time(timestamp).await;
+        Ok(true)
+    }
+
+    /// Handler for `evm_snapshot`
+    async fn evm_snapshot(&self) -> RpcResult<U256> {
+        AnvilApiServer::anvil_snapshot(self).await
+    }
+}
+
+/// Returns the number of blocks to mine for a request, one if not given.
+///
+/// Fails if more than [`MAX_MINE_BLOCKS`] blocks are requested, since the blocks are sealed one
+/// by one.
+fn blocks_to_mine(blocks: Option<u64>) -> RpcResult<u64> {
+    let blocks = blocks.unwrap_or(1);
+    if blocks > MAX_MINE_BLOCKS {
+        return Err(invalid_params_rpc_err(format!(
+            "cannot mine {blocks} blocks, at most {MAX_MINE_BLOCKS} blocks can be mined at once"
+        )))
+    }
+    Ok(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_mined_blocks() {
+        assert_eq!(blocks_to_mine(None).unwrap(), 1);
+        assert_eq!(blocks_to_mine(Some(0)).unwrap(), 0);
+        assert_eq!(blocks_to_mine(Some(MAX_MINE_BLOCKS)).unwrap(), MAX_MINE_BLOCKS);
+        assert!(blocks_to_mine(Some(MAX_MINE_BLOCKS + 1)).is_err());
+        assert!(blocks_to_mine(Some(u64::MAX)).is_err());
+    }
+}
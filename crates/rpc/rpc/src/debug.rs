@@ -10,12 +10,18 @@ use crate::{
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use parking_lot::Mutex;
+use reth_evm::{
+    execute::{BlockExecutorProvider, Executor},
+    witness::{ExecutionWitness, RecordingDatabase},
+};
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumberOrTag, Bytes,
-    TransactionSignedEcRecovered, Withdrawals, B256, U256,
+    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumberOrTag, BlockWithSenders,
+    Bytes, SealedBlock, TransactionSignedEcRecovered, Withdrawals, B256, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
+    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProvider, StateProviderBox,
+    TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::DebugApiServer;
@@ -24,8 +30,9 @@ use reth_rpc_types::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
+    BadBlock, BlockError, BlockTransactionsKind, Bundle, StateContext, TransactionRequest,
 };
+use reth_rpc_types_compat::block::from_block;
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::CacheDB,
@@ -35,22 +42,36 @@ use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
 };
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
-pub struct DebugApi<Provider, Eth> {
-    inner: Arc<DebugApiInner<Provider, Eth>>,
+pub struct DebugApi<Provider, Eth, BlockExecutor> {
+    inner: Arc<DebugApiInner<Provider, Eth, BlockExecutor>>,
 }
 
 // === impl DebugApi ===
 
-impl<Provider, Eth> DebugApi<Provider, Eth> {
+impl<Provider, Eth, BlockExecutor> DebugApi<Provider, Eth, BlockExecutor> {
     /// Create a new instance of the [`DebugApi`]
-    pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        block_executor: BlockExecutor,
+        bad_blocks: BadBlockStore,
+        eth_proof_window: u64,
+    ) -> Self {
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            blocking_task_guard,
+            block_executor,
+            bad_blocks,
+            eth_proof_window,
+        });
         Self { inner }
     }
 
@@ -62,10 +83,11 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
 
 // === impl DebugApi ===
 
-impl<Provider, Eth> DebugApi<Provider, Eth>
+impl<Provider, Eth, BlockExecutor> DebugApi<Provider, Eth, BlockExecutor>
 where
     Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
     Eth: EthTransactions + 'static,
+    BlockExecutor: BlockExecutorProvider,
 {
     /// Acquires a permit to execute a tracing call.
     async fn acquire_trace_permit(&self) -> Result<OwnedSemaphorePermit, AcquireError> {
@@ -137,7 +159,16 @@ where
     ) -> EthResult<Vec<TraceResult>> {
         let block =
             Block::decode(&mut rlp_block.as_ref()).map_err(BlockError::RlpDecodeRawBlock)?;
+        self.trace_unsealed_block(block, opts).await
+    }
 
+    /// Replays the given block on top of its parent block and returns the trace of each
+    /// transaction.
+    async fn trace_unsealed_block(
+        &self,
+        block: Block,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
         let (cfg, block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
         // we trace on top the block's parent block
         let parent = block.parent_hash;
@@ -167,6 +198,109 @@ where
         self.trace_block_with(parent.into(), transactions, cfg, block_env, opts).await
     }
 
+    /// Re-executes the block on top of its parent state and returns the [`ExecutionWitness`] with
+    /// all state accessed during execution, including the trie nodes proving it against the
+    /// parent state root and the ancestor headers proving the accessed block hashes.
+    ///
+    /// Like `eth_getProof`, the parent block must be within the configured proof window.
+    pub async fn debug_execution_witness(
+        &self,
+        block_id: BlockNumberOrTag,
+    ) -> EthResult<ExecutionWitness> {
+        let block = self
+            .inner
+            .eth_api
+            .block_by_id_with_senders(block_id.into())
+            .await?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?
+            .unseal();
+        let total_difficulty = self
+            .inner
+            .provider
+            .header_td_by_number(block.number)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+
+        // Check whether the distance to the parent block exceeds the maximum configured window.
+        let parent_number = block.number.saturating_sub(1);
+        let best_number = self.inner.provider.best_block_number()?;
+        if best_number.saturating_sub(parent_number) > self.inner.eth_proof_window {
+            return Err(EthApiError::ExceedsMaxProofWindow)
+        }
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let mut db = RecordingDatabase::new(StateProviderDatabase::new(&state));
+                this.inner
+                    .block_executor
+                    .executor(&mut db)
+                    .execute((&block, total_difficulty).into())
+                    .map_err(|err| EthApiError::Internal(err.into()))?;
+
+                let multiproof = state.multiproof(&db.proof_targets())?;
+                let headers =
+                    this.inner.provider.headers_range(db.ancestor_range(parent_number))?;
+                Ok(db.into_witness(multiproof, headers))
+            })
+            .await
+    }
+
+    /// Returns all blocks that were recently rejected as invalid.
+    pub fn bad_blocks(&self) -> EthResult<Vec<BadBlock>> {
+        self.inner
+            .bad_blocks
+            .blocks()
+            .into_iter()
+            .map(|(block, validation_error)| {
+                let hash = block.hash();
+                let block = Arc::unwrap_or_clone(block).unseal();
+                let rlp = alloy_rlp::encode(&block).into();
+
+                // the total difficulty is only known if the parent is known
+                let total_difficulty = self
+                    .inner
+                    .provider
+                    .header_td(&block.parent_hash)?
+                    .map(|td| td + block.difficulty)
+                    .unwrap_or_default();
+
+                // the block may have been rejected because of an invalid signature, in which case
+                // only the transaction hashes can be returned
+                let block = match block.clone().with_recovered_senders() {
+                    Some(block) => from_block(
+                        block,
+                        total_difficulty,
+                        BlockTransactionsKind::Full,
+                        Some(hash),
+                    )?,
+                    None => from_block(
+                        BlockWithSenders { block, senders: Vec::new() },
+                        total_difficulty,
+                        BlockTransactionsKind::Hashes,
+                        Some(hash),
+                    )?,
+                };
+
+                Ok(BadBlock { hash, block: block.into(), rlp, validation_error })
+            })
+            .collect()
+    }
+
+    /// Replays a block that was rejected as invalid and returns the trace of each transaction.
+    ///
+    /// The block is executed on top of the state of its parent block, which must be present.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let (block, _) = self.inner.bad_blocks.get(&block_hash).ok_or_else(|| {
+            EthApiError::InvalidParams(format!("bad block {block_hash} not found"))
+        })?;
+        self.trace_unsealed_block(Arc::unwrap_or_clone(block).unseal(), opts).await
+    }
+
     /// Replays a block and returns the trace of each transaction.
     pub async fn debug_trace_block(
         &self,
@@ -611,10 +745,11 @@ where
 }
 
 #[async_trait]
-impl<Provider, Eth> DebugApiServer for DebugApi<Provider, Eth>
+impl<Provider, Eth, BlockExecutor> DebugApiServer for DebugApi<Provider, Eth, BlockExecutor>
 where
     Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
     Eth: EthApiSpec + 'static,
+    BlockExecutor: BlockExecutorProvider,
 {
     /// Handler for `debug_getRawHeader`
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes> {
@@ -690,8 +825,8 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>> {
+        Ok(Self::bad_blocks(self)?)
     }
 
     /// Handler for `debug_traceChain`
@@ -764,6 +899,15 @@ where
         Ok(Self::debug_trace_call_many(self, bundles, state_context, opts).await?)
     }
 
+    /// Handler for `debug_executionWitness`
+    async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(Self::debug_execution_witness(self, block).await?)
+    }
+
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
         Ok(())
     }
@@ -947,12 +1091,14 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(Self::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default()).await?)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -976,23 +1122,114 @@ where
     }
 }
 
-impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
+impl<Provider, Eth, BlockExecutor> std::fmt::Debug for DebugApi<Provider, Eth, BlockExecutor> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider, Eth> Clone for DebugApi<Provider, Eth> {
+impl<Provider, Eth, BlockExecutor> Clone for DebugApi<Provider, Eth, BlockExecutor> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct DebugApiInner<Provider, Eth> {
+struct DebugApiInner<Provider, Eth, BlockExecutor> {
     /// The provider that can interact with the chain.
     provider: Provider,
     /// The implementation of `eth` API
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// block executor for re-executing blocks
+    block_executor: BlockExecutor,
+    /// recently rejected blocks
+    bad_blocks: BadBlockStore,
+    /// Maximum number of blocks back from the tip to create execution witnesses for
+    eth_proof_window: u64,
+}
+
+/// A bounded store of the most recent blocks that were rejected as invalid, together with their
+/// validation errors.
+///
+/// This is shared between the consensus engine, which reports invalid blocks, and the `debug`
+/// namespace, which serves them via `debug_getBadBlocks` and `debug_traceBadBlock`.
+#[derive(Debug, Clone)]
+pub struct BadBlockStore {
+    inner: Arc<BadBlockStoreInner>,
+}
+
+// === impl BadBlockStore ===
+
+impl BadBlockStore {
+    /// Creates a new store that keeps at most `max_blocks` blocks.
+    pub fn new(max_blocks: usize) -> Self {
+        Self {
+            inner: Arc::new(BadBlockStoreInner {
+                blocks: Mutex::new(VecDeque::with_capacity(max_blocks)),
+                max_blocks,
+            }),
+        }
+    }
+
+    /// Inserts a rejected block, evicting the oldest block if the store is full.
+    ///
+    /// Blocks that are already present are ignored.
+    pub fn insert(&self, block: Arc<SealedBlock>, validation_error: String) {
+        let mut blocks = self.inner.blocks.lock();
+        if self.inner.max_blocks == 0 || blocks.iter().any(|(b, _)| b.hash() == block.hash()) {
+            return
+        }
+        if blocks.len() == self.inner.max_blocks {
+            blocks.pop_front();
+        }
+        blocks.push_back((block, validation_error));
+    }
+
+    /// Returns the rejected block with the given hash and its validation error, if present.
+    pub fn get(&self, hash: &B256) -> Option<(Arc<SealedBlock>, String)> {
+        self.inner.blocks.lock().iter().find(|(block, _)| block.hash() == *hash).cloned()
+    }
+
+    /// Returns all rejected blocks and their validation errors, oldest first.
+    pub fn blocks(&self) -> Vec<(Arc<SealedBlock>, String)> {
+        self.inner.blocks.lock().iter().cloned().collect()
+    }
+}
+
+#[derive(Debug)]
+struct BadBlockStoreInner {
+    /// The rejected blocks, oldest first.
+    blocks: Mutex<VecDeque<(Arc<SealedBlock>, String)>>,
+    /// The maximum number of blocks to keep.
+    max_blocks: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+
+    fn block(number: u64) -> Arc<SealedBlock> {
+        let header = Header { number, ..Default::default() }.seal_slow();
+        Arc::new(SealedBlock { header, ..Default::default() })
+    }
+
+    #[test]
+    fn bad_block_store_is_bounded() {
+        let store = BadBlockStore::new(2);
+        let (first, second, third) = (block(1), block(2), block(3));
+
+        store.insert(first.clone(), "first".to_string());
+        store.insert(second.clone(), "second".to_string());
+        // duplicates are ignored
+        store.insert(first.clone(), "duplicate".to_string());
+        assert_eq!(store.get(&first.hash()).unwrap().1, "first");
+
+        // the oldest block is evicted
+        store.insert(third.clone(), "third".to_string());
+        assert!(store.get(&first.hash()).is_none());
+        let hashes = store.blocks().into_iter().map(|(block, _)| block.hash()).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![second.hash(), third.hash()]);
+    }
 }
@@ -69,9 +69,18 @@ where
             block_and_receipts = self
                 .provider()
                 .pending_block_and_receipts()?
-                .map(|(sb, receipts)| (sb, Arc::new(receipts)));
+                .map(|(sb, receipts)| {
+                    let block = sb
+                        .try_seal_with_senders()
+                        .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+                    Ok::<_, EthApiError>((block, Arc::new(receipts)))
+                })
+                .transpose()?;
         } else if let Some(block_hash) = self.provider().block_hash_for_id(block_id)? {
-            block_and_receipts = self.cache().get_block_and_receipts(block_hash).await?;
+            // use the stored senders, since the senders of impersonated transactions on dev
+            // chains can't be recovered from their signatures
+            block_and_receipts =
+                self.cache().get_block_with_senders_and_receipts(block_hash).await?;
         }
 
         if let Some((block, receipts)) = block_and_receipts {
@@ -84,13 +93,12 @@ where
 
             #[cfg(feature = "optimism")]
             let (block_timestamp, l1_block_info) = {
-                let body = reth_evm_optimism::extract_l1_info(&block);
+                let body = reth_evm_optimism::extract_l1_info(&block.block);
                 (block.timestamp, body.ok())
             };
 
             let receipts = block
-                .body
-                .into_iter()
+                .into_transactions_ecrecovered()
                 .zip(receipts.iter())
                 .enumerate()
                 .map(|(idx, (tx, receipt))| {
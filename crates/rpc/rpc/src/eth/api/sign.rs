@@ -3,7 +3,7 @@
 use crate::{
     eth::{
         error::{EthResult, SignError},
-        signer::{DevSigner, EthSigner},
+        signer::{DevSigner, EthSigner, ImpersonatedAccounts, ImpersonationSigner},
     },
     EthApi,
 };
@@ -44,6 +44,12 @@ impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConf
     /// Used in DEV mode.
     pub fn with_dev_accounts(&self) {
         let mut signers = self.inner.signers.write();
-        *signers = DevSigner::random_signers(20);
+        signers.extend(DevSigner::random_signers(20));
+    }
+
+    /// Allows sending transactions from the given impersonated accounts.
+    /// Used in DEV mode.
+    pub fn with_impersonated_accounts(&self, accounts: ImpersonatedAccounts) {
+        self.inner.signers.write().push(Box::new(ImpersonationSigner::new(accounts)));
     }
 }
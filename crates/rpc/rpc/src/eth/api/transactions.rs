@@ -747,12 +747,7 @@ where
                 match this.provider().transaction_by_hash_with_meta(hash)? {
                     None => Ok(None),
                     Some((tx, meta)) => {
-                        // Note: we assume this transaction is valid, because it's mined (or part of
-                        // pending block) and already. We don't need to
-                        // check for pre EIP-2 because this transaction could be pre-EIP-2.
-                        let transaction = tx
-                            .into_ecrecovered_unchecked()
-                            .ok_or(EthApiError::InvalidTransactionSignature)?;
+                        let transaction = this.recover_mined_transaction(tx)?;
 
                         let tx = TransactionSource::Block {
                             transaction,
@@ -834,7 +829,7 @@ where
                     None => return Ok(None),
                 };
 
-                Ok(Some((tx, meta, receipt)))
+                Ok(Some((this.recover_mined_transaction(tx)?, meta, receipt)))
             })
             .await?;
 
@@ -1045,8 +1040,9 @@ where
 
         let signed_tx = self.sign_request(&from, transaction)?;
 
-        let recovered =
-            signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+        // the transaction was signed for `from`, transactions of impersonated accounts can't be
+        // recovered from their signature
+        let recovered = signed_tx.with_signer(from);
 
         let pool_transaction = match recovered.try_into() {
             Ok(converted) => <Pool::Transaction>::from_recovered_pooled_transaction(converted),
@@ -1503,13 +1499,35 @@ impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConf
 where
     Provider: BlockReaderIdExt + ChainSpecProvider,
 {
+    /// Recovers the sender of a mined transaction.
+    ///
+    /// Falls back to the sender stored in the database if the signature can't be recovered, which
+    /// is the case for transactions of impersonated accounts on dev chains.
+    pub(crate) fn recover_mined_transaction(
+        &self,
+        tx: TransactionSigned,
+    ) -> EthResult<TransactionSignedEcRecovered> {
+        // Note: we assume this transaction is valid, because it's mined (or part of pending block)
+        // and already. We don't need to check for pre EIP-2 because this transaction could be
+        // pre-EIP-2.
+        if let Some(signer) = tx.recover_signer_unchecked() {
+            return Ok(tx.with_signer(signer))
+        }
+
+        let sender = match self.provider().transaction_id(tx.hash)? {
+            Some(id) => self.provider().transaction_sender(id)?,
+            None => None,
+        };
+        sender.map(|sender| tx.with_signer(sender)).ok_or(EthApiError::InvalidTransactionSignature)
+    }
+
     /// Helper function for `eth_getTransactionReceipt`
     ///
     /// Returns the receipt
     #[cfg(not(feature = "optimism"))]
     pub(crate) async fn build_transaction_receipt(
         &self,
-        tx: TransactionSigned,
+        tx: TransactionSignedEcRecovered,
         meta: TransactionMeta,
         receipt: Receipt,
     ) -> EthResult<AnyTransactionReceipt> {
@@ -1527,7 +1545,7 @@ where
     #[cfg(feature = "optimism")]
     pub(crate) async fn build_transaction_receipt(
         &self,
-        tx: TransactionSigned,
+        tx: TransactionSignedEcRecovered,
         meta: TransactionMeta,
         receipt: Receipt,
     ) -> EthResult<AnyTransactionReceipt> {
@@ -1685,16 +1703,13 @@ impl From<TransactionSource> for Transaction {
 /// Note: This requires _all_ block receipts because we need to calculate the gas used by the
 /// transaction.
 pub(crate) fn build_transaction_receipt_with_block_receipts(
-    transaction: TransactionSigned,
+    transaction: TransactionSignedEcRecovered,
     meta: TransactionMeta,
     receipt: Receipt,
     all_receipts: &[Receipt],
     #[cfg(feature = "optimism")] optimism_tx_meta: crate::eth::api::optimism::OptimismTxMeta,
 ) -> EthResult<AnyTransactionReceipt> {
-    // Note: we assume this transaction is valid, because it's mined (or part of pending block) and
-    // we don't need to check for pre EIP-2
-    let from =
-        transaction.recover_signer_unchecked().ok_or(EthApiError::InvalidTransactionSignature)?;
+    let from = transaction.signer();
 
     // get the previous transaction cumulative gas used
     let gas_used = if meta.index == 0 {
@@ -1805,6 +1820,7 @@ mod tests {
     use reth_network_api::noop::NoopNetwork;
     use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex};
     use reth_provider::test_utils::NoopProvider;
+    use reth_rpc_server_types::constants::DEFAULT_ETH_PROOF_WINDOW;
     use reth_tasks::pool::BlockingTaskPool;
     use reth_transaction_pool::test_utils::testing_pool;
 
@@ -1826,6 +1842,7 @@ mod tests {
             cache.clone(),
             GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_ETH_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
@@ -4,25 +4,42 @@ use crate::eth::{
     error::{EthApiError, EthResult, RpcInvalidTransactionError},
     revm_utils::FillableTransaction,
     utils::recover_raw_transaction,
-    EthTransactions,
+    EthTransactions, TransactionSource,
 };
 use jsonrpsee::core::RpcResult;
 use reth_primitives::{
     constants::eip4844::MAINNET_KZG_TRUSTED_SETUP,
     keccak256,
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    PooledTransactionsElement, U256,
+    BlockId, Bytes, PooledTransactionsElement, TransactionSignedEcRecovered, B256, U256,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_api::EthCallBundleApiServer;
-use reth_rpc_types::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use reth_rpc_api::{EthBundleApiServer, EthCallBundleApiServer, MevApiServer};
+use reth_rpc_types::{
+    BundleItem, CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
+    EthCallBundleResponse, EthCallBundleTransactionResult, EthSendBundle, Log,
+    PrivateTransactionRequest, SendBundleRequest, SendBundleResponse, SimBundleLogs,
+    SimBundleOverrides, SimBundleResponse,
+};
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_transaction_pool::{bundle::BundleTransaction, BundlePool, MevBundle};
 use revm::{
     db::CacheDB,
-    primitives::{ResultAndState, TxEnv},
+    primitives::{EVMError, ResultAndState, TxEnv},
 };
 use revm_primitives::{EnvWithHandlerCfg, MAX_BLOB_GAS_PER_BLOCK};
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The number of blocks a private transaction is considered for inclusion, if the request does not
+/// specify a maximum block number.
+pub const PRIVATE_TRANSACTION_MAX_BLOCKS: u64 = 25;
+
+/// The default timeout of `mev_simBundle`.
+pub const DEFAULT_SIM_BUNDLE_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
@@ -32,8 +49,19 @@ pub struct EthBundle<Eth> {
 
 impl<Eth> EthBundle<Eth> {
     /// Create a new `EthBundle` instance.
-    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard }) }
+    ///
+    /// Bundles sent to this instance are added to the given [`BundlePool`].
+    pub fn new(
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        bundle_pool: BundlePool,
+    ) -> Self {
+        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard, bundle_pool }) }
+    }
+
+    /// Returns the pool the bundles are added to.
+    pub fn bundle_pool(&self) -> &BundlePool {
+        &self.inner.bundle_pool
     }
 }
 
@@ -208,6 +236,391 @@ where
             })
             .await
     }
+
+    /// Adds a bundle to the bundle pool, to be included in the block with the given number.
+    ///
+    /// A bundle with a replacement UUID replaces the previous bundle with the same UUID.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> EthResult<EthBundleHash> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+        if block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            ))
+        }
+
+        let transactions = txs
+            .into_iter()
+            .map(|tx| {
+                let transaction = recover_bundle_transaction(tx)?;
+                let can_revert = reverting_tx_hashes.contains(&transaction.hash());
+                Ok(BundleTransaction { transaction, can_revert })
+            })
+            .collect::<EthResult<Vec<_>>>()?;
+
+        let bundle_hash = self.add_bundle(MevBundle {
+            transactions,
+            block_number,
+            max_block_number: block_number,
+            min_timestamp,
+            max_timestamp,
+            replacement_uuid,
+        })?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Removes the bundle with the given replacement UUID from the bundle pool.
+    ///
+    /// Bundles can't be cancelled by their hash, because anyone who sees a bundle's transactions
+    /// can compute it. Only the submitter knows the replacement UUID.
+    pub fn cancel_bundle(&self, request: CancelBundleRequest) -> EthResult<()> {
+        let CancelBundleRequest { bundle_hash: replacement_uuid } = request;
+        self.bundle_pool().cancel_bundle(&replacement_uuid);
+        Ok(())
+    }
+
+    /// Adds a single transaction to the bundle pool, to be included in one of the next blocks up to
+    /// the maximum block number, [`PRIVATE_TRANSACTION_MAX_BLOCKS`] blocks by default.
+    ///
+    /// The transaction is not added to the transaction pool, so it is not propagated to peers.
+    pub async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> EthResult<B256> {
+        let PrivateTransactionRequest { tx, max_block_number, preferences: _ } = request;
+        let transaction = recover_bundle_transaction(tx)?;
+        let hash = transaction.hash();
+
+        let (_, block_env, _) = self.inner.eth_api.evm_env_at(BlockId::latest()).await?;
+        let block_number = block_env.number.saturating_to::<u64>() + 1;
+        let max_block_number = max_block_number
+            .unwrap_or(block_number + PRIVATE_TRANSACTION_MAX_BLOCKS - 1)
+            .max(block_number);
+
+        self.add_bundle(MevBundle {
+            transactions: vec![BundleTransaction { transaction, can_revert: false }],
+            block_number,
+            max_block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        })?;
+        Ok(hash)
+    }
+
+    /// Cancels a transaction that was sent with [`Self::send_private_transaction`].
+    ///
+    /// A private transaction can only be cancelled by a request signed by the key that submitted
+    /// it, which can't be verified here, and the hash of the transaction alone doesn't prove that
+    /// the request comes from its sender. So this always fails, private transactions are removed
+    /// from the bundle pool once their maximum block number has been mined.
+    pub fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> EthResult<bool> {
+        Err(EthApiError::InvalidParams(
+            EthBundleError::UnauthenticatedCancellation(request.tx_hash).to_string(),
+        ))
+    }
+
+    /// Adds a bundle of the `mev` namespace to the bundle pool.
+    ///
+    /// Transactions referenced by hash must be pending in the transaction pool. Bundles with
+    /// refunds are rejected, because the payload builder can't pay them.
+    pub async fn send_mev_bundle(
+        &self,
+        request: SendBundleRequest,
+    ) -> EthResult<SendBundleResponse> {
+        let (transactions, refunds) = self.resolve_mev_bundle(&request).await?;
+        let has_refund_config = request
+            .validity
+            .as_ref()
+            .and_then(|validity| validity.refund_config.as_ref())
+            .is_some_and(|config| !config.is_empty());
+        if !refunds.is_empty() || has_refund_config {
+            return Err(EthApiError::InvalidParams(EthBundleError::RefundsNotSupported.to_string()))
+        }
+
+        let bundle_hash = self.add_bundle(MevBundle {
+            transactions,
+            block_number: request.inclusion.block_number(),
+            max_block_number: request
+                .inclusion
+                .max_block_number()
+                .unwrap_or(request.inclusion.block_number()),
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        })?;
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    /// Simulates a bundle of the `mev` namespace on top of the parent block of the overrides, the
+    /// latest block by default.
+    ///
+    /// The bundle is successful if all of its transactions are valid and none of the transactions
+    /// that are not allowed to revert reverts. The profit of the bundle is the increase of the
+    /// coinbase balance, minus the refunds to the senders of the transactions referenced by the
+    /// refund rules. Only the value paid by the other transactions is refundable.
+    pub async fn sim_bundle(
+        &self,
+        request: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> EthResult<SimBundleResponse> {
+        let (transactions, refunds) = self.resolve_mev_bundle(&request).await?;
+        let SimBundleOverrides {
+            parent_block,
+            block_number,
+            coinbase,
+            timestamp,
+            gas_limit,
+            base_fee,
+            timeout,
+        } = overrides;
+
+        let (cfg, mut block_env, at) =
+            self.inner.eth_api.evm_env_at(parent_block.unwrap_or_else(BlockId::latest)).await?;
+        let state_block = block_env.number.saturating_to::<u64>();
+
+        // derive the simulated block from the parent block
+        let block_number = block_number.unwrap_or(state_block + 1);
+        let max_block_number =
+            request.inclusion.max_block_number().unwrap_or(request.inclusion.block_number());
+        if !(request.inclusion.block_number()..=max_block_number).contains(&block_number) {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BlockNumberNotIncluded(block_number).to_string(),
+            ))
+        }
+        block_env.number = U256::from(block_number);
+        block_env.timestamp =
+            timestamp.map(U256::from).unwrap_or(block_env.timestamp + U256::from(12));
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(gas_limit) = gas_limit {
+            block_env.gas_limit = U256::from(gas_limit);
+        }
+        if let Some(base_fee) = base_fee {
+            block_env.basefee = U256::from(base_fee);
+        }
+        let timeout = timeout.map(Duration::from_secs).unwrap_or(DEFAULT_SIM_BUNDLE_TIMEOUT);
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(at, move |state| {
+                let deadline = Instant::now() + timeout;
+                let coinbase = block_env.coinbase;
+                let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+                let db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut evm =
+                    revm::Evm::builder().with_db(db).with_env_with_handler_cfg(env).build();
+
+                let refund_targets =
+                    refunds.iter().map(|refund| refund.body_idx).collect::<HashSet<_>>();
+                let mut gas_used = 0u64;
+                let mut coinbase_diff = U256::ZERO;
+                let mut refundable_value = U256::ZERO;
+                let mut logs = Vec::with_capacity(transactions.len());
+                let mut log_index = 0u64;
+
+                let failed = |gas_used, error: String| SimBundleResponse {
+                    success: false,
+                    error: Some(error),
+                    state_block,
+                    mev_gas_price: 0,
+                    profit: 0,
+                    refundable_value: 0,
+                    gas_used,
+                    logs: None,
+                };
+
+                for (idx, tx) in transactions.into_iter().enumerate() {
+                    if Instant::now() > deadline {
+                        return Err(EthApiError::ExecutionTimedOut(timeout))
+                    }
+
+                    let coinbase_before = DatabaseRef::basic_ref(&evm.context.evm.db, coinbase)?
+                        .map(|acc| acc.balance)
+                        .unwrap_or_default();
+
+                    let BundleTransaction { transaction, can_revert } = tx;
+                    let hash = transaction.hash();
+                    transaction.try_fill_tx_env(evm.tx_mut())?;
+                    let ResultAndState { result, state } = match evm.transact() {
+                        Ok(res) => res,
+                        Err(EVMError::Transaction(err)) => {
+                            return Ok(failed(gas_used, format!("tx {hash} invalid: {err}")))
+                        }
+                        Err(err) => return Err(err.into()),
+                    };
+                    if !result.is_success() && !can_revert {
+                        return Ok(failed(gas_used, format!("tx {hash} reverted")))
+                    }
+                    gas_used += result.gas_used();
+
+                    let coinbase_after =
+                        state.get(&coinbase).map(|acc| acc.info.balance).unwrap_or(coinbase_before);
+                    let diff = coinbase_after.saturating_sub(coinbase_before);
+                    coinbase_diff += diff;
+                    if !refund_targets.contains(&idx) {
+                        refundable_value += diff;
+                    }
+
+                    let tx_logs = result
+                        .into_logs()
+                        .into_iter()
+                        .map(|inner| {
+                            let log = Log {
+                                inner,
+                                block_hash: None,
+                                block_number: Some(block_number),
+                                block_timestamp: None,
+                                transaction_hash: Some(hash),
+                                transaction_index: Some(idx as u64),
+                                log_index: Some(log_index),
+                                removed: false,
+                            };
+                            log_index += 1;
+                            log
+                        })
+                        .collect();
+                    logs.push(SimBundleLogs { tx_logs: Some(tx_logs), bundle_logs: None });
+
+                    evm.context.evm.db.commit(state);
+                }
+
+                let refund_percent = refunds.iter().map(|refund| refund.percent).sum::<u64>();
+                let refund = refundable_value * U256::from(refund_percent) / U256::from(100);
+                let profit = coinbase_diff.saturating_sub(refund);
+                let mev_gas_price = profit.checked_div(U256::from(gas_used)).unwrap_or_default();
+
+                Ok(SimBundleResponse {
+                    success: true,
+                    error: None,
+                    state_block,
+                    mev_gas_price: mev_gas_price.saturating_to(),
+                    profit: profit.saturating_to(),
+                    refundable_value: refundable_value.saturating_to(),
+                    gas_used,
+                    logs: Some(logs),
+                })
+            })
+            .await
+    }
+
+    /// Resolves the transactions and refunds of a bundle of the `mev` namespace.
+    async fn resolve_mev_bundle(
+        &self,
+        request: &SendBundleRequest,
+    ) -> EthResult<(Vec<BundleTransaction>, Vec<BundleRefund>)> {
+        if request.bundle_body.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+        if request.inclusion.block_number() == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            ))
+        }
+
+        let mut transactions = Vec::with_capacity(request.bundle_body.len());
+        for item in &request.bundle_body {
+            let tx = match item {
+                BundleItem::Tx { tx, can_revert } => BundleTransaction {
+                    transaction: recover_bundle_transaction(tx.clone())?,
+                    can_revert: *can_revert,
+                },
+                BundleItem::Hash { hash } => {
+                    // transactions referenced by hash must still be pending
+                    match self.inner.eth_api.transaction_by_hash(*hash).await? {
+                        Some(TransactionSource::Pool(transaction)) => {
+                            BundleTransaction { transaction, can_revert: false }
+                        }
+                        _ => {
+                            return Err(EthApiError::InvalidParams(
+                                EthBundleError::UnknownBundleTransaction(*hash).to_string(),
+                            ))
+                        }
+                    }
+                }
+            };
+            transactions.push(tx);
+        }
+
+        let validity = request.validity.clone().unwrap_or_default();
+        let refunds = validity
+            .refund
+            .unwrap_or_default()
+            .into_iter()
+            .map(|refund| BundleRefund {
+                body_idx: refund.body_idx as usize,
+                percent: refund.percent,
+            })
+            .collect::<Vec<_>>();
+        if let Some(refund) = refunds.iter().find(|refund| refund.body_idx >= transactions.len()) {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::InvalidRefundIndex(refund.body_idx).to_string(),
+            ))
+        }
+        if refunds.iter().map(|refund| refund.percent).sum::<u64>() > 100 ||
+            validity
+                .refund_config
+                .unwrap_or_default()
+                .iter()
+                .map(|config| config.percent)
+                .sum::<u64>() >
+                100
+        {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::RefundPercentExceeded.to_string(),
+            ))
+        }
+
+        Ok((transactions, refunds))
+    }
+
+    /// Adds the bundle to the bundle pool.
+    fn add_bundle(&self, bundle: MevBundle) -> EthResult<B256> {
+        self.bundle_pool()
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))
+    }
+}
+
+/// A share of the profit of a bundle of the `mev` namespace that is refunded to the sender of one
+/// of its transactions.
+#[derive(Debug, Clone, Copy)]
+struct BundleRefund {
+    /// The index of the transaction in the bundle whose sender receives the refund.
+    body_idx: usize,
+    /// The percent of the profit of the bundle that is refunded.
+    percent: u64,
+}
+
+/// Recovers a raw transaction of a bundle.
+///
+/// Blob transactions are rejected, because their sidecars are not available to the payload
+/// builder.
+fn recover_bundle_transaction(tx: Bytes) -> EthResult<TransactionSignedEcRecovered> {
+    let transaction = recover_raw_transaction(tx)?.into_ecrecovered_transaction();
+    if transaction.is_eip4844() {
+        return Err(EthApiError::InvalidParams(
+            EthBundleError::BlobTransactionsNotSupported.to_string(),
+        ))
+    }
+    Ok(transaction)
 }
 
 #[async_trait::async_trait]
@@ -220,6 +633,65 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Ok(Self::send_bundle(self, bundle).await?)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        Ok(Self::call_bundle(self, request).await?)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        Ok(Self::cancel_bundle(self, request)?)
+    }
+
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        Ok(Self::send_private_transaction(self, request).await?)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        let request = PrivateTransactionRequest {
+            tx: bytes,
+            max_block_number: None,
+            preferences: Default::default(),
+        };
+        Ok(Self::send_private_transaction(self, request).await?)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Ok(Self::cancel_private_transaction(self, request)?)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        Ok(Self::send_mev_bundle(self, request).await?)
+    }
+
+    async fn sim_bundle(
+        &self,
+        bundle: SendBundleRequest,
+        sim_overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        Ok(Self::sim_bundle(self, bundle, sim_overrides).await?)
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
@@ -228,6 +700,8 @@ struct EthBundleInner<Eth> {
     // restrict the number of concurrent tracing calls.
     #[allow(dead_code)]
     blocking_task_guard: BlockingTaskGuard,
+    /// The pool sent bundles are added to.
+    bundle_pool: BundlePool,
 }
 
 impl<Eth> std::fmt::Debug for EthBundle<Eth> {
@@ -255,4 +729,26 @@ pub enum EthBundleError {
     /// [`MAX_BLOB_GAS_PER_BLOCK`].
     #[error("blob gas usage exceeds the limit of {MAX_BLOB_GAS_PER_BLOCK} gas per block.")]
     Eip4844BlobGasExceeded,
+    /// Thrown if a bundle sent to the bundle pool contains a blob transaction.
+    #[error("blob transactions are not supported in bundles")]
+    BlobTransactionsNotSupported,
+    /// Thrown if a transaction referenced by hash is not pending in the transaction pool.
+    #[error("unknown transaction {0}")]
+    UnknownBundleTransaction(B256),
+    /// Thrown if a refund refers to a transaction that is not part of the bundle.
+    #[error("refund index {0} out of bounds")]
+    InvalidRefundIndex(usize),
+    /// Thrown if the refunds of a bundle exceed 100 percent.
+    #[error("refund percent exceeds 100")]
+    RefundPercentExceeded,
+    /// Thrown if a bundle with refunds is sent to the bundle pool.
+    #[error("bundle refunds are not supported")]
+    RefundsNotSupported,
+    /// Thrown if a bundle is simulated for a block it can't be included in.
+    #[error("bundle not valid for block {0}")]
+    BlockNumberNotIncluded(u64),
+    /// Thrown if a private transaction is cancelled without proof that the request comes from its
+    /// submitter.
+    #[error("cancellation of private transaction {0} requires a signed request")]
+    UnauthenticatedCancellation(B256),
 }
@@ -250,6 +250,19 @@ impl EthStateCache {
         Ok(block.zip(receipts))
     }
 
+    /// Fetches both receipts and block with senders for the given block hash.
+    pub async fn get_block_with_senders_and_receipts(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Option<(SealedBlockWithSenders, Arc<Vec<Receipt>>)>> {
+        let block = self.get_sealed_block_with_senders(block_hash);
+        let receipts = self.get_receipts(block_hash);
+
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        Ok(block.zip(receipts))
+    }
+
     /// Requests the evm env config for the block hash.
     ///
     /// Returns an error if the corresponding header (required for populating the envs) was not
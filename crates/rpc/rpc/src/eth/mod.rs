@@ -22,7 +22,8 @@ pub use api::{
     EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP,
 };
 
-pub use bundle::EthBundle;
+pub use bundle::{EthBundle, DEFAULT_SIM_BUNDLE_TIMEOUT, PRIVATE_TRANSACTION_MAX_BLOCKS};
 pub use filter::{EthFilter, EthFilterConfig};
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pubsub::EthPubSub;
+pub use signer::ImpersonatedAccounts;
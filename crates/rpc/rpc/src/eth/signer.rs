@@ -2,15 +2,19 @@
 
 use crate::eth::error::SignError;
 use alloy_dyn_abi::TypedData;
+use parking_lot::RwLock;
 use reth_primitives::{
-    eip191_hash_message, sign_message, Address, Signature, TransactionSigned, B256,
+    eip191_hash_message, sign_message, Address, Signature, TransactionSigned, B256, U256,
 };
 use reth_rpc_types::TypedTransactionRequest;
 
 use dyn_clone::DynClone;
 use reth_rpc_types_compat::transaction::to_primitive_transaction;
 use secp256k1::SecretKey;
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 type Result<T> = std::result::Result<T, SignError>;
 
@@ -120,6 +124,96 @@ impl EthSigner for DevSigner {
     }
 }
 
+/// The set of accounts that transactions can be sent from without their private key.
+///
+/// Used by the `anvil_impersonateAccount` family of methods on dev chains.
+#[derive(Debug, Clone, Default)]
+pub struct ImpersonatedAccounts(Arc<RwLock<ImpersonatedAccountsInner>>);
+
+#[derive(Debug, Default)]
+struct ImpersonatedAccountsInner {
+    /// The impersonated accounts.
+    accounts: HashSet<Address>,
+    /// Whether all accounts are impersonated.
+    all: bool,
+}
+
+impl ImpersonatedAccounts {
+    /// Adds the account to the impersonated accounts.
+    pub fn impersonate(&self, address: Address) {
+        self.0.write().accounts.insert(address);
+    }
+
+    /// Removes the account from the impersonated accounts.
+    pub fn stop_impersonating(&self, address: &Address) {
+        self.0.write().accounts.remove(address);
+    }
+
+    /// Sets whether all accounts are impersonated.
+    pub fn set_impersonate_all(&self, enabled: bool) {
+        self.0.write().all = enabled;
+    }
+
+    /// Returns `true` if the account is impersonated.
+    pub fn contains(&self, address: &Address) -> bool {
+        let inner = self.0.read();
+        inner.all || inner.accounts.contains(address)
+    }
+}
+
+/// Signs transactions of impersonated accounts with a placeholder signature.
+///
+/// The signature is not valid, so the sender of such a transaction can't be recovered and has to
+/// be tracked alongside the transaction. Its `r` value is the address of the sender, so the same
+/// transaction of different senders has different hashes.
+#[derive(Clone)]
+pub(crate) struct ImpersonationSigner {
+    accounts: ImpersonatedAccounts,
+}
+
+impl ImpersonationSigner {
+    /// Creates a new signer for the given impersonated accounts.
+    pub(crate) const fn new(accounts: ImpersonatedAccounts) -> Self {
+        Self { accounts }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for ImpersonationSigner {
+    fn accounts(&self) -> Vec<Address> {
+        // impersonated accounts are not owned by the node
+        Vec::new()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.accounts.contains(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        let transaction =
+            to_primitive_transaction(request).ok_or(SignError::InvalidTransactionRequest)?;
+        let signature = Signature {
+            r: U256::from_be_slice(address.as_slice()),
+            s: U256::ZERO,
+            odd_y_parity: false,
+        };
+
+        Ok(TransactionSigned::from_transaction_and_signature(transaction, signature))
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
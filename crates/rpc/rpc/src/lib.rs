@@ -33,6 +33,7 @@ use pin_project as _;
 use tower as _;
 
 mod admin;
+mod anvil;
 mod debug;
 mod engine;
 pub mod eth;
@@ -42,16 +43,23 @@ mod reth;
 mod rpc;
 mod trace;
 mod txpool;
+mod validation;
 mod web3;
 pub use admin::AdminApi;
-pub use debug::DebugApi;
+pub use anvil::AnvilApi;
+pub use debug::{BadBlockStore, DebugApi};
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
+pub use eth::{
+    EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider, ImpersonatedAccounts,
+};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{
+    RethApi, DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE, MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE,
+};
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
+pub use validation::{ValidationApi, ValidationApiError};
 pub use web3::Web3Api;
 pub mod result;
@@ -1,39 +1,229 @@
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use revm_inspectors::transfer::{TransferInspector, TransferKind};
+use revm_inspectors::{
+    tracing::TracingInspectorConfig,
+    transfer::{TransferInspector, TransferKind},
+};
 use revm_primitives::ExecutionResult;
 
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_primitives::{Address, BlockId, BlockNumber, BlockNumberOrTag, TxHash, TxNumber, B256};
+use reth_provider::{AddressTransactionsReader, BlockReader, StageCheckpointReader};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
-    trace::otterscan::{
-        BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
-        OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
+    trace::{
+        otterscan::{
+            BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
+            OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
+        },
+        parity::{Action, CreateAction, CreateOutput, TraceOutput},
     },
-    BlockTransactions, Transaction,
+    AnyTransactionReceipt, BlockTransactions, Transaction,
 };
+use reth_stages_types::StageId;
 
-use crate::{eth::EthTransactions, result::internal_rpc_err};
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult},
+        EthTransactions,
+    },
+    result::internal_rpc_err,
+};
 
 const API_LEVEL: u64 = 8;
 
+/// Error returned by the endpoints that depend on the address transactions index, if the node
+/// doesn't maintain it.
+pub(crate) const ADDRESS_INDEX_DISABLED: &str =
+    "the address transactions index is not enabled on this node, \
+     set `stages.index_address_transactions.enabled` in the config to build it";
+
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub const fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockReader + StageCheckpointReader + AddressTransactionsReader + 'static,
+    Eth: EthApiServer + EthTransactions,
+{
+    /// Returns an error if the node doesn't maintain the address transactions index.
+    ///
+    /// The index is maintained once its stage has a checkpoint.
+    fn ensure_address_index(&self) -> EthResult<()> {
+        if self.provider.get_stage_checkpoint(StageId::IndexAddressTransactions)?.is_none() {
+            return Err(EthApiError::Unsupported(ADDRESS_INDEX_DISABLED))
+        }
+        Ok(())
+    }
+
+    /// Finds the transaction that deployed the contract by tracing the block in which its code
+    /// appeared. This also covers contracts deployed with `CREATE` or `CREATE2` by another
+    /// contract, which aren't recorded in the `ContractCreators` table.
+    ///
+    /// If the contract was deployed multiple times, the first deployment is returned.
+    async fn find_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        let highest = self.eth.block_number()?.saturating_to::<u64>();
+        if self.eth.get_code(address, Some(highest.into())).await?.is_empty() {
+            return Ok(None)
+        }
+
+        // Find the first block after which the contract has code.
+        let (mut low, mut high) = (0, highest);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.eth.get_code(address, Some(mid.into())).await?.is_empty() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let creators = self
+            .eth
+            .trace_block_with(
+                high.into(),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    let creator = inspector
+                        .into_parity_builder()
+                        .into_localized_transaction_traces(tx_info)
+                        .into_iter()
+                        .find_map(|tx_trace| {
+                            match (tx_trace.trace.action, tx_trace.trace.result) {
+                                (
+                                    Action::Create(CreateAction { from, .. }),
+                                    Some(TraceOutput::Create(CreateOutput {
+                                        address: contract,
+                                        ..
+                                    })),
+                                ) if contract == address && tx_trace.trace.error.is_none() => {
+                                    tx_trace.transaction_hash.map(|hash| (hash, from))
+                                }
+                                _ => None,
+                            }
+                        });
+                    Ok(creator)
+                },
+            )
+            .await?
+            .unwrap_or_default();
+
+        Ok(creators
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|(hash, creator)| ContractCreator { hash, creator }))
+    }
+
+    /// Collects the transactions the address appeared in, starting at `start` and walking
+    /// backwards if `reverse` is set, until at least `page_size` transactions are found.
+    ///
+    /// A block is never split across pages, so the transactions of the last block are always
+    /// complete. Returns the transaction numbers with their block numbers, and whether there are
+    /// more transactions left.
+    fn address_transactions_page(
+        &self,
+        address: Address,
+        start: TxNumber,
+        reverse: bool,
+        page_size: usize,
+    ) -> EthResult<(Vec<(TxNumber, BlockNumber)>, bool)> {
+        let limit = page_size.max(1);
+        let mut transactions = Vec::<(TxNumber, BlockNumber)>::new();
+        let mut next = start;
+        loop {
+            let chunk = if reverse {
+                self.provider.address_transactions_before(address, next, limit)?
+            } else {
+                self.provider.address_transactions_from(address, next, limit)?
+            };
+            let is_last_chunk = chunk.len() < limit;
+
+            for tx_number in chunk {
+                let block_number = self
+                    .provider
+                    .transaction_block(tx_number)?
+                    .ok_or(EthApiError::UnknownBlockOrTxIndex)?;
+                if transactions.len() >= page_size &&
+                    transactions.last().map(|(_, last_block)| *last_block) != Some(block_number)
+                {
+                    return Ok((transactions, true))
+                }
+                transactions.push((tx_number, block_number));
+            }
+
+            match transactions.last() {
+                Some((last, _)) if !is_last_chunk => {
+                    next = if reverse { *last } else { last + 1 };
+                }
+                _ => return Ok((transactions, false)),
+            }
+        }
+    }
+
+    /// Fetches the given transactions with their receipts.
+    async fn transactions_with_receipts(
+        &self,
+        transactions: Vec<(TxNumber, BlockNumber)>,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let mut txs = Vec::with_capacity(transactions.len());
+        let mut receipts = Vec::with_capacity(transactions.len());
+        let mut timestamp = None;
+        for (tx_number, block_number) in transactions {
+            let hash = self
+                .provider
+                .transaction_by_id(tx_number)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::TransactionNotFound)?
+                .hash();
+            let tx = self
+                .eth
+                .transaction_by_hash(hash)
+                .await?
+                .ok_or(EthApiError::TransactionNotFound)?;
+            let receipt = self
+                .eth
+                .transaction_receipt(hash)
+                .await?
+                .ok_or(EthApiError::TransactionNotFound)?;
+
+            // Transactions of the same block are next to each other.
+            let block_timestamp = match timestamp {
+                Some((number, block_timestamp)) if number == block_number => block_timestamp,
+                _ => {
+                    let block_timestamp = self
+                        .provider
+                        .header_by_number(block_number)
+                        .map_err(EthApiError::from)?
+                        .ok_or(EthApiError::UnknownBlockNumber)?
+                        .timestamp;
+                    timestamp = Some((block_number, block_timestamp));
+                    block_timestamp
+                }
+            };
+
+            txs.push(tx);
+            receipts.push(ots_transaction_receipt(receipt, Some(block_timestamp)));
+        }
+        Ok((txs, receipts))
     }
 }
 
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
+    Provider: BlockReader + StageCheckpointReader + AddressTransactionsReader + 'static,
     Eth: EthApiServer + EthTransactions,
 {
     /// Handler for `ots_hasCode`
@@ -156,57 +346,167 @@ where
         let timestamp = Some(block.header.timestamp);
         let receipts = receipts
             .drain(page_start..page_end)
-            .map(|receipt| {
-                let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
-                    status: receipt
-                        .inner
-                        .receipt
-                        .status
-                        .as_eip658()
-                        .expect("ETH API returned pre-EIP-658 status"),
-                    cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: receipt.r#type,
-                });
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|receipt| ots_transaction_receipt(receipt, timestamp))
             .collect();
         Ok(OtsBlockTransactions { fullblock: block.inner.into(), receipts })
     }
 
     /// Handler for `searchTransactionsBefore`
+    ///
+    /// Returns the transactions the address appeared in before the given block, most recent
+    /// first. Block number `0` searches from the tip of the chain.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        self.ensure_address_index()?;
+
+        let block_number = block_number.as_number().unwrap_or_default();
+        let before = match block_number {
+            0 => TxNumber::MAX,
+            number => self
+                .provider
+                .block_body_indices(number)
+                .map_err(EthApiError::from)?
+                .map_or(TxNumber::MAX, |body| body.first_tx_num()),
+        };
+
+        let (transactions, has_more) =
+            self.address_transactions_page(address, before, true, page_size)?;
+        let (txs, receipts) = self.transactions_with_receipts(transactions).await?;
+
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: block_number == 0,
+            last_page: !has_more,
+        })
     }
 
     /// Handler for `searchTransactionsAfter`
+    ///
+    /// Returns the transactions the address appeared in after the given block, most recent
+    /// first. Block number `0` searches from the genesis block.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        self.ensure_address_index()?;
+
+        let block_number = block_number.as_number().unwrap_or_default();
+        let from = match block_number {
+            0 => 0,
+            number => match self.provider.block_body_indices(number).map_err(EthApiError::from)? {
+                Some(body) => body.next_tx_num(),
+                None => {
+                    return Ok(TransactionsWithReceipts {
+                        txs: Vec::new(),
+                        receipts: Vec::new(),
+                        first_page: true,
+                        last_page: false,
+                    })
+                }
+            },
+        };
+
+        let (mut transactions, has_more) =
+            self.address_transactions_page(address, from, false, page_size)?;
+        transactions.reverse();
+        let (txs, receipts) = self.transactions_with_receipts(transactions).await?;
+
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: !has_more,
+            last_page: block_number == 0,
+        })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
+    ///
+    /// Binary searches the nonce history of the sender for the block that includes the
+    /// transaction, so this doesn't depend on the address transactions index.
     async fn get_transaction_by_sender_and_nonce(
         &self,
-        _sender: Address,
-        _nonce: u64,
+        sender: Address,
+        nonce: u64,
     ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+        let highest = self.eth.block_number()?.saturating_to::<u64>();
+
+        // The transaction doesn't exist if the nonce hasn't been used yet.
+        let latest_nonce = self.eth.transaction_count(sender, Some(highest.into())).await?;
+        if latest_nonce.saturating_to::<u64>() <= nonce {
+            return Ok(None)
+        }
+
+        // Find the first block after which the nonce of the sender exceeds the given nonce.
+        let (mut low, mut high) = (0, highest);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let mid_nonce = self.eth.transaction_count(sender, Some(mid.into())).await?;
+            if mid_nonce.saturating_to::<u64>() > nonce {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        let Some(block) = self.eth.block_by_number(high.into(), true).await? else {
+            return Ok(None)
+        };
+        let BlockTransactions::Full(transactions) = block.inner.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        Ok(transactions.into_iter().find(|tx| tx.from == sender && tx.nonce == nonce))
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    ///
+    /// Contracts deployed by a top-level contract creation transaction are looked up in the
+    /// address transactions index. Contracts deployed by another contract are not indexed, they
+    /// are found by tracing the block in which the contract code appeared.
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        self.ensure_address_index()?;
+
+        let Some(tx_number) =
+            self.provider.contract_creation_transaction(address).map_err(EthApiError::from)?
+        else {
+            return self.find_contract_creator(address).await
+        };
+        let Some(transaction) =
+            self.provider.transaction_by_id(tx_number).map_err(EthApiError::from)?
+        else {
+            return Ok(None)
+        };
+        let creator =
+            transaction.recover_signer().ok_or(EthApiError::InvalidTransactionSignature)?;
+
+        Ok(Some(ContractCreator { hash: transaction.hash(), creator }))
     }
 }
+
+/// Converts a receipt into an [`OtsTransactionReceipt`], which omits the logs.
+fn ots_transaction_receipt(
+    receipt: AnyTransactionReceipt,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
+        status: receipt
+            .inner
+            .receipt
+            .status
+            .as_eip658()
+            .expect("ETH API returned pre-EIP-658 status"),
+        cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: receipt.r#type,
+    });
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
@@ -1,14 +1,37 @@
-use crate::eth::error::{EthApiError, EthResult};
+use crate::{
+    eth::error::{EthApiError, EthResult},
+    otterscan::ADDRESS_INDEX_DISABLED,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_errors::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_errors::{RethError, RethResult};
+use reth_primitives::{Address, BlockId, BlockNumHash, B256, U256};
+use reth_provider::{
+    AddressTransactionsReader, BlockReaderIdExt, ChangeSetReader, StageCheckpointReader,
+    StateProviderFactory,
+};
 use reth_rpc_api::RethApiServer;
+use reth_rpc_types::{
+    AddressTransaction, AddressTransactionsPage, AddressTransactionsRequest, ArchivedBlobSidecar,
+};
+use reth_stages_types::StageId;
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::blobstore::{ArchivedSidecar, BlobSidecarArchive};
 use std::{collections::HashMap, future::Future, sync::Arc};
 use tokio::sync::oneshot;
 
+/// The number of transactions on a page of `reth_getAddressTransactions`, if the request doesn't
+/// specify it.
+pub const DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE: u64 = 100;
+
+/// The maximum number of transactions on a page of `reth_getAddressTransactions`.
+pub const MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE: u64 = 1_000;
+
+/// Error returned by the endpoints that depend on the blob sidecar archive, if the node doesn't
+/// maintain it.
+const BLOB_ARCHIVE_DISABLED: &str =
+    "the blob sidecar archive is not enabled on this node, start it with `--blobpool.archive`";
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -25,15 +48,31 @@ impl<Provider> RethApi<Provider> {
     }
 
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    ///
+    /// The blob sidecar endpoints are only served if a [`BlobSidecarArchive`] is given.
+    pub fn new(
+        provider: Provider,
+        task_spawner: Box<dyn TaskSpawner>,
+        blob_archive: Option<BlobSidecarArchive>,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner { provider, task_spawner, blob_archive });
         Self { inner }
     }
+
+    /// Returns the blob sidecar archive, or an error if the node doesn't maintain it.
+    fn blob_archive(&self) -> EthResult<&BlobSidecarArchive> {
+        self.inner.blob_archive.as_ref().ok_or(EthApiError::Unsupported(BLOB_ARCHIVE_DISABLED))
+    }
 }
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + AddressTransactionsReader
+        + StageCheckpointReader
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -81,12 +120,167 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns a page of the transactions the address appeared in.
+    pub async fn address_transactions(
+        &self,
+        address: Address,
+        request: AddressTransactionsRequest,
+    ) -> EthResult<AddressTransactionsPage> {
+        self.on_blocking_task(|this| async move { this.try_address_transactions(address, request) })
+            .await
+    }
+
+    fn try_address_transactions(
+        &self,
+        address: Address,
+        request: AddressTransactionsRequest,
+    ) -> EthResult<AddressTransactionsPage> {
+        // The index is maintained once its stage has a checkpoint.
+        if self.provider().get_stage_checkpoint(StageId::IndexAddressTransactions)?.is_none() {
+            return Err(EthApiError::Unsupported(ADDRESS_INDEX_DISABLED))
+        }
+
+        let AddressTransactionsRequest { cursor, page_size, reverse } = request;
+        let page_size = page_size
+            .unwrap_or(DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE)
+            .clamp(1, MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE) as usize;
+
+        // One more transaction is fetched to know whether there is a next page.
+        let mut tx_numbers = if reverse {
+            self.provider().address_transactions_before(
+                address,
+                cursor.unwrap_or(u64::MAX),
+                page_size + 1,
+            )?
+        } else {
+            self.provider().address_transactions_from(
+                address,
+                cursor.unwrap_or_default(),
+                page_size + 1,
+            )?
+        };
+        let next_cursor = (tx_numbers.len() > page_size).then(|| {
+            tx_numbers.truncate(page_size);
+            let last = tx_numbers[page_size - 1];
+            // `address_transactions_before` excludes the cursor, `address_transactions_from`
+            // includes it.
+            if reverse {
+                last
+            } else {
+                last + 1
+            }
+        });
+
+        let mut transactions = Vec::with_capacity(tx_numbers.len());
+        // Transactions of the same block are next to each other.
+        let mut block = None;
+        for tx_number in tx_numbers {
+            let block_number = self
+                .provider()
+                .transaction_block(tx_number)?
+                .ok_or(EthApiError::UnknownBlockOrTxIndex)?;
+            let (block_hash, first_tx_number) = match block {
+                Some((number, hash, first_tx_number)) if number == block_number => {
+                    (hash, first_tx_number)
+                }
+                _ => {
+                    let hash = self
+                        .provider()
+                        .block_hash(block_number)?
+                        .ok_or(EthApiError::UnknownBlockNumber)?;
+                    let first_tx_number = self
+                        .provider()
+                        .block_body_indices(block_number)?
+                        .ok_or(EthApiError::UnknownBlockNumber)?
+                        .first_tx_num();
+                    block = Some((block_number, hash, first_tx_number));
+                    (hash, first_tx_number)
+                }
+            };
+            let transaction_hash = self
+                .provider()
+                .transaction_by_id(tx_number)?
+                .ok_or(EthApiError::TransactionNotFound)?
+                .hash();
+
+            transactions.push(AddressTransaction {
+                transaction_hash,
+                block_hash,
+                block_number,
+                transaction_index: tx_number - first_tx_number,
+            });
+        }
+
+        Ok(AddressTransactionsPage { transactions, next_cursor })
+    }
+
+    /// Returns the archived blob sidecars of the blob transactions in a block.
+    pub async fn blob_sidecars(&self, block_id: BlockId) -> EthResult<Vec<ArchivedBlobSidecar>> {
+        self.on_blocking_task(|this| async move { this.try_blob_sidecars(block_id) }).await
+    }
+
+    fn try_blob_sidecars(&self, block_id: BlockId) -> EthResult<Vec<ArchivedBlobSidecar>> {
+        let archive = self.blob_archive()?;
+        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+
+        let Some(block) = archive.block(block_number).map_err(RethError::other)? else {
+            return Ok(Vec::new())
+        };
+        // only canonical blocks are archived
+        if let BlockId::Hash(hash) = block_id {
+            if hash.block_hash != block.hash {
+                return Ok(Vec::new())
+            }
+        }
+
+        let block_num_hash = BlockNumHash::new(block.number, block.hash);
+        Ok(block
+            .sidecars
+            .into_iter()
+            .map(|sidecar| archived_blob_sidecar(block_num_hash, sidecar))
+            .collect())
+    }
+
+    /// Returns the archived blob sidecar that contains the blob with the given versioned hash.
+    pub async fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> EthResult<Option<ArchivedBlobSidecar>> {
+        self.on_blocking_task(|this| async move {
+            let archive = this.blob_archive()?;
+            let sidecar =
+                archive.sidecar_by_versioned_hash(versioned_hash).map_err(RethError::other)?;
+            Ok(sidecar.map(|(block, sidecar)| archived_blob_sidecar(block, sidecar)))
+        })
+        .await
+    }
+}
+
+/// Converts an archived sidecar of a transaction in the given block into its RPC representation.
+fn archived_blob_sidecar(block: BlockNumHash, sidecar: ArchivedSidecar) -> ArchivedBlobSidecar {
+    let ArchivedSidecar { tx_hash, tx_index, versioned_hashes, sidecar } = sidecar;
+    ArchivedBlobSidecar {
+        transaction_hash: tx_hash,
+        block_hash: block.hash,
+        block_number: block.number,
+        transaction_index: tx_index,
+        blob_versioned_hashes: versioned_hashes,
+        sidecar,
+    }
 }
 
 #[async_trait]
 impl<Provider> RethApiServer for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + AddressTransactionsReader
+        + StageCheckpointReader
+        + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -95,6 +289,31 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getAddressTransactions`
+    async fn reth_get_address_transactions(
+        &self,
+        address: Address,
+        request: Option<AddressTransactionsRequest>,
+    ) -> RpcResult<AddressTransactionsPage> {
+        Ok(Self::address_transactions(self, address, request.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `reth_getBlobSidecars`
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<ArchivedBlobSidecar>> {
+        Ok(Self::blob_sidecars(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getBlobSidecarByVersionedHash`
+    async fn reth_get_blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<ArchivedBlobSidecar>> {
+        Ok(Self::blob_sidecar_by_versioned_hash(self, versioned_hash).await?)
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -114,4 +333,6 @@ struct RethApiInner<Provider> {
     provider: Provider,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// The archive serving the blob sidecar endpoints, if enabled.
+    blob_archive: Option<BlobSidecarArchive>,
 }
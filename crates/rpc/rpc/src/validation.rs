@@ -0,0 +1,505 @@
+use crate::result::{internal_rpc_err, invalid_params_rpc_err};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_consensus_common::validation::{validate_block_pre_execution, validate_header_standalone};
+use reth_errors::{BlockExecutionError, ConsensusError, ProviderError};
+use reth_evm::execute::{BatchExecutor, BlockExecutorProvider};
+use reth_primitives::{
+    constants::MINIMUM_GAS_LIMIT, Address, GotExpected, SealedBlock, B256, U256,
+};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderFactory, StateRootProvider,
+};
+use reth_prune_types::PruneModes;
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_types::{
+    beacon::relay::{BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2},
+    ExecutionPayload, PayloadError,
+};
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
+use reth_tasks::TaskSpawner;
+use std::{future::Future, sync::Arc};
+use tokio::sync::oneshot;
+use tracing::{trace, warn};
+
+/// The bound divisor of the gas limit, used to compute the maximum change of the gas limit between
+/// two blocks.
+const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// `flashbots` API implementation.
+///
+/// This type validates block submissions of builders for relays, by executing the submitted
+/// payload on top of its parent with the block executor of the node.
+pub struct ValidationApi<Provider, BlockExecutor> {
+    inner: Arc<ValidationApiInner<Provider, BlockExecutor>>,
+}
+
+// === impl ValidationApi ===
+
+impl<Provider, BlockExecutor> ValidationApi<Provider, BlockExecutor> {
+    /// The provider that can interact with the chain.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+
+    /// Create a new instance of the [`ValidationApi`]
+    pub fn new(
+        provider: Provider,
+        block_executor: BlockExecutor,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(ValidationApiInner { provider, block_executor, task_spawner });
+        Self { inner }
+    }
+}
+
+impl<Provider, BlockExecutor> ValidationApi<Provider, BlockExecutor>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + 'static,
+    BlockExecutor: BlockExecutorProvider,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> Result<R, ValidationApiError>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = Result<R, ValidationApiError>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| ValidationApiError::TaskClosed)?
+    }
+
+    /// Validates a block submission of a builder for a pre-Shanghai payload.
+    pub async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> Result<(), ValidationApiError> {
+        let block =
+            try_into_sealed_block(ExecutionPayload::V1(request.request.execution_payload), None)?;
+        let message = request.request.message;
+        let registered_gas_limit = request.registered_gas_limit;
+
+        self.on_blocking_task(|this| async move {
+            this.validate_block(block, message, registered_gas_limit)
+        })
+        .await
+    }
+
+    /// Validates a block submission of a builder for a Shanghai payload.
+    ///
+    /// In addition to [`Self::validate_builder_submission_v1`], this checks the withdrawals root
+    /// of the payload against the root computed by the relay.
+    pub async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> Result<(), ValidationApiError> {
+        let block =
+            try_into_sealed_block(ExecutionPayload::V2(request.request.execution_payload), None)?;
+        if block.withdrawals_root != Some(request.withdrawals_root) {
+            return Err(ValidationApiError::WithdrawalsRootMismatch(GotExpected {
+                got: block.withdrawals_root.unwrap_or_default(),
+                expected: request.withdrawals_root,
+            }))
+        }
+        let message = request.request.message;
+        let registered_gas_limit = request.registered_gas_limit;
+
+        self.on_blocking_task(|this| async move {
+            this.validate_block(block, message, registered_gas_limit)
+        })
+        .await
+    }
+
+    /// Validates the block against the bid and executes it on top of its parent.
+    ///
+    /// The block is valid if:
+    ///  - it matches the bid trace
+    ///  - it is a valid child of its parent, with the gas limit moved towards the gas limit
+    ///    registered by the proposer
+    ///  - it executes successfully with the state root of its header
+    ///  - it pays at least the value of the bid to the fee recipient of the proposer
+    fn validate_block(
+        &self,
+        block: SealedBlock,
+        message: BidTrace,
+        registered_gas_limit: u64,
+    ) -> Result<(), ValidationApiError> {
+        trace!(target: "rpc::flashbots", hash = ?block.hash(), number = block.number, "Validating builder submission");
+
+        if block.hash() != message.block_hash {
+            return Err(ValidationApiError::BlockHashMismatch(GotExpected {
+                got: block.hash(),
+                expected: message.block_hash,
+            }))
+        }
+        if block.parent_hash != message.parent_hash {
+            return Err(ValidationApiError::ParentHashMismatch(GotExpected {
+                got: block.parent_hash,
+                expected: message.parent_hash,
+            }))
+        }
+        if block.gas_limit != message.gas_limit {
+            return Err(ValidationApiError::GasLimitMismatch(GotExpected {
+                got: block.gas_limit,
+                expected: message.gas_limit,
+            }))
+        }
+        if block.gas_used != message.gas_used {
+            return Err(ValidationApiError::GasUsedMismatch(GotExpected {
+                got: block.gas_used,
+                expected: message.gas_used,
+            }))
+        }
+
+        let chain_spec = self.provider().chain_spec();
+        let parent = self
+            .provider()
+            .header(&block.parent_hash)?
+            .ok_or(ValidationApiError::MissingParentBlock(block.parent_hash))?
+            .seal(block.parent_hash);
+
+        validate_header_standalone(&block.header, &chain_spec)?;
+        block.header.validate_against_parent(&parent, &chain_spec).map_err(ConsensusError::from)?;
+        validate_block_pre_execution(&block, &chain_spec)?;
+
+        let expected_gas_limit = calculate_gas_limit(parent.gas_limit, registered_gas_limit);
+        if block.gas_limit != expected_gas_limit {
+            return Err(ValidationApiError::GasLimitMismatch(GotExpected {
+                got: block.gas_limit,
+                expected: expected_gas_limit,
+            }))
+        }
+
+        let total_difficulty = self
+            .provider()
+            .header_td(&block.parent_hash)?
+            .ok_or(ValidationApiError::MissingParentBlock(block.parent_hash))? +
+            block.difficulty;
+        let block =
+            block.seal_with_senders().ok_or(ValidationApiError::InvalidTransactionSignature)?;
+        let block_hash = block.hash();
+        let block = block.unseal();
+
+        let state = self.provider().state_by_block_hash(block.parent_hash)?;
+        let mut executor = self
+            .inner
+            .block_executor
+            .batch_executor(StateProviderDatabase::new(&state), PruneModes::none());
+        executor.execute_and_verify_one((&block, total_difficulty).into())?;
+        let outcome = executor.finalize();
+
+        // The fee recipient is either paid directly, e.g. by being the beneficiary of the block, or
+        // by a transfer at the end of the block.
+        let fee_recipient = message.proposer_fee_recipient;
+        let balance_diff = outcome
+            .bundle
+            .account(&fee_recipient)
+            .map(|account| {
+                let before = account.original_info.as_ref().map(|info| info.balance);
+                let after = account.info.as_ref().map(|info| info.balance);
+                after.unwrap_or_default().saturating_sub(before.unwrap_or_default())
+            })
+            .unwrap_or_default();
+        if balance_diff < message.value {
+            let last_receipt =
+                outcome.receipts_by_block(block.number).last().and_then(Option::as_ref);
+            let is_payment = match (block.body.last(), last_receipt) {
+                (Some(tx), Some(receipt)) => {
+                    receipt.success &&
+                        tx.to() == Some(fee_recipient) &&
+                        tx.value() == message.value &&
+                        tx.input().is_empty()
+                }
+                _ => false,
+            };
+            if !is_payment {
+                return Err(ValidationApiError::ProposerPayment {
+                    fee_recipient,
+                    expected: message.value,
+                })
+            }
+        } else if balance_diff > message.value {
+            warn!(target: "rpc::flashbots", ?block_hash, %balance_diff, value = %message.value, "Proposer payment exceeds the bid value");
+        }
+
+        let state_root = state.state_root(&outcome.bundle)?;
+        if state_root != block.state_root {
+            return Err(ValidationApiError::StateRootMismatch(GotExpected {
+                got: state_root,
+                expected: block.state_root,
+            }))
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Provider, BlockExecutor> BlockSubmissionValidationApiServer
+    for ValidationApi<Provider, BlockExecutor>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + 'static,
+    BlockExecutor: BlockExecutorProvider,
+{
+    /// Handler for `flashbots_validateBuilderSubmissionV1`
+    async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()> {
+        trace!(target: "rpc::flashbots", "Serving flashbots_validateBuilderSubmissionV1");
+        Ok(Self::validate_builder_submission_v1(self, request).await?)
+    }
+
+    /// Handler for `flashbots_validateBuilderSubmissionV2`
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> RpcResult<()> {
+        trace!(target: "rpc::flashbots", "Serving flashbots_validateBuilderSubmissionV2");
+        Ok(Self::validate_builder_submission_v2(self, request).await?)
+    }
+}
+
+impl<Provider, BlockExecutor> std::fmt::Debug for ValidationApi<Provider, BlockExecutor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, BlockExecutor> Clone for ValidationApi<Provider, BlockExecutor> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct ValidationApiInner<Provider, BlockExecutor> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// Executes the submitted blocks.
+    block_executor: BlockExecutor,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Returns the gas limit of a child of a block with the given gas limit, moved as far as allowed
+/// towards the desired gas limit.
+fn calculate_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let desired_gas_limit = desired_gas_limit.max(MINIMUM_GAS_LIMIT);
+    if parent_gas_limit < desired_gas_limit {
+        (parent_gas_limit + delta).min(desired_gas_limit)
+    } else {
+        parent_gas_limit.saturating_sub(delta).max(desired_gas_limit)
+    }
+}
+
+/// Errors that can occur when validating a block submission.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationApiError {
+    /// The payload could not be converted into a block.
+    #[error(transparent)]
+    Payload(#[from] PayloadError),
+    /// The block hash does not match the bid.
+    #[error("block hash mismatch: {0}")]
+    BlockHashMismatch(GotExpected<B256>),
+    /// The parent hash does not match the bid.
+    #[error("parent hash mismatch: {0}")]
+    ParentHashMismatch(GotExpected<B256>),
+    /// The gas limit does not match the bid or the registered gas limit.
+    #[error("gas limit mismatch: {0}")]
+    GasLimitMismatch(GotExpected<u64>),
+    /// The gas used does not match the bid.
+    #[error("gas used mismatch: {0}")]
+    GasUsedMismatch(GotExpected<u64>),
+    /// The withdrawals root does not match the root computed by the relay.
+    #[error("withdrawals root mismatch: {0}")]
+    WithdrawalsRootMismatch(GotExpected<B256>),
+    /// The parent of the block is unknown.
+    #[error("missing parent block {0}")]
+    MissingParentBlock(B256),
+    /// The senders of the transactions could not be recovered.
+    #[error("invalid transaction signature")]
+    InvalidTransactionSignature,
+    /// The proposer was not paid the value of the bid.
+    #[error("proposer payment of {expected} to {fee_recipient} not found")]
+    ProposerPayment {
+        /// The fee recipient of the proposer.
+        fee_recipient: Address,
+        /// The value of the bid.
+        expected: U256,
+    },
+    /// The state root does not match the state after execution.
+    #[error("state root mismatch: {0}")]
+    StateRootMismatch(GotExpected<B256>),
+    /// The block is not a valid child of its parent.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    /// The execution of the block failed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// Failed to read from the database.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The validation task was dropped before it completed.
+    #[error("validation task closed")]
+    TaskClosed,
+}
+
+impl From<ValidationApiError> for jsonrpsee::types::error::ErrorObject<'static> {
+    fn from(error: ValidationApiError) -> Self {
+        match error {
+            ValidationApiError::Provider(_) | ValidationApiError::TaskClosed => {
+                internal_rpc_err(error.to_string())
+            }
+            _ => invalid_params_rpc_err(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use reth_evm_ethereum::execute::EthExecutorProvider;
+    use reth_primitives::{constants::EIP1559_INITIAL_BASE_FEE, Block, ChainSpecBuilder, Header};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+
+    const PARENT_GAS_LIMIT: u64 = 30_000_000;
+
+    /// Returns an API backed by a post-merge chain with a single parent block, and an empty child
+    /// of that parent with the bid that matches it.
+    fn setup() -> (ValidationApi<MockEthProvider, EthExecutorProvider>, SealedBlock, BidTrace) {
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().paris_activated().build());
+        let provider = MockEthProvider { chain_spec: chain_spec.clone(), ..Default::default() };
+
+        let parent = Header {
+            gas_limit: PARENT_GAS_LIMIT,
+            base_fee_per_gas: Some(EIP1559_INITIAL_BASE_FEE),
+            ..Default::default()
+        }
+        .seal_slow();
+        provider.add_header(parent.hash(), parent.header().clone());
+
+        let header = Header {
+            parent_hash: parent.hash(),
+            number: 1,
+            timestamp: 12,
+            gas_limit: PARENT_GAS_LIMIT,
+            base_fee_per_gas: parent
+                .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(12)),
+            // the state root of the mock provider
+            state_root: B256::ZERO,
+            ..Default::default()
+        };
+        let block = Block { header, ..Default::default() }.seal_slow();
+
+        let message = BidTrace {
+            slot: 1,
+            parent_hash: block.parent_hash,
+            block_hash: block.hash(),
+            builder_pubkey: Default::default(),
+            proposer_pubkey: Default::default(),
+            proposer_fee_recipient: Address::random(),
+            gas_limit: block.gas_limit,
+            gas_used: block.gas_used,
+            value: U256::ZERO,
+        };
+
+        let api = ValidationApi::new(
+            provider,
+            EthExecutorProvider::ethereum(chain_spec),
+            Box::<TokioTaskExecutor>::default(),
+        );
+        (api, block, message)
+    }
+
+    #[test]
+    fn accepts_valid_submission() {
+        let (api, block, message) = setup();
+        api.validate_block(block, message, PARENT_GAS_LIMIT).unwrap();
+    }
+
+    #[test]
+    fn rejects_block_hash_mismatch() {
+        let (api, block, mut message) = setup();
+        message.block_hash = B256::random();
+        let expected = message.block_hash;
+
+        assert_matches!(
+            api.validate_block(block.clone(), message, PARENT_GAS_LIMIT),
+            Err(ValidationApiError::BlockHashMismatch(diff))
+                if diff.got == block.hash() && diff.expected == expected
+        );
+    }
+
+    #[test]
+    fn rejects_parent_hash_mismatch() {
+        let (api, block, mut message) = setup();
+        message.parent_hash = B256::random();
+        let expected = message.parent_hash;
+
+        assert_matches!(
+            api.validate_block(block.clone(), message, PARENT_GAS_LIMIT),
+            Err(ValidationApiError::ParentHashMismatch(diff))
+                if diff.got == block.parent_hash && diff.expected == expected
+        );
+    }
+
+    #[test]
+    fn rejects_gas_limit_mismatch() {
+        let (api, block, mut message) = setup();
+        message.gas_limit += 1;
+
+        assert_matches!(
+            api.validate_block(block, message, PARENT_GAS_LIMIT),
+            Err(ValidationApiError::GasLimitMismatch(diff))
+                if diff.got == PARENT_GAS_LIMIT && diff.expected == PARENT_GAS_LIMIT + 1
+        );
+    }
+
+    #[test]
+    fn rejects_gas_limit_not_moved_towards_registered_gas_limit() {
+        let (api, block, message) = setup();
+        let registered_gas_limit = 36_000_000;
+
+        assert_matches!(
+            api.validate_block(block, message, registered_gas_limit),
+            Err(ValidationApiError::GasLimitMismatch(diff))
+                if diff.got == PARENT_GAS_LIMIT &&
+                    diff.expected == calculate_gas_limit(PARENT_GAS_LIMIT, registered_gas_limit)
+        );
+    }
+
+    #[test]
+    fn rejects_missing_proposer_payment() {
+        let (api, block, mut message) = setup();
+        message.value = U256::from(1);
+        let fee_recipient = message.proposer_fee_recipient;
+
+        assert_matches!(
+            api.validate_block(block, message, PARENT_GAS_LIMIT),
+            Err(ValidationApiError::ProposerPayment { fee_recipient: recipient, expected })
+                if recipient == fee_recipient && expected == U256::from(1)
+        );
+    }
+
+    #[test]
+    fn gas_limit_moves_towards_registered_gas_limit() {
+        let parent_gas_limit = 30_000_000;
+        let delta = parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR - 1;
+
+        assert_eq!(calculate_gas_limit(parent_gas_limit, parent_gas_limit), parent_gas_limit);
+        assert_eq!(calculate_gas_limit(parent_gas_limit, 30_000_100), 30_000_100);
+        assert_eq!(calculate_gas_limit(parent_gas_limit, 36_000_000), parent_gas_limit + delta);
+        assert_eq!(calculate_gas_limit(parent_gas_limit, 29_999_900), 29_999_900);
+        assert_eq!(calculate_gas_limit(parent_gas_limit, 0), parent_gas_limit - delta);
+    }
+}
@@ -246,6 +246,9 @@ where
     /// - [`StaticFileSegment::Transactions`](reth_primitives::static_file::StaticFileSegment::Transactions)
     ///   -> [`StageId::Bodies`]
     ///
+    /// Changesets and transaction senders are not moved here, because stages read them from the
+    /// database when unwinding. They are moved once their blocks are finalized.
+    ///
     /// CAUTION: This method locks the static file producer Mutex, hence can block the thread if the
     /// lock is occupied.
     pub fn move_to_static_files(&self) -> RethResult<()> {
@@ -265,6 +268,9 @@ where
                 headers: stages_checkpoints[0],
                 receipts: stages_checkpoints[1],
                 transactions: stages_checkpoints[2],
+                account_changesets: None,
+                storage_changesets: None,
+                transaction_senders: None,
             })?;
             static_file_producer.run(targets)?;
             stages_checkpoints.into_iter().min().expect("exists")
@@ -36,8 +36,8 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexAddressTransactionsStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -48,6 +48,7 @@ use reth_evm::execute::BlockExecutorProvider;
 use reth_network_p2p::{bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader};
 use reth_provider::{HeaderSyncGapProvider, HeaderSyncMode};
 use reth_prune_types::PruneModes;
+use reth_stages_api::StageId;
 use std::sync::Arc;
 
 /// A set containing all stages to run a fully syncing instance of reth.
@@ -70,6 +71,7 @@ use std::sync::Arc;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexAddressTransactionsStage`] (optional)
 /// - [`FinishStage`]
 #[derive(Debug)]
 pub struct DefaultStages<Provider, H, B, EF> {
@@ -365,6 +367,7 @@ pub struct HistoryIndexingStages {
 
 impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
     fn builder(self) -> StageSetBuilder<DB> {
+        let index_address_transactions = self.stages_config.index_address_transactions;
         StageSetBuilder::default()
             .add_stage(TransactionLookupStage::new(
                 self.stages_config.transaction_lookup,
@@ -381,5 +384,11 @@ impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            .add_stage(IndexAddressTransactionsStage::new(
+                index_address_transactions,
+                self.stages_config.etl.clone(),
+                self.prune_modes.address_transactions,
+            ))
+            .disable_if(StageId::IndexAddressTransactions, || !index_address_transactions.enabled)
     }
 }
@@ -101,14 +101,13 @@ impl<DB: Database> Stage<DB> for IndexAccountHistoryStage {
         }
 
         info!(target: "sync::stages::index_account_history::exec", ?first_sync, "Collecting indices");
-        let collector =
-            collect_history_indices::<_, tables::AccountChangeSets, tables::AccountsHistory, _>(
-                provider.tx_ref(),
-                range.clone(),
-                ShardedKey::new,
-                |(index, value)| (index, value.address),
-                &self.etl_config,
-            )?;
+        let collector = collect_history_indices::<tables::AccountsHistory, _, _>(
+            range.clone(),
+            |range| provider.account_changesets(range),
+            ShardedKey::new,
+            |(index, value)| (index, value.address),
+            &self.etl_config,
+        )?;
 
         info!(target: "sync::stages::index_account_history::exec", "Loading indices into database");
         load_history_indices::<_, tables::AccountsHistory, _>(
@@ -259,6 +258,30 @@ mod tests {
         assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0])]));
     }
 
+    #[tokio::test]
+    async fn insert_index_from_static_file_changesets() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup
+        partial_setup(&db);
+        db.move_changesets_to_static_files(2).unwrap();
+
+        // run
+        run(&db, 5, None);
+
+        // verify
+        let table = cast(db.table::<tables::AccountsHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3, 4, 5])]));
+
+        // unwind
+        unwind(&db, 5, 1);
+
+        // verify
+        let table = cast(db.table::<tables::AccountsHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1])]));
+    }
+
     #[tokio::test]
     async fn insert_index_to_not_empty_shard() {
         // init
@@ -0,0 +1,255 @@
+use super::load_history_indices;
+use reth_config::config::{EtlConfig, IndexAddressTransactionsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{database::Database, models::ShardedKey, table::Decode, transaction::DbTxMut};
+use reth_etl::Collector;
+use reth_primitives::{Address, TxNumber};
+use reth_provider::{
+    BlockReader, DatabaseProviderRW, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Stage is indexing the transactions each address appeared in, as the sender, the recipient, the
+/// contract created by the transaction, the emitter of an event or an indexed address argument of
+/// a well-known event emitted by it, into [`tables::AddressTransactions`]. The transaction that
+/// created each contract is recorded in [`tables::ContractCreators`].
+///
+/// Log topics are read from the receipts written by the
+/// [`ExecutionStage`][crate::stages::ExecutionStage].
+///
+/// This stage is optional and not part of [`StageId::ALL`]. Once it has run, the index is also
+/// kept up to date by [`HistoryWriter::update_history_indices`] when blocks are appended outside of
+/// the pipeline.
+#[derive(Debug)]
+pub struct IndexAddressTransactionsStage {
+    /// The maximum number of transactions to process before pushing the collected indices to
+    /// the [`Collector`].
+    pub chunk_size: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexAddressTransactionsStage {
+    /// Create new instance of [`IndexAddressTransactionsStage`].
+    pub const fn new(
+        config: IndexAddressTransactionsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { chunk_size: config.chunk_size, prune_mode, etl_config }
+    }
+}
+
+impl Default for IndexAddressTransactionsStage {
+    fn default() -> Self {
+        Self { chunk_size: 5_000_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexAddressTransactionsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressTransactions,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::AddressTransactions)?.is_none() {
+                    let tx_number = provider
+                        .block_body_indices(target_prunable_block)?
+                        .map(|indices| indices.last_tx_num());
+                    provider.save_prune_checkpoint(
+                        PruneSegment::AddressTransactions,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // On first sync we clear the tables, since it's faster to load the indices in append-only
+        // mode.
+        let first_sync = input.checkpoint().block_number == 0;
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressTransactions>()?;
+            provider.tx_ref().clear::<tables::ContractCreators>()?;
+        }
+
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+
+        loop {
+            let (tx_range, block_range, is_final_range) =
+                input.next_block_range_with_transaction_threshold(provider, self.chunk_size)?;
+
+            info!(target: "sync::stages::index_address_transactions::exec", ?tx_range, "Collecting indices");
+
+            let (appearances, creations) = provider.address_appearances_by_tx_range(tx_range)?;
+
+            let mut cache = HashMap::<Address, Vec<TxNumber>>::new();
+            for (address, tx_number) in appearances {
+                let indices = cache.entry(address).or_default();
+                // The sender can also be the recipient of the same transaction.
+                if indices.last() != Some(&tx_number) {
+                    indices.push(tx_number);
+                }
+            }
+            for (address, indices) in cache {
+                let last = *indices.last().expect("qed");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+
+            for (contract, tx_number) in creations {
+                provider.tx_ref().put::<tables::ContractCreators>(contract, tx_number)?;
+            }
+
+            input.checkpoint = Some(StageCheckpoint::new(*block_range.end()));
+
+            if is_final_range {
+                break
+            }
+        }
+
+        info!(target: "sync::stages::index_address_transactions::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressTransactions, _>(
+            provider.tx_ref(),
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        provider.unwind_address_transactions_indices(input.unwind_block_range())?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use reth_provider::AddressTransactionsReader;
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block, random_block_range},
+    };
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let genesis = random_block(&mut rng, 0, None, Some(0), None);
+        let mut blocks = random_block_range(&mut rng, 1..=100, genesis.hash(), 1..4);
+        blocks.insert(0, genesis);
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("failed to insert blocks");
+
+        let mut stage = IndexAddressTransactionsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(100), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(100), done: true });
+
+        let transactions = blocks
+            .iter()
+            .flat_map(|block| {
+                block
+                    .body
+                    .iter()
+                    .map(move |tx| (block.number, tx.recover_signer().unwrap(), tx.to()))
+            })
+            .collect::<Vec<_>>();
+
+        // Random senders and recipients are unique, so every address appears exactly once.
+        for (tx_number, (_, sender, to)) in transactions.iter().enumerate() {
+            let tx_number = tx_number as TxNumber;
+            assert_eq!(
+                provider.address_transactions_before(*sender, u64::MAX, 10).unwrap(),
+                vec![tx_number]
+            );
+            assert_eq!(
+                provider.address_transactions_from(*sender, 0, 10).unwrap(),
+                vec![tx_number]
+            );
+            assert!(provider
+                .address_transactions_from(*sender, tx_number + 1, 10)
+                .unwrap()
+                .is_empty());
+            if let Some(to) = to {
+                assert_eq!(
+                    provider.address_transactions_from(*to, 0, 10).unwrap(),
+                    vec![tx_number]
+                );
+            }
+        }
+
+        stage
+            .unwind(
+                &provider,
+                UnwindInput {
+                    checkpoint: StageCheckpoint::new(100),
+                    unwind_to: 50,
+                    bad_block: None,
+                },
+            )
+            .unwrap();
+
+        for (tx_number, (block_number, sender, _)) in transactions.iter().enumerate() {
+            let expected =
+                if *block_number <= 50 { vec![tx_number as TxNumber] } else { Vec::new() };
+            assert_eq!(
+                provider.address_transactions_before(*sender, u64::MAX, 10).unwrap(),
+                expected
+            );
+        }
+    }
+}
@@ -104,16 +104,15 @@ impl<DB: Database> Stage<DB> for IndexStorageHistoryStage {
         }
 
         info!(target: "sync::stages::index_storage_history::exec", ?first_sync, "Collecting indices");
-        let collector =
-            collect_history_indices::<_, tables::StorageChangeSets, tables::StoragesHistory, _>(
-                provider.tx_ref(),
-                BlockNumberAddress::range(range.clone()),
-                |AddressStorageKey((address, storage_key)), highest_block_number| {
-                    StorageShardedKey::new(address, storage_key, highest_block_number)
-                },
-                |(key, value)| (key.block_number(), AddressStorageKey((key.address(), value.key))),
-                &self.etl_config,
-            )?;
+        let collector = collect_history_indices::<tables::StoragesHistory, _, _>(
+            range.clone(),
+            |range| provider.storage_changesets(range),
+            |AddressStorageKey((address, storage_key)), highest_block_number| {
+                StorageShardedKey::new(address, storage_key, highest_block_number)
+            },
+            |(key, value)| (key.block_number(), AddressStorageKey((key.address(), value.key))),
+            &self.etl_config,
+        )?;
 
         info!(target: "sync::stages::index_storage_history::exec", "Loading indices into database");
         load_history_indices::<_, tables::StoragesHistory, _>(
@@ -278,6 +277,30 @@ mod tests {
         assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0])]));
     }
 
+    #[tokio::test]
+    async fn insert_index_from_static_file_changesets() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup
+        partial_setup(&db);
+        db.move_changesets_to_static_files(2).unwrap();
+
+        // run
+        run(&db, 5, None);
+
+        // verify
+        let table = cast(db.table::<tables::StoragesHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3, 4, 5])]));
+
+        // unwind
+        unwind(&db, 5, 1);
+
+        // verify
+        let table = cast(db.table::<tables::StoragesHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1])]));
+    }
+
     #[tokio::test]
     async fn insert_index_to_not_empty_shard() {
         // init
@@ -246,12 +246,14 @@ impl<DB: Database> Stage<DB> for MerkleStage {
             }
         } else {
             debug!(target: "sync::stages::merkle::exec", current = ?current_block_number, target = ?to_block, "Updating trie");
-            let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), range)
-                    .map_err(|e| {
-                        error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
-                        StageError::Fatal(Box::new(e))
-                    })?;
+            let prefix_sets = provider.changeset_prefix_sets(range)?;
+            let (root, updates) = StateRoot::from_tx(provider.tx_ref())
+                .with_prefix_sets(prefix_sets)
+                .root_with_updates()
+                .map_err(|e| {
+                    error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
+                    StageError::Fatal(Box::new(e))
+                })?;
             updates.flush(provider.tx_ref())?;
 
             let total_hashed_entries = (provider.count_entries::<tables::HashedAccounts>()? +
@@ -315,7 +317,10 @@ impl<DB: Database> Stage<DB> for MerkleStage {
 
         // Unwind trie only if there are transitions
         if !range.is_empty() {
-            let (block_root, updates) = StateRoot::incremental_root_with_updates(tx, range)
+            let prefix_sets = provider.changeset_prefix_sets(range)?;
+            let (block_root, updates) = StateRoot::from_tx(tx)
+                .with_prefix_sets(prefix_sets)
+                .root_with_updates()
                 .map_err(|e| StageError::Fatal(Box::new(e)))?;
 
             // Validate the calculated state root
@@ -460,6 +465,32 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    /// Update trie with the changesets of the first blocks moved to static files
+    #[tokio::test]
+    async fn execute_merkle_with_static_file_changesets() {
+        let (previous_stage, stage_progress) = (10, 1);
+
+        // Set up the runner
+        let mut runner = MerkleTestRunner::default();
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+        runner.db.move_changesets_to_static_files(6).expect("failed to move changesets");
+
+        let rx = runner.execute(input);
+
+        // Assert the successful result, the state root is validated within the stage
+        let result = rx.await.unwrap();
+        assert_matches!(
+            result,
+            Ok(ExecOutput { checkpoint: StageCheckpoint { block_number, .. }, done: true })
+                if block_number == previous_stage
+        );
+    }
+
     struct MerkleTestRunner {
         db: TestStageDB,
         clean_threshold: u64,
@@ -8,8 +8,8 @@ use reth_db_api::{
 };
 use reth_primitives::{Address, StaticFileSegment, TransactionSignedNoHash, TxNumber};
 use reth_provider::{
-    BlockReader, DatabaseProviderRW, HeaderProvider, ProviderError, PruneCheckpointReader,
-    StatsReader,
+    providers::StaticFileWriter, BlockReader, DatabaseProviderRW, HeaderProvider, ProviderError,
+    PruneCheckpointReader, StatsReader,
 };
 use reth_prune_types::PruneSegment;
 use reth_stages_api::{
@@ -123,6 +123,18 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
             .last_tx_num();
         provider.unwind_table_by_num::<tables::TransactionSenders>(latest_tx_id)?;
 
+        // Senders of finalized blocks may have been moved to static files, in which case they're
+        // pruned once the static file provider is committed.
+        let static_file_provider = provider.static_file_provider();
+        if let Some(highest_static_file_tx) = static_file_provider
+            .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+            .filter(|highest| *highest > latest_tx_id)
+        {
+            static_file_provider
+                .latest_writer(StaticFileSegment::TransactionSenders)?
+                .prune_transaction_senders(highest_static_file_tx - latest_tx_id, unwind_to)?;
+        }
+
         Ok(UnwindOutput {
             checkpoint: StageCheckpoint::new(unwind_to)
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
@@ -482,6 +494,75 @@ mod tests {
         );
     }
 
+    /// Unwinds senders that were moved to static files, and then takes the unwound blocks like
+    /// the blockchain tree does, making sure the static file senders are pruned exactly once.
+    #[test]
+    fn unwind_static_file_senders() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=5, B256::ZERO, 2..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let senders = blocks
+            .iter()
+            .flat_map(|block| &block.body)
+            .map(|transaction| transaction.recover_signer().expect("recover signer"))
+            .collect::<Vec<_>>();
+
+        // senders of blocks 0..=3 are in static files, the rest in the database
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::TransactionSenders).unwrap();
+        for block in &blocks[..=3] {
+            writer.increment_block(StaticFileSegment::TransactionSenders, block.number).unwrap();
+            let first_tx_num = block.number * 2;
+            for tx_number in first_tx_num..first_tx_num + 2 {
+                writer.append_transaction_sender(tx_number, senders[tx_number as usize]).unwrap();
+            }
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        db.insert_transaction_senders(
+            senders.iter().copied().enumerate().skip(8).map(|(tx, sender)| (tx as u64, sender)),
+        )
+        .expect("insert transaction senders");
+
+        let provider = db.factory.provider_rw().unwrap();
+        let mut stage = SenderRecoveryStage::default();
+        stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(5), unwind_to: 1, bad_block: None },
+            )
+            .expect("unwind");
+        assert_eq!(
+            provider.get_or_take_transaction_senders::<true>(4..=11).expect("take senders"),
+            senders
+                .iter()
+                .copied()
+                .enumerate()
+                .skip(4)
+                .take(4)
+                .map(|(tx, sender)| (tx as u64, sender))
+                .collect::<Vec<_>>()
+        );
+        provider.commit().expect("commit");
+        static_file_provider.commit().expect("commit static files");
+
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
+            Some(1)
+        );
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+            Some(3)
+        );
+        assert!(db.table::<tables::TransactionSenders>().unwrap().is_empty());
+        assert_eq!(db.factory.provider().unwrap().senders_by_tx_range(0..4).unwrap(), senders[..4]);
+    }
+
     struct SenderRecoveryTestRunner {
         db: TestStageDB,
         threshold: u64,
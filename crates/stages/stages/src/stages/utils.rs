@@ -10,15 +10,22 @@ use reth_db_api::{
 };
 use reth_etl::Collector;
 use reth_primitives::BlockNumber;
+use reth_provider::ProviderResult;
 use reth_stages_api::StageError;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
 const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
-/// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
-/// [`Collector`].
+/// Number of blocks whose changesets are read at once by [`collect_history_indices`].
+const CHANGESETS_BATCH_SIZE: u64 = 10_000;
+
+/// Collects all history (`H`) indices for the changesets (`C`) of a block range and stores them in
+/// a [`Collector`].
+///
+/// Changesets are fetched in batches of blocks with `changesets`, which is expected to read them
+/// from static files as well as from the database.
 ///
 /// ## Process
 /// The function utilizes a `HashMap` cache with a structure of `PartialKey` (`P`) (Address or
@@ -34,21 +41,17 @@ const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 ///
 /// As a result, the `Collector` will contain entries such as `(Address1.3, [1,2,3])` and
 /// `(Address1.300, [100,300])`. The entries may be stored across one or more files.
-pub(crate) fn collect_history_indices<TX, CS, H, P>(
-    tx: &TX,
-    range: impl RangeBounds<CS::Key>,
+pub(crate) fn collect_history_indices<H, P, C>(
+    range: RangeInclusive<BlockNumber>,
+    changesets: impl Fn(RangeInclusive<BlockNumber>) -> ProviderResult<Vec<C>>,
     sharded_key_factory: impl Fn(P, BlockNumber) -> H::Key,
-    partial_key_factory: impl Fn((CS::Key, CS::Value)) -> (u64, P),
+    partial_key_factory: impl Fn(C) -> (u64, P),
     etl_config: &EtlConfig,
 ) -> Result<Collector<H::Key, H::Value>, StageError>
 where
-    TX: DbTxMut + DbTx,
-    CS: Table,
     H: Table<Value = BlockNumberList>,
     P: Copy + Eq + Hash,
 {
-    let mut changeset_cursor = tx.cursor_read::<CS>()?;
-
     let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
     let mut cache: HashMap<P, Vec<u64>> = HashMap::new();
 
@@ -64,29 +67,37 @@ where
     };
 
     // observability
-    let total_changesets = tx.entries::<CS>()?;
-    let interval = (total_changesets / 1000).max(1);
+    let (start, end) = range.into_inner();
+    let total_blocks = end.saturating_sub(start) + 1;
 
     let mut flush_counter = 0;
     let mut current_block_number = u64::MAX;
-    for (idx, entry) in changeset_cursor.walk_range(range)?.enumerate() {
-        let (block_number, key) = partial_key_factory(entry?);
-        cache.entry(key).or_default().push(block_number);
+    let mut batch_start = start;
+    while batch_start <= end {
+        let batch_end = batch_start.saturating_add(CHANGESETS_BATCH_SIZE - 1).min(end);
 
-        if idx > 0 && idx % interval == 0 && total_changesets > 1000 {
-            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_changesets as f64) * 100.0), "Collecting indices");
+        if total_blocks > CHANGESETS_BATCH_SIZE {
+            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", ((batch_start - start) as f64 / total_blocks as f64) * 100.0), "Collecting indices");
         }
 
-        // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
-        if current_block_number != block_number {
-            current_block_number = block_number;
-            flush_counter += 1;
-            if flush_counter > DEFAULT_CACHE_THRESHOLD {
-                collect(&cache)?;
-                cache.clear();
-                flush_counter = 0;
+        for entry in changesets(batch_start..=batch_end)? {
+            let (block_number, key) = partial_key_factory(entry);
+            cache.entry(key).or_default().push(block_number);
+
+            // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
+            if current_block_number != block_number {
+                current_block_number = block_number;
+                flush_counter += 1;
+                if flush_counter > DEFAULT_CACHE_THRESHOLD {
+                    collect(&cache)?;
+                    cache.clear();
+                    flush_counter = 0;
+                }
             }
         }
+
+        let Some(next) = batch_end.checked_add(1) else { break };
+        batch_start = next;
     }
     collect(&cache)?;
 
@@ -9,7 +9,10 @@ use reth_db_api::{
     common::KeyValue,
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
     database::Database,
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    models::{
+        AccountBeforeTx, BlockNumberAddress, StorageBeforeTx, StoredBlockAccountChanges,
+        StoredBlockBodyIndices, StoredBlockStorageChanges,
+    },
     table::Table,
     transaction::{DbTx, DbTxMut},
     DatabaseError as DbError,
@@ -447,6 +450,61 @@ impl TestStageDB {
         })
     }
 
+    /// Moves the account and storage changesets of the blocks up to `to_block` to static files and
+    /// deletes them from the database, like the static file producer and the pruner do.
+    ///
+    /// The changeset static files are expected to be empty.
+    pub fn move_changesets_to_static_files(&self, to_block: BlockNumber) -> ProviderResult<()> {
+        let mut account_changes = BTreeMap::<BlockNumber, Vec<AccountBeforeTx>>::new();
+        for (block, account) in self.table::<tables::AccountChangeSets>()? {
+            account_changes.entry(block).or_default().push(account);
+        }
+        let mut storage_changes = BTreeMap::<BlockNumber, Vec<StorageBeforeTx>>::new();
+        for (key, entry) in self.table::<tables::StorageChangeSets>()? {
+            storage_changes.entry(key.block_number()).or_default().push(StorageBeforeTx {
+                address: key.address(),
+                key: entry.key,
+                value: entry.value,
+            });
+        }
+
+        let static_file_provider = self.factory.static_file_provider();
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets)?;
+            for block in 0..=to_block {
+                let changes = account_changes.remove(&block).unwrap_or_default();
+                writer.append_account_changeset(block, StoredBlockAccountChanges { changes })?;
+            }
+            writer.commit()?;
+        }
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets)?;
+            for block in 0..=to_block {
+                let changes = storage_changes.remove(&block).unwrap_or_default();
+                writer.append_storage_changeset(block, StoredBlockStorageChanges { changes })?;
+            }
+            writer.commit()?;
+        }
+
+        self.commit(|tx| {
+            let mut cursor = tx.cursor_write::<tables::AccountChangeSets>()?;
+            let mut walker = cursor.walk_range(..=to_block)?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+
+            let mut cursor = tx.cursor_write::<tables::StorageChangeSets>()?;
+            let mut walker =
+                cursor.walk_range(..BlockNumberAddress((to_block + 1, Address::ZERO)))?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+            Ok(())
+        })
+    }
+
     pub fn insert_history<I>(&self, changesets: I, _block_offset: Option<u64>) -> ProviderResult<()>
     where
         I: IntoIterator<Item = ChangeSet>,
@@ -0,0 +1,96 @@
+use crate::segments::{
+    changes_by_block, changeset_dataset_range, create_changeset_static_file, prepare_jar, Segment,
+};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, models::StoredBlockAccountChanges, table::Compress,
+    transaction::DbTx,
+};
+use reth_primitives::{static_file::SegmentConfig, BlockNumber, StaticFileSegment};
+use reth_provider::{
+    providers::{StaticFileProvider, StaticFileWriter},
+    DatabaseProviderRO,
+};
+use reth_storage_errors::provider::ProviderResult;
+use std::{ops::RangeInclusive, path::Path};
+
+/// Static File segment responsible for [`StaticFileSegment::AccountChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct AccountChangeSets;
+
+impl AccountChangeSets {
+    /// Returns the account changes of every block in the range, including the blocks without
+    /// changes.
+    fn block_changes<C: DbCursorRO<tables::AccountChangeSets>>(
+        cursor: &mut C,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<
+        impl Iterator<Item = ProviderResult<(BlockNumber, StoredBlockAccountChanges)>> + '_,
+    > {
+        let walker = cursor.walk_range(block_range.clone())?;
+        Ok(changes_by_block(block_range, walker, |block| *block).map(|entry| {
+            entry.map(|(block, changes)| {
+                let changes = changes.into_iter().map(|(_, change)| change).collect();
+                (block, StoredBlockAccountChanges { changes })
+            })
+        }))
+    }
+}
+
+impl<DB: Database> Segment<DB> for AccountChangeSets {
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::AccountChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: DatabaseProviderRO<DB>,
+        static_file_provider: StaticFileProvider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::AccountChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+        for entry in Self::block_changes(&mut changesets_cursor, block_range)? {
+            let (block, changes) = entry?;
+
+            let _static_file_block = static_file_writer.append_account_changeset(block, changes)?;
+            debug_assert_eq!(_static_file_block, block);
+        }
+
+        Ok(())
+    }
+
+    fn create_static_file_file(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: &Path,
+        config: SegmentConfig,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let range_len = block_range.clone().count();
+        let jar = prepare_jar::<DB, 1>(
+            provider,
+            directory,
+            StaticFileSegment::AccountChangeSets,
+            config,
+            block_range.clone(),
+            range_len,
+            || {
+                let mut cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+                let dataset =
+                    Self::block_changes(&mut cursor, changeset_dataset_range(&block_range))?
+                        .map(|entry| entry.map(|(_, changes)| changes.compress()))
+                        .collect::<ProviderResult<_>>()?;
+                Ok([dataset])
+            },
+        )?;
+
+        let mut cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+        let rows = Self::block_changes(&mut cursor, block_range.clone())?
+            .map(|entry| entry.map(|(_, changes)| changes.compress()));
+
+        create_changeset_static_file(provider, config, block_range, rows, jar)
+    }
+}
@@ -9,8 +9,19 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
-use reth_db::{RawKey, RawTable};
-use reth_db_api::{cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx};
+mod account_changesets;
+pub use account_changesets::AccountChangeSets;
+
+mod storage_changesets;
+pub use storage_changesets::StorageChangeSets;
+
+mod transaction_senders;
+pub use transaction_senders::TransactionSenders;
+
+use reth_db::{tables, RawKey, RawTable};
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, DatabaseError,
+};
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{
     static_file::{
@@ -23,7 +34,7 @@ use reth_provider::{
     providers::StaticFileProvider, DatabaseProviderRO, ProviderError, TransactionsProviderExt,
 };
 use reth_storage_errors::provider::ProviderResult;
-use std::{ops::RangeInclusive, path::Path};
+use std::{error::Error as StdError, ops::RangeInclusive, path::Path};
 
 pub(crate) type Rows<const COLUMNS: usize> = [Vec<Vec<u8>>; COLUMNS];
 
@@ -64,8 +75,12 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
     prepare_compression: impl Fn() -> ProviderResult<Rows<COLUMNS>>,
 ) -> ProviderResult<NippyJar<SegmentHeader>> {
     let tx_range = match segment {
-        StaticFileSegment::Headers => None,
-        StaticFileSegment::Receipts | StaticFileSegment::Transactions => {
+        StaticFileSegment::Headers |
+        StaticFileSegment::AccountChangeSets |
+        StaticFileSegment::StorageChangeSets => None,
+        StaticFileSegment::Receipts |
+        StaticFileSegment::Transactions |
+        StaticFileSegment::TransactionSenders => {
             Some(provider.transaction_range_by_block_range(block_range.clone())?.into())
         }
     };
@@ -117,3 +132,58 @@ pub(crate) fn dataset_for_compression<DB: Database, T: Table<Key = u64>>(
         .map(|row| row.map(|(_key, value)| value.into_value()).expect("should exist"))
         .collect::<Vec<_>>())
 }
+
+/// Groups the rows of a changeset table walked over `block_range` by block.
+///
+/// Yields the changes of every block in the range in order, including the blocks without changes.
+pub(crate) fn changes_by_block<K, V>(
+    block_range: RangeInclusive<BlockNumber>,
+    rows: impl Iterator<Item = Result<(K, V), DatabaseError>>,
+    block_number: impl Fn(&K) -> BlockNumber,
+) -> impl Iterator<Item = ProviderResult<(BlockNumber, Vec<(K, V)>)>> {
+    let mut rows = rows.peekable();
+    block_range.map(move |block| {
+        let mut changes = Vec::new();
+        while let Some(row) = rows
+            .next_if(|row| row.as_ref().map_or(true, |(key, _)| block_number(key) == block))
+            .transpose()?
+        {
+            changes.push(row);
+        }
+        Ok((block, changes))
+    })
+}
+
+/// Returns the most recent blocks (at most 1000) of the range, used to generate the dataset to
+/// train a zstd dictionary for changeset segments.
+pub(crate) fn changeset_dataset_range(
+    block_range: &RangeInclusive<BlockNumber>,
+) -> RangeInclusive<BlockNumber> {
+    block_range.end().saturating_sub(999).max(*block_range.start())..=*block_range.end()
+}
+
+/// Creates a changeset static file with one row of compressed changes per block of the range.
+/// Canonical block hashes are used as keys for filters & PHF.
+pub(crate) fn create_changeset_static_file<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    config: SegmentConfig,
+    block_range: RangeInclusive<BlockNumber>,
+    rows: impl Iterator<Item = ProviderResult<Vec<u8>>>,
+    mut jar: NippyJar<SegmentHeader>,
+) -> ProviderResult<()> {
+    let range_len = block_range.clone().count();
+
+    if config.filters.has_filters() {
+        let mut cursor = provider.tx_ref().cursor_read::<RawTable<tables::CanonicalHeaders>>()?;
+        let hashes = cursor
+            .walk(Some(RawKey::from(*block_range.start())))?
+            .take(range_len)
+            .map(|row| row.map(|(_key, value)| value.into_value()).map_err(|e| e.into()));
+        jar.prepare_index(hashes, range_len).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+    }
+
+    let rows = rows.map(|row| row.map_err(|e| Box::new(e) as Box<dyn StdError + Send + Sync>));
+    jar.freeze(vec![rows], range_len as u64).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+    Ok(())
+}
@@ -61,12 +61,20 @@ pub struct StaticFileTargets {
     headers: Option<RangeInclusive<BlockNumber>>,
     receipts: Option<RangeInclusive<BlockNumber>>,
     transactions: Option<RangeInclusive<BlockNumber>>,
+    account_changesets: Option<RangeInclusive<BlockNumber>>,
+    storage_changesets: Option<RangeInclusive<BlockNumber>>,
+    transaction_senders: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
     /// Returns `true` if any of the targets are [Some].
     pub const fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some() ||
+            self.transaction_senders.is_some()
     }
 
     // Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -76,6 +84,9 @@ impl StaticFileTargets {
             (self.headers.as_ref(), static_files.headers),
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
+            (self.account_changesets.as_ref(), static_files.account_changesets),
+            (self.storage_changesets.as_ref(), static_files.storage_changesets),
+            (self.transaction_senders.as_ref(), static_files.transaction_senders),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -134,6 +145,15 @@ impl<DB: Database> StaticFileProducerInner<DB> {
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_changesets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_changesets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.transaction_senders.clone() {
+            segments.push((Box::new(segments::TransactionSenders), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -199,6 +219,38 @@ impl<DB: Database> StaticFileProducerInner<DB> {
                     finalized_block_number,
                 )
             }),
+            // StaticFile changesets only if they're not pruned according to the user configuration
+            account_changesets: if self.prune_modes.account_history.is_none() {
+                finalized_block_numbers.account_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            storage_changesets: if self.prune_modes.storage_history.is_none() {
+                finalized_block_numbers.storage_changesets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_changesets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            // StaticFile senders only if they're not pruned according to the user configuration
+            transaction_senders: if self.prune_modes.sender_recovery.is_none() {
+                finalized_block_numbers.transaction_senders.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.transaction_senders,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -229,17 +281,23 @@ mod tests {
         StaticFileProducer, StaticFileProducerInner, StaticFileTargets,
     };
     use assert_matches::assert_matches;
-    use reth_db::{test_utils::TempDatabase, DatabaseEnv};
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db::{tables, test_utils::TempDatabase, DatabaseEnv};
+    use reth_db_api::{
+        database::Database,
+        transaction::{DbTx, DbTxMut},
+    };
     use reth_primitives::{static_file::HighestStaticFiles, StaticFileSegment, B256, U256};
     use reth_provider::{
         providers::StaticFileWriter, ProviderError, ProviderFactory, StaticFileProviderFactory,
+        TransactionsProvider,
     };
     use reth_prune_types::PruneModes;
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::{
         generators,
-        generators::{random_block_range, random_receipt},
+        generators::{
+            random_block_range, random_changeset_range, random_eoa_accounts, random_receipt,
+        },
     };
     use std::{
         sync::{mpsc::channel, Arc},
@@ -270,13 +328,19 @@ mod tests {
         tx.commit().expect("commit tx");
 
         let mut receipts = Vec::new();
+        let mut senders = Vec::new();
         for block in &blocks {
             for transaction in &block.body {
                 receipts
                     .push((receipts.len() as u64, random_receipt(&mut rng, transaction, Some(0))));
+                senders.push((
+                    senders.len() as u64,
+                    transaction.recover_signer().expect("recover signer"),
+                ));
             }
         }
         db.insert_receipts(receipts).expect("insert receipts");
+        db.insert_transaction_senders(senders).expect("insert transaction senders");
 
         let provider_factory = db.factory;
         (provider_factory, db.temp_static_files_dir)
@@ -294,6 +358,7 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -301,13 +366,21 @@ mod tests {
             StaticFileTargets {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
-                transactions: Some(0..=1)
+                transactions: Some(0..=1),
+                account_changesets: None,
+                storage_changesets: None,
+                transaction_senders: None
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+            HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -315,6 +388,7 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -322,13 +396,21 @@ mod tests {
             StaticFileTargets {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
-                transactions: Some(2..=3)
+                transactions: Some(2..=3),
+                account_changesets: None,
+                storage_changesets: None,
+                transaction_senders: None
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -336,6 +418,7 @@ mod tests {
                 headers: Some(4),
                 receipts: Some(4),
                 transactions: Some(4),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -343,7 +426,10 @@ mod tests {
             StaticFileTargets {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
-                transactions: Some(4..=4)
+                transactions: Some(4..=4),
+                account_changesets: None,
+                storage_changesets: None,
+                transaction_senders: None
             }
         );
         assert_matches!(
@@ -352,8 +438,137 @@ mod tests {
         );
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn run_changesets() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 0..1);
+        let accounts = random_eoa_accounts(&mut rng, 3);
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..3,
+            0..16,
+        );
+        db.insert_changesets(changesets, None).expect("insert changesets");
+
+        let account_changesets = db.table::<tables::AccountChangeSets>().unwrap();
+        let storage_changesets = db.table::<tables::StorageChangeSets>().unwrap();
+        assert!(!account_changesets.is_empty());
+        assert!(!storage_changesets.is_empty());
+
+        let static_file_producer =
+            StaticFileProducerInner::new(db.factory.clone(), PruneModes::default());
+
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
+                ..Default::default()
+            })
+            .expect("get static file targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                account_changesets: Some(0..=3),
+                storage_changesets: Some(0..=3),
+                transaction_senders: None
+            }
+        );
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = db.factory.static_file_provider();
+        let highest_static_files = static_file_provider.get_highest_static_files();
+        assert_eq!(highest_static_files.account_changesets, Some(3));
+        assert_eq!(highest_static_files.storage_changesets, Some(3));
+
+        // Every block has a row, and the rows hold the same changes as the database
+        for block in 0..=3 {
+            assert!(static_file_provider.account_block_changeset(block).unwrap().is_some());
+            assert!(static_file_provider.storage_block_changeset(block).unwrap().is_some());
+        }
+        assert_eq!(
+            static_file_provider.account_changesets_range(0..=3).unwrap(),
+            account_changesets
+        );
+        assert_eq!(
+            static_file_provider.storage_changesets_range(0..=3).unwrap(),
+            storage_changesets
+        );
+    }
+
+    #[test]
+    fn run_transaction_senders() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let senders = provider_factory.provider().unwrap().senders_by_tx_range(0..8).unwrap();
+        assert_eq!(senders.len(), 8);
+
+        let static_file_producer =
+            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                transaction_senders: Some(1),
+                ..Default::default()
+            })
+            .expect("get static file targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                account_changesets: None,
+                storage_changesets: None,
+                transaction_senders: Some(0..=1)
+            }
+        );
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = provider_factory.static_file_provider();
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
+            Some(1)
+        );
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+            Some(3)
+        );
+        assert_eq!(
+            static_file_provider.transaction_senders_range(0..=3).unwrap(),
+            (0..4).zip(senders[..4].iter().copied()).collect::<Vec<_>>()
         );
+
+        // Delete the copied senders from the database like the pruner does, so that they can only
+        // be read from static files
+        let tx = provider_factory.db_ref().tx_mut().expect("init tx");
+        for tx_number in 0..4 {
+            tx.delete::<tables::TransactionSenders>(tx_number, None).expect("delete sender");
+        }
+        tx.commit().expect("commit tx");
+
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(provider.senders_by_tx_range(0..8).unwrap(), senders);
+        assert_eq!(provider.senders_by_tx_range(2..6).unwrap(), senders[2..6]);
+        for (tx_number, sender) in senders.iter().enumerate() {
+            assert_eq!(provider.transaction_sender(tx_number as u64).unwrap(), Some(*sender));
+        }
     }
 
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
@@ -380,6 +595,7 @@ mod tests {
                         headers: Some(1),
                         receipts: Some(1),
                         transactions: Some(1),
+                        ..Default::default()
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
@@ -34,6 +34,15 @@ pub enum StaticFileSegment {
     #[strum(serialize = "receipts")]
     /// Static File segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "account-changesets")]
+    /// Static File segment responsible for the `AccountChangeSets` table.
+    AccountChangeSets,
+    #[strum(serialize = "storage-changesets")]
+    /// Static File segment responsible for the `StorageChangeSets` table.
+    StorageChangeSets,
+    #[strum(serialize = "transaction-senders")]
+    /// Static File segment responsible for the `TransactionSenders` table.
+    TransactionSenders,
 }
 
 impl StaticFileSegment {
@@ -43,6 +52,9 @@ impl StaticFileSegment {
             Self::Headers => "headers",
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
+            Self::AccountChangeSets => "account-changesets",
+            Self::StorageChangeSets => "storage-changesets",
+            Self::TransactionSenders => "transaction-senders",
         }
     }
 
@@ -57,7 +69,12 @@ impl StaticFileSegment {
         };
 
         match self {
-            Self::Headers | Self::Transactions | Self::Receipts => default_config,
+            Self::Headers |
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets |
+            Self::TransactionSenders => default_config,
         }
     }
 
@@ -65,7 +82,11 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets |
+            Self::TransactionSenders => 1,
         }
     }
 
@@ -138,6 +159,22 @@ impl StaticFileSegment {
     pub const fn is_receipts(&self) -> bool {
         matches!(self, Self::Receipts)
     }
+
+    /// Returns `true` if the segment is `StaticFileSegment::TransactionSenders`.
+    pub const fn is_transaction_senders(&self) -> bool {
+        matches!(self, Self::TransactionSenders)
+    }
+
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_changesets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
+    /// Returns `true` if the segment has one row per block instead of one row per transaction.
+    pub const fn is_block_based(&self) -> bool {
+        matches!(self, Self::Headers | Self::AccountChangeSets | Self::StorageChangeSets)
+    }
 }
 
 /// A segment header that contains information common to all segments. Used for storage.
@@ -239,8 +276,12 @@ impl SegmentHeader {
     /// Increments tx end range depending on segment
     pub fn increment_tx(&mut self) {
         match self.segment {
-            StaticFileSegment::Headers => (),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => (),
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::TransactionSenders => {
                 if let Some(tx_range) = &mut self.tx_range {
                     tx_range.end += 1;
                 } else {
@@ -253,7 +294,9 @@ impl SegmentHeader {
     /// Removes `num` elements from end of tx or block range.
     pub fn prune(&mut self, num: u64) {
         match self.segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 if let Some(range) = &mut self.block_range {
                     if num > range.end {
                         self.block_range = None;
@@ -262,7 +305,9 @@ impl SegmentHeader {
                     }
                 };
             }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::TransactionSenders => {
                 if let Some(range) = &mut self.tx_range {
                     if num > range.end {
                         self.tx_range = None;
@@ -297,8 +342,12 @@ impl SegmentHeader {
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> Option<u64> {
         match self.segment {
-            StaticFileSegment::Headers => self.block_start(),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => self.tx_start(),
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.block_start(),
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::TransactionSenders => self.tx_start(),
         }
     }
 }
@@ -377,6 +426,24 @@ mod tests {
                 "static_file_transactions_1123233_11223233",
                 None,
             ),
+            (
+                StaticFileSegment::AccountChangeSets,
+                0..=499_999,
+                "static_file_account-changesets_0_499999",
+                None,
+            ),
+            (
+                StaticFileSegment::StorageChangeSets,
+                500_000..=999_999,
+                "static_file_storage-changesets_500000_999999",
+                None,
+            ),
+            (
+                StaticFileSegment::TransactionSenders,
+                1_000_000..=1_499_999,
+                "static_file_transaction-senders_1000000_1499999",
+                None,
+            ),
             (
                 StaticFileSegment::Headers,
                 2..=30,
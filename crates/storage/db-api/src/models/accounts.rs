@@ -8,7 +8,7 @@ use crate::{
     DatabaseError,
 };
 use reth_codecs::{derive_arbitrary, Compact};
-use reth_primitives::{Account, Address, BlockNumber, Buf, StorageKey};
+use reth_primitives::{Account, Address, BlockNumber, Buf, StorageEntry, StorageKey, U256};
 use serde::{Deserialize, Serialize};
 
 /// Account as it is saved in the database.
@@ -57,6 +57,90 @@ impl Compact for AccountBeforeTx {
     }
 }
 
+/// Storage slot of an account before a transaction, as it is saved in the storage changeset static
+/// files.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct StorageBeforeTx {
+    /// Address of the account.
+    pub address: Address,
+    /// Storage key.
+    pub key: StorageKey,
+    /// Value of the storage slot before the transaction.
+    pub value: U256,
+}
+
+impl StorageBeforeTx {
+    /// Returns the storage entry of the slot.
+    pub const fn entry(&self) -> StorageEntry {
+        StorageEntry { key: self.key, value: self.value }
+    }
+}
+
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        self.entry().to_compact(buf) + 20
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        let (entry, buf) = StorageEntry::from_compact(&buf[20..], len - 20);
+        (Self { address, key: entry.key, value: entry.value }, buf)
+    }
+}
+
+/// Account changes of a block, as they are saved in the account changeset static files.
+///
+/// Changes are sorted by address, like in the `AccountChangeSets` table.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+pub struct StoredBlockAccountChanges {
+    /// Accounts before the block.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+impl Compact for StoredBlockAccountChanges {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.changes.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = Vec::from_compact(buf, len);
+        (Self { changes }, buf)
+    }
+}
+
+/// Storage changes of a block, as they are saved in the storage changeset static files.
+///
+/// Changes are sorted by address and storage key, like in the `StorageChangeSets` table.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+pub struct StoredBlockStorageChanges {
+    /// Storage slots before the block.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+impl Compact for StoredBlockStorageChanges {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.changes.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = Vec::from_compact(buf, len);
+        (Self { changes }, buf)
+    }
+}
+
 /// [`BlockNumber`] concatenated with [`Address`].
 ///
 /// Since it's used as a key, it isn't compressed when encoding it.
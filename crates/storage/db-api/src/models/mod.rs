@@ -216,6 +216,8 @@ impl_compression_for_compact!(
     StoredBlockWithdrawals,
     Bytecode,
     AccountBeforeTx,
+    StoredBlockAccountChanges,
+    StoredBlockStorageChanges,
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
@@ -0,0 +1,194 @@
+//! Reth state dump utility functions.
+
+use crate::init::{GenesisAccountWithAddress, StateRoot};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    database::Database,
+    transaction::DbTx,
+};
+use reth_primitives::{Address, BlockNumber, GenesisAccount, B256};
+use reth_provider::{
+    AccountExtReader, HeaderProvider, ProviderError, ProviderFactory, StageCheckpointReader,
+    StateProvider, StorageReader,
+};
+use reth_stages_types::StageId;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+};
+use tracing::info;
+
+/// Soft limit for the number of written accounts after which to log progress.
+const SOFT_LIMIT_COUNT_WRITTEN_ACCOUNTS: usize = 1_000_000;
+
+/// Writes the state at the given block to the writer in the JSONL format read by
+/// [`init_from_state_dump`](crate::init::init_from_state_dump), and returns the number of
+/// written accounts.
+///
+/// The first line contains the state root of the block, the remaining lines contain the accounts
+/// ordered by address.
+///
+/// The state of blocks below the execution checkpoint is read from the account and storage
+/// history, which must not have been pruned up to the block.
+pub fn write_state_dump<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    block: BlockNumber,
+    mut writer: impl Write,
+) -> eyre::Result<usize> {
+    let provider = factory.provider()?;
+    let state_root = provider
+        .header_by_number(block)?
+        .ok_or(ProviderError::HeaderNotFound(block.into()))?
+        .state_root;
+    let tip = provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+    if block > tip {
+        eyre::bail!("state of block {block} is not available, execution is at block {tip}")
+    }
+
+    // accounts and storage slots that were changed after the block may not be in the plain state
+    // anymore
+    let mut changed_accounts = provider.changed_accounts_with_range(block + 1..=tip)?;
+    let changed_storage = provider.changed_storages_with_range(block + 1..=tip)?;
+    changed_accounts.extend(changed_storage.keys().copied());
+
+    let tx = provider.tx_ref();
+
+    serde_json::to_writer(&mut writer, &StateRoot { root: state_root })?;
+    writeln!(writer)?;
+
+    let state = factory.history_by_block_number(block)?;
+    let mut dumper = StateDumper {
+        state: state.as_ref(),
+        plain_storage: tx.cursor_dup_read::<tables::PlainStorageState>()?,
+        changed_storage,
+        writer,
+        accounts: 0,
+    };
+
+    // merge the addresses of the plain state with the changed addresses, in order
+    let mut changed_accounts = changed_accounts.into_iter().peekable();
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, _) = entry?;
+        while let Some(changed) = changed_accounts.next_if(|changed| *changed < address) {
+            dumper.write_account(changed)?;
+        }
+        changed_accounts.next_if_eq(&address);
+        dumper.write_account(address)?;
+    }
+    for changed in changed_accounts {
+        dumper.write_account(changed)?;
+    }
+    dumper.writer.flush()?;
+
+    info!(target: "reth::cli", block, accounts = dumper.accounts, "Wrote state dump");
+
+    Ok(dumper.accounts)
+}
+
+/// Writes the accounts of a state to a state dump.
+struct StateDumper<'a, C, W> {
+    /// The state at the dumped block.
+    state: &'a dyn StateProvider,
+    /// Cursor over the plain storage.
+    plain_storage: C,
+    /// The storage slots that were changed after the dumped block, by address.
+    changed_storage: BTreeMap<Address, BTreeSet<B256>>,
+    /// The writer of the state dump.
+    writer: W,
+    /// The number of written accounts.
+    accounts: usize,
+}
+
+impl<'a, C: DbDupCursorRO<tables::PlainStorageState>, W: Write> StateDumper<'a, C, W> {
+    /// Writes the account with the given address if it exists at the dumped block.
+    fn write_account(&mut self, address: Address) -> eyre::Result<()> {
+        let Some(account) = self.state.basic_account(address)? else { return Ok(()) };
+
+        let mut slots = self.changed_storage.remove(&address).unwrap_or_default();
+        for entry in self.plain_storage.walk_dup(Some(address), None)? {
+            slots.insert(entry?.1.key);
+        }
+        let mut storage = BTreeMap::new();
+        for slot in slots {
+            let value = self.state.storage(address, slot)?.unwrap_or_default();
+            if !value.is_zero() {
+                storage.insert(slot, B256::from(value));
+            }
+        }
+
+        let code = match account.bytecode_hash {
+            Some(hash) => self.state.bytecode_by_hash(hash)?.map(|code| code.original_bytes()),
+            None => None,
+        };
+
+        let genesis_account = GenesisAccount {
+            nonce: Some(account.nonce),
+            balance: account.balance,
+            code,
+            storage: (!storage.is_empty()).then_some(storage),
+            ..Default::default()
+        };
+        serde_json::to_writer(
+            &mut self.writer,
+            &GenesisAccountWithAddress { genesis_account, address },
+        )?;
+        writeln!(self.writer)?;
+
+        self.accounts += 1;
+        if self.accounts % SOFT_LIMIT_COUNT_WRITTEN_ACCOUNTS == 0 {
+            info!(target: "reth::cli", accounts = self.accounts, "Writing state dump");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::{init_from_state_dump, init_genesis};
+    use reth_config::config::EtlConfig;
+    use reth_primitives::{Bytes, Chain, ChainSpec, Genesis, U256};
+    use reth_provider::test_utils::create_test_provider_factory_with_chain_spec;
+    use std::sync::Arc;
+
+    fn chain_spec(alloc: BTreeMap<Address, GenesisAccount>) -> Arc<ChainSpec> {
+        Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis { alloc, ..Default::default() },
+            hardforks: BTreeMap::default(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn dump_and_init_state() {
+        let address = Address::with_last_byte(1);
+        let account = GenesisAccount {
+            nonce: Some(1),
+            balance: U256::from(2),
+            code: Some(Bytes::from_static(&[0x60, 0x00])),
+            storage: Some(BTreeMap::from([(B256::with_last_byte(3), B256::with_last_byte(4))])),
+            ..Default::default()
+        };
+        let chain_spec = chain_spec(BTreeMap::from([(address, account.clone())]));
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(factory.clone()).unwrap();
+
+        let mut dump = Vec::new();
+        assert_eq!(write_state_dump(&factory, 0, &mut dump).unwrap(), 1);
+
+        let mut lines = std::str::from_utf8(&dump).unwrap().lines();
+        let root: StateRoot = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(root.root, chain_spec.genesis_header().state_root);
+        let line: GenesisAccountWithAddress = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(line.address, address);
+        assert_eq!(line.genesis_account, account);
+        assert!(lines.next().is_none());
+
+        // the dump initializes the same state on top of an empty genesis
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec(BTreeMap::new()));
+        init_genesis(factory.clone()).unwrap();
+        init_from_state_dump(dump.as_slice(), factory, EtlConfig::default()).unwrap();
+    }
+}
@@ -1,10 +1,15 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{
+    AccountChangeSetMask, ReceiptMask, StorageChangeSetMask, TransactionMask, TransactionSenderMask,
+};
 use crate::{
     add_static_file_mask,
     static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
-    HeaderTerminalDifficulties, RawValue, Receipts, Transactions,
+    HeaderTerminalDifficulties, RawValue, Receipts, TransactionSenders, Transactions,
+};
+use reth_db_api::{
+    models::{StoredBlockAccountChanges, StoredBlockStorageChanges},
+    table::Table,
 };
-use reth_db_api::table::Table;
 use reth_primitives::{BlockHash, Header};
 
 // HEADER MASKS
@@ -20,3 +25,12 @@ add_static_file_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 // TRANSACTION MASKS
 add_static_file_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
 add_static_file_mask!(TransactionMask, RawValue<<Transactions as Table>::Value>, 0b1);
+
+// ACCOUNT CHANGESET MASKS
+add_static_file_mask!(AccountChangeSetMask, StoredBlockAccountChanges, 0b1);
+
+// STORAGE CHANGESET MASKS
+add_static_file_mask!(StorageChangeSetMask, StoredBlockStorageChanges, 0b1);
+
+// TRANSACTION SENDER MASKS
+add_static_file_mask!(TransactionSenderMask, <TransactionSenders as Table>::Value, 0b1);
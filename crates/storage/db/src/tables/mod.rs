@@ -360,6 +360,24 @@ tables! {
     /// Code example can be found in `reth_provider::HistoricalStateProviderRef`
     table StoragesHistory<Key = StorageShardedKey, Value = BlockNumberList>;
 
+    /// Stores pointers to the transactions each address appeared in, either as the sender, the
+    /// recipient, the contract created by the transaction, the emitter of an event or an indexed
+    /// address argument of a well-known event emitted by it.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], except that the shards contain transaction
+    /// numbers instead of block numbers, and the last shard key of an address contains `u64::MAX`.
+    ///
+    /// This index is optional and only maintained once the
+    /// `IndexAddressTransactionsStage` has been enabled.
+    table AddressTransactions<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the number of the transaction that deployed the contract at an address.
+    ///
+    /// Only contracts deployed by a top-level contract creation transaction are recorded, contracts
+    /// deployed by another contract are found by tracing instead. Maintained together with
+    /// [`AddressTransactions`].
+    table ContractCreators<Key = Address, Value = TxNumber>;
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -134,6 +134,9 @@ pub enum ProviderError {
     /// Storage lock error.
     #[error(transparent)]
     StorageLockError(#[from] crate::lockfile::StorageLockError),
+    /// State that was accessed during stateless execution is not part of the execution witness.
+    #[error("{0} is missing from the execution witness")]
+    MissingWitnessState(String),
 }
 
 impl From<reth_fs_util::FsPathError> for ProviderError {
@@ -45,6 +45,21 @@ pub trait Compression: Serialize + for<'a> Deserialize<'a> {
 pub enum Compressors {
     Zstd(Zstd),
     Lz4(Lz4),
+    /// A compressor per column. Columns without one are stored uncompressed.
+    PerColumn(Vec<Option<Compressors>>),
+}
+
+impl Compressors {
+    /// Returns the compressor used by `column` alongside the index of its dictionary, if the column
+    /// is compressed.
+    pub fn column(&self, column: usize) -> Option<(&Self, usize)> {
+        match self {
+            Self::PerColumn(compressors) => {
+                compressors.get(column)?.as_ref().map(|compressor| (compressor, 0))
+            }
+            _ => Some((self, column)),
+        }
+    }
 }
 
 impl Compression for Compressors {
@@ -52,12 +67,14 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.decompress_to(value, dest),
             Self::Lz4(lz4) => lz4.decompress_to(value, dest),
+            Self::PerColumn(_) => Err(NippyJarError::PerColumnCompression),
         }
     }
     fn decompress(&self, value: &[u8]) -> Result<Vec<u8>, NippyJarError> {
         match self {
             Self::Zstd(zstd) => zstd.decompress(value),
             Self::Lz4(lz4) => lz4.decompress(value),
+            Self::PerColumn(_) => Err(NippyJarError::PerColumnCompression),
         }
     }
 
@@ -67,6 +84,7 @@ impl Compression for Compressors {
             let result = match self {
                 Self::Zstd(zstd) => zstd.compress_to(src, dest),
                 Self::Lz4(lz4) => lz4.compress_to(src, dest),
+                Self::PerColumn(_) => Err(NippyJarError::PerColumnCompression),
             };
 
             match result {
@@ -85,6 +103,7 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.compress(src),
             Self::Lz4(lz4) => lz4.compress(src),
+            Self::PerColumn(_) => Err(NippyJarError::PerColumnCompression),
         }
     }
 
@@ -92,6 +111,7 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.is_ready(),
             Self::Lz4(lz4) => lz4.is_ready(),
+            Self::PerColumn(compressors) => compressors.iter().flatten().all(|c| c.is_ready()),
         }
     }
 
@@ -102,6 +122,19 @@ impl Compression for Compressors {
         match self {
             Self::Zstd(zstd) => zstd.prepare_compression(columns),
             Self::Lz4(lz4) => lz4.prepare_compression(columns),
+            Self::PerColumn(compressors) => {
+                if columns.len() != compressors.len() {
+                    return Err(NippyJarError::ColumnLenMismatch(compressors.len(), columns.len()))
+                }
+
+                // Every column compressor only gets its own column to prepare from
+                for (compressor, column) in compressors.iter_mut().zip(columns) {
+                    if let Some(compressor) = compressor {
+                        compressor.prepare_compression(vec![column])?;
+                    }
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -84,6 +84,24 @@ impl Zstd {
         }
     }
 
+    /// Creates a [`Compressor`] with the dictionary at `index`, if using dictionaries and they
+    /// haven't been loaded for decompression only.
+    pub(crate) fn dictionary_compressor(
+        &self,
+        index: usize,
+    ) -> Result<Option<Compressor<'static>>, NippyJarError> {
+        if !self.use_dict {
+            return Ok(None)
+        }
+
+        match self.dictionaries.as_ref().and_then(|dictionaries| dictionaries.get(index)) {
+            Some(ZstdDictionary::Raw(dictionary)) => {
+                Ok(Some(Compressor::with_dictionary(self.level, dictionary)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// Compresses a value using a dictionary. Reserves additional capacity for `buffer` if
     /// necessary.
     pub fn compress_with_dictionary(
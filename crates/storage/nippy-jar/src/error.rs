@@ -53,6 +53,8 @@ pub enum NippyJarError {
     DictionaryNotLoaded,
     #[error("it's not possible to generate a compressor after loading a dictionary.")]
     CompressorNotAllowed,
+    #[error("per column compression requires selecting the compressor of a column.")]
+    PerColumnCompression,
     #[error("number of offsets ({0}) is smaller than prune request ({1}).")]
     InvalidPruning(u64, u64),
     #[error("jar has been frozen and cannot be modified.")]
@@ -188,6 +188,12 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Adds a compressor per column. Columns set to `None` are stored uncompressed.
+    pub fn with_column_compression(mut self, compressors: Vec<Option<Compressors>>) -> Self {
+        self.compressor = Some(Compressors::PerColumn(compressors));
+        self
+    }
+
     /// Adds [`filter::Cuckoo`] filter.
     pub fn with_cuckoo_filter(mut self, max_capacity: usize) -> Self {
         self.filter = Some(InclusionFilters::Cuckoo(Cuckoo::new(max_capacity)));
@@ -417,6 +423,12 @@ impl<H: NippyJarHeader> NippyJar<H> {
         }
 
         if let Some(compression) = &self.compressor {
+            if let Compressors::PerColumn(compressors) = compression {
+                if compressors.len() != self.columns {
+                    return Err(NippyJarError::ColumnLenMismatch(self.columns, compressors.len()))
+                }
+            }
+
             if !compression.is_ready() {
                 return Err(NippyJarError::CompressorNotReady)
             }
@@ -862,6 +874,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_per_column_compression() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 3;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_column_compression(vec![None, None]);
+
+        // There should be a compressor choice for every column
+        assert!(matches!(
+            nippy.freeze(
+                vec![
+                    clone_with_result(&col1),
+                    clone_with_result(&col2),
+                    clone_with_result(&col2)
+                ],
+                num_rows
+            ),
+            Err(NippyJarError::ColumnLenMismatch(columns, 2)) if columns == num_columns
+        ));
+
+        let mut nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_column_compression(vec![
+                Some(Compressors::Zstd(compression::Zstd::new(true, 5000, 1))),
+                Some(Compressors::Lz4(compression::Lz4::default())),
+                None,
+            ]);
+
+        nippy.prepare_compression(vec![col1.clone(), col2.clone(), col2.clone()]).unwrap();
+
+        let nippy = nippy
+            .freeze(
+                vec![clone_with_result(&col1), clone_with_result(&col2), clone_with_result(&col2)],
+                num_rows,
+            )
+            .unwrap();
+
+        let mut loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        loaded_nippy.load_filters().unwrap();
+        assert_eq!(nippy.columns, loaded_nippy.columns);
+        assert_eq!(nippy.max_row_size, loaded_nippy.max_row_size);
+
+        if let Some(Compressors::PerColumn(compressors)) = loaded_nippy.compressor() {
+            assert!(matches!(compressors[0], Some(Compressors::Zstd(ref zstd)) if zstd.use_dict));
+            assert!(matches!(compressors[1], Some(Compressors::Lz4(_))));
+            assert!(compressors[2].is_none());
+
+            let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+
+            // Iterate over compressed values and compare
+            let mut row_index = 0usize;
+            while let Some(row) = cursor.next_row().unwrap() {
+                assert_eq!(
+                    (row[0], row[1], row[2]),
+                    (
+                        col1[row_index].as_slice(),
+                        col2[row_index].as_slice(),
+                        col2[row_index].as_slice()
+                    )
+                );
+                row_index += 1;
+            }
+            assert_eq!(row_index, num_rows as usize);
+        } else {
+            panic!("Expected per column compressors")
+        }
+    }
+
     /// Tests `NippyJar` with everything enabled: compression, filter, offset list and offset index.
     #[test]
     fn test_full_nippy_jar() {
@@ -1,10 +1,14 @@
-use crate::{compression::Compression, ColumnResult, NippyJar, NippyJarError, NippyJarHeader};
+use crate::{
+    compression::{Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarError, NippyJarHeader,
+};
 use std::{
     cmp::Ordering,
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
 };
+use zstd::bulk::Compressor;
 
 /// Size of one offset in bytes.
 const OFFSET_SIZE_BYTES: u8 = 8;
@@ -33,6 +37,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     offsets_file: BufWriter<File>,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
+    /// Zstd compressors of the columns compressed with a dictionary.
+    dictionary_compressors: DictionaryCompressors,
     /// Used to find the maximum uncompressed size of a row in a jar.
     uncompressed_row_size: usize,
     /// Partial offset list which hasn't been flushed to disk.
@@ -58,11 +64,14 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         // Makes sure we don't have dangling data and offset files
         jar.freeze_config()?;
 
+        let dictionary_compressors = DictionaryCompressors::new(&jar)?;
+
         let mut writer = Self {
             jar,
             data_file: BufWriter::new(data_file),
             offsets_file: BufWriter::new(offsets_file),
             tmp_buf: Vec::with_capacity(1_000_000),
+            dictionary_compressors,
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
@@ -306,7 +315,12 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
+        let compression = self.jar.compressor.as_ref().and_then(|c| c.column(self.column));
+        let len = if let Some(compressor) = self.dictionary_compressors.get_mut(self.column) {
+            let compressed = compressor.compress(value)?;
+            self.data_file.write_all(&compressed)?;
+            compressed.len()
+        } else if let Some((compression, _)) = compression {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
             self.data_file.write_all(&self.tmp_buf[before..before + len])?;
@@ -532,6 +546,42 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     }
 }
 
+/// Zstd compressors of the columns compressed with a dictionary.
+///
+/// Dictionaries are only available for compression before the jar has been loaded from disk, so
+/// appending to a loaded jar compresses without them.
+struct DictionaryCompressors(Vec<Option<Compressor<'static>>>);
+
+impl DictionaryCompressors {
+    /// Creates a [`Compressor`] for every column of the jar compressed with a dictionary.
+    fn new<H: NippyJarHeader>(jar: &NippyJar<H>) -> Result<Self, NippyJarError> {
+        let Some(compressor) = jar.compressor() else { return Ok(Self(Vec::new())) };
+
+        (0..jar.columns)
+            .map(|column| match compressor.column(column) {
+                Some((Compressors::Zstd(zstd), dictionary)) => {
+                    zstd.dictionary_compressor(dictionary)
+                }
+                _ => Ok(None),
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// Returns the [`Compressor`] of `column`, if it's compressed with a dictionary.
+    fn get_mut(&mut self, column: usize) -> Option<&mut Compressor<'static>> {
+        self.0.get_mut(column).and_then(Option::as_mut)
+    }
+}
+
+impl std::fmt::Debug for DictionaryCompressors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DictionaryCompressors")
+            .field("columns", &self.0.iter().filter(|c| c.is_some()).count())
+            .finish()
+    }
+}
+
 /// Strategy on encountering an inconsistent state when creating a [`NippyJarWriter`].
 #[derive(Debug, Copy, Clone)]
 pub enum ConsistencyFailStrategy {
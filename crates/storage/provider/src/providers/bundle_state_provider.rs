@@ -1,10 +1,14 @@
 use crate::{
     AccountReader, BlockHashReader, ExecutionDataProvider, StateProvider, StateRootProvider,
 };
-use reth_primitives::{proofs::AccountProof, Account, Address, BlockNumber, Bytecode, B256};
+use reth_primitives::{
+    proofs::{AccountProof, MultiProof},
+    Account, Address, BlockNumber, Bytecode, B256,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::updates::TrieUpdates;
 use revm::db::BundleState;
+use std::collections::{HashMap, HashSet};
 
 /// A state provider that resolves to data from either a wrapped [`crate::ExecutionOutcome`]
 /// or an underlying state provider.
@@ -111,4 +115,8 @@ impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProvider for BundleStat
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
     }
+
+    fn multiproof(&self, _targets: &HashMap<B256, HashSet<B256>>) -> ProviderResult<MultiProof> {
+        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    }
 }
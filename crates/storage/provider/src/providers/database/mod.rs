@@ -2,9 +2,9 @@ use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode,
-    ProviderError, PruneCheckpointReader, RequestsProvider, StageCheckpointReader,
+    AddressTransactionsReader, BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HeaderSyncMode, ProviderError, PruneCheckpointReader, RequestsProvider, StageCheckpointReader,
     StateProviderBox, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
     WithdrawalsProvider,
 };
@@ -568,6 +568,30 @@ where
     }
 }
 
+impl<DB: Database> AddressTransactionsReader for ProviderFactory<DB> {
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions_before(address, before, limit)
+    }
+
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions_from(address, from, limit)
+    }
+
+    fn contract_creation_transaction(&self, address: Address) -> ProviderResult<Option<TxNumber>> {
+        self.provider()?.contract_creation_transaction(address)
+    }
+}
+
 impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     fn get_prune_checkpoint(
         &self,
@@ -5,12 +5,13 @@ use crate::{
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    Chain, EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter, HashingWriter,
-    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode, HistoricalStateProvider,
-    HistoryWriter, LatestStateProvider, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, RequestsProvider, StageCheckpointReader, StateProviderBox, StateWriter,
-    StatsReader, StorageReader, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    AccountReader, AddressTransactionsReader, BlockExecutionWriter, BlockHashReader,
+    BlockNumReader, BlockReader, BlockWriter, Chain, EvmEnvProvider, FinalizedBlockReader,
+    FinalizedBlockWriter, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HeaderSyncMode, HistoricalStateProvider, HistoryWriter, LatestStateProvider,
+    OriginalValuesKnown, ProviderError, PruneCheckpointReader, PruneCheckpointWriter,
+    RequestsProvider, StageCheckpointReader, StateProviderBox, StateWriter, StatsReader,
+    StorageReader, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
     WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
@@ -30,10 +31,10 @@ use reth_db_api::{
 use reth_evm::ConfigureEvmEnv;
 use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
-    keccak256,
+    b256, keccak256,
     revm::{config::revm_spec, env::fill_block_env},
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    ChainInfo, ChainSpec, GotExpected, Head, Header, Receipt, Requests, SealedBlock,
+    ChainInfo, ChainSpec, GotExpected, Head, Header, Log, Receipt, Requests, SealedBlock,
     SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta,
     TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
     Withdrawal, Withdrawals, B256, U256,
@@ -42,7 +43,7 @@ use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
-    prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
+    prefix_set::{PrefixSet, PrefixSetLoader, PrefixSetMut, TriePrefixSets},
     updates::TrieUpdates,
     HashedPostState, Nibbles, StateRoot,
 };
@@ -249,6 +250,48 @@ where
     Ok(Vec::new())
 }
 
+/// The signatures of well-known events with the positions of their indexed `address` arguments in
+/// the topics of their logs.
+///
+/// The indexed arguments of an event are not self-describing, so topics of other events are not
+/// indexed: a small integer argument can't be told apart from an address.
+const ADDRESS_EVENTS: &[(B256, &[usize])] = &[
+    // `Transfer(address,address,uint256)` of ERC-20 and ERC-721
+    (b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"), &[1, 2]),
+    // `Approval(address,address,uint256)` of ERC-20 and ERC-721
+    (b256!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"), &[1, 2]),
+    // `ApprovalForAll(address,address,bool)` of ERC-721 and ERC-1155
+    (b256!("17307eab39ab6107e8899845ad3d59bd9653f200f220920489ca2b5937696c31"), &[1, 2]),
+    // `TransferSingle(address,address,address,uint256,uint256)` of ERC-1155
+    (b256!("c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62"), &[1, 2, 3]),
+    // `TransferBatch(address,address,address,uint256[],uint256[])` of ERC-1155
+    (b256!("4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb"), &[1, 2, 3]),
+    // `Deposit(address,uint256)` of WETH
+    (b256!("e1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c"), &[1]),
+    // `Withdrawal(address,uint256)` of WETH
+    (b256!("7fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b65"), &[1]),
+    // `OwnershipTransferred(address,address)` of `Ownable`
+    (b256!("8be0079c531659141344cd1fd0a4f28419497f9722a3daafe3b4186f6b6457e0"), &[1, 2]),
+];
+
+/// Returns the addresses a log refers to: the contract that emitted it and, for the
+/// [`ADDRESS_EVENTS`], the indexed `address` arguments.
+fn log_addresses(log: &Log) -> impl Iterator<Item = Address> + '_ {
+    let topics = log.topics();
+    let positions = topics
+        .first()
+        .and_then(|signature| ADDRESS_EVENTS.iter().find(|(event, _)| event == signature))
+        .map(|(_, positions)| *positions)
+        .unwrap_or_default();
+    std::iter::once(log.address).chain(
+        positions
+            .iter()
+            .filter_map(|position| topics.get(*position))
+            .filter(|topic| !topic.is_zero())
+            .map(|topic| Address::from_word(*topic)),
+    )
+}
+
 impl<TX: DbTx> DatabaseProvider<TX> {
     /// Creates a provider with an inner read-only transaction.
     pub fn new(
@@ -301,6 +344,78 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             .collect::<Result<Vec<_>, DatabaseError>>()
     }
 
+    /// Returns the account changesets of the block range.
+    ///
+    /// Blocks that were already moved to static files are read from there, the rest from the
+    /// database.
+    pub fn account_changesets(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let range = to_range(range);
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            range,
+            |static_file, range, _| {
+                static_file.account_changesets_range(range.start..=range.end - 1)
+            },
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::AccountChangeSets>()?
+                    .walk_range(range)?
+                    .map(|entry| entry.map_err(Into::into))
+                    .collect()
+            },
+            |_| true,
+        )
+    }
+
+    /// Returns the storage changesets of the block range.
+    ///
+    /// See [`Self::account_changesets`].
+    pub fn storage_changesets(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let range = to_range(range);
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            range,
+            |static_file, range, _| {
+                static_file.storage_changesets_range(range.start..=range.end - 1)
+            },
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::StorageChangeSets>()?
+                    .walk_range(BlockNumberAddress::range(range.start..=range.end - 1))?
+                    .map(|entry| entry.map_err(Into::into))
+                    .collect()
+            },
+            |_| true,
+        )
+    }
+
+    /// Loads the trie prefix sets of all accounts and storage slots changed in the block range.
+    pub fn changeset_prefix_sets(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<TriePrefixSets> {
+        let account_changesets = self.account_changesets(range.clone())?;
+        let storage_changesets = self.storage_changesets(range)?;
+        Ok(PrefixSetLoader::new(&self.tx).load(
+            account_changesets.into_iter().map(|(_, account)| account),
+            storage_changesets.into_iter().map(|(key, storage)| (key.address(), storage)),
+        )?)
+    }
+
     /// Iterates over read only values in the given table and collects them into a vector.
     ///
     /// Early-returns if the range is empty, without opening a cursor transaction.
@@ -356,6 +471,72 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         )
     }
 
+    /// Returns the addresses touched by the transactions in the given range, in transaction order,
+    /// and the contracts deployed by top-level contract creation transactions.
+    ///
+    /// An address is touched by a transaction if it is the sender, the recipient or the contract
+    /// created by it, or if it emitted an event or is an indexed address argument of one of the
+    /// [`ADDRESS_EVENTS`] emitted by the transaction. Logs are only considered if the receipts of
+    /// the transactions have not been pruned.
+    pub fn address_appearances_by_tx_range(
+        &self,
+        range: Range<TxNumber>,
+    ) -> ProviderResult<(Vec<(Address, TxNumber)>, Vec<(Address, TxNumber)>)> {
+        let transactions = self.transactions_by_tx_range(range.clone())?;
+        let mut senders = self.senders_by_tx_range(range.clone())?;
+        // Senders might have been pruned, in which case they have to be recovered.
+        if senders.len() != transactions.len() {
+            senders = TransactionSignedNoHash::recover_signers(&transactions, transactions.len())
+                .ok_or(ProviderError::SenderRecoveryError)?;
+        }
+
+        let mut receipts: Vec<Option<Receipt>> =
+            self.receipts_by_tx_range(range.clone())?.into_iter().map(Some).collect();
+        // Receipts might have been pruned partially, in which case they have to be matched to
+        // their transactions one by one.
+        if receipts.len() != transactions.len() {
+            receipts =
+                range.clone().map(|tx_number| self.receipt(tx_number)).collect::<Result<_, _>>()?;
+        }
+
+        let mut appearances = Vec::with_capacity(transactions.len() * 2);
+        let mut creations = Vec::new();
+        for (((tx_number, transaction), sender), receipt) in
+            range.zip(transactions).zip(senders).zip(receipts)
+        {
+            appearances.push((sender, tx_number));
+            if let Some(to) = transaction.to() {
+                appearances.push((to, tx_number));
+            } else {
+                let contract = sender.create(transaction.nonce());
+                appearances.push((contract, tx_number));
+                creations.push((contract, tx_number));
+            }
+
+            for log in receipt.iter().flat_map(|receipt| &receipt.logs) {
+                appearances.extend(log_addresses(log).map(|address| (address, tx_number)));
+            }
+        }
+
+        Ok((appearances, creations))
+    }
+
+    /// Returns the range of transaction numbers of the given block range.
+    fn tx_range_by_block_range(
+        &self,
+        range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Range<TxNumber>> {
+        let from = self
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num();
+        let to = self
+            .block_body_indices(*range.end())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.end()))?
+            .next_tx_num();
+        Ok(from..to)
+    }
+
     /// Returns a range of blocks from the database.
     ///
     /// Uses the provided `headers_range` to get the headers for the range, and `assemble_block` to
@@ -487,11 +668,23 @@ impl<TX: DbTx> DatabaseProvider<TX> {
                     .into_iter()
                     .map(Into::into)
                     .collect::<Vec<TransactionSigned>>();
-                // fetch senders from the senders table
-                let known_senders =
-                    senders_cursor
-                        .walk_range(tx_range.clone())?
-                        .collect::<Result<HashMap<_, _>, _>>()?;
+                // fetch senders from the senders static files and table
+                let known_senders = self
+                    .static_file_provider
+                    .get_range_with_static_file_or_database(
+                        StaticFileSegment::TransactionSenders,
+                        tx_range.clone(),
+                        |static_file, range, _| static_file.transaction_senders_range(range),
+                        |range, _| {
+                            senders_cursor
+                                .walk_range(range)?
+                                .map(|entry| entry.map_err(Into::into))
+                                .collect()
+                        },
+                        |_| true,
+                    )?
+                    .into_iter()
+                    .collect::<HashMap<_, _>>();
 
                 let mut senders = Vec::with_capacity(body.len());
                 for (tx_num, tx) in tx_range.zip(body.iter()) {
@@ -532,8 +725,8 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     /// 1. Iterate over the [`BlockBodyIndices`][tables::BlockBodyIndices] table to get all the
     ///    transaction ids.
     /// 2. Iterate over the [`StorageChangeSets`][tables::StorageChangeSets] table and the
-    ///    [`AccountChangeSets`][tables::AccountChangeSets] tables in reverse order to reconstruct
-    ///    the changesets.
+    ///    [`AccountChangeSets`][tables::AccountChangeSets] tables, and over the changesets of the
+    ///    range already moved to static files, in reverse order to reconstruct the changesets.
     ///    - In order to have both the old and new values in the changesets, we also access the
     ///      plain state tables.
     /// 3. While iterating over the changeset tables, if we encounter a new account or storage slot,
@@ -564,11 +757,8 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset =
-            self.get_or_take::<tables::StorageChangeSets, TAKE>(storage_range)?;
-        let account_changeset = self.get_or_take::<tables::AccountChangeSets, TAKE>(range)?;
+        let storage_changeset = self.get_or_take_storage_changesets::<TAKE>(range.clone())?;
+        let account_changeset = self.get_or_take_account_changesets::<TAKE>(range)?;
 
         // iterate previous value and get plain state value to create changeset
         // Double option around Account represent if Account state is know (first option) and
@@ -721,6 +911,132 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
     }
 
+    /// Return the account changesets of the block range from static files and the database.
+    ///
+    /// If TAKE is true, the changesets are removed from the database and pruned from static files.
+    /// Static file prunes are only applied once the static file provider is committed. Since
+    /// static files can only be pruned from their tip, the range is expected to end at the tip.
+    ///
+    /// # Panics
+    ///
+    /// If TAKE is true and the range ends below the highest static file block.
+    pub fn get_or_take_account_changesets<const TAKE: bool>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let database = self.get_or_take::<tables::AccountChangeSets, TAKE>(range.clone())?;
+        let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .filter(|highest| highest >= range.start())
+        else {
+            return Ok(database)
+        };
+
+        let mut changesets = self
+            .static_file_provider
+            .account_changesets_range(*range.start()..=highest.min(*range.end()))?;
+        changesets.extend(database.into_iter().filter(|(block, _)| *block > highest));
+
+        if TAKE {
+            assert!(
+                *range.end() >= highest,
+                "static file changesets can only be taken from the tip, range {range:?}, tip {highest}"
+            );
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::AccountChangeSets)?
+                .prune_account_changesets(highest - range.start() + 1)?;
+        }
+
+        Ok(changesets)
+    }
+
+    /// Return the storage changesets of the block range from static files and the database.
+    ///
+    /// See [`Self::get_or_take_account_changesets`].
+    pub fn get_or_take_storage_changesets<const TAKE: bool>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let database = self.get_or_take::<tables::StorageChangeSets, TAKE>(
+            BlockNumberAddress::range(range.clone()),
+        )?;
+        let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest| highest >= range.start())
+        else {
+            return Ok(database)
+        };
+
+        let mut changesets = self
+            .static_file_provider
+            .storage_changesets_range(*range.start()..=highest.min(*range.end()))?;
+        changesets.extend(database.into_iter().filter(|(key, _)| key.block_number() > highest));
+
+        if TAKE {
+            assert!(
+                *range.end() >= highest,
+                "static file changesets can only be taken from the tip, range {range:?}, tip {highest}"
+            );
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::StorageChangeSets)?
+                .prune_storage_changesets(highest - range.start() + 1)?;
+        }
+
+        Ok(changesets)
+    }
+
+    /// Return the transaction senders of the transaction range from static files and the database.
+    ///
+    /// If TAKE is true, the senders are only removed from the database. Senders in static files
+    /// are pruned by the unwind of the sender recovery stage, like the transactions are by the
+    /// bodies stage.
+    pub fn get_or_take_transaction_senders<const TAKE: bool>(
+        &self,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<(TxNumber, Address)>> {
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let database = self.get_or_take::<tables::TransactionSenders, TAKE>(range.clone())?;
+        let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+            .filter(|highest| highest >= range.start())
+        else {
+            return Ok(database)
+        };
+
+        let mut senders = self
+            .static_file_provider
+            .transaction_senders_range(*range.start()..=highest.min(*range.end()))?;
+        senders.extend(database.into_iter().filter(|(tx_number, _)| *tx_number > highest));
+
+        Ok(senders)
+    }
+
+    /// Return the storage changesets in the key range from static files and the database.
+    fn storage_changesets_in_range(
+        &self,
+        range: Range<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        // Ranges created with `BlockNumberAddress::range` end at the first key of the next block.
+        let blocks = range.start.block_number()..=range.end.block_number().saturating_sub(1);
+        let mut changesets = self.get_or_take_storage_changesets::<false>(blocks)?;
+        changesets.retain(|(key, _)| range.contains(key));
+        Ok(changesets)
+    }
+
     /// Get requested blocks transaction with signer
     pub(crate) fn get_take_block_transaction_range<const TAKE: bool>(
         &self,
@@ -749,9 +1065,8 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             .map(|(id, tx)| (id, tx.into()))
             .collect::<Vec<(u64, TransactionSigned)>>();
 
-        let mut senders = self.get_or_take::<tables::TransactionSenders, TAKE>(
-            first_transaction..=last_transaction,
-        )?;
+        let mut senders =
+            self.get_or_take_transaction_senders::<TAKE>(first_transaction..=last_transaction)?;
 
         // Recover senders manually if not found in db
         // NOTE: Transactions are always guaranteed to be in the database whereas
@@ -1213,13 +1528,11 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -1237,16 +1550,10 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
-            BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
-                accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
-            },
-        )?;
+        let mut account_transitions = BTreeMap::<Address, Vec<u64>>::new();
+        for (index, account) in self.account_changesets(range)? {
+            account_transitions.entry(account.address).or_default().push(index);
+        }
 
         Ok(account_transitions)
     }
@@ -1257,15 +1564,25 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        self.static_file_provider
+            .get_with_static_file_or_database(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+                |static_file| static_file.account_block_changeset(block_number),
+                || {
+                    let range = block_number..=block_number;
+                    self.tx
+                        .cursor_read::<tables::AccountChangeSets>()?
+                        .walk_range(range)?
+                        .map(|result| -> ProviderResult<_> {
+                            let (_, account_before) = result?;
+                            Ok(account_before)
+                        })
+                        .collect::<ProviderResult<_>>()
+                        .map(Some)
+                },
+            )
+            .map(Option::unwrap_or_default)
     }
 }
 
@@ -1866,11 +2183,28 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.cursor_read_collect::<tables::TransactionSenders>(range).map_err(Into::into)
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::TransactionSenders,
+            to_range(range),
+            |static_file, range, _| {
+                Ok(static_file
+                    .transaction_senders_range(range)?
+                    .into_iter()
+                    .map(|(_, sender)| sender)
+                    .collect())
+            },
+            |range, _| self.cursor_read_collect::<tables::TransactionSenders>(range),
+            |_| true,
+        )
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.tx.get::<tables::TransactionSenders>(id)?)
+        self.static_file_provider.get_with_static_file_or_database(
+            StaticFileSegment::TransactionSenders,
+            id,
+            |static_file| static_file.transaction_sender(id),
+            || Ok(self.tx.get::<tables::TransactionSenders>(id)?),
+        )
     }
 }
 
@@ -2151,36 +2485,27 @@ impl<TX: DbTx> StorageReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
-            // fold all storages and save its old state so we can remove it from HashedStorage
-            // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
-                accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+        // fold all storages and save its old state so we can remove it from HashedStorage
+        // it is needed as it is dup table.
+        let mut accounts = BTreeMap::<Address, BTreeSet<B256>>::new();
+        for (BlockNumberAddress((_, address)), storage_entry) in self.storage_changesets(range)? {
+            accounts.entry(address).or_default().insert(storage_entry.key);
+        }
+
+        Ok(accounts)
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let mut storage_changeset_lists = BTreeMap::<(Address, B256), Vec<u64>>::new();
+        for (index, storage) in self.storage_changesets(range)? {
+            storage_changeset_lists
+                .entry((index.address(), storage.key))
+                .or_default()
+                .push(index.block_number());
+        }
 
         Ok(storage_changeset_lists)
     }
@@ -2195,12 +2520,9 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         // Note that collecting and then reversing the order is necessary to ensure that the
         // changes are applied in the correct order.
         let hashed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(_, e)| (keccak256(e.address), e.info)))
-            .collect::<Result<Vec<_>, _>>()?
+            .get_or_take_account_changesets::<false>(range)?
             .into_iter()
+            .map(|(_, e)| (keccak256(e.address), e.info))
             .rev()
             .collect::<BTreeMap<_, _>>();
 
@@ -2239,15 +2561,13 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
         // Aggregate all block changesets and make list of accounts that have been changed.
-        let mut changesets = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-        let mut hashed_storages = changesets
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((_, address)), storage_entry)| {
-                    (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
-                })
+        let mut hashed_storages = self
+            .storage_changesets_in_range(range)?
+            .into_iter()
+            .map(|(BlockNumberAddress((_, address)), storage_entry)| {
+                (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Vec<_>>();
         hashed_storages.sort_by_key(|(ha, hk, _)| (*ha, *hk));
 
         // Apply values to HashedState, and remove the account if it's None.
@@ -2360,8 +2680,8 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
 
         // merkle tree
         {
-            // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
-            // are pre-loaded.
+            // The prefix sets were collected above, so there is no need to load them again with
+            // `Self::changeset_prefix_sets`.
             let prefix_sets = TriePrefixSets {
                 account_prefix_set: account_prefix_set.freeze(),
                 storage_prefix_sets: storage_prefix_sets
@@ -2397,11 +2717,10 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<usize> {
         let mut last_indices = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(index, account)| (account.address, index)))
-            .collect::<Result<Vec<_>, _>>()?;
+            .get_or_take_account_changesets::<false>(range)?
+            .into_iter()
+            .map(|(index, account)| (account.address, index))
+            .collect::<Vec<_>>();
         last_indices.sort_by_key(|(a, _)| *a);
 
         // Unwind the account history index.
@@ -2443,13 +2762,10 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
         let mut storage_changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+            .storage_changesets_in_range(range)?
+            .into_iter()
+            .map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
+            .collect::<Vec<_>>();
         storage_changesets.sort_by_key(|(address, key, _)| (*address, *key));
 
         let mut cursor = self.tx.cursor_write::<tables::StoragesHistory>()?;
@@ -2490,6 +2806,69 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         )
     }
 
+    fn unwind_address_transactions_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let tx_range = self.tx_range_by_block_range(&range)?;
+        let (appearances, creations) = self.address_appearances_by_tx_range(tx_range.clone())?;
+        let addresses =
+            appearances.into_iter().map(|(address, _)| address).collect::<BTreeSet<_>>();
+
+        // Unwind the address transactions index.
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for &address in &addresses {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                tx_range.start,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        for (contract, _) in creations {
+            self.tx.delete::<tables::ContractCreators>(contract, None)?;
+        }
+
+        Ok(addresses.len())
+    }
+
+    fn insert_address_transactions_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let tx_range = self.tx_range_by_block_range(&range)?;
+        let (appearances, creations) = self.address_appearances_by_tx_range(tx_range)?;
+
+        let mut address_transactions = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (address, tx_number) in appearances {
+            let indices = address_transactions.entry(address).or_default();
+            // The sender can also be the recipient of the same transaction.
+            if indices.last() != Some(&tx_number) {
+                indices.push(tx_number);
+            }
+        }
+        self.append_history_index::<_, tables::AddressTransactions>(
+            address_transactions,
+            ShardedKey::new,
+        )?;
+
+        for (contract, tx_number) in creations {
+            self.tx.put::<tables::ContractCreators>(contract, tx_number)?;
+        }
+
+        Ok(())
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2499,10 +2878,22 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
 
         // storage history stage
         {
-            let indices = self.changed_storages_and_blocks_with_range(range)?;
+            let indices = self.changed_storages_and_blocks_with_range(range.clone())?;
             self.insert_storage_history_index(indices)?;
         }
 
+        // address transactions stage, only if this node maintains the optional index and it is
+        // up to date with the appended range.
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexAddressTransactions)? {
+            if checkpoint.block_number + 1 == *range.start() {
+                self.insert_address_transactions_index(range.clone())?;
+                self.save_stage_checkpoint(
+                    StageId::IndexAddressTransactions,
+                    StageCheckpoint::new(*range.end()),
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -2546,9 +2937,24 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
             // Unwind storage history indices.
             self.unwind_storage_history_indices(storage_range)?;
 
+            // Unwind address transactions indices, if this node maintains them.
+            if let Some(checkpoint) =
+                self.get_stage_checkpoint(StageId::IndexAddressTransactions)?
+            {
+                if checkpoint.block_number >= *range.start() {
+                    self.unwind_address_transactions_indices(
+                        *range.start()..=checkpoint.block_number.min(*range.end()),
+                    )?;
+                    self.save_stage_checkpoint(
+                        StageId::IndexAddressTransactions,
+                        StageCheckpoint::new(range.start().saturating_sub(1)),
+                    )?;
+                }
+            }
+
             // Calculate the reverted merkle root.
-            // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
-            // are pre-loaded.
+            // The prefix sets were collected above, so there is no need to load them again with
+            // `Self::changeset_prefix_sets`.
             let prefix_sets = TriePrefixSets {
                 account_prefix_set: account_prefix_set.freeze(),
                 storage_prefix_sets,
@@ -2795,6 +3201,77 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> AddressTransactionsReader for DatabaseProvider<TX> {
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut transactions = Vec::new();
+        if limit == 0 {
+            return Ok(transactions)
+        }
+
+        // The last shard of an address is keyed with `u64::MAX`, so seeking always lands on a shard
+        // of the address if it has any. Lower transaction numbers are in the preceding shards.
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut entry = cursor.seek(ShardedKey::new(address, before))?;
+        while let Some((sharded_key, list)) = entry {
+            if sharded_key.key != address {
+                break
+            }
+
+            let shard = list.iter().take_while(|tx_number| *tx_number < before).collect::<Vec<_>>();
+            transactions.extend(shard.into_iter().rev().take(limit - transactions.len()));
+            if transactions.len() >= limit {
+                break
+            }
+
+            entry = cursor.prev()?;
+        }
+
+        Ok(transactions)
+    }
+
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut transactions = Vec::new();
+        if limit == 0 {
+            return Ok(transactions)
+        }
+
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut entry = cursor.seek(ShardedKey::new(address, from))?;
+        while let Some((sharded_key, list)) = entry {
+            if sharded_key.key != address {
+                break
+            }
+
+            transactions.extend(
+                list.iter()
+                    .skip_while(|tx_number| *tx_number < from)
+                    .take(limit - transactions.len()),
+            );
+            if transactions.len() >= limit {
+                break
+            }
+
+            entry = cursor.next()?;
+        }
+
+        Ok(transactions)
+    }
+
+    fn contract_creation_transaction(&self, address: Address) -> ProviderResult<Option<TxNumber>> {
+        Ok(self.tx.get::<tables::ContractCreators>(address)?)
+    }
+}
+
 impl<TX: DbTx> PruneCheckpointReader for DatabaseProvider<TX> {
     fn get_prune_checkpoint(
         &self,
@@ -2864,3 +3341,150 @@ fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
     };
     end.checked_sub(start).map(|x| x as _)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, StaticFileProviderFactory};
+    use reth_db_api::models::{
+        StorageBeforeTx, StoredBlockAccountChanges, StoredBlockStorageChanges,
+    };
+    use reth_primitives::{alloy_primitives::LogData, Bytes};
+
+    #[test]
+    fn address_event_signatures() {
+        let signatures = [
+            "Transfer(address,address,uint256)",
+            "Approval(address,address,uint256)",
+            "ApprovalForAll(address,address,bool)",
+            "TransferSingle(address,address,address,uint256,uint256)",
+            "TransferBatch(address,address,address,uint256[],uint256[])",
+            "Deposit(address,uint256)",
+            "Withdrawal(address,uint256)",
+            "OwnershipTransferred(address,address)",
+        ];
+        assert_eq!(signatures.len(), ADDRESS_EVENTS.len());
+        for (signature, (event, _)) in signatures.iter().zip(ADDRESS_EVENTS) {
+            assert_eq!(keccak256(signature), *event, "{signature}");
+        }
+    }
+
+    #[test]
+    fn log_addresses_of_known_events_only() {
+        let emitter = Address::random();
+        let from = Address::random();
+        let to = Address::random();
+        let log = |topics| Log {
+            address: emitter,
+            data: LogData::new_unchecked(topics, Bytes::default()),
+        };
+
+        // the indexed token id of an ERC-721 transfer is not an address
+        let transfer = log(vec![
+            ADDRESS_EVENTS[0].0,
+            from.into_word(),
+            to.into_word(),
+            B256::with_last_byte(1),
+        ]);
+        assert_eq!(log_addresses(&transfer).collect::<Vec<_>>(), vec![emitter, from, to]);
+
+        // a mint has no sender
+        let mint = log(vec![ADDRESS_EVENTS[0].0, B256::ZERO, to.into_word()]);
+        assert_eq!(log_addresses(&mint).collect::<Vec<_>>(), vec![emitter, to]);
+
+        // small integers of unknown events are not indexed as addresses
+        let unknown = log(vec![B256::random(), B256::with_last_byte(1)]);
+        assert_eq!(log_addresses(&unknown).collect::<Vec<_>>(), vec![emitter]);
+
+        let anonymous = log(Vec::new());
+        assert_eq!(log_addresses(&anonymous).collect::<Vec<_>>(), vec![emitter]);
+    }
+
+    #[test]
+    fn take_changesets_from_static_files_and_database() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+
+        let account = |block: BlockNumber| AccountBeforeTx {
+            address: Address::with_last_byte(block as u8),
+            info: Some(Account { nonce: block, ..Default::default() }),
+        };
+        let storage = |block: BlockNumber| StorageBeforeTx {
+            address: Address::with_last_byte(block as u8),
+            key: B256::with_last_byte(block as u8),
+            value: U256::from(block),
+        };
+        let accounts = |range: RangeInclusive<BlockNumber>| {
+            range.map(|block| (block, account(block))).collect::<Vec<_>>()
+        };
+        let storages = |range: RangeInclusive<BlockNumber>| {
+            range
+                .map(|block| {
+                    let change = storage(block);
+                    ((block, change.address).into(), change.entry())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // changesets of blocks 0..=4 are in static files
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            for block in 0..=4 {
+                let changes = vec![account(block)];
+                writer
+                    .append_account_changeset(block, StoredBlockAccountChanges { changes })
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=4 {
+                let changes = vec![storage(block)];
+                writer
+                    .append_storage_changeset(block, StoredBlockStorageChanges { changes })
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        // changesets of blocks 5..=7 are in the database
+        let provider = factory.provider_rw().unwrap();
+        for block in 5..=7 {
+            let change = storage(block);
+            provider.tx_ref().put::<tables::AccountChangeSets>(block, account(block)).unwrap();
+            provider
+                .tx_ref()
+                .put::<tables::StorageChangeSets>((block, change.address).into(), change.entry())
+                .unwrap();
+        }
+
+        assert_eq!(provider.get_or_take_account_changesets::<false>(2..=6), Ok(accounts(2..=6)));
+        assert_eq!(provider.get_or_take_storage_changesets::<false>(2..=6), Ok(storages(2..=6)));
+
+        // unwind blocks 3..=7
+        assert_eq!(provider.get_or_take_account_changesets::<true>(3..=7), Ok(accounts(3..=7)));
+        assert_eq!(provider.get_or_take_storage_changesets::<true>(3..=7), Ok(storages(3..=7)));
+        provider.commit().unwrap();
+        static_file_provider.commit().unwrap();
+
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(2)
+        );
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            Some(2)
+        );
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSets>(), Ok(0));
+        assert_eq!(provider.tx_ref().entries::<tables::StorageChangeSets>(), Ok(0));
+        assert_eq!(provider.get_or_take_account_changesets::<false>(0..=7), Ok(accounts(0..=2)));
+        assert_eq!(provider.get_or_take_storage_changesets::<false>(0..=7), Ok(storages(0..=2)));
+    }
+}
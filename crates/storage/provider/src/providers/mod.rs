@@ -1,11 +1,12 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockSource, BlockchainTreePendingStateProvider,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, FullExecutionDataProvider,
+    HeaderProvider, ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, TreeViewer,
+    WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -53,6 +54,11 @@ pub use state::{
 mod bundle_state_provider;
 pub use bundle_state_provider::BundleStateProvider;
 
+mod state_cache;
+pub use state_cache::{
+    CachedStateProvider, StateCache, StateCacheUpdate, DEFAULT_STATE_CACHE_SIZE,
+};
+
 mod chain_info;
 use chain_info::ChainInfoTracker;
 
@@ -578,6 +584,33 @@ where
     }
 }
 
+impl<DB> AddressTransactionsReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_transactions_before(address, before, limit)
+    }
+
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_transactions_from(address, from, limit)
+    }
+
+    fn contract_creation_transaction(&self, address: Address) -> ProviderResult<Option<TxNumber>> {
+        self.database.provider()?.contract_creation_transaction(address)
+    }
+}
+
 impl<DB> PruneCheckpointReader for BlockchainProvider<DB>
 where
     DB: Database,
@@ -715,6 +748,18 @@ where
     fn make_canonical(&self, block_hash: BlockHash) -> Result<CanonicalOutcome, CanonicalError> {
         self.tree.make_canonical(block_hash)
     }
+
+    fn unwind(&self, unwind_to: BlockNumber) -> Result<(), CanonicalError> {
+        self.tree.unwind(unwind_to)
+    }
+
+    fn clear_state_cache(&self) {
+        self.tree.clear_state_cache()
+    }
+
+    fn invalidate_canonical_tip_state(&self) {
+        self.tree.invalidate_canonical_tip_state()
+    }
 }
 
 impl<DB> BlockchainTreeViewer for BlockchainProvider<DB>
@@ -5,18 +5,22 @@ use crate::{
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress, ShardedKey},
     table::Table,
     transaction::DbTx,
 };
 use reth_primitives::{
-    constants::EPOCH_SLOTS, proofs::AccountProof, Account, Address, BlockNumber, Bytecode,
-    StaticFileSegment, StorageKey, StorageValue, B256,
+    constants::EPOCH_SLOTS,
+    proofs::{AccountProof, MultiProof},
+    Account, Address, BlockNumber, Bytecode, StaticFileSegment, StorageKey, StorageValue, B256,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{updates::TrieUpdates, HashedPostState};
 use revm::db::BundleState;
-use std::fmt::Debug;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -29,6 +33,9 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+///
+/// Changesets of blocks that have been moved to static files are read through the
+/// [`StaticFileProvider`] instead.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -130,7 +137,42 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             );
         }
 
-        Ok(HashedPostState::from_revert_range(self.tx, self.block_number..=tip)?)
+        let range = self.block_number..tip + 1;
+        let account_changesets = self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            range.clone(),
+            |static_file, range, _| {
+                static_file.account_changesets_range(range.start..=range.end - 1)
+            },
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::AccountChangeSets>()?
+                    .walk_range(range)?
+                    .map(|entry| entry.map_err(Into::into))
+                    .collect()
+            },
+            |_| true,
+        )?;
+        let storage_changesets = self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            range,
+            |static_file, range, _| {
+                static_file.storage_changesets_range(range.start..=range.end - 1)
+            },
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::StorageChangeSets>()?
+                    .walk_range(BlockNumberAddress::range(range.start..=range.end - 1))?
+                    .map(|entry| entry.map_err(Into::into))
+                    .collect()
+            },
+            |_| true,
+        )?;
+
+        Ok(HashedPostState::from_reverts(
+            account_changesets.into_iter().map(|(_, account)| account),
+            storage_changesets.into_iter().map(|(key, storage)| (key.address(), storage)),
+        ))
     }
 
     fn history_info<T, K>(
@@ -203,10 +245,28 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .static_file_provider
+                .get_with_static_file_or_database(
+                    StaticFileSegment::AccountChangeSets,
+                    changeset_block_number,
+                    |static_file| {
+                        Ok(static_file.account_block_changeset(changeset_block_number)?.and_then(
+                            |mut changes| {
+                                changes
+                                    .binary_search_by_key(&address, |acc| acc.address)
+                                    .ok()
+                                    .map(|index| changes.swap_remove(index))
+                            },
+                        ))
+                    },
+                    || {
+                        Ok(self
+                            .tx
+                            .cursor_dup_read::<tables::AccountChangeSets>()?
+                            .seek_by_key_subkey(changeset_block_number, address)?
+                            .filter(|acc| acc.address == address))
+                    },
+                )?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
@@ -281,10 +341,33 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.static_file_provider
+                    .get_with_static_file_or_database(
+                        StaticFileSegment::StorageChangeSets,
+                        changeset_block_number,
+                        |static_file| {
+                            Ok(static_file
+                                .storage_block_changeset(changeset_block_number)?
+                                .and_then(|changes| {
+                                    changes
+                                        .binary_search_by_key(&(address, storage_key), |change| {
+                                            (change.address, change.key)
+                                        })
+                                        .ok()
+                                        .map(|index| changes[index].entry())
+                                }))
+                        },
+                        || {
+                            Ok(self
+                                .tx
+                                .cursor_dup_read::<tables::StorageChangeSets>()?
+                                .seek_by_key_subkey(
+                                    (changeset_block_number, address).into(),
+                                    storage_key,
+                                )?
+                                .filter(|entry| entry.key == storage_key))
+                        },
+                    )?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -308,8 +391,22 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    ///
+    /// The proof is generated by overlaying the reverts between this block and the tip on top of
+    /// the current trie, see [`HashedPostState::account_proof`].
+    fn proof(&self, address: Address, slots: &[B256]) -> ProviderResult<AccountProof> {
+        Ok(self
+            .revert_state()?
+            .account_proof(self.tx, address, slots)
+            .map_err(Into::<reth_db::DatabaseError>::into)?)
+    }
+
+    /// Get a state multiproof, see [`Self::proof`].
+    fn multiproof(&self, targets: &HashMap<B256, HashSet<B256>>) -> ProviderResult<MultiProof> {
+        Ok(self
+            .revert_state()?
+            .multiproof(self.tx, targets)
+            .map_err(Into::<reth_db::DatabaseError>::into)?)
     }
 }
 
@@ -401,17 +498,25 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::{
+            state::historical::{HistoryInfo, LowestAvailableBlocks},
+            StaticFileWriter,
+        },
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
         StaticFileProviderFactory,
     };
     use reth_db::{tables, BlockNumberList};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey, StorageBeforeTx,
+            StoredBlockAccountChanges, StoredBlockStorageChanges,
+        },
         transaction::{DbTx, DbTxMut},
     };
-    use reth_primitives::{address, b256, Account, Address, StorageEntry, B256, U256};
+    use reth_primitives::{
+        address, b256, Account, Address, StaticFileSegment, StorageEntry, B256, U256,
+    };
     use reth_storage_errors::provider::ProviderError;
 
     const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
@@ -655,6 +760,134 @@ mod tests {
         );
     }
 
+    #[test]
+    fn history_provider_changesets_in_static_files() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        let static_file_provider = factory.static_file_provider();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([3, 7, 10, 15]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: HIGHER_ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([7]).unwrap(),
+        )
+        .unwrap();
+        for address in [ADDRESS, HIGHER_ADDRESS] {
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey {
+                    address,
+                    sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+                },
+                BlockNumberList::new(if address == ADDRESS { vec![3, 7, 10, 15] } else { vec![7] })
+                    .unwrap(),
+            )
+            .unwrap();
+        }
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at15 = Account { nonce: 15, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at10 = Account { nonce: 10, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at7 = Account { nonce: 7, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let higher_acc_plain = Account { nonce: 4, balance: U256::ZERO, bytecode_hash: None };
+
+        // changesets of blocks 0..=7 are in static files
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        for block in 0..=7 {
+            let changes = match block {
+                3 => vec![AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) }],
+                7 => vec![
+                    AccountBeforeTx { address: ADDRESS, info: Some(acc_at7) },
+                    AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+                ],
+                _ => Vec::new(),
+            };
+            writer.append_account_changeset(block, StoredBlockAccountChanges { changes }).unwrap();
+        }
+        writer.commit().unwrap();
+
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=7 {
+            let changes = match block {
+                3 => vec![StorageBeforeTx { address: ADDRESS, key: STORAGE, value: U256::from(3) }],
+                7 => vec![
+                    StorageBeforeTx { address: ADDRESS, key: B256::ZERO, value: U256::from(1) },
+                    StorageBeforeTx { address: ADDRESS, key: STORAGE, value: U256::from(7) },
+                    StorageBeforeTx { address: HIGHER_ADDRESS, key: STORAGE, value: U256::from(4) },
+                ],
+                _ => Vec::new(),
+            };
+            writer.append_storage_changeset(block, StoredBlockStorageChanges { changes }).unwrap();
+        }
+        writer.commit().unwrap();
+
+        // changesets of the later blocks are still in the database
+        tx.put::<tables::AccountChangeSets>(
+            10,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at10) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            15,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at15) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            (10, ADDRESS).into(),
+            StorageEntry { key: STORAGE, value: U256::from(10) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            (15, ADDRESS).into(),
+            StorageEntry { key: STORAGE, value: U256::from(15) },
+        )
+        .unwrap();
+
+        // setup plain state
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainAccountState>(HIGHER_ADDRESS, higher_acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(
+            ADDRESS,
+            StorageEntry { key: STORAGE, value: U256::from(100) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainStorageState>(
+            HIGHER_ADDRESS,
+            StorageEntry { key: STORAGE, value: U256::from(1000) },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let tx = factory.provider().unwrap().into_tx();
+        let provider_at =
+            |block| HistoricalStateProviderRef::new(&tx, block, static_file_provider.clone());
+
+        // run
+        for (block, account, storage) in [
+            (2, acc_at3, U256::from(3)),
+            (3, acc_at3, U256::from(3)),
+            (4, acc_at7, U256::from(7)),
+            (7, acc_at7, U256::from(7)),
+            (8, acc_at10, U256::from(10)),
+            (11, acc_at15, U256::from(15)),
+            (16, acc_plain, U256::from(100)),
+        ] {
+            assert_eq!(provider_at(block).basic_account(ADDRESS), Ok(Some(account)));
+            assert_eq!(provider_at(block).storage(ADDRESS, STORAGE), Ok(Some(storage)));
+        }
+
+        assert_eq!(provider_at(7).basic_account(HIGHER_ADDRESS), Ok(None));
+        assert_eq!(provider_at(7).storage(HIGHER_ADDRESS, STORAGE), Ok(Some(U256::from(4))));
+        assert_eq!(provider_at(8).basic_account(HIGHER_ADDRESS), Ok(Some(higher_acc_plain)));
+        assert_eq!(provider_at(8).storage(HIGHER_ADDRESS, STORAGE), Ok(Some(U256::from(1000))));
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
@@ -8,12 +8,13 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_primitives::{
-    proofs::AccountProof, Account, Address, BlockNumber, Bytecode, StaticFileSegment, StorageKey,
-    StorageValue, B256,
+    proofs::{AccountProof, MultiProof},
+    Account, Address, BlockNumber, Bytecode, StaticFileSegment, StorageKey, StorageValue, B256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{proof::Proof, updates::TrieUpdates, HashedPostState};
 use revm::db::BundleState;
+use std::collections::{HashMap, HashSet};
 
 /// State provider over latest state that takes tx reference.
 #[derive(Debug)]
@@ -117,6 +118,10 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
             .account_proof(address, slots)
             .map_err(Into::<reth_db::DatabaseError>::into)?)
     }
+
+    fn multiproof(&self, targets: &HashMap<B256, HashSet<B256>>) -> ProviderResult<MultiProof> {
+        Ok(Proof::new(self.tx).multiproof(targets).map_err(Into::<reth_db::DatabaseError>::into)?)
+    }
 }
 
 /// State provider for the latest state.
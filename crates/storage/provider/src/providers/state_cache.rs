@@ -0,0 +1,412 @@
+//! A cache of the state of the canonical tip that is shared across blocks.
+
+use crate::{AccountReader, BlockHashReader, StateProvider, StateRootProvider};
+use parking_lot::Mutex;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{
+    proofs::{AccountProof, MultiProof},
+    revm::compat::into_reth_acc,
+    Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::updates::TrieUpdates;
+use revm::db::BundleState;
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
+
+/// The default maximum number of cached accounts and of cached storage slots.
+pub const DEFAULT_STATE_CACHE_SIZE: u32 = 1_000_000;
+
+/// The maximum number of cached bytecodes.
+const MAX_CACHED_BYTECODES: u32 = 10_000;
+
+/// A bounded cache of accounts, storage slots and bytecodes that is shared across blocks.
+///
+/// The cached accounts and storage slots belong to the state after a single block, the tip of the
+/// cache. They are only read and filled by [`CachedStateProvider`]s over the state of that block,
+/// see [`StateCache::provider`]. When blocks are committed on top of the tip, their changes are
+/// applied to the cache with [`StateCache::apply`] and the tip moves to the last committed block.
+/// Committing blocks that do not extend the tip, for example after an unwind, clears the cache.
+///
+/// Bytecodes are immutable and shared by all states.
+#[derive(Clone, Debug)]
+pub struct StateCache {
+    inner: Arc<Mutex<StateCacheInner>>,
+    /// The maximum number of cached accounts and of cached storage slots.
+    size: u32,
+    metrics: StateCacheMetrics,
+}
+
+impl StateCache {
+    /// Creates a new cache that holds up to `size` accounts and `size` storage slots.
+    ///
+    /// The cache is disabled if `size` is `0`.
+    pub fn new(size: u32) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(StateCacheInner {
+                tip: None,
+                accounts: LruMap::new(ByLength::new(size)),
+                storage: LruMap::new(ByLength::new(size)),
+                bytecodes: LruMap::new(ByLength::new(MAX_CACHED_BYTECODES.min(size))),
+            })),
+            size,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Creates a disabled cache.
+    pub fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    /// Returns `true` if the cache holds any state.
+    pub const fn is_enabled(&self) -> bool {
+        self.size > 0
+    }
+
+    /// Returns the hash of the block whose state is cached.
+    pub fn tip(&self) -> Option<B256> {
+        self.inner.lock().tip
+    }
+
+    /// Wraps the given provider of the state after the block with the given hash.
+    ///
+    /// The accounts and storage slots of the cache are only used if the block is the tip of the
+    /// cache.
+    pub fn provider<SP: StateProvider>(
+        &self,
+        state_provider: SP,
+        block_hash: B256,
+    ) -> CachedStateProvider<SP> {
+        let is_tip = self.tip() == Some(block_hash);
+        CachedStateProvider { state_provider, cache: self.clone(), is_tip }
+    }
+
+    /// Returns the update of the cache for committing the given state of the blocks between the
+    /// child of `parent_hash` and `tip_hash`.
+    ///
+    /// The update has to be applied with [`StateCache::apply`] once the state is committed.
+    pub fn update(
+        &self,
+        parent_hash: B256,
+        tip_hash: B256,
+        state: &BundleState,
+    ) -> StateCacheUpdate {
+        let mut update = StateCacheUpdate { parent_hash, tip_hash, ..Default::default() };
+        if !self.is_enabled() {
+            return update
+        }
+
+        for (address, account) in state.state() {
+            update.accounts.push((*address, account.info.clone().map(into_reth_acc)));
+            if account.was_destroyed() {
+                update.wiped.push(*address);
+            }
+            for (key, slot) in &account.storage {
+                let value = slot.present_value;
+                update.storage.push((
+                    *address,
+                    B256::new(key.to_be_bytes()),
+                    (!value.is_zero()).then_some(value),
+                ));
+            }
+        }
+        update.bytecodes =
+            state.contracts.iter().map(|(hash, code)| (*hash, Bytecode(code.clone()))).collect();
+        update
+    }
+
+    /// Applies a committed update.
+    ///
+    /// The cache is cleared if the committed blocks do not extend its tip.
+    pub fn apply(&self, update: StateCacheUpdate) {
+        let mut inner = self.inner.lock();
+        if inner.tip != Some(update.parent_hash) {
+            inner.clear();
+            self.metrics.invalidations.increment(1);
+        } else {
+            for address in update.wiped {
+                inner.wipe_storage(address);
+            }
+            for (address, account) in update.accounts {
+                inner.accounts.insert(address, account);
+            }
+            for (address, key, value) in update.storage {
+                inner.storage.insert((address, key), value);
+            }
+        }
+        for (hash, bytecode) in update.bytecodes {
+            inner.bytecodes.insert(hash, bytecode);
+        }
+        inner.tip = Some(update.tip_hash);
+    }
+
+    /// Clears all accounts and storage slots and unsets the tip of the cache.
+    ///
+    /// This has to be called when the canonical chain is unwound, and whenever the canonical state
+    /// is written without going through the cache, for example by the pipeline.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.clear();
+        inner.tip = None;
+        self.metrics.invalidations.increment(1);
+    }
+}
+
+impl Default for StateCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_STATE_CACHE_SIZE)
+    }
+}
+
+/// The changes of committed blocks to apply to a [`StateCache`].
+///
+/// Created with [`StateCache::update`].
+#[derive(Debug, Default)]
+pub struct StateCacheUpdate {
+    /// The hash of the parent of the first committed block.
+    parent_hash: B256,
+    /// The hash of the last committed block.
+    tip_hash: B256,
+    /// The changed accounts, `None` if the account was destroyed.
+    accounts: Vec<(Address, Option<Account>)>,
+    /// The accounts whose storage was wiped, before their changed storage slots are applied.
+    wiped: Vec<Address>,
+    /// The changed storage slots, `None` if the slot was cleared.
+    storage: Vec<(Address, B256, Option<StorageValue>)>,
+    /// The new bytecodes.
+    bytecodes: Vec<(B256, Bytecode)>,
+}
+
+struct StateCacheInner {
+    /// The hash of the block whose state is cached, `None` if unknown.
+    tip: Option<B256>,
+    /// Cached accounts, `None` if the account does not exist.
+    accounts: LruMap<Address, Option<Account>, ByLength>,
+    /// Cached storage slots, `None` if the slot is empty.
+    storage: LruMap<(Address, B256), Option<StorageValue>, ByLength>,
+    /// Cached bytecodes by code hash.
+    bytecodes: LruMap<B256, Bytecode, ByLength>,
+}
+
+impl StateCacheInner {
+    /// Removes all accounts and storage slots.
+    fn clear(&mut self) {
+        self.accounts.clear();
+        self.storage.clear();
+    }
+
+    /// Removes all storage slots of the account.
+    fn wipe_storage(&mut self, address: Address) {
+        let keys = self
+            .storage
+            .iter()
+            .filter(|((slot_address, _), _)| *slot_address == address)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in keys {
+            self.storage.remove(&key);
+        }
+    }
+}
+
+impl fmt::Debug for StateCacheInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateCacheInner")
+            .field("tip", &self.tip)
+            .field("accounts", &self.accounts.len())
+            .field("storage", &self.storage.len())
+            .field("bytecodes", &self.bytecodes.len())
+            .finish()
+    }
+}
+
+/// A state provider that reads through a [`StateCache`].
+///
+/// Created with [`StateCache::provider`].
+#[derive(Debug)]
+pub struct CachedStateProvider<SP> {
+    /// The inner state provider.
+    state_provider: SP,
+    /// The shared cache.
+    cache: StateCache,
+    /// Whether the state of the provider is the state of the cache tip.
+    is_tip: bool,
+}
+
+impl<SP: StateProvider> BlockHashReader for CachedStateProvider<SP> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.state_provider.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.state_provider.canonical_hashes_range(start, end)
+    }
+}
+
+impl<SP: StateProvider> AccountReader for CachedStateProvider<SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        if !self.is_tip {
+            return self.state_provider.basic_account(address)
+        }
+        if let Some(account) = self.cache.inner.lock().accounts.get(&address) {
+            self.cache.metrics.account_hits.increment(1);
+            return Ok(*account)
+        }
+        self.cache.metrics.account_misses.increment(1);
+
+        let account = self.state_provider.basic_account(address)?;
+        self.cache.inner.lock().accounts.insert(address, account);
+        Ok(account)
+    }
+}
+
+impl<SP: StateProvider> StateRootProvider for CachedStateProvider<SP> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.state_provider.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state_provider.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<SP: StateProvider> StateProvider for CachedStateProvider<SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if !self.is_tip {
+            return self.state_provider.storage(account, storage_key)
+        }
+        if let Some(value) = self.cache.inner.lock().storage.get(&(account, storage_key)) {
+            self.cache.metrics.storage_hits.increment(1);
+            return Ok(*value)
+        }
+        self.cache.metrics.storage_misses.increment(1);
+
+        let value = self.state_provider.storage(account, storage_key)?;
+        self.cache.inner.lock().storage.insert((account, storage_key), value);
+        Ok(value)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.cache.inner.lock().bytecodes.get(&code_hash) {
+            self.cache.metrics.bytecode_hits.increment(1);
+            return Ok(Some(bytecode.clone()))
+        }
+        self.cache.metrics.bytecode_misses.increment(1);
+
+        let bytecode = self.state_provider.bytecode_by_hash(code_hash)?;
+        if let Some(bytecode) = &bytecode {
+            self.cache.inner.lock().bytecodes.insert(code_hash, bytecode.clone());
+        }
+        Ok(bytecode)
+    }
+
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.state_provider.proof(address, keys)
+    }
+
+    fn multiproof(&self, targets: &HashMap<B256, HashSet<B256>>) -> ProviderResult<MultiProof> {
+        self.state_provider.multiproof(targets)
+    }
+}
+
+/// Metrics of the [`StateCache`].
+#[derive(Clone, Metrics)]
+#[metrics(scope = "storage.providers.state_cache")]
+struct StateCacheMetrics {
+    /// Number of account reads served from the cache
+    account_hits: Counter,
+    /// Number of account reads served from the database
+    account_misses: Counter,
+    /// Number of storage reads served from the cache
+    storage_hits: Counter,
+    /// Number of storage reads served from the database
+    storage_misses: Counter,
+    /// Number of bytecode reads served from the cache
+    bytecode_hits: Counter,
+    /// Number of bytecode reads served from the database
+    bytecode_misses: Counter,
+    /// Number of times the cached accounts and storage slots were discarded
+    invalidations: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_primitives::U256;
+    use revm::{
+        db::{states::StorageSlot, AccountStatus, BundleAccount},
+        primitives::AccountInfo,
+    };
+
+    #[test]
+    fn cache_follows_committed_state() {
+        let address = Address::with_last_byte(1);
+        let key = B256::with_last_byte(2);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(1, U256::ZERO).extend_storage([(key, U256::from(3))]),
+        );
+
+        let cache = StateCache::new(10);
+        let (parent, tip) = (B256::with_last_byte(10), B256::with_last_byte(11));
+        cache.apply(cache.update(B256::ZERO, parent, &BundleState::default()));
+        assert_eq!(cache.tip(), Some(parent));
+
+        let cached = cache.provider(&provider, parent);
+        assert_eq!(cached.storage(address, key).unwrap(), Some(U256::from(3)));
+        assert_eq!(cached.basic_account(address).unwrap().unwrap().nonce, 1);
+        // cached values are not read from the database again
+        provider.add_account(address, ExtendedAccount::new(2, U256::ZERO));
+        assert_eq!(cached.storage(address, key).unwrap(), Some(U256::from(3)));
+        assert_eq!(cached.basic_account(address).unwrap().unwrap().nonce, 1);
+
+        // committing blocks on top of the tip applies their changes
+        let mut state = BundleState::default();
+        state.state.insert(
+            address,
+            BundleAccount::new(
+                Some(AccountInfo { nonce: 1, ..Default::default() }),
+                Some(AccountInfo { nonce: 2, ..Default::default() }),
+                [(U256::from_be_bytes(key.0), StorageSlot::new_changed(U256::from(3), U256::ZERO))]
+                    .into_iter()
+                    .collect(),
+                AccountStatus::Changed,
+            ),
+        );
+        cache.apply(cache.update(parent, tip, &state));
+        let cached = cache.provider(&provider, tip);
+        assert_eq!(cached.basic_account(address).unwrap().unwrap().nonce, 2);
+        assert_eq!(cached.storage(address, key).unwrap(), None);
+
+        // providers of other states bypass the cache
+        let other = B256::with_last_byte(12);
+        provider.add_account(address, ExtendedAccount::new(3, U256::ZERO));
+        assert_eq!(
+            cache.provider(&provider, other).basic_account(address).unwrap().unwrap().nonce,
+            3
+        );
+
+        // committing blocks that do not extend the tip clears the cache
+        cache.apply(cache.update(other, B256::with_last_byte(13), &BundleState::default()));
+        let cached = cache.provider(&provider, B256::with_last_byte(13));
+        assert_eq!(cached.basic_account(address).unwrap().unwrap().nonce, 3);
+    }
+}
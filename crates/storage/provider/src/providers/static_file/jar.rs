@@ -6,8 +6,14 @@ use crate::{
     to_range, BlockHashReader, BlockNumReader, HeaderProvider, ReceiptProvider,
     TransactionsProvider,
 };
-use reth_db::static_file::{HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask};
-use reth_db_api::models::CompactU256;
+use reth_db::static_file::{
+    AccountChangeSetMask, HeaderMask, ReceiptMask, StaticFileCursor, StorageChangeSetMask,
+    TransactionMask, TransactionSenderMask,
+};
+use reth_db_api::models::{
+    AccountBeforeTx, CompactU256, StorageBeforeTx, StoredBlockAccountChanges,
+    StoredBlockStorageChanges,
+};
 use reth_primitives::{
     Address, BlockHash, BlockHashOrNumber, BlockNumber, ChainInfo, Header, Receipt, SealedHeader,
     TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, U256,
@@ -71,6 +77,33 @@ impl<'a> StaticFileJarProvider<'a> {
         self.metrics = Some(metrics);
         self
     }
+
+    /// Returns the account changes of a block, sorted by address.
+    pub fn account_block_changeset(
+        &self,
+        num: BlockNumber,
+    ) -> ProviderResult<Option<Vec<AccountBeforeTx>>> {
+        Ok(self
+            .cursor()?
+            .get_one::<AccountChangeSetMask<StoredBlockAccountChanges>>(num.into())?
+            .map(|changeset| changeset.changes))
+    }
+
+    /// Returns the storage changes of a block, sorted by address and storage key.
+    pub fn storage_block_changeset(
+        &self,
+        num: BlockNumber,
+    ) -> ProviderResult<Option<Vec<StorageBeforeTx>>> {
+        Ok(self
+            .cursor()?
+            .get_one::<StorageChangeSetMask<StoredBlockStorageChanges>>(num.into())?
+            .map(|changeset| changeset.changes))
+    }
+
+    /// Returns the sender of a transaction, as stored in the transaction senders static files.
+    pub fn sender_by_id(&self, num: TxNumber) -> ProviderResult<Option<Address>> {
+        self.cursor()?.get_one::<TransactionSenderMask<Address>>(num.into())
+    }
 }
 
 impl<'a> HeaderProvider for StaticFileJarProvider<'a> {
@@ -11,12 +11,18 @@ use dashmap::{mapref::entry::Entry as DashMapEntry, DashMap};
 use parking_lot::RwLock;
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{
+        iter_static_files, AccountChangeSetMask, HeaderMask, ReceiptMask, StaticFileCursor,
+        StorageChangeSetMask, TransactionMask, TransactionSenderMask,
+    },
     tables,
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::{CompactU256, StoredBlockBodyIndices},
+    models::{
+        AccountBeforeTx, BlockNumberAddress, CompactU256, StorageBeforeTx,
+        StoredBlockAccountChanges, StoredBlockBodyIndices, StoredBlockStorageChanges,
+    },
     table::Table,
     transaction::DbTx,
 };
@@ -25,9 +31,9 @@ use reth_primitives::{
     keccak256,
     static_file::{find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive},
     Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo, Header,
-    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -45,6 +51,13 @@ use tracing::{info, warn};
 /// range.
 type SegmentRanges = HashMap<StaticFileSegment, BTreeMap<TxNumber, SegmentRangeInclusive>>;
 
+/// Prefix of the directories in which the replacement of a static file is staged.
+const REPLACEMENT_DIR_PREFIX: &str = ".recompress-";
+
+/// File marking a staged replacement as complete. Replacements with the marker are rolled forward
+/// on startup, those without it are discarded.
+const REPLACEMENT_MARKER: &str = "REPLACE";
+
 /// Access mode on a static file provider. RO/RW.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub enum StaticFileAccess {
@@ -74,7 +87,11 @@ pub struct StaticFileProvider(pub(crate) Arc<StaticFileProviderInner>);
 impl StaticFileProvider {
     /// Creates a new [`StaticFileProvider`].
     fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
+        let is_read_write = access.is_read_write();
         let provider = Self(Arc::new(StaticFileProviderInner::new(path, access)?));
+        if is_read_write {
+            provider.recover_replacements()?;
+        }
         provider.initialize_index()?;
         Ok(provider)
     }
@@ -284,6 +301,72 @@ impl StaticFileProvider {
         self.map.remove(&(fixed_block_range_end, segment));
     }
 
+    /// Returns the directory in which a replacement of the static file of the segment and block
+    /// range is staged before [`Self::replace_jar`].
+    pub fn replacement_dir(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) -> PathBuf {
+        self.path.join(format!("{REPLACEMENT_DIR_PREFIX}{}", segment.filename(fixed_block_range)))
+    }
+
+    /// Replaces the static file of the segment and block range with the one staged in its
+    /// [`Self::replacement_dir`].
+    ///
+    /// The staged files are synced and marked as complete before any of them is moved, so that a
+    /// replacement interrupted by a crash is rolled forward on the next startup.
+    ///
+    /// CAUTION: destructive. Overwrites files on disk.
+    pub fn replace_jar(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: SegmentRangeInclusive,
+    ) -> ProviderResult<()> {
+        let staging_dir = self.replacement_dir(segment, &fixed_block_range);
+        for entry in reth_fs_util::read_dir(&staging_dir)? {
+            let entry =
+                entry.map_err(|err| reth_fs_util::FsPathError::read_dir(err, &staging_dir))?;
+            sync_path(&entry.path())?;
+        }
+        let marker = staging_dir.join(REPLACEMENT_MARKER);
+        reth_fs_util::write(&marker, b"")?;
+        sync_path(&marker)?;
+        sync_path(&staging_dir)?;
+
+        self.remove_cached_provider(segment, fixed_block_range.end());
+        finish_replacement(&self.path, &staging_dir)
+    }
+
+    /// Rolls forward the replacements of static files that were marked as complete, and discards
+    /// all others.
+    fn recover_replacements(&self) -> ProviderResult<()> {
+        if !self.path.exists() {
+            return Ok(())
+        }
+
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let entry =
+                entry.map_err(|err| reth_fs_util::FsPathError::read_dir(err, &self.path))?;
+            let staging_dir = entry.path();
+            if !staging_dir.is_dir() ||
+                !entry.file_name().to_string_lossy().starts_with(REPLACEMENT_DIR_PREFIX)
+            {
+                continue
+            }
+
+            if staging_dir.join(REPLACEMENT_MARKER).exists() {
+                info!(target: "reth::providers::static_file", ?staging_dir, "Finishing interrupted static file replacement");
+                finish_replacement(&self.path, &staging_dir)?;
+            } else {
+                info!(target: "reth::providers::static_file", ?staging_dir, "Discarding incomplete static file replacement");
+                reth_fs_util::remove_dir_all(&staging_dir)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Given a segment and block range it deletes the jar and all files associated with it.
     ///
     /// CAUTION: destructive. Deletes files on disk.
@@ -630,6 +713,19 @@ impl StaticFileProvider {
                     highest_tx,
                     highest_block,
                 )?,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.ensure_changeset_invariants(provider, segment, highest_block)?
+                }
+                // Senders are only moved to static files if they're not pruned, so a node without
+                // any sender static file has nothing to check against the database.
+                StaticFileSegment::TransactionSenders if highest_tx.is_none() => None,
+                StaticFileSegment::TransactionSenders => self
+                    .ensure_invariants::<_, tables::TransactionSenders>(
+                        provider,
+                        segment,
+                        highest_tx,
+                        highest_block,
+                    )?,
             } {
                 update_unwind_target(unwind);
             }
@@ -689,7 +785,10 @@ impl StaticFileProvider {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
+                StaticFileSegment::TransactionSenders => StageId::SenderRecovery,
             })?
             .unwrap_or_default()
             .block_number;
@@ -724,6 +823,8 @@ impl StaticFileProvider {
                 let number = highest_static_file_entry - block.last_tx_num();
                 if segment.is_receipts() {
                     writer.prune_receipts(number, checkpoint_block_number)?;
+                } else if segment.is_transaction_senders() {
+                    writer.prune_transaction_senders(number, checkpoint_block_number)?;
                 } else {
                     writer.prune_transactions(number, checkpoint_block_number)?;
                 }
@@ -734,6 +835,45 @@ impl StaticFileProvider {
         Ok(None)
     }
 
+    /// Check invariants of a changeset static file segment.
+    ///
+    /// Changesets are copied to static files after their blocks have been executed, so the
+    /// database usually holds the changesets of the blocks above the highest static file block and
+    /// there is nothing to check against the database. If the execution checkpoint is behind the
+    /// highest static file block, an unwind was committed to the database but not to static files,
+    /// so the extra static file rows are removed.
+    fn ensure_changeset_invariants<TX: DbTx>(
+        &self,
+        provider: &DatabaseProvider<TX>,
+        segment: StaticFileSegment,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(None) };
+
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            let to_delete = highest_static_file_block - checkpoint_block_number;
+            if segment == StaticFileSegment::AccountChangeSets {
+                writer.prune_account_changesets(to_delete)?;
+            } else {
+                writer.prune_storage_changesets(to_delete)?;
+            }
+            writer.commit()?;
+        }
+
+        Ok(None)
+    }
+
     /// Gets the highest static file block if it exists for a static file segment.
     pub fn get_highest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
         self.static_files_max_block.read().get(&segment).copied()
@@ -753,9 +893,127 @@ impl StaticFileProvider {
             headers: self.get_highest_static_file_block(StaticFileSegment::Headers),
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
+            account_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            transaction_senders: self
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
         }
     }
 
+    /// Returns the account changes of a block, sorted by address.
+    ///
+    /// Returns `None` if the block is not in static files.
+    pub fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<Vec<AccountBeforeTx>>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.account_block_changeset(block_number))
+        .or_else(|err| {
+            if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                Ok(None)
+            } else {
+                Err(err)
+            }
+        })
+    }
+
+    /// Returns the storage changes of a block, sorted by address and storage key.
+    ///
+    /// Returns `None` if the block is not in static files.
+    pub fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<Vec<StorageBeforeTx>>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.storage_block_changeset(block_number))
+        .or_else(|err| {
+            if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                Ok(None)
+            } else {
+                Err(err)
+            }
+        })
+    }
+
+    /// Returns the account changesets of a block range, in the same order and format as they are
+    /// stored in the `AccountChangeSets` table.
+    pub fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::AccountChangeSets,
+            to_range(range),
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<AccountChangeSetMask<StoredBlockAccountChanges>>(number.into())?
+                    .map(|changeset| (number, changeset.changes)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(number, changes)| changes.into_iter().map(move |change| (number, change)))
+            .collect())
+    }
+
+    /// Returns the storage changesets of a block range, in the same order and format as they are
+    /// stored in the `StorageChangeSets` table.
+    pub fn storage_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::StorageChangeSets,
+            to_range(range),
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<StorageChangeSetMask<StoredBlockStorageChanges>>(number.into())?
+                    .map(|changeset| (number, changeset.changes)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(number, changes)| {
+                changes.into_iter().map(move |change| {
+                    (BlockNumberAddress((number, change.address)), change.entry())
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the transaction senders of a transaction range along with their [`TxNumber`], in the
+    /// same format as they are stored in the `TransactionSenders` table.
+    pub fn transaction_senders_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<(TxNumber, Address)>> {
+        self.fetch_range_with_predicate(
+            StaticFileSegment::TransactionSenders,
+            to_range(range),
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<TransactionSenderMask<Address>>(number.into())?
+                    .map(|sender| (number, sender)))
+            },
+            |_| true,
+        )
+    }
+
     /// Iterates through segment `static_files` in reverse order, executing a function until it
     /// returns some object. Useful for finding objects by [`TxHash`] or [`BlockHash`].
     pub fn find_static_file<T>(
@@ -796,10 +1054,14 @@ impl StaticFileProvider {
         P: FnMut(&T) -> bool,
     {
         let get_provider = |start: u64| match segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::TransactionSenders => {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -834,7 +1096,7 @@ impl StaticFileProvider {
                                 "Could not find block or tx number on a range request"
                             );
 
-                            let err = if segment.is_headers() {
+                            let err = if segment.is_block_based() {
                                 ProviderError::MissingStaticFileBlock(segment, number)
                             } else {
                                 ProviderError::MissingStaticFileTx(segment, number)
@@ -866,10 +1128,14 @@ impl StaticFileProvider {
         T: std::fmt::Debug,
     {
         let get_provider = move |start: u64| match segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::TransactionSenders => {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -913,10 +1179,12 @@ impl StaticFileProvider {
     {
         // If there is, check the maximum block or transaction number of the segment.
         let static_file_upper_bound = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.get_highest_static_file_block(segment),
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::TransactionSenders => self.get_highest_static_file_tx(segment),
         };
 
         if static_file_upper_bound
@@ -955,10 +1223,12 @@ impl StaticFileProvider {
 
         // If there is, check the maximum block or transaction number of the segment.
         if let Some(static_file_upper_bound) = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.get_highest_static_file_block(segment),
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::TransactionSenders => self.get_highest_static_file_tx(segment),
         } {
             if block_or_tx_range.start <= static_file_upper_bound {
                 let end = block_or_tx_range.end.min(static_file_upper_bound + 1);
@@ -1373,13 +1643,44 @@ impl TransactionsProvider for StaticFileProvider {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        let txes = self.transactions_by_tx_range(range)?;
-        TransactionSignedNoHash::recover_signers(&txes, txes.len())
-            .ok_or(ProviderError::SenderRecoveryError)
+        let range = to_range(range);
+
+        // Senders that have been moved to static files don't need to be recovered.
+        let recover_start = self
+            .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+            .map_or(range.start, |highest| (highest + 1).clamp(range.start, range.end));
+        let mut senders = if recover_start > range.start {
+            self.transaction_senders_range(range.start..recover_start)?
+                .into_iter()
+                .map(|(_, sender)| sender)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if recover_start < range.end {
+            let txes = self.transactions_by_tx_range(recover_start..range.end)?;
+            senders.extend(
+                TransactionSignedNoHash::recover_signers(&txes, txes.len())
+                    .ok_or(ProviderError::SenderRecoveryError)?,
+            );
+        }
+
+        Ok(senders)
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.transaction_by_id_no_hash(id)?.and_then(|tx| tx.recover_signer()))
+        match self.get_segment_provider_from_transaction(
+            StaticFileSegment::TransactionSenders,
+            id,
+            None,
+        ) {
+            Ok(provider) => provider.sender_by_id(id),
+            Err(ProviderError::MissingStaticFileTx(_, _)) => {
+                Ok(self.transaction_by_id_no_hash(id)?.and_then(|tx| tx.recover_signer()))
+            }
+            Err(err) => Err(err),
+        }
     }
 }
 
@@ -1536,3 +1837,38 @@ fn calculate_hash(
     tx.transaction.encode_with_signature(&tx.signature, rlp_buf, false);
     Ok((keccak256(rlp_buf), tx_id))
 }
+
+/// Moves the files staged in `staging_dir` into `dir`, overwriting the files of the replaced
+/// static file, and removes `staging_dir`.
+///
+/// Can be called again if it was interrupted, since moved files are no longer staged.
+fn finish_replacement(dir: &Path, staging_dir: &Path) -> ProviderResult<()> {
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(staging_dir)? {
+        let entry = entry.map_err(|err| reth_fs_util::FsPathError::read_dir(err, staging_dir))?;
+        if entry.file_name() != REPLACEMENT_MARKER {
+            files.push(entry.path());
+        }
+    }
+    // The configuration holds the rows of the static file, so it's moved last
+    files.sort_by_key(|path| path.extension().map_or(false, |extension| extension == "conf"));
+
+    for path in files {
+        if let Some(filename) = path.file_name() {
+            reth_fs_util::rename(&path, dir.join(filename))?;
+        }
+    }
+    sync_path(dir)?;
+
+    reth_fs_util::remove_dir_all(staging_dir)?;
+    Ok(())
+}
+
+/// Flushes a file or directory to disk.
+fn sync_path(path: &Path) -> ProviderResult<()> {
+    std::fs::File::open(path)
+        .map_err(|err| reth_fs_util::FsPathError::open(err, path))?
+        .sync_all()
+        .map_err(|err| reth_fs_util::FsPathError::write(err, path))?;
+    Ok(())
+}
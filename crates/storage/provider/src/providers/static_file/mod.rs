@@ -179,4 +179,33 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_recover_replacements() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let dir = static_files_path.path();
+
+        // A replacement that was interrupted after some of its files were moved
+        reth_fs_util::write(dir.join("finished"), b"old").unwrap();
+        reth_fs_util::write(dir.join("finished.conf"), b"old").unwrap();
+        let finished = dir.join(".recompress-finished");
+        reth_fs_util::create_dir_all(&finished).unwrap();
+        reth_fs_util::write(finished.join("finished.conf"), b"new").unwrap();
+        reth_fs_util::write(finished.join("REPLACE"), b"").unwrap();
+        reth_fs_util::write(dir.join("finished"), b"new").unwrap();
+
+        // A replacement that was interrupted before it was complete
+        reth_fs_util::write(dir.join("incomplete"), b"old").unwrap();
+        let incomplete = dir.join(".recompress-incomplete");
+        reth_fs_util::create_dir_all(&incomplete).unwrap();
+        reth_fs_util::write(incomplete.join("incomplete"), b"new").unwrap();
+
+        StaticFileProvider::read_write(dir).unwrap();
+
+        assert_eq!(std::fs::read(dir.join("finished")).unwrap(), b"new");
+        assert_eq!(std::fs::read(dir.join("finished.conf")).unwrap(), b"new");
+        assert_eq!(std::fs::read(dir.join("incomplete")).unwrap(), b"old");
+        assert!(!finished.exists());
+        assert!(!incomplete.exists());
+    }
 }
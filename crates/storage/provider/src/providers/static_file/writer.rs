@@ -5,12 +5,12 @@ use super::{
 };
 use dashmap::mapref::one::RefMut;
 use reth_codecs::Compact;
-use reth_db_api::models::CompactU256;
+use reth_db_api::models::{CompactU256, StoredBlockAccountChanges, StoredBlockStorageChanges};
 use reth_nippy_jar::{ConsistencyFailStrategy, NippyJar, NippyJarError, NippyJarWriter};
 use reth_primitives::{
     static_file::{find_fixed_range, SegmentHeader, SegmentRangeInclusive},
-    BlockHash, BlockNumber, Header, Receipt, StaticFileSegment, TransactionSignedNoHash, TxNumber,
-    U256,
+    Address, BlockHash, BlockNumber, Header, Receipt, StaticFileSegment, TransactionSignedNoHash,
+    TxNumber, U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
@@ -145,7 +145,7 @@ impl StaticFileProviderRW {
         })?;
 
         // If we have lost rows (in this run or previous), we need to update the [SegmentHeader].
-        let expected_rows = if self.user_header().segment().is_headers() {
+        let expected_rows = if self.user_header().segment().is_block_based() {
             self.user_header().block_len().unwrap_or_default()
         } else {
             self.user_header().tx_len().unwrap_or_default()
@@ -178,6 +178,13 @@ impl StaticFileProviderRW {
                 StaticFileSegment::Receipts => {
                     self.prune_receipt_data(to_delete, last_block_number.expect("should exist"))?
                 }
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.prune_changeset_data(to_delete)?
+                }
+                StaticFileSegment::TransactionSenders => self.prune_transaction_sender_data(
+                    to_delete,
+                    last_block_number.expect("should exist"),
+                )?,
             }
         }
 
@@ -348,10 +355,14 @@ impl StaticFileProviderRW {
         let mut remaining_rows = num_rows;
         while remaining_rows > 0 {
             let len = match segment {
-                StaticFileSegment::Headers => {
+                StaticFileSegment::Headers |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => {
                     self.writer.user_header().block_len().unwrap_or_default()
                 }
-                StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+                StaticFileSegment::Transactions |
+                StaticFileSegment::Receipts |
+                StaticFileSegment::TransactionSenders => {
                     self.writer.user_header().tx_len().unwrap_or_default()
                 }
             };
@@ -385,7 +396,7 @@ impl StaticFileProviderRW {
             }
         }
 
-        // Only Transactions and Receipts
+        // Only Transactions, Receipts and TransactionSenders
         if let Some(last_block) = last_block {
             let mut expected_block_start = self.writer.user_header().expected_block_start();
 
@@ -493,6 +504,61 @@ impl StaticFileProviderRW {
         Ok(block_number)
     }
 
+    /// Appends the account changes of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()` since there is one row per block, even for blocks without
+    /// account changes.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_account_changeset(
+        &mut self,
+        block_number: BlockNumber,
+        changes: StoredBlockAccountChanges,
+    ) -> ProviderResult<BlockNumber> {
+        self.append_block_changes(StaticFileSegment::AccountChangeSets, block_number, changes)
+    }
+
+    /// Appends the storage changes of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()` since there is one row per block, even for blocks without
+    /// storage changes.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_storage_changeset(
+        &mut self,
+        block_number: BlockNumber,
+        changes: StoredBlockStorageChanges,
+    ) -> ProviderResult<BlockNumber> {
+        self.append_block_changes(StaticFileSegment::StorageChangeSets, block_number, changes)
+    }
+
+    /// Appends the changes of a block to a changeset static file.
+    fn append_block_changes<V: Compact>(
+        &mut self,
+        segment: StaticFileSegment,
+        block_number: BlockNumber,
+        changes: V,
+    ) -> ProviderResult<BlockNumber> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        debug_assert!(self.writer.user_header().segment() == segment);
+
+        let block_number = self.increment_block(segment, block_number)?;
+
+        self.append_column(changes)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                segment,
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(block_number)
+    }
+
     /// Appends transaction to static file.
     ///
     /// It **DOES NOT CALL** `increment_block()`, it should be handled elsewhere. There might be
@@ -547,6 +613,34 @@ impl StaticFileProviderRW {
         Ok(result)
     }
 
+    /// Appends transaction sender to static file.
+    ///
+    /// It **DOES NOT** call `increment_block()`, it should be handled elsewhere. There might be
+    /// empty blocks and this function wouldn't be called.
+    ///
+    /// Returns the current [`TxNumber`] as seen in the static file.
+    pub fn append_transaction_sender(
+        &mut self,
+        tx_num: TxNumber,
+        sender: Address,
+    ) -> ProviderResult<TxNumber> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        let result =
+            self.append_with_tx_number(StaticFileSegment::TransactionSenders, tx_num, sender)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::TransactionSenders,
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(result)
+    }
+
     /// Adds an instruction to prune `to_delete`transactions during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at.
@@ -571,12 +665,41 @@ impl StaticFileProviderRW {
         self.queue_prune(to_delete, Some(last_block))
     }
 
+    /// Adds an instruction to prune `to_delete` transaction senders during commit.
+    ///
+    /// Note: `last_block` refers to the block the unwinds ends at.
+    pub fn prune_transaction_senders(
+        &mut self,
+        to_delete: u64,
+        last_block: BlockNumber,
+    ) -> ProviderResult<()> {
+        debug_assert_eq!(
+            self.writer.user_header().segment(),
+            StaticFileSegment::TransactionSenders
+        );
+        self.queue_prune(to_delete, Some(last_block))
+    }
+
     /// Adds an instruction to prune `to_delete` headers during commit.
     pub fn prune_headers(&mut self, to_delete: u64) -> ProviderResult<()> {
         debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::Headers);
         self.queue_prune(to_delete, None)
     }
 
+    /// Adds an instruction to prune the account changesets of the last `to_delete` blocks during
+    /// commit.
+    pub fn prune_account_changesets(&mut self, to_delete: u64) -> ProviderResult<()> {
+        debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::AccountChangeSets);
+        self.queue_prune(to_delete, None)
+    }
+
+    /// Adds an instruction to prune the storage changesets of the last `to_delete` blocks during
+    /// commit.
+    pub fn prune_storage_changesets(&mut self, to_delete: u64) -> ProviderResult<()> {
+        debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::StorageChangeSets);
+        self.queue_prune(to_delete, None)
+    }
+
     /// Adds an instruction to prune `to_delete` elements during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at if dealing with transaction-based
@@ -649,6 +772,30 @@ impl StaticFileProviderRW {
         Ok(())
     }
 
+    /// Prunes the last `to_delete` transaction senders from the data file.
+    fn prune_transaction_sender_data(
+        &mut self,
+        to_delete: u64,
+        last_block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        let segment = StaticFileSegment::TransactionSenders;
+        debug_assert!(self.writer.user_header().segment() == segment);
+
+        self.truncate(segment, to_delete, Some(last_block))?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::TransactionSenders,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Prunes the last `to_delete` headers from the data file.
     fn prune_header_data(&mut self, to_delete: u64) -> ProviderResult<()> {
         let start = Instant::now();
@@ -669,6 +816,26 @@ impl StaticFileProviderRW {
         Ok(())
     }
 
+    /// Prunes the changesets of the last `to_delete` blocks from the data file.
+    fn prune_changeset_data(&mut self, to_delete: u64) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        let segment = self.writer.user_header().segment();
+        debug_assert!(segment.is_changesets());
+
+        self.truncate(segment, to_delete, None)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                segment,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     fn reader(&self) -> StaticFileProvider {
         Self::upgrade_provider_to_strong_reference(&self.reader)
     }
@@ -722,8 +889,8 @@ fn create_jar(
     );
 
     // Transaction and Receipt already have the compression scheme used natively in its encoding.
-    // (zstd-dictionary)
-    if segment.is_headers() {
+    // (zstd-dictionary), and senders are plain addresses which don't compress.
+    if segment.is_block_based() {
         jar = jar.with_lz4();
     }
 
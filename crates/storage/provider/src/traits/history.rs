@@ -38,6 +38,24 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear address transaction indices and contract creators of the given block
+    /// range.
+    ///
+    /// Returns number of addresses unwound.
+    fn unwind_address_transactions_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Read the transactions of the given block range and insert the addresses they touch and the
+    /// contracts they create into the address transactions index.
+    fn insert_address_transactions_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
+    ///
+    /// The address transactions index is updated as well if this node maintains it.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -0,0 +1,39 @@
+use auto_impl::auto_impl;
+use reth_primitives::{Address, TxNumber};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for reading the optional index of transactions by the addresses they touch.
+///
+/// An address appears in a transaction if it is the sender, the recipient or the contract created
+/// by the transaction, or if it emitted an event or is an indexed address argument of a well-known
+/// event emitted by the transaction, e.g. the sender or recipient of a token transfer.
+#[auto_impl(&, Arc, Box)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns up to `limit` numbers of transactions the address appeared in, that are lower than
+    /// `before`.
+    ///
+    /// Transaction numbers are returned in descending order.
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns up to `limit` numbers of transactions the address appeared in, that are greater
+    /// than or equal to `from`.
+    ///
+    /// Transaction numbers are returned in ascending order.
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns the number of the transaction that deployed the contract at the given address.
+    ///
+    /// Returns `None` if the contract was not deployed by a top-level contract creation
+    /// transaction.
+    fn contract_creation_transaction(&self, address: Address) -> ProviderResult<Option<TxNumber>>;
+}
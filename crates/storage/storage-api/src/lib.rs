@@ -13,6 +13,9 @@ pub use reth_storage_errors as errors;
 mod account;
 pub use account::*;
 
+mod address_transactions;
+pub use address_transactions::*;
+
 mod block;
 pub use block::*;
 
@@ -1,3 +1,4 @@
+use reth_primitives::BlockNumber;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_storage_errors::provider::ProviderResult;
 
@@ -9,6 +10,27 @@ pub trait PruneCheckpointReader: Send + Sync {
         &self,
         segment: PruneSegment,
     ) -> ProviderResult<Option<PruneCheckpoint>>;
+
+    /// Returns the earliest block for which headers, bodies and receipts are all still available.
+    ///
+    /// This is the block after the highest block pruned by any of the segments that remove block
+    /// data, or `0` if none of them pruned anything yet.
+    fn earliest_available_block(&self) -> ProviderResult<BlockNumber> {
+        let mut earliest = 0;
+        for segment in [
+            PruneSegment::Headers,
+            PruneSegment::Transactions,
+            PruneSegment::Receipts,
+            PruneSegment::ContractLogs,
+        ] {
+            if let Some(block) =
+                self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+            {
+                earliest = earliest.max(block + 1);
+            }
+        }
+        Ok(earliest)
+    }
 }
 
 /// The trait for updating prune checkpoint related data.
@@ -0,0 +1,572 @@
+//! An archive of the blob sidecars of canonical transactions.
+
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use parking_lot::RwLock;
+use reth_primitives::{BlobTransactionSidecar, BlockHash, BlockNumHash, BlockNumber, TxHash, B256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{debug, trace};
+
+/// How long blob sidecars are kept in the archive by default.
+///
+/// This matches the 4096 epochs consensus clients are required to serve blob sidecars for.
+pub const DEFAULT_BLOB_SIDECAR_RETENTION: Duration = Duration::from_secs(4096 * 32 * 12);
+
+/// The name of the file holding the metadata of an archived block.
+const BLOCK_META_FILE: &str = "meta";
+
+/// The name of the file holding the number of the last block the archive is up to date with.
+const TIP_FILE: &str = "tip";
+
+/// An archive of the blob sidecars of canonical blob transactions.
+///
+/// Unlike a [`BlobStore`](crate::blobstore::BlobStore), which only keeps sidecars until their
+/// transactions are finalized, the archive keeps them on disk for a configured retention period
+/// and across restarts. Sidecars can be looked up by block or by the versioned hash of one of
+/// their blobs.
+///
+/// Every archived block is a directory named after the block number, holding a file per blob
+/// transaction and a metadata file that is written last. Blocks without a valid metadata file are
+/// removed when the archive is opened. All files are synced to disk before the files that refer to
+/// them are written, so the metadata and the tip never point to missing sidecars after a crash.
+#[derive(Clone, Debug)]
+pub struct BlobSidecarArchive {
+    inner: Arc<BlobSidecarArchiveInner>,
+}
+
+impl BlobSidecarArchive {
+    /// Opens the archive in the given directory, creating it if it doesn't exist.
+    pub fn open(
+        archive_dir: impl Into<PathBuf>,
+        config: BlobSidecarArchiveConfig,
+    ) -> Result<Self, BlobSidecarArchiveError> {
+        let archive_dir = archive_dir.into();
+        let BlobSidecarArchiveConfig { retention } = config;
+        let inner = BlobSidecarArchiveInner { archive_dir, retention, index: Default::default() };
+
+        inner.create_archive_dir()?;
+        inner.load_index()?;
+
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Returns how long blob sidecars are kept in the archive.
+    pub fn retention(&self) -> Duration {
+        self.inner.retention
+    }
+
+    /// Returns the number of the last block the archive is up to date with, if any.
+    ///
+    /// All canonical blocks up to the tip were archived, unless their sidecars weren't available.
+    pub fn tip(&self) -> Option<BlockNumber> {
+        self.inner.index.read().tip
+    }
+
+    /// Sets the number of the last block the archive is up to date with.
+    pub fn set_tip(&self, number: BlockNumber) -> Result<(), BlobSidecarArchiveError> {
+        let mut index = self.inner.index.write();
+        if index.tip == Some(number) {
+            return Ok(())
+        }
+        write_file(&self.inner.archive_dir.join(TIP_FILE), &number.to_be_bytes())?;
+        index.tip = Some(number);
+        Ok(())
+    }
+
+    /// Returns the number of archived blocks.
+    pub fn blocks_len(&self) -> usize {
+        self.inner.index.read().blocks.len()
+    }
+
+    /// Archives the blob sidecars of a block.
+    ///
+    /// Replaces any block previously archived at the same height, for example after a reorg.
+    /// Blocks without sidecars are not archived.
+    pub fn insert_block(&self, block: ArchivedBlock) -> Result<(), BlobSidecarArchiveError> {
+        let mut index = self.inner.index.write();
+        self.inner.remove_block(&mut index, block.number)?;
+        if block.sidecars.is_empty() {
+            return Ok(())
+        }
+
+        trace!(target: "txpool::blob::archive", number = block.number, hash = ?block.hash, sidecars = block.sidecars.len(), "Archiving blob sidecars");
+        let meta = self.inner.write_block(&block)?;
+        index.insert(block.number, meta);
+        Ok(())
+    }
+
+    /// Removes the blob sidecars of the block at the given height from the archive.
+    ///
+    /// Returns `true` if a block was archived at this height.
+    pub fn remove_block(&self, number: BlockNumber) -> Result<bool, BlobSidecarArchiveError> {
+        let mut index = self.inner.index.write();
+        self.inner.remove_block(&mut index, number)
+    }
+
+    /// Removes all blocks that are older than the retention period, relative to the given
+    /// timestamp of the chain tip.
+    ///
+    /// Returns the number of removed blocks.
+    pub fn prune(&self, tip_timestamp: u64) -> Result<usize, BlobSidecarArchiveError> {
+        let cutoff = tip_timestamp.saturating_sub(self.inner.retention.as_secs());
+        let mut index = self.inner.index.write();
+
+        let expired = index
+            .blocks
+            .iter()
+            .take_while(|(_, meta)| meta.timestamp < cutoff)
+            .map(|(number, _)| *number)
+            .collect::<Vec<_>>();
+        for number in &expired {
+            self.inner.remove_block(&mut index, *number)?;
+        }
+
+        if !expired.is_empty() {
+            debug!(target: "txpool::blob::archive", removed = expired.len(), cutoff, "Pruned blob sidecar archive");
+        }
+        Ok(expired.len())
+    }
+
+    /// Returns the archived blob sidecars of the block at the given height.
+    pub fn block(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<ArchivedBlock>, BlobSidecarArchiveError> {
+        let index = self.inner.index.read();
+        let Some(meta) = index.blocks.get(&number) else { return Ok(None) };
+
+        let sidecars = meta
+            .transactions
+            .iter()
+            .map(|tx| self.inner.read_sidecar(number, tx))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(ArchivedBlock { number, hash: meta.hash, timestamp: meta.timestamp, sidecars }))
+    }
+
+    /// Returns the archived sidecar that contains the blob with the given versioned hash, together
+    /// with the block of its transaction.
+    pub fn sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> Result<Option<(BlockNumHash, ArchivedSidecar)>, BlobSidecarArchiveError> {
+        let index = self.inner.index.read();
+        let Some(number) = index.versioned_hashes.get(&versioned_hash).copied() else {
+            return Ok(None)
+        };
+        let Some(meta) = index.blocks.get(&number) else { return Ok(None) };
+        let Some(tx) =
+            meta.transactions.iter().find(|tx| tx.versioned_hashes.contains(&versioned_hash))
+        else {
+            return Ok(None)
+        };
+
+        let sidecar = self.inner.read_sidecar(number, tx)?;
+        Ok(Some((BlockNumHash::new(number, meta.hash), sidecar)))
+    }
+}
+
+/// The archived blob sidecars of a canonical block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedBlock {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: BlockHash,
+    /// The timestamp of the block, used to prune the archive.
+    pub timestamp: u64,
+    /// The sidecars of the blob transactions in the block, in block order.
+    pub sidecars: Vec<ArchivedSidecar>,
+}
+
+/// The archived blob sidecar of a canonical transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedSidecar {
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// The index of the transaction in its block.
+    pub tx_index: u64,
+    /// The versioned hashes of the blobs of the transaction.
+    pub versioned_hashes: Vec<B256>,
+    /// The blobs, commitments and proofs of the transaction.
+    pub sidecar: BlobTransactionSidecar,
+}
+
+/// Configuration for a [`BlobSidecarArchive`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobSidecarArchiveConfig {
+    /// How long blob sidecars are kept, relative to the timestamp of the chain tip.
+    pub retention: Duration,
+}
+
+impl Default for BlobSidecarArchiveConfig {
+    fn default() -> Self {
+        Self { retention: DEFAULT_BLOB_SIDECAR_RETENTION }
+    }
+}
+
+impl BlobSidecarArchiveConfig {
+    /// Set how long blob sidecars are kept.
+    pub const fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+}
+
+/// Errors that can occur when interacting with a [`BlobSidecarArchive`].
+#[derive(Debug, thiserror::Error)]
+pub enum BlobSidecarArchiveError {
+    /// Thrown during [`BlobSidecarArchive::open`] if the archive directory cannot be opened.
+    #[error("failed to open blob sidecar archive at {0}: {1}")]
+    Open(PathBuf, io::Error),
+    /// Failure while reading an archive file.
+    #[error("failed to read blob sidecar archive file at {0}: {1}")]
+    ReadFile(PathBuf, io::Error),
+    /// Failure while writing an archive file.
+    #[error("failed to write blob sidecar archive file at {0}: {1}")]
+    WriteFile(PathBuf, io::Error),
+    /// Failure while deleting an archived block.
+    #[error("failed to delete archived blob sidecars at {0}: {1}")]
+    DeleteBlock(PathBuf, io::Error),
+    /// Failed to decode an archive file.
+    #[error("failed to decode blob sidecar archive file: {0}")]
+    Decode(#[from] alloy_rlp::Error),
+}
+
+struct BlobSidecarArchiveInner {
+    archive_dir: PathBuf,
+    retention: Duration,
+    /// Guards the archive files, which are only modified while holding the write lock.
+    index: RwLock<ArchiveIndex>,
+}
+
+impl BlobSidecarArchiveInner {
+    /// Creates the directory where the archive is stored on disk.
+    fn create_archive_dir(&self) -> Result<(), BlobSidecarArchiveError> {
+        debug!(target: "txpool::blob::archive", archive_dir = ?self.archive_dir, "Opening blob sidecar archive");
+        fs::create_dir_all(&self.archive_dir)
+            .map_err(|e| BlobSidecarArchiveError::Open(self.archive_dir.clone(), e))
+    }
+
+    /// Loads the index from the metadata files of the archived blocks.
+    ///
+    /// Removes blocks whose metadata is missing or can't be decoded, which were interrupted while
+    /// being written.
+    fn load_index(&self) -> Result<(), BlobSidecarArchiveError> {
+        let mut index = self.index.write();
+
+        let tip_file = self.archive_dir.join(TIP_FILE);
+        if tip_file.exists() {
+            match <[u8; 8]>::try_from(read_file(&tip_file)?.as_slice()) {
+                Ok(tip) => index.tip = Some(BlockNumber::from_be_bytes(tip)),
+                Err(_) => {
+                    debug!(target: "txpool::blob::archive", "Ignoring incomplete blob sidecar archive tip")
+                }
+            }
+        }
+
+        let entries = fs::read_dir(&self.archive_dir)
+            .map_err(|e| BlobSidecarArchiveError::Open(self.archive_dir.clone(), e))?;
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| BlobSidecarArchiveError::Open(self.archive_dir.clone(), e))?;
+            let Some(number) =
+                entry.file_name().to_str().and_then(|name| name.parse::<BlockNumber>().ok())
+            else {
+                continue
+            };
+
+            match self.read_block_meta(number) {
+                Ok(meta) => index.insert(number, meta),
+                Err(err) => {
+                    debug!(target: "txpool::blob::archive", %err, number, "Removing incomplete archived block");
+                    remove_dir(&entry.path())?;
+                }
+            }
+        }
+
+        debug!(target: "txpool::blob::archive", blocks = index.blocks.len(), tip = ?index.tip, "Loaded blob sidecar archive");
+        Ok(())
+    }
+
+    /// Removes the block at the given height from the index and from disk.
+    fn remove_block(
+        &self,
+        index: &mut ArchiveIndex,
+        number: BlockNumber,
+    ) -> Result<bool, BlobSidecarArchiveError> {
+        if index.remove(number).is_none() {
+            return Ok(false)
+        }
+        remove_dir(&self.block_dir(number))?;
+        Ok(true)
+    }
+
+    /// Writes the sidecars of the block to disk, followed by its metadata.
+    ///
+    /// The sidecars and the block directory are synced before the metadata is written, and the
+    /// metadata is synced before this returns.
+    fn write_block(&self, block: &ArchivedBlock) -> Result<BlockMeta, BlobSidecarArchiveError> {
+        let block_dir = self.block_dir(block.number);
+        fs::create_dir_all(&block_dir)
+            .map_err(|e| BlobSidecarArchiveError::WriteFile(block_dir.clone(), e))?;
+        sync_dir(&self.archive_dir)?;
+
+        let mut transactions = Vec::with_capacity(block.sidecars.len());
+        for sidecar in &block.sidecars {
+            let mut buf = Vec::with_capacity(sidecar.sidecar.fields_len());
+            sidecar.sidecar.encode(&mut buf);
+            write_file(&self.sidecar_file(block.number, sidecar.tx_hash), &buf)?;
+
+            transactions.push(TransactionMeta {
+                hash: sidecar.tx_hash,
+                index: sidecar.tx_index,
+                versioned_hashes: sidecar.versioned_hashes.clone(),
+            });
+        }
+
+        sync_dir(&block_dir)?;
+
+        let meta = BlockMeta { hash: block.hash, timestamp: block.timestamp, transactions };
+        let mut buf = Vec::with_capacity(meta.length());
+        meta.encode(&mut buf);
+        write_file(&block_dir.join(BLOCK_META_FILE), &buf)?;
+        sync_dir(&block_dir)?;
+
+        Ok(meta)
+    }
+
+    /// Reads the metadata of the block at the given height.
+    fn read_block_meta(&self, number: BlockNumber) -> Result<BlockMeta, BlobSidecarArchiveError> {
+        let data = read_file(&self.block_dir(number).join(BLOCK_META_FILE))?;
+        Ok(BlockMeta::decode(&mut data.as_slice())?)
+    }
+
+    /// Reads the archived sidecar of a transaction in the block at the given height.
+    fn read_sidecar(
+        &self,
+        number: BlockNumber,
+        tx: &TransactionMeta,
+    ) -> Result<ArchivedSidecar, BlobSidecarArchiveError> {
+        let data = read_file(&self.sidecar_file(number, tx.hash))?;
+        let sidecar = BlobTransactionSidecar::decode(&mut data.as_slice())?;
+        Ok(ArchivedSidecar {
+            tx_hash: tx.hash,
+            tx_index: tx.index,
+            versioned_hashes: tx.versioned_hashes.clone(),
+            sidecar,
+        })
+    }
+
+    /// Returns the path to the directory of the block at the given height.
+    #[inline]
+    fn block_dir(&self, number: BlockNumber) -> PathBuf {
+        self.archive_dir.join(number.to_string())
+    }
+
+    /// Returns the path to the sidecar file of a transaction in the block at the given height.
+    #[inline]
+    fn sidecar_file(&self, number: BlockNumber, tx: TxHash) -> PathBuf {
+        self.block_dir(number).join(format!("{tx:x}"))
+    }
+}
+
+impl fmt::Debug for BlobSidecarArchiveInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlobSidecarArchiveInner")
+            .field("archive_dir", &self.archive_dir)
+            .field("retention", &self.retention)
+            .field("blocks", &self.index.try_read().map(|index| index.blocks.len()))
+            .finish()
+    }
+}
+
+/// The in memory index of the archive.
+#[derive(Debug, Default)]
+struct ArchiveIndex {
+    /// The metadata of the archived blocks.
+    blocks: BTreeMap<BlockNumber, BlockMeta>,
+    /// Maps the versioned hash of every archived blob to the height of its block.
+    versioned_hashes: HashMap<B256, BlockNumber>,
+    /// The last block the archive is up to date with.
+    tip: Option<BlockNumber>,
+}
+
+impl ArchiveIndex {
+    fn insert(&mut self, number: BlockNumber, meta: BlockMeta) {
+        for tx in &meta.transactions {
+            self.versioned_hashes.extend(tx.versioned_hashes.iter().map(|hash| (*hash, number)));
+        }
+        self.blocks.insert(number, meta);
+    }
+
+    fn remove(&mut self, number: BlockNumber) -> Option<BlockMeta> {
+        let meta = self.blocks.remove(&number)?;
+        for hash in meta.transactions.iter().flat_map(|tx| &tx.versioned_hashes) {
+            // the same blob may have been included again in a later block
+            if self.versioned_hashes.get(hash) == Some(&number) {
+                self.versioned_hashes.remove(hash);
+            }
+        }
+        Some(meta)
+    }
+}
+
+/// The metadata of an archived block.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct BlockMeta {
+    hash: BlockHash,
+    timestamp: u64,
+    transactions: Vec<TransactionMeta>,
+}
+
+/// The metadata of an archived blob transaction.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct TransactionMeta {
+    hash: TxHash,
+    index: u64,
+    versioned_hashes: Vec<B256>,
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, BlobSidecarArchiveError> {
+    fs::read(path).map_err(|e| BlobSidecarArchiveError::ReadFile(path.to_path_buf(), e))
+}
+
+/// Writes the file and syncs it to disk.
+fn write_file(path: &Path, data: &[u8]) -> Result<(), BlobSidecarArchiveError> {
+    fs::File::create(path)
+        .and_then(|mut file| {
+            file.write_all(data)?;
+            file.sync_all()
+        })
+        .map_err(|e| BlobSidecarArchiveError::WriteFile(path.to_path_buf(), e))
+}
+
+/// Syncs the entries of the directory to disk, so files created in it survive a crash.
+fn sync_dir(path: &Path) -> Result<(), BlobSidecarArchiveError> {
+    fs::File::open(path)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| BlobSidecarArchiveError::WriteFile(path.to_path_buf(), e))
+}
+
+fn remove_dir(path: &Path) -> Result<(), BlobSidecarArchiveError> {
+    match fs::remove_dir_all(path) {
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(BlobSidecarArchiveError::DeleteBlock(path.to_path_buf(), err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn tmp_archive() -> (BlobSidecarArchive, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = BlobSidecarArchive::open(
+            dir.path(),
+            BlobSidecarArchiveConfig::default().with_retention(Duration::from_secs(DAY)),
+        )
+        .unwrap();
+        (archive, dir)
+    }
+
+    fn rng_block(number: BlockNumber, timestamp: u64, num_sidecars: usize) -> ArchivedBlock {
+        let mut rng = rand::thread_rng();
+        let sidecars = (0..num_sidecars)
+            .map(|index| ArchivedSidecar {
+                tx_hash: TxHash::random_with(&mut rng),
+                tx_index: index as u64,
+                versioned_hashes: vec![B256::random_with(&mut rng), B256::random_with(&mut rng)],
+                sidecar: BlobTransactionSidecar {
+                    blobs: vec![],
+                    commitments: vec![],
+                    proofs: vec![],
+                },
+            })
+            .collect();
+        ArchivedBlock { number, hash: B256::random_with(&mut rng), timestamp, sidecars }
+    }
+
+    #[test]
+    fn archive_insert_get_reopen() {
+        let (archive, dir) = tmp_archive();
+
+        let block = rng_block(10, DAY, 3);
+        archive.insert_block(block.clone()).unwrap();
+        archive.insert_block(rng_block(11, DAY + 12, 0)).unwrap();
+
+        assert_eq!(archive.blocks_len(), 1);
+        assert_eq!(archive.block(10).unwrap(), Some(block.clone()));
+        assert_eq!(archive.block(11).unwrap(), None);
+
+        let versioned_hash = block.sidecars[1].versioned_hashes[1];
+        let (num_hash, sidecar) =
+            archive.sidecar_by_versioned_hash(versioned_hash).unwrap().unwrap();
+        assert_eq!(num_hash, BlockNumHash::new(10, block.hash));
+        assert_eq!(sidecar, block.sidecars[1]);
+
+        // interrupted write of another block
+        fs::create_dir_all(dir.path().join("12")).unwrap();
+        assert_eq!(archive.tip(), None);
+        archive.set_tip(11).unwrap();
+        drop(archive);
+
+        let archive = BlobSidecarArchive::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(archive.tip(), Some(11));
+        assert_eq!(archive.blocks_len(), 1);
+        assert_eq!(archive.block(10).unwrap(), Some(block.clone()));
+        assert!(archive.sidecar_by_versioned_hash(versioned_hash).unwrap().is_some());
+        assert!(!dir.path().join("12").exists());
+    }
+
+    #[test]
+    fn archive_replace_block() {
+        let (archive, _dir) = tmp_archive();
+
+        let old = rng_block(10, DAY, 2);
+        archive.insert_block(old.clone()).unwrap();
+        let new = rng_block(10, DAY, 1);
+        archive.insert_block(new.clone()).unwrap();
+
+        assert_eq!(archive.block(10).unwrap(), Some(new.clone()));
+        assert!(archive
+            .sidecar_by_versioned_hash(old.sidecars[0].versioned_hashes[0])
+            .unwrap()
+            .is_none());
+        assert!(archive
+            .sidecar_by_versioned_hash(new.sidecars[0].versioned_hashes[0])
+            .unwrap()
+            .is_some());
+
+        assert!(archive.remove_block(10).unwrap());
+        assert!(!archive.remove_block(10).unwrap());
+        assert_eq!(archive.block(10).unwrap(), None);
+    }
+
+    #[test]
+    fn archive_prune() {
+        let (archive, _dir) = tmp_archive();
+
+        for number in 0..4 {
+            archive.insert_block(rng_block(number, number * DAY, 1)).unwrap();
+        }
+
+        // blocks older than a day before the tip are removed
+        assert_eq!(archive.prune(3 * DAY).unwrap(), 2);
+        assert_eq!(archive.block(1).unwrap(), None);
+        assert!(archive.block(2).unwrap().is_some());
+        assert!(archive.block(3).unwrap().is_some());
+
+        assert_eq!(archive.prune(3 * DAY).unwrap(), 0);
+        assert_eq!(archive.blocks_len(), 2);
+    }
+}
@@ -1,5 +1,9 @@
 //! Storage for blob data of EIP4844 transactions.
 
+pub use archive::{
+    ArchivedBlock, ArchivedSidecar, BlobSidecarArchive, BlobSidecarArchiveConfig,
+    BlobSidecarArchiveError, DEFAULT_BLOB_SIDECAR_RETENTION,
+};
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
@@ -10,6 +14,7 @@ use std::{
 };
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
+pub mod archive;
 pub mod disk;
 mod mem;
 mod noop;
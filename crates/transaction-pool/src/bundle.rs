@@ -0,0 +1,358 @@
+//! A pool of transaction bundles that are kept next to the transaction pool.
+//!
+//! Bundles are ordered lists of transactions that must be included in full and in order at the top
+//! of a block. Each bundle targets a range of blocks and optionally a range of timestamps. A bundle
+//! submitted with a replacement UUID replaces the previous bundle with the same UUID and can be
+//! cancelled by it.
+
+use parking_lot::RwLock;
+use reth_primitives::{keccak256, TransactionSignedEcRecovered, TxHash, B256};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::mpsc;
+use tracing::{debug, trace};
+
+/// The maximum number of bundles the [`BundlePool`] holds.
+pub const BUNDLE_POOL_MAX_BUNDLES: usize = 10_000;
+
+/// The size of the channel of a new bundle listener.
+const NEW_BUNDLE_LISTENER_BUFFER_SIZE: usize = 256;
+
+/// A transaction of a [`MevBundle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleTransaction {
+    /// The signed transaction with its sender.
+    pub transaction: TransactionSignedEcRecovered,
+    /// Whether the transaction can revert without invalidating the bundle.
+    pub can_revert: bool,
+}
+
+/// A bundle of transactions that must be included in full and in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MevBundle {
+    /// The transactions of the bundle.
+    pub transactions: Vec<BundleTransaction>,
+    /// The first block the bundle can be included in.
+    pub block_number: u64,
+    /// The last block the bundle can be included in.
+    pub max_block_number: u64,
+    /// The earliest timestamp of a block the bundle can be included in.
+    pub min_timestamp: Option<u64>,
+    /// The latest timestamp of a block the bundle can be included in.
+    pub max_timestamp: Option<u64>,
+    /// The UUID that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl MevBundle {
+    /// Returns the hash of the bundle, which is the keccak256 hash of the concatenated hashes of
+    /// its transactions.
+    pub fn hash(&self) -> B256 {
+        let mut hashes = Vec::with_capacity(self.transactions.len() * 32);
+        for tx in &self.transactions {
+            hashes.extend_from_slice(tx.transaction.hash().as_slice());
+        }
+        keccak256(hashes)
+    }
+
+    /// Returns the hashes of the transactions of the bundle.
+    pub fn transaction_hashes(&self) -> impl Iterator<Item = TxHash> + '_ {
+        self.transactions.iter().map(|tx| tx.transaction.hash())
+    }
+
+    /// Returns `true` if the bundle can be included in the block with the given number and
+    /// timestamp.
+    pub fn is_valid_at(&self, block_number: u64, timestamp: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.map_or(true, |min| timestamp >= min) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [`BundlePool`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum BundlePoolError {
+    /// The bundle does not contain any transactions.
+    #[error("bundle missing txs")]
+    EmptyBundle,
+    /// The last block of the bundle is before its first block.
+    #[error("invalid block range {0}..={1}")]
+    InvalidBlockRange(u64, u64),
+    /// The last block of the bundle has already been mined.
+    #[error("bundle expired at block {0}")]
+    Expired(u64),
+    /// The pool holds [`BUNDLE_POOL_MAX_BUNDLES`] bundles that all target future blocks.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
+
+/// A shareable pool of [`MevBundle`]s.
+///
+/// Bundles are removed once the last block they target has been mined, see
+/// [`BundlePool::on_new_block`]. If the pool is full, bundles whose first target block has already
+/// been mined are evicted to make room for new bundles.
+#[derive(Debug, Clone, Default)]
+pub struct BundlePool {
+    inner: Arc<RwLock<BundlePoolInner>>,
+}
+
+// === impl BundlePool ===
+
+impl BundlePool {
+    /// Adds a bundle to the pool and returns its hash.
+    ///
+    /// If the bundle has a replacement UUID, it replaces the bundle with the same UUID. If the pool
+    /// is full, the bundle that expires first out of the bundles whose first target block has
+    /// already been mined is evicted.
+    pub fn add_bundle(&self, bundle: MevBundle) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if bundle.max_block_number < bundle.block_number {
+            return Err(BundlePoolError::InvalidBlockRange(
+                bundle.block_number,
+                bundle.max_block_number,
+            ))
+        }
+
+        let mut inner = self.inner.write();
+        if bundle.max_block_number <= inner.last_seen_block_number {
+            return Err(BundlePoolError::Expired(bundle.max_block_number))
+        }
+
+        let hash = bundle.hash();
+        let replaced = bundle
+            .replacement_uuid
+            .as_ref()
+            .and_then(|uuid| inner.by_replacement_uuid.get(uuid).copied());
+        if let Some(replaced) = replaced {
+            inner.remove(&replaced);
+        } else if inner.bundles.len() >= BUNDLE_POOL_MAX_BUNDLES &&
+            !inner.bundles.contains_key(&hash)
+        {
+            let Some(evicted) = inner.stale_bundle() else { return Err(BundlePoolError::PoolFull) };
+            trace!(target: "txpool::bundles", hash=?evicted, "Evicting stale bundle");
+            inner.remove(&evicted);
+        }
+
+        trace!(target: "txpool::bundles", ?hash, block_number = bundle.block_number, max_block_number = bundle.max_block_number, "Adding bundle");
+        inner.insert(hash, bundle);
+        inner.listeners.retain(|listener| match listener.try_send(hash) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                debug!(target: "txpool::bundles", ?hash, "failed to send new bundle; channel full");
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+        Ok(hash)
+    }
+
+    /// Returns a new listener that receives the hashes of all bundles added to the pool.
+    pub fn new_bundles_listener(&self) -> mpsc::Receiver<B256> {
+        let (sender, rx) = mpsc::channel(NEW_BUNDLE_LISTENER_BUFFER_SIZE);
+        self.inner.write().listeners.push(sender);
+        rx
+    }
+
+    /// Removes the bundle with the given replacement UUID and returns it.
+    pub fn cancel_bundle(&self, replacement_uuid: &str) -> Option<MevBundle> {
+        let mut inner = self.inner.write();
+        let hash = inner.by_replacement_uuid.get(replacement_uuid).copied()?;
+        inner.remove(&hash)
+    }
+
+    /// Removes the bundle with the given hash and returns it.
+    pub fn remove_bundle(&self, hash: &B256) -> Option<MevBundle> {
+        self.inner.write().remove(hash)
+    }
+
+    /// Returns the bundle with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<MevBundle> {
+        self.inner.read().bundles.get(hash).cloned()
+    }
+
+    /// Returns all bundles that can be included in the block with the given number and timestamp.
+    pub fn bundles_at(&self, block_number: u64, timestamp: u64) -> Vec<MevBundle> {
+        self.inner
+            .read()
+            .bundles
+            .values()
+            .filter(|bundle| bundle.is_valid_at(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes all bundles that can't be included in blocks after the given block anymore.
+    pub fn on_new_block(&self, block_number: u64) {
+        let mut inner = self.inner.write();
+        inner.last_seen_block_number = block_number;
+
+        let expired = inner
+            .bundles
+            .iter()
+            .filter(|(_, bundle)| bundle.max_block_number <= block_number)
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        for hash in expired {
+            inner.remove(&hash);
+        }
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns `true` if the pool holds no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+}
+
+/// The bundles of a [`BundlePool`].
+#[derive(Debug, Default)]
+struct BundlePoolInner {
+    /// All bundles by hash.
+    bundles: HashMap<B256, MevBundle>,
+    /// Hashes of the bundles by replacement UUID.
+    by_replacement_uuid: HashMap<String, B256>,
+    /// The number of the last block the pool has seen.
+    last_seen_block_number: u64,
+    /// Listeners for new bundles added to the pool.
+    listeners: Vec<mpsc::Sender<B256>>,
+}
+
+impl BundlePoolInner {
+    fn insert(&mut self, hash: B256, bundle: MevBundle) {
+        // the same bundle may have been submitted with a different replacement UUID before
+        self.remove(&hash);
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_replacement_uuid.insert(uuid.clone(), hash);
+        }
+        self.bundles.insert(hash, bundle);
+    }
+
+    /// Returns the hash of the bundle with the lowest last block, out of the bundles whose first
+    /// block has already been mined.
+    fn stale_bundle(&self) -> Option<B256> {
+        self.bundles
+            .iter()
+            .filter(|(_, bundle)| bundle.block_number <= self.last_seen_block_number)
+            .min_by_key(|(_, bundle)| bundle.max_block_number)
+            .map(|(hash, _)| *hash)
+    }
+
+    fn remove(&mut self, hash: &B256) -> Option<MevBundle> {
+        let bundle = self.bundles.remove(hash)?;
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_replacement_uuid.remove(uuid);
+        }
+        Some(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, Signature, Transaction, TransactionSigned, TxLegacy};
+
+    fn bundle(nonce: u64, block_number: u64, max_block_number: u64) -> MevBundle {
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+            Signature::default(),
+        );
+        MevBundle {
+            transactions: vec![BundleTransaction {
+                transaction: TransactionSignedEcRecovered::from_signed_transaction(
+                    transaction,
+                    Address::ZERO,
+                ),
+                can_revert: false,
+            }],
+            block_number,
+            max_block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        }
+    }
+
+    #[test]
+    fn bundles_target_block_range() {
+        let pool = BundlePool::default();
+        let hash = pool.add_bundle(bundle(0, 2, 3)).unwrap();
+        pool.add_bundle(MevBundle { min_timestamp: Some(100), ..bundle(1, 2, 2) }).unwrap();
+
+        assert!(pool.bundles_at(1, 100).is_empty());
+        assert_eq!(pool.bundles_at(2, 100).len(), 2);
+        assert_eq!(pool.bundles_at(2, 99).len(), 1);
+        assert_eq!(pool.bundles_at(3, 100).len(), 1);
+        assert!(pool.bundles_at(4, 100).is_empty());
+
+        pool.on_new_block(2);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.get(&hash).is_some());
+        assert_eq!(pool.add_bundle(bundle(2, 2, 2)), Err(BundlePoolError::Expired(2)));
+        assert_eq!(pool.add_bundle(bundle(2, 4, 3)), Err(BundlePoolError::InvalidBlockRange(4, 3)));
+
+        pool.on_new_block(3);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn replace_and_cancel_bundle() {
+        let pool = BundlePool::default();
+        let uuid = Some("e8f0c1a6-4a5b-4c9e-8e43-2c1c9d2a0b7f".to_string());
+        let first = pool
+            .add_bundle(MevBundle { replacement_uuid: uuid.clone(), ..bundle(0, 1, 1) })
+            .unwrap();
+        let second = pool
+            .add_bundle(MevBundle { replacement_uuid: uuid.clone(), ..bundle(1, 1, 1) })
+            .unwrap();
+
+        assert_eq!(pool.len(), 1);
+        assert!(pool.get(&first).is_none());
+        assert!(pool.get(&second).is_some());
+
+        assert_eq!(pool.cancel_bundle(uuid.as_deref().unwrap()).map(|b| b.hash()), Some(second));
+        assert!(pool.is_empty());
+        assert!(pool.cancel_bundle(uuid.as_deref().unwrap()).is_none());
+    }
+
+    #[test]
+    fn evicts_stale_bundles_when_full() {
+        let pool = BundlePool::default();
+        let stale = pool.add_bundle(bundle(0, 1, 3)).unwrap();
+        for nonce in 1..BUNDLE_POOL_MAX_BUNDLES as u64 {
+            pool.add_bundle(bundle(nonce, 2, 2)).unwrap();
+        }
+        assert_eq!(pool.len(), BUNDLE_POOL_MAX_BUNDLES);
+        assert_eq!(pool.add_bundle(bundle(u64::MAX, 3, 3)), Err(BundlePoolError::PoolFull));
+
+        // the first target block of the first bundle was mined, but it can still be included
+        pool.on_new_block(1);
+        assert_eq!(pool.len(), BUNDLE_POOL_MAX_BUNDLES);
+        let hash = pool.add_bundle(bundle(u64::MAX, 3, 3)).unwrap();
+        assert_eq!(pool.len(), BUNDLE_POOL_MAX_BUNDLES);
+        assert!(pool.get(&stale).is_none());
+        assert!(pool.get(&hash).is_some());
+        assert_eq!(pool.add_bundle(bundle(u64::MAX - 1, 3, 3)), Err(BundlePoolError::PoolFull));
+    }
+
+    #[test]
+    fn notifies_new_bundle_listeners() {
+        let pool = BundlePool::default();
+        let mut listener = pool.new_bundles_listener();
+        let dropped = pool.new_bundles_listener();
+        drop(dropped);
+
+        let hash = pool.add_bundle(bundle(0, 1, 1)).unwrap();
+        assert_eq!(listener.try_recv(), Ok(hash));
+        assert!(listener.try_recv().is_err());
+        assert_eq!(pool.inner.read().listeners.len(), 1);
+
+        // rejected bundles are not announced
+        assert!(pool.add_bundle(bundle(1, 2, 1)).is_err());
+        assert!(listener.try_recv().is_err());
+    }
+}
@@ -1,34 +1,41 @@
 //! Support for maintaining the state of the transaction pool
 
 use crate::{
-    blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    blobstore::{
+        ArchivedBlock, ArchivedSidecar, BlobSidecarArchive, BlobStoreCanonTracker, BlobStoreUpdates,
+    },
     error::PoolError,
-    metrics::MaintainPoolMetrics,
+    metrics::{BlobSidecarArchiveMetrics, MaintainPoolMetrics},
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
-    BlockInfo,
+    BlockInfo, TransactionOrigin,
 };
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
-    future::{BoxFuture, Fuse, FusedFuture},
+    future::{self, BoxFuture, Either, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
 };
 use reth_fs_util::FsPathError;
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, FromRecoveredPooledTransaction,
-    IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, TransactionSigned,
-    TryFromRecoveredTransaction,
+    IntoRecoveredTransaction, PooledTransactionsElement, PooledTransactionsElementEcRecovered,
+    SealedBlock, TransactionSigned, TryFromRecoveredTransaction,
 };
 use reth_provider::{
-    BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, ExecutionOutcome, ProviderError,
-    StateProviderFactory,
+    BlockReader, BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, ExecutionOutcome,
+    ProviderError, StateProviderFactory,
 };
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
+    io::Write,
+    ops::Range,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
-use tokio::sync::oneshot;
+use tokio::{sync::oneshot, time::MissedTickBehavior};
 use tracing::{debug, error, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
@@ -65,6 +72,32 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// Settings for the task that snapshots all subpools.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolSnapshotConfig {
+    /// Path to the pool snapshot file
+    pub snapshot_path: PathBuf,
+    /// How often the snapshot is written while the node is running, it's always written on
+    /// shutdown.
+    pub interval: Duration,
+}
+
+impl PoolSnapshotConfig {
+    /// The default interval between two snapshots.
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+    /// Returns a config writing the snapshot to the given file at the default interval.
+    pub const fn new(snapshot_path: PathBuf) -> Self {
+        Self { snapshot_path, interval: Self::DEFAULT_INTERVAL }
+    }
+
+    /// Sets how often the snapshot is written while the node is running.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<Client, P, St, Tasks>(
     client: Client,
@@ -674,16 +707,361 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// A transaction of a pool snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct SnapshotTransaction {
+    /// The [`TransactionOrigin`] of the transaction, see [`snapshot_origin_id`].
+    origin: u8,
+    /// The transaction, including the sidecar of blob transactions.
+    transaction: PooledTransactionsElement,
+}
+
+/// Returns the identifier of the origin in a pool snapshot.
+const fn snapshot_origin_id(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+/// Returns the origin with the given identifier in a pool snapshot.
+const fn snapshot_origin(id: u8) -> Option<TransactionOrigin> {
+    match id {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Loads a pool snapshot, and reinserts its transactions into the pool with their original origin.
+///
+/// Inserting the transactions validates them again, against the state of the current head. The
+/// file is kept until it's replaced by the next snapshot, so that the transactions aren't lost if
+/// the node stops before then.
+async fn load_and_reinsert_pool_snapshot<P>(
+    pool: P,
+    file_path: &Path,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Check persistent storage for a pool snapshot");
+    let data = reth_fs_util::read(file_path)?;
+
+    if data.is_empty() {
+        return Ok(())
+    }
+
+    let snapshot: Vec<SnapshotTransaction> = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    let num_snapshot_txs = snapshot.len();
+
+    let mut transactions_by_origin = HashMap::<_, Vec<_>>::new();
+    for SnapshotTransaction { origin, transaction } in snapshot {
+        let Some(origin) = snapshot_origin(origin) else { continue };
+        let Ok(transaction) = transaction.try_into_ecrecovered() else { continue };
+        transactions_by_origin.entry(origin).or_default().push(
+            <P as TransactionPool>::Transaction::from_recovered_pooled_transaction(transaction),
+        );
+    }
+
+    let mut num_txs = 0;
+    for (origin, transactions) in transactions_by_origin {
+        let outcome = pool.add_transactions(origin, transactions).await;
+        num_txs += outcome.iter().filter(|res| res.is_ok()).count();
+    }
+
+    info!(target: "txpool", snapshot_file =?file_path, num_snapshot_txs, num_txs, "Reinserted transactions from pool snapshot");
+    Ok(())
+}
+
+/// Writes the transactions of all subpools to the snapshot file.
+///
+/// Blob transactions are only written if their sidecar is still in the blob store.
+fn save_pool_snapshot<P>(pool: &P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let mut transactions =
+        [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+            .into_iter()
+            .flat_map(|origin| pool.get_transactions_by_origin(origin))
+            .collect::<Vec<_>>();
+    // reinsert the transactions of a sender in nonce order
+    transactions.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+
+    let snapshot = transactions
+        .into_iter()
+        .filter_map(|tx| {
+            let transaction = pool.get_pooled_transaction_element(*tx.hash())?;
+            Some(SnapshotTransaction { origin: snapshot_origin_id(tx.origin), transaction })
+        })
+        .collect::<Vec<_>>();
+
+    let num_txs = snapshot.len();
+    let mut buf = Vec::new();
+    alloy_rlp::encode_list(&snapshot, &mut buf);
+    debug!(target: "txpool", snapshot_file =?file_path, num_txs, "Saving pool snapshot");
+
+    // write to a temporary file first, so the previous snapshot survives a failed write
+    let tmp_path = file_path.with_extension("tmp");
+    let write = || -> Result<(), FsPathError> {
+        if let Some(parent) = file_path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(&tmp_path)
+            .map_err(|err| FsPathError::create_file(err, &tmp_path))?;
+        file.write_all(&buf)
+            .and_then(|_| file.sync_all())
+            .map_err(|err| FsPathError::write(err, &tmp_path))?;
+        reth_fs_util::rename(&tmp_path, file_path)
+    };
+    match write() {
+        Ok(_) => {
+            info!(target: "txpool", snapshot_file=?file_path, num_txs, "Wrote pool snapshot to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file=?file_path, "Failed to write pool snapshot to file");
+        }
+    }
+}
+
+/// Saves a pool snapshot on the blocking pool, see [`save_pool_snapshot`].
+async fn spawn_save_pool_snapshot<P>(pool: &P, file_path: &Path)
+where
+    P: TransactionPool + 'static,
+{
+    let pool = pool.clone();
+    let file_path = file_path.to_path_buf();
+    if let Err(err) =
+        tokio::task::spawn_blocking(move || save_pool_snapshot(&pool, &file_path)).await
+    {
+        error!(target: "txpool", %err, "Failed to save pool snapshot");
+    }
+}
+
+/// Task which snapshots all subpools, including blob sidecars, periodically and on shutdown.
+/// Reloads the snapshot on boot up and reinserts its transactions into the pool.
+///
+/// This supersedes [`backup_local_transactions_task`], the snapshot includes the local
+/// transactions.
+pub async fn pool_snapshot_task<P>(
+    mut shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: PoolSnapshotConfig,
+) where
+    P: TransactionPool + 'static,
+{
+    let PoolSnapshotConfig { snapshot_path, interval } = config;
+
+    if let Err(err) = load_and_reinsert_pool_snapshot(pool.clone(), &snapshot_path).await {
+        error!(target: "txpool", "{}", err)
+    }
+
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let graceful_guard = loop {
+        match future::select(&mut shutdown, std::pin::pin!(interval.tick())).await {
+            Either::Left((graceful_guard, _)) => break graceful_guard,
+            Either::Right(_) => spawn_save_pool_snapshot(&pool, &snapshot_path).await,
+        }
+    };
+
+    // write the final snapshot to disk
+    spawn_save_pool_snapshot(&pool, &snapshot_path).await;
+
+    drop(graceful_guard)
+}
+
+/// The maximum number of missed canonical blocks the blob sidecar archive task backfills.
+///
+/// The blob store only keeps sidecars until their transactions are finalized, so the sidecars of
+/// older blocks are gone anyway.
+const MAX_ARCHIVE_BACKFILL_BLOCKS: u64 = 1024;
+
+/// Task which archives the blob sidecars of the blob transactions in new canonical blocks, and
+/// prunes the archive relative to the timestamp of the new tip.
+///
+/// Sidecars are taken from the blob store of the pool, which keeps them until their transactions
+/// are finalized. Sidecars of transactions that never went through the pool can't be archived.
+///
+/// Canonical blocks between the [tip](BlobSidecarArchive::tip) of the archive and a new
+/// notification, which were missed because the notification stream lagged behind or the node was
+/// offline, are read from the `client` and archived as well, as far as their sidecars are still
+/// available.
+pub async fn archive_blob_sidecars_task<Client, P, St>(
+    client: Client,
+    pool: P,
+    archive: BlobSidecarArchive,
+    mut events: St,
+) where
+    Client: BlockReader + Clone + 'static,
+    P: TransactionPool + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    let metrics = Arc::new(BlobSidecarArchiveMetrics::default());
+    while let Some(event) = events.next().await {
+        let client = client.clone();
+        let pool = pool.clone();
+        let archive = archive.clone();
+        let metrics = metrics.clone();
+        // the archive is written to disk
+        let archived = tokio::task::spawn_blocking(move || {
+            archive_canonical_state(&client, &pool, &archive, &metrics, event)
+        })
+        .await;
+        if let Err(err) = archived {
+            error!(target: "txpool::blob::archive", %err, "Failed to archive blob sidecars");
+        }
+    }
+}
+
+/// Updates the archive with the blocks of a canonical state notification.
+fn archive_canonical_state<Client, P>(
+    client: &Client,
+    pool: &P,
+    archive: &BlobSidecarArchive,
+    metrics: &BlobSidecarArchiveMetrics,
+    event: CanonStateNotification,
+) where
+    Client: BlockReader,
+    P: TransactionPool,
+{
+    // the blocks of the old chain are replaced by the new chain, which may be shorter
+    if let Some(reverted) = event.reverted() {
+        for block in reverted.blocks_iter() {
+            if let Err(err) = archive.remove_block(block.number) {
+                warn!(target: "txpool::blob::archive", %err, number = block.number, "Failed to remove reverted block from blob sidecar archive");
+            }
+        }
+    }
+
+    let committed = event.committed();
+    let first = committed.first().number;
+    if let Some(tip) = archive.tip().filter(|tip| first > tip + 1) {
+        backfill_blob_sidecars(client, pool, archive, metrics, tip + 1..first);
+    }
+
+    for block in committed.blocks_iter() {
+        archive_block(pool, archive, block);
+    }
+
+    let tip = committed.tip();
+    if let Err(err) = archive.set_tip(tip.number) {
+        warn!(target: "txpool::blob::archive", %err, number = tip.number, "Failed to update blob sidecar archive tip");
+    }
+    if let Err(err) = archive.prune(tip.timestamp) {
+        warn!(target: "txpool::blob::archive", %err, "Failed to prune blob sidecar archive");
+    }
+}
+
+/// Archives the canonical blocks of the range that were missed by the archive task.
+fn backfill_blob_sidecars<Client, P>(
+    client: &Client,
+    pool: &P,
+    archive: &BlobSidecarArchive,
+    metrics: &BlobSidecarArchiveMetrics,
+    missed: Range<BlockNumber>,
+) where
+    Client: BlockReader,
+    P: TransactionPool,
+{
+    let start = missed.start.max(missed.end.saturating_sub(MAX_ARCHIVE_BACKFILL_BLOCKS));
+    if start > missed.start {
+        warn!(target: "txpool::blob::archive", from = missed.start, to = start - 1, "Too many missed blocks, skipping blob sidecar archive backfill");
+        metrics.blob_archive_missed_blocks.increment(start - missed.start);
+    }
+
+    debug!(target: "txpool::blob::archive", from = start, to = missed.end - 1, "Backfilling blob sidecar archive");
+    let blocks = match client.block_range(start..=missed.end - 1) {
+        Ok(blocks) => blocks,
+        Err(err) => {
+            warn!(target: "txpool::blob::archive", %err, from = start, to = missed.end - 1, "Failed to read missed blocks to archive");
+            metrics.blob_archive_missed_blocks.increment(missed.end - start);
+            return
+        }
+    };
+
+    let backfilled = blocks.len() as u64;
+    for block in blocks {
+        archive_block(pool, archive, &block.seal_slow());
+    }
+    metrics.blob_archive_backfilled_blocks.increment(backfilled);
+    metrics.blob_archive_missed_blocks.increment(missed.end - start - backfilled);
+}
+
+/// Archives the blob sidecars of a canonical block that are available in the pool.
+fn archive_block<P>(pool: &P, archive: &BlobSidecarArchive, block: &SealedBlock)
+where
+    P: TransactionPool,
+{
+    let blob_txs = block
+        .body
+        .iter()
+        .enumerate()
+        .filter_map(|(index, tx)| {
+            tx.blob_versioned_hashes().map(|hashes| (index as u64, tx.hash, hashes))
+        })
+        .collect::<Vec<_>>();
+
+    let mut sidecars = if blob_txs.is_empty() {
+        HashMap::new()
+    } else {
+        let tx_hashes = blob_txs.iter().map(|(_, tx_hash, _)| *tx_hash).collect();
+        match pool.get_all_blobs(tx_hashes) {
+            Ok(sidecars) => sidecars.into_iter().collect(),
+            Err(err) => {
+                warn!(target: "txpool::blob::archive", %err, number = block.number, "Failed to read blob sidecars to archive");
+                return
+            }
+        }
+    };
+
+    let sidecars = blob_txs
+        .into_iter()
+        .filter_map(|(tx_index, tx_hash, versioned_hashes)| {
+            let Some(sidecar) = sidecars.remove(&tx_hash) else {
+                debug!(target: "txpool::blob::archive", ?tx_hash, number = block.number, "Blob sidecar not found in the pool, skipping");
+                return None
+            };
+            Some(ArchivedSidecar { tx_hash, tx_index, versioned_hashes, sidecar })
+        })
+        .collect();
+
+    let block = ArchivedBlock {
+        number: block.number,
+        hash: block.hash(),
+        timestamp: block.timestamp,
+        sidecars,
+    };
+    if let Err(err) = archive.insert_block(block) {
+        warn!(target: "txpool::blob::archive", %err, "Failed to archive blob sidecars");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
+        blobstore::{BlobStore, InMemoryBlobStore},
+        validate::EthTransactionValidatorBuilder,
+        CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction,
     };
     use reth_fs_util as fs;
-    use reth_primitives::{hex, PooledTransactionsElement, MAINNET, U256};
-    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_primitives::{
+        hex, BlobTransactionSidecar, Block, Header, SealedBlockWithSenders, Signature, Transaction,
+        TxEip4844, B256, MAINNET, U256,
+    };
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        Chain,
+    };
     use reth_tasks::TaskManager;
 
     #[test]
@@ -746,4 +1124,125 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pool_snapshot_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = temp_dir.path().join("txpool-snapshot").with_extension(EXTENSION);
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
+        let transaction = EthPooledTransaction::from_recovered_pooled_transaction(
+            tx.try_into_ecrecovered().unwrap(),
+        );
+        let provider = MockEthProvider::default();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, blob_store.clone());
+
+        let txpool = Pool::new(
+            validator.clone(),
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+
+        let handle = tokio::runtime::Handle::current();
+        let manager = TaskManager::new(handle);
+        let config = PoolSnapshotConfig::new(snapshot_path.clone());
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            pool_snapshot_task(shutdown, txpool.clone(), config)
+        });
+
+        // shutdown the executor, which writes the snapshot
+        manager.graceful_shutdown();
+
+        let data = fs::read(&snapshot_path).unwrap();
+        let snapshot: Vec<SnapshotTransaction> =
+            alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].origin, snapshot_origin_id(TransactionOrigin::External));
+        assert_eq!(snapshot[0].transaction.hash(), transaction.hash());
+
+        // reinsert into a fresh pool
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        load_and_reinsert_pool_snapshot(txpool.clone(), &snapshot_path).await.unwrap();
+
+        let txs = txpool.get_transactions_by_origin(TransactionOrigin::External);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].hash(), transaction.hash());
+        // kept until the next snapshot replaces it
+        assert!(snapshot_path.exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn archive_backfills_missed_blocks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let provider = MockEthProvider::default();
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let txpool = Pool::new(
+            validator,
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+        let archive = BlobSidecarArchive::open(temp_dir.path(), Default::default()).unwrap();
+        archive.set_tip(1).unwrap();
+
+        // canonical blocks with a blob transaction each, whose sidecars are in the blob store
+        let blocks = (2..=5)
+            .map(|number| {
+                let tx = TransactionSigned::from_transaction_and_signature(
+                    Transaction::Eip4844(TxEip4844 {
+                        nonce: number,
+                        blob_versioned_hashes: vec![B256::with_last_byte(number as u8)],
+                        ..Default::default()
+                    }),
+                    Signature::default(),
+                );
+                let sidecar =
+                    BlobTransactionSidecar { blobs: vec![], commitments: vec![], proofs: vec![] };
+                blob_store.insert(tx.hash, sidecar).unwrap();
+                let block = Block {
+                    header: Header { number, ..Default::default() },
+                    body: vec![tx],
+                    ..Default::default()
+                }
+                .seal_slow();
+                provider.add_block(block.hash(), block.clone().unseal());
+                block
+            })
+            .collect::<Vec<_>>();
+
+        // the notification of the last block is the first one the archive sees after block 1
+        let tip = blocks.last().unwrap().clone();
+        let tip = SealedBlockWithSenders::new(tip, vec![Address::ZERO]).unwrap();
+        let event = CanonStateNotification::Commit {
+            new: Arc::new(Chain::new([tip], ExecutionOutcome::default(), None)),
+        };
+        archive_canonical_state(
+            &provider,
+            &txpool,
+            &archive,
+            &BlobSidecarArchiveMetrics::default(),
+            event,
+        );
+
+        assert_eq!(archive.tip(), Some(5));
+        assert_eq!(archive.blocks_len(), 4);
+        for block in &blocks {
+            let archived = archive.block(block.number).unwrap().unwrap();
+            assert_eq!(archived.hash, block.hash());
+            assert_eq!(archived.sidecars[0].tx_hash, block.body[0].hash);
+        }
+
+        temp_dir.close().unwrap();
+    }
 }
@@ -94,6 +94,16 @@ impl MaintainPoolMetrics {
     }
 }
 
+/// Blob sidecar archive metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct BlobSidecarArchiveMetrics {
+    /// Number of canonical blocks the archive task missed and archived afterwards
+    pub(crate) blob_archive_backfilled_blocks: Counter,
+    /// Number of canonical blocks the archive task missed and couldn't archive afterwards
+    pub(crate) blob_archive_missed_blocks: Counter,
+}
+
 /// All Transactions metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
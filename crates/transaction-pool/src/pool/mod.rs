@@ -98,6 +98,7 @@ use tracing::{debug, trace, warn};
 mod events;
 use crate::{
     blobstore::BlobStore,
+    bundle::BundlePool,
     metrics::BlobStoreMetrics,
     pool::txpool::UpdateOutcome,
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
@@ -149,6 +150,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Bundles that are considered for inclusion next to the transactions of the pool.
+    bundles: BundlePool,
 }
 
 // === impl PoolInner ===
@@ -172,6 +175,7 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            bundles: Default::default(),
         }
     }
 
@@ -180,6 +184,11 @@ where
         &self.blob_store
     }
 
+    /// Returns the pool of bundles.
+    pub(crate) const fn bundles(&self) -> &BundlePool {
+        &self.bundles
+    }
+
     /// Returns stats about the size of the pool.
     pub(crate) fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -373,6 +382,7 @@ where
         let block_info = update.block_info();
         let CanonicalStateUpdate { new_tip, changed_accounts, mined_transactions, .. } = update;
         self.validator.on_new_head_block(new_tip);
+        self.bundles.on_new_block(new_tip.number);
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
@@ -2,6 +2,7 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
     error::PoolResult,
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
     validate::ValidPoolTransaction,
@@ -385,6 +386,15 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
+
+    /// Returns the [`BundlePool`] that holds the bundles considered for inclusion next to the
+    /// transactions of this pool.
+    ///
+    /// By default, this returns a new empty [`BundlePool`] that is not shared with the pool, so
+    /// pools that don't support bundles don't have to implement this.
+    fn bundle_pool(&self) -> BundlePool {
+        BundlePool::default()
+    }
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
@@ -713,6 +723,27 @@ pub trait BestTransactions: Iterator + Send {
     }
 }
 
+impl<T> BestTransactions for Box<T>
+where
+    T: BestTransactions + ?Sized,
+{
+    fn mark_invalid(&mut self, transaction: &Self::Item) {
+        (**self).mark_invalid(transaction)
+    }
+
+    fn no_updates(&mut self) {
+        (**self).no_updates()
+    }
+
+    fn skip_blobs(&mut self) {
+        (**self).skip_blobs()
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        (**self).set_skip_blobs(skip_blobs)
+    }
+}
+
 /// A no-op implementation that yields no transactions.
 impl<T> BestTransactions for std::iter::Empty<T> {
     fn mark_invalid(&mut self, _tx: &T) {}
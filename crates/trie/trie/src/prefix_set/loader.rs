@@ -8,12 +8,17 @@ use reth_db_api::{
     transaction::DbTx,
     DatabaseError,
 };
-use reth_primitives::{keccak256, BlockNumber, StorageEntry, B256};
+use reth_primitives::{keccak256, Address, BlockNumber, StorageEntry, B256};
 use std::{
     collections::{HashMap, HashSet},
     ops::RangeInclusive,
 };
-/// A wrapper around a database transaction that loads prefix sets within a given block range.
+
+/// A wrapper around a database transaction that loads prefix sets from account and storage
+/// changesets.
+///
+/// Changesets may live in static files as well as in the database, so they are read by the caller
+/// and passed to [`PrefixSetLoader::load`].
 #[derive(Deref, Debug)]
 pub struct PrefixSetLoader<'a, TX>(&'a TX);
 
@@ -25,18 +30,34 @@ impl<'a, TX> PrefixSetLoader<'a, TX> {
 }
 
 impl<'a, TX: DbTx> PrefixSetLoader<'a, TX> {
-    /// Load all account and storage changes for the given block range.
-    pub fn load(self, range: RangeInclusive<BlockNumber>) -> Result<TriePrefixSets, DatabaseError> {
+    /// Load all account and storage changes in the database for the given block range.
+    ///
+    /// NOTE: Changesets that were moved to static files are not included.
+    #[deprecated(
+        note = "changesets moved to static files are not included, pass all changesets to `PrefixSetLoader::load` instead"
+    )]
+    pub fn load_range(
+        self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<TriePrefixSets, DatabaseError> {
+        let (account_changesets, storage_changesets) = database_changesets(self.0, range)?;
+        self.load(account_changesets, storage_changesets)
+    }
+
+    /// Load the prefix sets of all changed accounts and storage slots.
+    pub fn load(
+        self,
+        account_changesets: impl IntoIterator<Item = AccountBeforeTx>,
+        storage_changesets: impl IntoIterator<Item = (Address, StorageEntry)>,
+    ) -> Result<TriePrefixSets, DatabaseError> {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_sets = HashMap::<B256, PrefixSetMut>::default();
         let mut destroyed_accounts = HashSet::default();
 
         // Walk account changeset and insert account prefixes.
-        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
         let mut account_plain_state_cursor = self.cursor_read::<tables::PlainAccountState>()?;
-        for account_entry in account_changeset_cursor.walk_range(range.clone())? {
-            let (_, AccountBeforeTx { address, .. }) = account_entry?;
+        for AccountBeforeTx { address, .. } in account_changesets {
             let hashed_address = keccak256(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
 
@@ -47,10 +68,7 @@ impl<'a, TX: DbTx> PrefixSetLoader<'a, TX> {
 
         // Walk storage changeset and insert storage prefixes as well as account prefixes if missing
         // from the account prefix set.
-        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
-        let storage_range = BlockNumberAddress::range(range);
-        for storage_entry in storage_cursor.walk_range(storage_range)? {
-            let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
+        for (address, StorageEntry { key, .. }) in storage_changesets {
             let hashed_address = keccak256(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
             storage_prefix_sets
@@ -69,3 +87,21 @@ impl<'a, TX: DbTx> PrefixSetLoader<'a, TX> {
         })
     }
 }
+
+/// Reads the account and storage changesets of the block range from the database.
+pub(crate) fn database_changesets<TX: DbTx>(
+    tx: &TX,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<(Vec<AccountBeforeTx>, Vec<(Address, StorageEntry)>), DatabaseError> {
+    let account_changesets = tx
+        .cursor_read::<tables::AccountChangeSets>()?
+        .walk_range(range.clone())?
+        .map(|entry| entry.map(|(_, account)| account))
+        .collect::<Result<Vec<_>, _>>()?;
+    let storage_changesets = tx
+        .cursor_read::<tables::StorageChangeSets>()?
+        .walk_range(BlockNumberAddress::range(range))?
+        .map(|entry| entry.map(|(key, storage)| (key.address(), storage)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((account_changesets, storage_changesets))
+}
@@ -6,6 +6,7 @@ use std::{
 };
 
 mod loader;
+pub(crate) use loader::database_changesets;
 pub use loader::PrefixSetLoader;
 
 /// Collection of trie prefix sets.
@@ -1,7 +1,7 @@
 use crate::{
     hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
     node_iter::{TrieElement, TrieNodeIter},
-    prefix_set::PrefixSetMut,
+    prefix_set::{PrefixSetMut, TriePrefixSets},
     trie_cursor::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
     walker::TrieWalker,
     HashBuilder, Nibbles,
@@ -11,29 +11,51 @@ use reth_db::tables;
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::{StateRootError, StorageRootError};
 use reth_primitives::{
-    constants::EMPTY_ROOT_HASH,
     keccak256,
-    proofs::{AccountProof, IntoTrieAccount, StorageProof},
+    proofs::{AccountProof, IntoTrieAccount, MultiProof, StorageMultiProof, StorageProof},
     Address, B256,
 };
 use reth_trie_types::proof::ProofRetainer;
+use std::collections::{HashMap, HashSet};
+
 /// A struct for generating merkle proofs.
 ///
 /// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
 /// on the hash builder and follows the same algorithm as the state root calculator.
 /// See `StateRoot::root` for more info.
+///
+/// Proofs for state that differs from the one committed to the database (e.g. historical state)
+/// can be generated by providing the overlay via [`Proof::with_hashed_cursor_factory`] and the
+/// corresponding changed keys via [`Proof::with_prefix_sets`]. The prefix sets force the walker
+/// to skip the stale intermediate nodes and rebuild them from the hashed state.
 #[derive(Debug)]
 pub struct Proof<'a, TX, H> {
     /// A reference to the database transaction.
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// A set of prefix sets that have changes.
+    prefix_sets: TriePrefixSets,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
-    pub const fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+    pub fn new(tx: &'a TX) -> Self {
+        Self { tx, hashed_cursor_factory: tx, prefix_sets: TriePrefixSets::default() }
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof { tx: self.tx, hashed_cursor_factory, prefix_sets: self.prefix_sets }
+    }
+
+    /// Set the prefix sets. They have to be provided for all keys that have been changed relative
+    /// to the trie committed to the database.
+    pub fn with_prefix_sets(mut self, prefix_sets: TriePrefixSets) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
     }
 }
 
@@ -48,23 +70,40 @@ where
         address: Address,
         slots: &[B256],
     ) -> Result<AccountProof, StateRootError> {
-        let target_hashed_address = keccak256(address);
-        let target_nibbles = Nibbles::unpack(target_hashed_address);
-        let mut account_proof = AccountProof::new(address);
+        let targets = HashMap::from([(
+            keccak256(address),
+            slots.iter().map(keccak256).collect::<HashSet<_>>(),
+        )]);
+        Ok(self.multiproof(&targets)?.account_proof(address, slots))
+    }
 
+    /// Generate a state multiproof for the specified targets.
+    ///
+    /// The targets are provided as a mapping of hashed addresses to the sets of hashed storage
+    /// slots. All targets are proven in a single walk of the account trie, so the branch nodes
+    /// they have in common are retained only once.
+    pub fn multiproof(
+        &self,
+        targets: &HashMap<B256, HashSet<B256>>,
+    ) -> Result<MultiProof, StateRootError> {
         let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let trie_cursor =
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
-        prefix_set.insert(target_nibbles.clone());
+        let mut prefix_set =
+            PrefixSetMut::from(self.prefix_sets.account_prefix_set.iter().cloned());
+        for hashed_address in targets.keys() {
+            prefix_set.insert(Nibbles::unpack(hashed_address));
+        }
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
-        let retainer = ProofRetainer::from_iter([target_nibbles]);
+        let retainer = ProofRetainer::from_iter(targets.keys().map(Nibbles::unpack));
         let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
 
+        let mut accounts = HashMap::default();
+        let mut storages = HashMap::default();
         let mut account_rlp = Vec::with_capacity(128);
         let mut account_node_iter = TrieNodeIter::new(walker, hashed_account_cursor);
         while let Some(account_node) = account_node_iter.try_next()? {
@@ -73,10 +112,11 @@ where
                     hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
                 }
                 TrieElement::Leaf(hashed_address, account) => {
-                    let storage_root = if hashed_address == target_hashed_address {
-                        let (storage_root, storage_proofs) =
-                            self.storage_root_with_proofs(hashed_address, slots)?;
-                        account_proof.set_account(account, storage_root, storage_proofs);
+                    let storage_root = if let Some(slots) = targets.get(&hashed_address) {
+                        let storage_multiproof = self.storage_multiproof(hashed_address, slots)?;
+                        let storage_root = storage_multiproof.root;
+                        accounts.insert(hashed_address, account);
+                        storages.insert(hashed_address, storage_multiproof);
                         storage_root
                     } else {
                         self.storage_root(hashed_address)?
@@ -93,16 +133,12 @@ where
 
         let _ = hash_builder.root();
 
-        let proofs = hash_builder.take_proofs();
-        account_proof.set_proof(proofs.values().cloned().collect());
-
-        Ok(account_proof)
+        Ok(MultiProof { account_subtree: hash_builder.take_proofs(), accounts, storages })
     }
 
     /// Compute storage root.
     pub fn storage_root(&self, hashed_address: B256) -> Result<B256, StorageRootError> {
-        let (storage_root, _) = self.storage_root_with_proofs(hashed_address, &[])?;
-        Ok(storage_root)
+        Ok(self.storage_multiproof(hashed_address, &HashSet::default())?.root)
     }
 
     /// Compute the storage root and retain proofs for requested slots.
@@ -111,26 +147,43 @@ where
         hashed_address: B256,
         slots: &[B256],
     ) -> Result<(B256, Vec<StorageProof>), StorageRootError> {
+        let targets = slots.iter().map(keccak256).collect();
+        let storage_multiproof = self.storage_multiproof(hashed_address, &targets)?;
+        let proofs = slots.iter().map(|slot| storage_multiproof.storage_proof(*slot)).collect();
+        Ok((storage_multiproof.root, proofs))
+    }
+
+    /// Generate a storage multiproof for the given hashed slots.
+    pub fn storage_multiproof(
+        &self,
+        hashed_address: B256,
+        targets: &HashSet<B256>,
+    ) -> Result<StorageMultiProof, StorageRootError> {
         let mut hashed_storage_cursor =
             self.hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
 
-        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
-
         // short circuit on empty storage
         if hashed_storage_cursor.is_storage_empty()? {
-            return Ok((EMPTY_ROOT_HASH, proofs))
+            return Ok(StorageMultiProof::default())
         }
 
-        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let target_nibbles = targets.iter().map(Nibbles::unpack).collect::<Vec<_>>();
+        let mut prefix_set = PrefixSetMut::from(target_nibbles.clone());
+        if let Some(storage_prefix_set) = self.prefix_sets.storage_prefix_sets.get(&hashed_address)
+        {
+            for nibbles in storage_prefix_set.iter() {
+                prefix_set.insert(nibbles.clone());
+            }
+        }
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
         );
-        let walker = TrieWalker::new(trie_cursor, prefix_set);
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
         let retainer = ProofRetainer::from_iter(target_nibbles);
         let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        let mut values = HashMap::default();
         let mut storage_node_iter = TrieNodeIter::new(walker, hashed_storage_cursor);
         while let Some(node) = storage_node_iter.try_next()? {
             match node {
@@ -138,39 +191,32 @@ where
                     hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
                 }
                 TrieElement::Leaf(hashed_slot, value) => {
-                    let nibbles = Nibbles::unpack(hashed_slot);
-                    if let Some(proof) = proofs.iter_mut().find(|proof| proof.nibbles == nibbles) {
-                        proof.set_value(value);
+                    if targets.contains(&hashed_slot) {
+                        values.insert(hashed_slot, value);
                     }
-                    hash_builder.add_leaf(nibbles, alloy_rlp::encode_fixed_size(&value).as_ref());
+                    hash_builder.add_leaf(
+                        Nibbles::unpack(hashed_slot),
+                        alloy_rlp::encode_fixed_size(&value).as_ref(),
+                    );
                 }
             }
         }
 
         let root = hash_builder.root();
-
-        let all_proof_nodes = hash_builder.take_proofs();
-        for proof in &mut proofs {
-            // Iterate over all proof nodes and find the matching ones.
-            // The filtered results are guaranteed to be in order.
-            let matching_proof_nodes = all_proof_nodes
-                .iter()
-                .filter(|(path, _)| proof.nibbles.starts_with(path))
-                .map(|(_, node)| node.clone());
-            proof.set_proof(matching_proof_nodes.collect());
-        }
-
-        Ok((root, proofs))
+        Ok(StorageMultiProof { root, subtree: hash_builder.take_proofs(), values })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{HashedPostState, HashedStorage, StateRoot};
     use once_cell::sync::Lazy;
     use reth_db_api::database::Database;
-    use reth_primitives::{Account, Bytes, Chain, ChainSpec, StorageEntry, HOLESKY, MAINNET, U256};
+    use reth_primitives::{
+        constants::EMPTY_ROOT_HASH, Account, Bytes, Chain, ChainSpec, StorageEntry, HOLESKY,
+        MAINNET, U256,
+    };
     use reth_provider::{test_utils::create_test_provider_factory, HashingWriter, ProviderFactory};
     use reth_storage_errors::provider::ProviderResult;
     use std::{str::FromStr, sync::Arc};
@@ -450,4 +496,69 @@ mod tests {
         similar_asserts::assert_eq!(account_proof, expected);
         assert_eq!(account_proof.verify(root), Ok(()));
     }
+
+    #[test]
+    fn testspec_overlay_proofs() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let changed = Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap();
+        let unchanged = Address::from_str("0x62b0dd4aab2b1a0a04e279e2b828791a10755528").unwrap();
+        let slot = B256::with_last_byte(1);
+
+        // Overlay a balance change and a new storage slot on top of the database state.
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(
+            keccak256(changed),
+            Some(Account { nonce: 1, balance: U256::from(1337), bytecode_hash: None }),
+        );
+        hashed_state.storages.insert(
+            keccak256(changed),
+            HashedStorage::from_iter(false, [(keccak256(slot), U256::from(42))]),
+        );
+
+        let provider = factory.provider().unwrap();
+        let root = hashed_state.state_root(provider.tx_ref()).unwrap();
+
+        let account_proof =
+            hashed_state.account_proof(provider.tx_ref(), changed, &[slot]).unwrap();
+        assert_eq!(account_proof.info.map(|info| info.balance), Some(U256::from(1337)));
+        assert_eq!(account_proof.storage_proofs[0].value, U256::from(42));
+        assert_eq!(account_proof.verify(root), Ok(()));
+
+        // Proofs for accounts that were not changed must be verifiable against the new root too.
+        let account_proof = hashed_state.account_proof(provider.tx_ref(), unchanged, &[]).unwrap();
+        assert_eq!(account_proof.verify(root), Ok(()));
+    }
+
+    #[test]
+    fn testspec_multiproof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        let root = insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let slots = Vec::from([B256::with_last_byte(1), B256::with_last_byte(3)]);
+        let targets = TEST_SPEC.genesis().alloc.keys().copied().collect::<Vec<_>>();
+        let hashed_targets: HashMap<B256, HashSet<B256>> = targets
+            .iter()
+            .map(|address| (keccak256(address), slots.iter().map(keccak256).collect()))
+            .collect();
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let multiproof = proof.multiproof(&hashed_targets).unwrap();
+
+        let mut total_nodes = 0;
+        for target in targets {
+            let expected = proof.account_proof(target, &slots).unwrap();
+            let account_proof = multiproof.account_proof(target, &slots);
+            total_nodes += account_proof.proof.len();
+            similar_asserts::assert_eq!(account_proof, expected);
+            assert_eq!(account_proof.verify(root), Ok(()));
+        }
+
+        // The branch nodes shared between the accounts must be retained only once.
+        assert!(multiproof.account_subtree.len() < total_nodes);
+    }
 }
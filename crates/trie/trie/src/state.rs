@@ -1,19 +1,18 @@
 use crate::{
     hashed_cursor::HashedPostStateCursorFactory,
-    prefix_set::{PrefixSetMut, TriePrefixSets},
+    prefix_set::{database_changesets, PrefixSetMut, TriePrefixSets},
+    proof::Proof,
     updates::TrieUpdates,
     Nibbles, StateRoot,
 };
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
-use reth_db::{tables, DatabaseError};
-use reth_db_api::{
-    cursor::DbCursorRO,
-    models::{AccountBeforeTx, BlockNumberAddress},
-    transaction::DbTx,
-};
+use reth_db_api::{models::AccountBeforeTx, transaction::DbTx, DatabaseError};
 use reth_execution_errors::StateRootError;
 use reth_primitives::{
-    keccak256, revm::compat::into_reth_acc, Account, Address, BlockNumber, B256, U256,
+    keccak256,
+    proofs::{AccountProof, MultiProof},
+    revm::compat::into_reth_acc,
+    Account, Address, BlockNumber, StorageEntry, B256, U256,
 };
 use revm::db::BundleAccount;
 use std::{
@@ -66,16 +65,31 @@ impl HashedPostState {
     /// apply them to hashed state in reverse.
     ///
     /// NOTE: In order to have the resulting [`HashedPostState`] be a correct
-    /// overlay of the plain state, the end of the range must be the current tip.
+    /// overlay of the plain state, the end of the range must be the current tip. Changesets that
+    /// were moved to static files are not included.
+    #[deprecated(
+        note = "changesets moved to static files are not included, use `HashedPostState::from_reverts` instead"
+    )]
     pub fn from_revert_range<TX: DbTx>(
         tx: &TX,
         range: RangeInclusive<BlockNumber>,
     ) -> Result<Self, DatabaseError> {
+        let (account_changesets, storage_changesets) = database_changesets(tx, range)?;
+        Ok(Self::from_reverts(account_changesets, storage_changesets))
+    }
+
+    /// Initialize [`HashedPostState`] from account and storage changesets ordered by block number,
+    /// keeping the value before the first occurring change of every account and storage slot.
+    ///
+    /// NOTE: In order to have the resulting [`HashedPostState`] be a correct
+    /// overlay of the plain state, the changesets must end at the current tip.
+    pub fn from_reverts(
+        account_changesets: impl IntoIterator<Item = AccountBeforeTx>,
+        storage_changesets: impl IntoIterator<Item = (Address, StorageEntry)>,
+    ) -> Self {
         // Iterate over account changesets and record value before first occurring account change.
         let mut accounts = HashMap::<Address, Option<Account>>::default();
-        let mut account_changesets_cursor = tx.cursor_read::<tables::AccountChangeSets>()?;
-        for entry in account_changesets_cursor.walk_range(range.clone())? {
-            let (_, AccountBeforeTx { address, info }) = entry?;
+        for AccountBeforeTx { address, info } in account_changesets {
             if let hash_map::Entry::Vacant(entry) = accounts.entry(address) {
                 entry.insert(info);
             }
@@ -83,9 +97,7 @@ impl HashedPostState {
 
         // Iterate over storage changesets and record value before first occurring storage change.
         let mut storages = HashMap::<Address, HashMap<B256, U256>>::default();
-        let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSets>()?;
-        for entry in storage_changesets_cursor.walk_range(BlockNumberAddress::range(range))? {
-            let (BlockNumberAddress((_, address)), storage) = entry?;
+        for (address, storage) in storage_changesets {
             let account_storage = storages.entry(address).or_default();
             if let hash_map::Entry::Vacant(entry) = account_storage.entry(storage.key) {
                 entry.insert(storage.value);
@@ -101,15 +113,15 @@ impl HashedPostState {
                 keccak256(address),
                 HashedStorage::from_iter(
                     // The `wiped` flag indicates only whether previous storage entries
-                    // should be looked up in db or not. For reverts it's a noop since all
-                    // wiped changes had been written as storage reverts.
+                    // should be looked up in db or not. For reverts it's a noop since all
+                    // wiped changes had been written as storage reverts.
                     false,
                     storage.into_iter().map(|(slot, value)| (keccak256(slot), value)),
                 ),
             )
         }));
 
-        Ok(Self { accounts: hashed_accounts, storages: hashed_storages })
+        Self { accounts: hashed_accounts, storages: hashed_storages }
     }
 
     /// Set account entries on hashed state.
@@ -257,6 +269,41 @@ impl HashedPostState {
             .with_prefix_sets(prefix_sets)
             .root_with_updates()
     }
+
+    /// Generates the account proof for the target address and storage slots against the state
+    /// that results from applying this [`HashedPostState`] on top of the database state.
+    ///
+    /// All changed keys are added to the prefix sets, so the intermediate nodes affected by the
+    /// overlay are rebuilt from the hashed state instead of being read from the database.
+    pub fn account_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets(prefix_sets)
+            .account_proof(address, slots)
+    }
+
+    /// Generates the state multiproof for the targets against the state that results from
+    /// applying this [`HashedPostState`] on top of the database state.
+    /// See [`Proof::multiproof`] for more info.
+    pub fn multiproof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        targets: &HashMap<B256, HashSet<B256>>,
+    ) -> Result<MultiProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets(prefix_sets)
+            .multiproof(targets)
+    }
 }
 
 /// Representation of in-memory hashed storage.
@@ -108,23 +108,35 @@ impl<'a, TX: DbTx> StateRoot<&'a TX, &'a TX> {
     /// Given a block number range, identifies all the accounts and storage keys that
     /// have changed.
     ///
+    /// NOTE: Changesets that were moved to static files are not included.
+    ///
     /// # Returns
     ///
     /// An instance of state root calculator with account and storage prefixes loaded.
+    #[deprecated(
+        note = "changesets moved to static files are not included, load the prefix sets with `PrefixSetLoader::load` and use `StateRoot::with_prefix_sets` instead"
+    )]
+    #[allow(deprecated)]
     pub fn incremental_root_calculator(
         tx: &'a TX,
         range: RangeInclusive<BlockNumber>,
     ) -> Result<Self, StateRootError> {
-        let loaded_prefix_sets = PrefixSetLoader::new(tx).load(range)?;
+        let loaded_prefix_sets = PrefixSetLoader::new(tx).load_range(range)?;
         Ok(Self::from_tx(tx).with_prefix_sets(loaded_prefix_sets))
     }
 
     /// Computes the state root of the trie with the changed account and storage prefixes and
     /// existing trie nodes.
     ///
+    /// NOTE: Changesets that were moved to static files are not included.
+    ///
     /// # Returns
     ///
     /// The updated state root.
+    #[deprecated(
+        note = "changesets moved to static files are not included, load the prefix sets with `PrefixSetLoader::load` and use `StateRoot::with_prefix_sets` instead"
+    )]
+    #[allow(deprecated)]
     pub fn incremental_root(
         tx: &'a TX,
         range: RangeInclusive<BlockNumber>,
@@ -138,9 +150,15 @@ impl<'a, TX: DbTx> StateRoot<&'a TX, &'a TX> {
     ///
     /// Ignores the threshold.
     ///
+    /// NOTE: Changesets that were moved to static files are not included.
+    ///
     /// # Returns
     ///
     /// The updated state root and the trie updates.
+    #[deprecated(
+        note = "changesets moved to static files are not included, load the prefix sets with `PrefixSetLoader::load` and use `StateRoot::with_prefix_sets` instead"
+    )]
+    #[allow(deprecated)]
     pub fn incremental_root_with_updates(
         tx: &'a TX,
         range: RangeInclusive<BlockNumber>,
@@ -152,9 +170,15 @@ impl<'a, TX: DbTx> StateRoot<&'a TX, &'a TX> {
     /// Computes the state root of the trie with the changed account and storage prefixes and
     /// existing trie nodes collecting updates in the process.
     ///
+    /// NOTE: Changesets that were moved to static files are not included.
+    ///
     /// # Returns
     ///
     /// The intermediate progress of state root computation.
+    #[deprecated(
+        note = "changesets moved to static files are not included, load the prefix sets with `PrefixSetLoader::load` and use `StateRoot::with_prefix_sets` instead"
+    )]
+    #[allow(deprecated)]
     pub fn incremental_root_with_progress(
         tx: &'a TX,
         range: RangeInclusive<BlockNumber>,